@@ -1,5 +0,0 @@
-Object.assign(Boolean.prototype, {
-    $clone() {
-        return this.valueOf()
-    },
-})
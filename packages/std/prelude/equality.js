@@ -0,0 +1,65 @@
+import { structuralKey } from "./structural-key.js"
+
+/**
+ * Buri values are compared by structure. The compiler builds a comparator out of
+ * these helpers from the type of the operands, so every field, element and payload
+ * is compared with the comparator for its own type.
+ *
+ * ```buri
+ * isSame = [[1], [2]] == [[1], [2]]
+ * ```
+ *
+ * This is compiled to the following JS code:
+ *
+ * ```js
+ * const BisSame = $equalsList($equalsList($equalsPrimitive))([[1], [2]], [[1], [2]])
+ * ```
+ */
+
+export const $equalsPrimitive = (left, right) => left === right
+
+/**
+ * The type of a generic function's arguments isn't known when it is compiled, so
+ * they are compared by the structure of the values they turn out to be.
+ *
+ * ```buri
+ * same = (a, b) => a == b
+ * ```
+ *
+ * This is compiled to the following JS code:
+ *
+ * ```js
+ * const Bsame = (Ba, Bb) => $equals(Ba, Bb)
+ * ```
+ */
+export const $equals = (left, right) =>
+    left === right || (typeof left === "object" && structuralKey(left) === structuralKey(right))
+
+export const $equalsList = (equalsElement) => (left, right) =>
+    left.length === right.length && left.every((element, index) => equalsElement(element, right[index]))
+
+export const $equalsTuple = (equalsElements) => (left, right) =>
+    equalsElements.every((equalsElement, index) => equalsElement(left[index], right[index]))
+
+export const $equalsRecord = (equalsFields) => (left, right) =>
+    Object.keys(equalsFields).every((field) => equalsFields[field](left[field], right[field]))
+
+/**
+ * Tags (and enums with payloads) are arrays of their name followed by their contents.
+ * Compact builds represent tags without contents by just their discriminant.
+ */
+export const $equalsTags = (equalsContents) => (left, right) =>
+    typeof left === "number" || typeof right === "number"
+        ? left === right
+        : left[0] === right[0] && $equalsTuple(equalsContents[left[0]])(left.slice(1), right.slice(1))
+
+/** Map keys are already stored by structure, so only the values need a comparator. */
+export const $equalsMap = (equalsValue) => (left, right) =>
+    left.$entries.size === right.$entries.size &&
+    [...left.$entries].every(([key, [, value]]) => {
+        const entry = right.$entries.get(key)
+        return entry !== undefined && equalsValue(value, entry[1])
+    })
+
+export const $equalsSet = (left, right) =>
+    left.$elements.size === right.$elements.size && [...left.$elements.keys()].every((key) => right.$elements.has(key))
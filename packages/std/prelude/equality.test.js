@@ -0,0 +1,133 @@
+import { describe, expect, it } from "bun:test"
+import {
+    $equals,
+    $equalsList,
+    $equalsMap,
+    $equalsPrimitive,
+    $equalsRecord,
+    $equalsSet,
+    $equalsTags,
+    $equalsTuple,
+} from "./equality.js"
+import { $Map } from "./map.js"
+import { $Set } from "./set.js"
+
+describe("$equalsPrimitive", () => {
+    it("compares by value", () => {
+        expect($equalsPrimitive(42, 42)).toBe(true)
+        expect($equalsPrimitive("foo", "bar")).toBe(false)
+    })
+})
+
+describe("$equals", () => {
+    it("compares primitives by value", () => {
+        expect($equals(42, 42)).toBe(true)
+        expect($equals(3.5, 3)).toBe(false)
+        expect($equals("foo", "bar")).toBe(false)
+    })
+
+    it("compares lists by their elements", () => {
+        expect($equals([[1], [2, 3]], [[1], [2, 3]])).toBe(true)
+        expect($equals([[1], [2, 3]], [[1], [2, 4]])).toBe(false)
+    })
+
+    it("compares records by their fields, in any order", () => {
+        expect($equals({ name: "Sam", friends: ["Theodore"] }, { friends: ["Theodore"], name: "Sam" })).toBe(true)
+        expect($equals({ name: "Sam", friends: ["Theodore"] }, { name: "Sam", friends: [] })).toBe(false)
+    })
+
+    it("compares maps and sets by their contents", () => {
+        expect($equals(new $Map([["one", [1]]]), new $Map([["one", [1]]]))).toBe(true)
+        expect($equals(new $Map([["one", [1]]]), new $Map([["one", [2]]]))).toBe(false)
+        expect($equals(new $Set([1, 2]), new $Set([2, 1]))).toBe(true)
+    })
+
+    it("compares tags by their names and contents", () => {
+        expect($equals(["some", [1]], ["some", [1]])).toBe(true)
+        expect($equals(["some", [1]], ["none"])).toBe(false)
+    })
+})
+
+describe("$equalsList", () => {
+    const equals = $equalsList($equalsList($equalsPrimitive))
+
+    it("compares every element", () => {
+        expect(equals([[1], [2, 3]], [[1], [2, 3]])).toBe(true)
+        expect(equals([[1], [2, 3]], [[1], [2, 4]])).toBe(false)
+    })
+
+    it("lists of different lengths are not equal", () => {
+        expect(equals([[1]], [[1], [2]])).toBe(false)
+        expect(equals([[1], [2]], [[1]])).toBe(false)
+    })
+})
+
+describe("$equalsTuple", () => {
+    it("compares each element with its own comparator", () => {
+        const equals = $equalsTuple([$equalsPrimitive, $equalsList($equalsPrimitive)])
+        expect(equals([1, [2]], [1, [2]])).toBe(true)
+        expect(equals([1, [2]], [1, [3]])).toBe(false)
+    })
+})
+
+describe("$equalsRecord", () => {
+    const equals = $equalsRecord({ name: $equalsPrimitive, friends: $equalsList($equalsPrimitive) })
+
+    it("compares every field", () => {
+        expect(equals({ name: "Sam", friends: ["Theodore"] }, { friends: ["Theodore"], name: "Sam" })).toBe(true)
+        expect(equals({ name: "Sam", friends: ["Theodore"] }, { name: "Sam", friends: [] })).toBe(false)
+    })
+})
+
+describe("$equalsTags", () => {
+    const equals = $equalsTags({ none: [], some: [$equalsList($equalsPrimitive)] })
+
+    it("compares the names", () => {
+        expect(equals(["none"], ["none"])).toBe(true)
+        expect(equals(["none"], ["some", [1]])).toBe(false)
+    })
+
+    it("compares the contents", () => {
+        expect(equals(["some", [1]], ["some", [1]])).toBe(true)
+        expect(equals(["some", [1]], ["some", [2]])).toBe(false)
+    })
+
+    it("compares enum payloads by variant index", () => {
+        const equalsEnum = $equalsTags({ 0: [], 1: [$equalsPrimitive] })
+        expect(equalsEnum([1, "a"], [1, "a"])).toBe(true)
+        expect(equalsEnum([1, "a"], [0])).toBe(false)
+    })
+
+    it("compares compact tags without contents by discriminant", () => {
+        const equalsCompact = $equalsTags({ 7: [], 9: [$equalsPrimitive] })
+        expect(equalsCompact(7, 7)).toBe(true)
+        expect(equalsCompact(7, [9, "a"])).toBe(false)
+        expect(equalsCompact([9, "a"], [9, "a"])).toBe(true)
+    })
+})
+
+describe("$equalsMap", () => {
+    const equals = $equalsMap($equalsPrimitive)
+
+    it("maps with the same entries are equal regardless of order", () => {
+        expect(equals(new $Map([["one", 1], ["two", 2]]), new $Map([["two", 2], ["one", 1]]))).toBe(true)
+    })
+
+    it("maps with different values are not equal", () => {
+        expect(equals(new $Map([["one", 1]]), new $Map([["one", 2]]))).toBe(false)
+    })
+
+    it("maps with different keys are not equal", () => {
+        expect(equals(new $Map([["one", 1]]), new $Map([["two", 1]]))).toBe(false)
+    })
+})
+
+describe("$equalsSet", () => {
+    it("sets with the same elements are equal regardless of order", () => {
+        expect($equalsSet(new $Set([1, 2]), new $Set([2, 1]))).toBe(true)
+    })
+
+    it("sets with different elements are not equal", () => {
+        expect($equalsSet(new $Set([1, 2]), new $Set([1, 3]))).toBe(false)
+    })
+})
@@ -0,0 +1,13 @@
+/**
+ * Floats are JS numbers. Their methods are compiled to calls of these helpers,
+ * with the float as the first argument. `|| 0` turns `-0` into `0`, since `Int`s
+ * don't have a negative zero.
+ */
+
+export const $floatFloor = (float) => Math.floor(float) || 0
+
+export const $floatCeil = (float) => Math.ceil(float) || 0
+
+export const $floatRound = (float) => Math.round(float) || 0
+
+export const $floatTruncate = (float) => Math.trunc(float) || 0
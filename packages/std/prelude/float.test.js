@@ -0,0 +1,15 @@
+import { expect, it } from "bun:test"
+import { $floatCeil, $floatFloor, $floatRound, $floatTruncate } from "./float.js"
+
+it("floats should be able to be rounded to integers", () => {
+    expect($floatFloor(2.5)).toBe(2)
+    expect($floatCeil(2.5)).toBe(3)
+    expect($floatRound(2.5)).toBe(3)
+    expect($floatTruncate(-2.5)).toBe(-2)
+})
+
+it("rounding never returns negative zero", () => {
+    expect(Object.is($floatCeil(-0.5), 0)).toBe(true)
+    expect(Object.is($floatRound(-0.2), 0)).toBe(true)
+    expect(Object.is($floatTruncate(-0.5), 0)).toBe(true)
+})
@@ -0,0 +1,68 @@
+// Types for the prelude's helpers, which compiled Buri files import. The map and set
+// interfaces are also global, so JSDoc annotations in compiled files can refer to them
+// without importing the classes.
+
+type Option<T> = readonly ["none"] | readonly ["some", T]
+type Equals<T> = (left: T, right: T) => boolean
+
+declare global {
+    interface $Map<K, V> {
+        get(key: K): Option<V>
+        insert(key: K, value: V): $Map<K, V>
+        remove(key: K): $Map<K, V>
+        has(key: K): boolean
+        size(): number
+        keys(): ReadonlyArray<K>
+        values(): ReadonlyArray<V>
+    }
+
+    interface $Set<T> {
+        insert(element: T): $Set<T>
+        remove(element: T): $Set<T>
+        has(element: T): boolean
+        size(): number
+        values(): ReadonlyArray<T>
+    }
+}
+
+export declare const $Map: {
+    new <K, V>(entries: Iterable<readonly [K, V]>): $Map<K, V>
+}
+export declare const $Set: {
+    new <T>(elements: Iterable<T>): $Set<T>
+}
+
+export declare const $equalsPrimitive: Equals<unknown>
+export declare const $equals: Equals<unknown>
+export declare const $equalsList: <T>(equalsElement: Equals<T>) => Equals<ReadonlyArray<T>>
+export declare const $equalsTuple: (equalsElements: ReadonlyArray<Equals<any>>) => Equals<ReadonlyArray<unknown>>
+export declare const $equalsRecord: (equalsFields: Record<string, Equals<any>>) => Equals<Record<string, unknown>>
+export declare const $equalsTags: (
+    equalsContents: Record<string, ReadonlyArray<Equals<any>>>,
+) => Equals<number | ReadonlyArray<unknown>>
+export declare const $equalsMap: <V>(equalsValue: Equals<V>) => Equals<$Map<unknown, V>>
+export declare const $equalsSet: Equals<$Set<unknown>>
+
+export declare const $floatFloor: (float: number) => number
+export declare const $floatCeil: (float: number) => number
+export declare const $floatRound: (float: number) => number
+export declare const $floatTruncate: (float: number) => number
+
+export declare const $intToFloat: (int: number | bigint) => number
+
+export declare const $listGet: <T>(list: ReadonlyArray<T>, index: number | bigint) => Option<T>
+export declare const $listAppend: <T>(list: ReadonlyArray<T>, element: T) => ReadonlyArray<T>
+export declare const $listSize: (list: ReadonlyArray<unknown>) => number
+
+export declare const $callMethod: (receiver: unknown, name: string, ...args: unknown[]) => unknown
+
+export declare const $modulo: <T extends number | bigint>(left: T, right: T) => T
+export declare const $divide: <T extends number | bigint>(left: T, right: T) => T
+
+export declare const $updateRecord: <T extends object>(record: T, newValues: Partial<T>) => T
+
+export declare const $stringSize: (string: string) => number
+export declare const $stringGetCharCode: (string: string, n: number | bigint) => Option<number>
+
+export declare const $getTagDiscriminant: (name: string) => number
+export declare const $compactTag: (tag: readonly [string, ...unknown[]]) => number | readonly [number, ...unknown[]]
@@ -1,5 +1,11 @@
-import "./boolean.js"
-import "./integer.js"
-import "./list.js"
-import "./record.js"
-import "./string.js"
+export * from "./equality.js"
+export * from "./float.js"
+export * from "./integer.js"
+export * from "./list.js"
+export * from "./map.js"
+export * from "./methods.js"
+export * from "./operators.js"
+export * from "./record.js"
+export * from "./set.js"
+export * from "./string.js"
+export * from "./tags.js"
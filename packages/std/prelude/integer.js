@@ -1,64 +1,7 @@
-Object.assign(Number.prototype, {
-    $clone() {
-        return new this.constructor(this.valueOf())
-    },
+/**
+ * Ints are JS numbers or `BigInt`s, depending on how the file was compiled. Their
+ * methods are compiled to calls of these helpers, with the int as the first
+ * argument.
+ */
 
-    add(num) {
-        return this.constructor(this.valueOf() + num.valueOf())
-    },
-
-    subtract(num) {
-        return this.constructor(this.valueOf() - num.valueOf())
-    },
-
-    multiply(num) {
-        let result = this.valueOf() * num.valueOf()
-        if (result === -0) result = 0
-        return this.constructor(result)
-    },
-
-    divide(num) {
-        const result = this.valueOf() / num.valueOf()
-        const truncated = Math.trunc(result)
-        if (truncated === -0) return this.constructor(0)
-        return this.constructor(truncated)
-    },
-
-    // Using a custom modulo function because the built-in one is mathematically incorrect.
-    // https://stackoverflow.com/questions/4467539/javascript-modulo-gives-a-negative-result-for-negative-numbers
-    modulo(num) {
-        let m = this.valueOf()
-        let n = num.valueOf()
-        let result = ((m % n) + n) % n
-        if (result === -0) result = 0
-        return this.constructor(result)
-    },
-
-    power(num) {
-        return this.constructor(this.valueOf() ** num.valueOf())
-    },
-
-    equals(num) {
-        return this.valueOf() === num.valueOf()
-    },
-
-    notEquals(num) {
-        return this.valueOf() !== num.valueOf()
-    },
-
-    lessThan(num) {
-        return this.valueOf() < num.valueOf()
-    },
-
-    lessThanOrEquals(num) {
-        return this.valueOf() <= num.valueOf()
-    },
-
-    greaterThan(num) {
-        return this.valueOf() > num.valueOf()
-    },
-
-    greaterThanOrEquals(num) {
-        return this.valueOf() >= num.valueOf()
-    },
-})
+export const $intToFloat = (int) => Number(int)
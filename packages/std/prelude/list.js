@@ -1,28 +1,25 @@
-Object.assign(Array.prototype, {
-    $clone() {
-        return new this.constructor(...this.valueOf())
-    },
+/**
+ * Lists are JS arrays. Their methods are compiled to calls of these helpers, with
+ * the list as the first argument.
+ *
+ * ```buri
+ * first = [1, 2]:get(0)
+ * ```
+ *
+ * This is compiled to the following JS code:
+ *
+ * ```js
+ * const Bfirst = $listGet([1, 2], 0)
+ * ```
+ */
 
-    equals(other) {
-        return this[0] == other[0]
-    },
+export const $listGet = (list, index) => {
+    if (index >= list.length || index < 0) {
+        return ["none"]
+    }
+    return ["some", list[index]]
+}
 
-    notEquals(other) {
-        return this[0] != other[0]
-    },
+export const $listAppend = (list, element) => [...list, element]
 
-    get(index) {
-        if (index > this.length || index < 0) {
-            return ["none"]
-        }
-        return ["some", this[index]]
-    },
-
-    append(value) {
-        return [...this, value]
-    },
-
-    size() {
-        return this.length
-    },
-})
+export const $listSize = (list) => list.length
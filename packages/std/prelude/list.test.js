@@ -1,42 +1,32 @@
 import { describe, expect, it } from "bun:test"
-import "./index.js"
+import { $listAppend, $listGet, $listSize } from "./list.js"
 
-it("cloning an list should keep the same value", () => {
-    const list = [1, 2, 3]
-    expect(list.$clone()).toEqual(list)
-})
-
-it("cloning an list should return a new list", () => {
-    const list = [1, 2, 3]
-    expect(list.$clone()).not.toBe(list)
-})
-
-it("trait methods added to lists should be available after cloning", () => {
-    class MyList extends Array {
-        greet() {
-            return "hello"
-        }
-    }
-    const list = new MyList(1, 2, 3)
-    expect(list.$clone().greet()).toBe("hello")
-})
+describe("$listGet", () => {
+    it("returns some element when the index is in the list", () => {
+        expect($listGet([1, 2, 3], 1)).toEqual(["some", 2])
+    })
 
-describe("equality with tags", () => {
-    it("the names should be equal", () => {
-        expect(["foo"].equals(["foo"])).toBe(true)
+    it("returns none when the index is outside the list", () => {
+        expect($listGet([1, 2, 3], 3)).toEqual(["none"])
+        expect($listGet([1, 2, 3], -1)).toEqual(["none"])
     })
 
-    it("the names should not be equal", () => {
-        expect(["foo"].equals(["bar"])).toBe(false)
+    it("accepts bigint indices", () => {
+        expect($listGet([1, 2, 3], 1n)).toEqual(["some", 2])
     })
 })
 
-describe("inequality with tags", () => {
-    it("the names should not be equal", () => {
-        expect(["foo"].notEquals(["foo"])).toBe(false)
+describe("$listAppend", () => {
+    it("returns a new list with the element at the end", () => {
+        const list = [1, 2]
+        expect($listAppend(list, 3)).toEqual([1, 2, 3])
+        expect(list).toEqual([1, 2])
     })
+})
 
-    it("the names should be equal", () => {
-        expect(["foo"].notEquals(["bar"])).toBe(true)
+describe("$listSize", () => {
+    it("returns the number of elements", () => {
+        expect($listSize([])).toBe(0)
+        expect($listSize([1, 2, 3])).toBe(3)
     })
 })
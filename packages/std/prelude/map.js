@@ -0,0 +1,64 @@
+import { structuralKey } from "./structural-key.js"
+
+/**
+ * An immutable map whose keys are compared by structure.
+ *
+ * ```buri
+ * ages = Map["Sam" => 30, "Theodore" => 31]
+ * ```
+ *
+ * This is compiled to the following JS code:
+ *
+ * ```js
+ * const Bages = new $Map([["Sam", 30], ["Theodore", 31]])
+ * ```
+ */
+export class $Map {
+    constructor(entries) {
+        // Maps a structural key to the original [key, value] pair.
+        this.$entries = new Map()
+        for (const [key, value] of entries) {
+            this.$entries.set(structuralKey(key), [key, value])
+        }
+    }
+
+    $clone() {
+        return new $Map(this.$entries.values())
+    }
+
+    get(key) {
+        const entry = this.$entries.get(structuralKey(key))
+        if (entry === undefined) {
+            return ["none"]
+        }
+        return ["some", entry[1]]
+    }
+
+    insert(key, value) {
+        const result = this.$clone()
+        result.$entries.set(structuralKey(key), [key, value])
+        return result
+    }
+
+    remove(key) {
+        const result = this.$clone()
+        result.$entries.delete(structuralKey(key))
+        return result
+    }
+
+    has(key) {
+        return this.$entries.has(structuralKey(key))
+    }
+
+    size() {
+        return this.$entries.size
+    }
+
+    keys() {
+        return [...this.$entries.values()].map(([key]) => key)
+    }
+
+    values() {
+        return [...this.$entries.values()].map(([, value]) => value)
+    }
+}
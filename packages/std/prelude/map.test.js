@@ -0,0 +1,77 @@
+import { describe, expect, it } from "bun:test"
+import { $Map } from "./map.js"
+
+describe("get", () => {
+    it("returns some value when the key exists", () => {
+        const map = new $Map([["one", 1]])
+        expect(map.get("one")).toEqual(["some", 1])
+    })
+
+    it("returns none when the key does not exist", () => {
+        const map = new $Map([["one", 1]])
+        expect(map.get("two")).toEqual(["none"])
+    })
+
+    it("compares keys by structure", () => {
+        const map = new $Map([[["some", 1], "found"]])
+        expect(map.get(["some", 1])).toEqual(["some", "found"])
+    })
+
+    it("does not confuse numbers and strings", () => {
+        const map = new $Map([[1, "number"]])
+        expect(map.get("1")).toEqual(["none"])
+    })
+})
+
+describe("insert", () => {
+    it("adds a new entry", () => {
+        const map = new $Map([]).insert("one", 1)
+        expect(map.get("one")).toEqual(["some", 1])
+    })
+
+    it("replaces an existing entry", () => {
+        const map = new $Map([["one", 1]]).insert("one", 2)
+        expect(map.get("one")).toEqual(["some", 2])
+        expect(map.size()).toBe(1)
+    })
+
+    it("does not mutate the original map", () => {
+        const map = new $Map([])
+        map.insert("one", 1)
+        expect(map.size()).toBe(0)
+    })
+})
+
+describe("remove", () => {
+    it("removes an entry", () => {
+        const map = new $Map([["one", 1]]).remove("one")
+        expect(map.has("one")).toBe(false)
+    })
+
+    it("does not mutate the original map", () => {
+        const map = new $Map([["one", 1]])
+        map.remove("one")
+        expect(map.has("one")).toBe(true)
+    })
+})
+
+describe("keys and values", () => {
+    it("keys returns a list of the keys", () => {
+        const map = new $Map([["one", 1], ["two", 2]])
+        expect(map.keys()).toEqual(["one", "two"])
+    })
+
+    it("values returns a list of the values", () => {
+        const map = new $Map([["one", 1], ["two", 2]])
+        expect(map.values()).toEqual([1, 2])
+    })
+})
+
+describe("bigint keys", () => {
+    it("are only equal to the same bigint", () => {
+        const map = new $Map([[1n, "one"], [2n, "two"]])
+        expect(map.get(1n)).toEqual(["some", "one"])
+        expect(map.get(2n)).toEqual(["some", "two"])
+        expect(map.has(3n)).toBe(false)
+    })
+})
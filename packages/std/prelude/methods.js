@@ -0,0 +1,47 @@
+import { $floatCeil, $floatFloor, $floatRound, $floatTruncate } from "./float.js"
+import { $intToFloat } from "./integer.js"
+import { $listAppend, $listGet, $listSize } from "./list.js"
+import { $stringGetCharCode, $stringSize } from "./string.js"
+
+/**
+ * The type of a generic function's arguments isn't known when it is compiled, so
+ * their methods are looked up by the type of the value they are called on.
+ *
+ * ```buri
+ * grow = (list) => list:append(1)
+ * ```
+ *
+ * This is compiled to the following JS code:
+ *
+ * ```js
+ * const Bgrow = (Blist) => $callMethod(Blist, "append", 1)
+ * ```
+ */
+
+const listMethods = { get: $listGet, append: $listAppend, size: $listSize }
+
+const stringMethods = { size: $stringSize, getCharCode: $stringGetCharCode }
+
+// `Int`s and `Float`s can both be JS numbers, but they don't have any methods with
+// the same name, so the method's name tells them apart.
+const numberMethods = {
+    toFloat: $intToFloat,
+    floor: $floatFloor,
+    ceil: $floatCeil,
+    round: $floatRound,
+    truncate: $floatTruncate,
+}
+
+export const $callMethod = (receiver, name, ...args) => {
+    if (Array.isArray(receiver)) {
+        return listMethods[name](receiver, ...args)
+    }
+    if (typeof receiver === "string") {
+        return stringMethods[name](receiver, ...args)
+    }
+    if (typeof receiver === "number" || typeof receiver === "bigint") {
+        return numberMethods[name](receiver, ...args)
+    }
+    // Maps and sets are instances of the prelude's classes, which have the methods.
+    return receiver[name](...args)
+}
@@ -0,0 +1,27 @@
+import { describe, expect, it } from "bun:test"
+import { $Map } from "./map.js"
+import { $callMethod } from "./methods.js"
+
+describe("$callMethod", () => {
+    it("calls the helpers of lists", () => {
+        expect($callMethod([1, 2], "append", 3)).toEqual([1, 2, 3])
+        expect($callMethod([1, 2], "get", 1)).toEqual(["some", 2])
+    })
+
+    it("calls the helpers of strings", () => {
+        expect($callMethod("a😀b", "size")).toBe(3)
+    })
+
+    it("calls the helpers of floats", () => {
+        expect($callMethod(1.5, "floor")).toBe(1)
+    })
+
+    it("calls the helpers of ints", () => {
+        expect($callMethod(2, "toFloat")).toBe(2)
+        expect($callMethod(2n, "toFloat")).toBe(2)
+    })
+
+    it("calls the methods of maps", () => {
+        expect($callMethod(new $Map([["one", 1]]), "get", "one")).toEqual(["some", 1])
+    })
+})
@@ -0,0 +1,35 @@
+/**
+ * `Int`, `Float`, `Str` and `Bool` operands are compiled to JS's own operators.
+ * The operators that JS can't express directly are compiled to these helpers.
+ *
+ * ```buri
+ * remainder = -1 % 3
+ * ```
+ *
+ * This is compiled to the following JS code:
+ *
+ * ```js
+ * const Bremainder = $modulo(-1, 3)
+ * ```
+ */
+
+// Using a custom modulo function because the built-in one is mathematically incorrect.
+// https://stackoverflow.com/questions/4467539/javascript-modulo-gives-a-negative-result-for-negative-numbers
+// BigInts don't have a negative zero, and `|| 0` would turn them into numbers.
+export const $modulo = (left, right) => {
+    const remainder = ((left % right) + right) % right
+    return typeof remainder === "bigint" ? remainder : remainder || 0
+}
+
+/**
+ * Generic functions may divide `Int`s or `Float`s. `Int`s are truncated towards zero,
+ * which BigInts already are. A `Float` with a whole value can't be told apart from an
+ * `Int`, so calls which pass `Float`s are compiled to a copy of the function which
+ * doesn't use this helper.
+ */
+export const $divide = (left, right) => {
+    if (typeof left === "bigint" || !Number.isInteger(left) || !Number.isInteger(right)) {
+        return left / right
+    }
+    return Math.trunc(left / right) || 0
+}
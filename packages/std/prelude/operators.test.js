@@ -0,0 +1,37 @@
+import { describe, expect, it } from "bun:test"
+import { $divide, $modulo } from "./operators.js"
+
+describe("$modulo", () => {
+    it("returns the remainder of positive numbers", () => {
+        expect($modulo(42, 4)).toBe(2)
+        expect($modulo(42, 3)).toBe(0)
+    })
+
+    it("is always positive for a positive divisor", () => {
+        expect($modulo(-1, 3)).toBe(2)
+    })
+
+    it("never returns negative zero", () => {
+        expect(Object.is($modulo(-3, 3), 0)).toBe(true)
+    })
+
+    it("keeps bigints as bigints", () => {
+        expect($modulo(-1n, 3n)).toBe(2n)
+        expect($modulo(-3n, 3n)).toBe(0n)
+    })
+})
+
+describe("$divide", () => {
+    it("truncates ints towards zero", () => {
+        expect($divide(7, 2)).toBe(3)
+        expect(Object.is($divide(-1, 2), 0)).toBe(true)
+    })
+
+    it("does not truncate floats", () => {
+        expect($divide(7.5, 2)).toBe(3.75)
+    })
+
+    it("keeps bigints as bigints", () => {
+        expect($divide(-7n, 2n)).toBe(-3n)
+    })
+})
@@ -0,0 +1,52 @@
+import { structuralKey } from "./structural-key.js"
+
+/**
+ * An immutable set whose elements are compared by structure.
+ *
+ * ```buri
+ * primes = Set[2, 3, 5]
+ * ```
+ *
+ * This is compiled to the following JS code:
+ *
+ * ```js
+ * const Bprimes = new $Set([2, 3, 5])
+ * ```
+ */
+export class $Set {
+    constructor(elements) {
+        // Maps a structural key to the original element.
+        this.$elements = new Map()
+        for (const element of elements) {
+            this.$elements.set(structuralKey(element), element)
+        }
+    }
+
+    $clone() {
+        return new $Set(this.$elements.values())
+    }
+
+    insert(element) {
+        const result = this.$clone()
+        result.$elements.set(structuralKey(element), element)
+        return result
+    }
+
+    remove(element) {
+        const result = this.$clone()
+        result.$elements.delete(structuralKey(element))
+        return result
+    }
+
+    has(element) {
+        return this.$elements.has(structuralKey(element))
+    }
+
+    size() {
+        return this.$elements.size
+    }
+
+    values() {
+        return [...this.$elements.values()]
+    }
+}
@@ -0,0 +1,33 @@
+import { describe, expect, it } from "bun:test"
+import { $Set } from "./set.js"
+
+describe("has", () => {
+    it("returns true for elements in the set", () => {
+        expect(new $Set([1, 2]).has(1)).toBe(true)
+    })
+
+    it("returns false for elements not in the set", () => {
+        expect(new $Set([1, 2]).has(3)).toBe(false)
+    })
+
+    it("compares elements by structure", () => {
+        expect(new $Set([{ name: "Sam" }]).has({ name: "Sam" })).toBe(true)
+    })
+})
+
+describe("insert and remove", () => {
+    it("inserting a duplicate does not change the size", () => {
+        expect(new $Set([1]).insert(1).size()).toBe(1)
+    })
+
+    it("removing an element removes it", () => {
+        expect(new $Set([1, 2]).remove(1).values()).toEqual([2])
+    })
+
+    it("does not mutate the original set", () => {
+        const set = new $Set([1])
+        set.insert(2)
+        set.remove(1)
+        expect(set.values()).toEqual([1])
+    })
+})
@@ -1,3 +1,9 @@
+/**
+ * Strings are JS strings. Their methods are compiled to calls of these helpers,
+ * with the string as the first argument, and count characters rather than UTF-16
+ * code units.
+ */
+
 const isUtf16SurrogateAtIndex = (string, jsIndex) => (string.charCodeAt(jsIndex) & 0xF800) === 0xD800
 
 /**
@@ -31,31 +37,26 @@ const jsIndexForNthCharacter = (string, n) => {
     return -1
 }
 
-Object.assign(String.prototype, {
-    $clone() {
-        return new this.constructor(this.valueOf())
-    },
-
-    size() {
-        let characterCount = 0
-        let jsIndex = 0
-        while (jsIndex < this.length) {
-            characterCount += 1
-            if (isUtf16SurrogateAtIndex(this, jsIndex)) {
-                jsIndex += 2
-            } else {
-                jsIndex += 1
-            }
-        }
-        return characterCount
-    },
-
-    getCharCode(n) {
-        let jsIndex = jsIndexForNthCharacter(this, n)
-        if (jsIndex === -1) {
-            return ["none"]
+export const $stringSize = (string) => {
+    let characterCount = 0
+    let jsIndex = 0
+    while (jsIndex < string.length) {
+        characterCount += 1
+        if (isUtf16SurrogateAtIndex(string, jsIndex)) {
+            jsIndex += 2
         } else {
-            return ["some", this.codePointAt(jsIndex)]
+            jsIndex += 1
         }
-    },
-})
+    }
+    return characterCount
+}
+
+export const $stringGetCharCode = (string, n) => {
+    let jsIndex = jsIndexForNthCharacter(string, Number(n))
+    if (jsIndex === -1) {
+        return ["none"]
+    }
+    // `Int`s compiled with `--bigint` are passed and returned as BigInts.
+    const charCode = string.codePointAt(jsIndex)
+    return ["some", typeof n === "bigint" ? BigInt(charCode) : charCode]
+}
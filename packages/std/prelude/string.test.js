@@ -1,27 +1,30 @@
-import { expect, it } from "bun:test"
-import "./index.js"
+import { describe, expect, it } from "bun:test"
+import { $stringGetCharCode, $stringSize } from "./string.js"
 
-it("cloning a string should keep the same value", () => {
-    const str = "hello"
-    expect(str.$clone().valueOf()).toBe("hello")
+describe("$stringSize", () => {
+    it("counts characters rather than code units", () => {
+        expect($stringSize("hello")).toBe(5)
+        expect($stringSize("a😀b")).toBe(3)
+    })
 })
 
-it("strings should be able to be concatenated", () => {
-    const str = "hello"
-    expect(str.concat(" world").valueOf()).toBe("hello world")
-})
+describe("$stringGetCharCode", () => {
+    it("returns the code point of the nth character", () => {
+        expect($stringGetCharCode("a😀b", 1)).toEqual(["some", 0x1f600])
+        expect($stringGetCharCode("a😀b", 2)).toEqual(["some", 98])
+    })
 
-it("strings should retain their original properties after cloning", () => {
-    const str = "hello"
-    expect(str.$clone().length).toBe(5)
-})
+    it("counts from the end for negative indices", () => {
+        expect($stringGetCharCode("a😀b", -2)).toEqual(["some", 0x1f600])
+    })
+
+    it("returns none outside the string", () => {
+        expect($stringGetCharCode("abc", 3)).toEqual(["none"])
+    })
 
-it("trait methods added to strings should be available after cloning", () => {
-    class MyString extends String {
-        greet() {
-            return "hello"
-        }
-    }
-    const str = new MyString("hello")
-    expect(str.$clone().greet()).toBe("hello")
+    it("returns bigints when the index is a bigint", () => {
+        expect($stringGetCharCode("héllo", 1)).toEqual(["some", 233])
+        expect($stringGetCharCode("héllo", 1n)).toEqual(["some", 233n])
+        expect($stringGetCharCode("héllo", 9n)).toEqual(["none"])
+    })
 })
@@ -0,0 +1,34 @@
+import { $Map } from "./map.js"
+import { $Set } from "./set.js"
+
+/**
+ * Buri values are compared by structure rather than by reference, so maps and sets
+ * can't use JS values directly as keys. Instead, every key is converted into a string
+ * that is the same for any two structurally equal values.
+ */
+export const structuralKey = (value) => {
+    if (typeof value === "number" || value instanceof Number) {
+        return `n${value.valueOf()}`
+    }
+    if (typeof value === "bigint") {
+        return `i${value}`
+    }
+    if (typeof value === "string" || value instanceof String) {
+        return `s${JSON.stringify(value.valueOf())}`
+    }
+    if (typeof value === "boolean" || value instanceof Boolean) {
+        return `b${value.valueOf()}`
+    }
+    if (Array.isArray(value)) {
+        return `[${value.map(structuralKey).join(",")}]`
+    }
+    if (value instanceof $Map) {
+        const entries = [...value.$entries.keys()].sort()
+        return `m[${entries.map((key) => `${key}:${structuralKey(value.$entries.get(key)[1])}`).join(",")}]`
+    }
+    if (value instanceof $Set) {
+        return `t[${[...value.$elements.keys()].sort().join(",")}]`
+    }
+    const fields = Object.keys(value).sort()
+    return `{${fields.map((field) => `${JSON.stringify(field)}:${structuralKey(value[field])}`).join(",")}}`
+}
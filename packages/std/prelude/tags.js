@@ -0,0 +1,28 @@
+/**
+ * Compact builds represent tags by an integer computed from the tag's name, so that
+ * every module agrees on it without sharing a table. Tags without contents are just
+ * the integer, while tags with contents are arrays of the integer followed by the
+ * contents.
+ *
+ * ```buri
+ * first = [1, 2]:get(0)
+ * ```
+ *
+ * The prelude always returns tags with their names, so compact builds convert them:
+ *
+ * ```js
+ * const Bfirst = $compactTag($listGet([1, 2], 0))
+ * ```
+ */
+
+/** A 32-bit FNV-1a hash of the name, cut down to 30 bits so engines store it unboxed. */
+export const $getTagDiscriminant = (name) => {
+    let hash = 0x811c9dc5
+    for (let index = 0; index < name.length; index++) {
+        hash = Math.imul(hash ^ name.charCodeAt(index), 0x01000193)
+    }
+    return hash & 0x3fffffff
+}
+
+export const $compactTag = (tag) =>
+    tag.length === 1 ? $getTagDiscriminant(tag[0]) : [$getTagDiscriminant(tag[0]), ...tag.slice(1)]
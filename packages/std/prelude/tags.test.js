@@ -0,0 +1,27 @@
+import { describe, expect, it } from "bun:test"
+import { $compactTag, $getTagDiscriminant } from "./tags.js"
+
+describe("$getTagDiscriminant", () => {
+    it("matches the compiler", () => {
+        expect($getTagDiscriminant("none")).toBe(0x2da7afdb)
+        expect($getTagDiscriminant("some")).toBe(0x33611c71)
+    })
+
+    it("is a small non-negative integer", () => {
+        for (const name of ["a", "red", "someVeryLongTagName"]) {
+            const discriminant = $getTagDiscriminant(name)
+            expect(discriminant).toBeGreaterThanOrEqual(0)
+            expect(discriminant).toBeLessThan(2 ** 30)
+        }
+    })
+})
+
+describe("$compactTag", () => {
+    it("converts tags without contents to their discriminant", () => {
+        expect($compactTag(["none"])).toBe($getTagDiscriminant("none"))
+    })
+
+    it("keeps the contents of other tags", () => {
+        expect($compactTag(["some", 3])).toEqual([$getTagDiscriminant("some"), 3])
+    })
+})
@@ -1,7 +1,9 @@
 mod nodes;
 mod parser_input;
 mod parser_node;
+mod syntax_tree;
 
 pub use nodes::*;
 pub use parser_input::*;
 pub use parser_node::*;
+pub use syntax_tree::*;
@@ -121,6 +121,18 @@ pub struct ImportValue<'a> {
     pub identifiers: Vec<ImportedIdentifier<'a>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapEntryValue<'a> {
+    pub key: Expression<'a>,
+    pub value: Expression<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapTypeValue<'a> {
+    pub key_type: TypeExpression<'a>,
+    pub value_type: TypeExpression<'a>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RecordValue<'a> {
     /// The name of the record.
@@ -155,6 +167,13 @@ pub struct TagTypeValue<'a> {
     pub contents: Vec<TypeExpression<'a>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TupleDeclarationValue<'a> {
+    pub identifiers: Vec<IdentifierNode<'a>>,
+    pub type_expression: Option<TypeExpression<'a>>,
+    pub expression: Box<Expression<'a>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypeDeclarationValue<'a> {
     pub identifier: TypeIdentifierNode<'a>,
@@ -218,6 +237,8 @@ pub type EnumLiteralNode<'a> = ParsedNode<'a, EnumLiteralValue<'a>>;
 pub type EnumVariantTypeNode<'a> = ParsedNode<'a, EnumVariantTypeValue<'a>>;
 pub type EnumTypeNode<'a> = ParsedNode<'a, EnumTypeValue<'a>>;
 pub type FunctionNode<'a> = ParsedNode<'a, FunctionValue<'a>>;
+// stored as written (minus underscores) because f64 does not implement Eq
+pub type FloatNode<'a> = ParsedNode<'a, String>;
 pub type FunctionApplicationArgumentsNode<'a> =
     ParsedNode<'a, FunctionApplicationArgumentsValue<'a>>;
 pub type FunctionArgumentNode<'a> = ParsedNode<'a, FunctionArgumentValue<'a>>;
@@ -229,14 +250,21 @@ pub type ImportNode<'a> = ParsedNode<'a, ImportValue<'a>>;
 pub type IntegerNode<'a> = ParsedNode<'a, u64>;
 pub type ListNode<'a> = ParsedNode<'a, Vec<Expression<'a>>>;
 pub type ListTypeNode<'a> = ParsedNode<'a, TypeExpression<'a>>;
+pub type MapNode<'a> = ParsedNode<'a, Vec<MapEntryValue<'a>>>;
+pub type MapTypeNode<'a> = ParsedNode<'a, MapTypeValue<'a>>;
 pub type RecordAssignmentNode<'a> = ParsedNode<'a, RecordAssignmentValue<'a>>;
 pub type RecordNode<'a> = ParsedNode<'a, Vec<RecordValue<'a>>>;
 pub type RecordTypeNode<'a> = ParsedNode<'a, Vec<RecordTypeValue<'a>>>;
+pub type SetNode<'a> = ParsedNode<'a, Vec<Expression<'a>>>;
+pub type SetTypeNode<'a> = ParsedNode<'a, TypeExpression<'a>>;
 pub type StringLiteralNode<'a> = ParsedNode<'a, String>;
 pub type TagIdentifierNode<'a> = ParsedNode<'a, String>;
 pub type TagGroupTypeNode<'a> = ParsedNode<'a, Vec<TagTypeNode<'a>>>;
 pub type TagNode<'a> = ParsedNode<'a, TagValue<'a>>;
 pub type TagTypeNode<'a> = ParsedNode<'a, TagTypeValue<'a>>;
+pub type TupleNode<'a> = ParsedNode<'a, Vec<Expression<'a>>>;
+pub type TupleDeclarationNode<'a> = ParsedNode<'a, TupleDeclarationValue<'a>>;
+pub type TupleTypeNode<'a> = ParsedNode<'a, Vec<TypeExpression<'a>>>;
 pub type TypeIdentifierNode<'a> = ParsedNode<'a, String>;
 pub type TypeDeclarationNode<'a> = ParsedNode<'a, TypeDeclarationValue<'a>>;
 pub type UnaryOperatorNode<'a> = ParsedNode<'a, UnaryOperatorValue<'a>>;
@@ -249,16 +277,21 @@ pub enum Expression<'a> {
     Block(BlockNode<'a>),
     Declaration(DeclarationNode<'a>),
     EnumLiteral(EnumLiteralNode<'a>),
+    Float(FloatNode<'a>),
     Function(FunctionNode<'a>),
     FunctionApplicationArguments(FunctionApplicationArgumentsNode<'a>),
     Identifier(IdentifierNode<'a>),
     If(IfNode<'a>),
     Integer(IntegerNode<'a>),
     List(ListNode<'a>),
+    Map(MapNode<'a>),
     Record(RecordNode<'a>),
     RecordAssignment(RecordAssignmentNode<'a>),
+    Set(SetNode<'a>),
     StringLiteral(StringLiteralNode<'a>),
     Tag(TagNode<'a>),
+    Tuple(TupleNode<'a>),
+    TupleDeclaration(TupleDeclarationNode<'a>),
     TypeDeclaration(TypeDeclarationNode<'a>),
     UnaryOperator(UnaryOperatorNode<'a>),
     When(WhenNode<'a>),
@@ -270,8 +303,11 @@ pub enum TypeExpression<'a> {
     Function(FunctionTypeNode<'a>),
     Identifier(TypeIdentifierNode<'a>),
     List(Box<ListTypeNode<'a>>),
+    Map(Box<MapTypeNode<'a>>),
     Record(RecordTypeNode<'a>),
+    Set(Box<SetTypeNode<'a>>),
     TagGroup(TagGroupTypeNode<'a>),
+    Tuple(TupleTypeNode<'a>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -0,0 +1,173 @@
+use crate::DocumentNode;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// Spaces and tabs.
+    Whitespace,
+    /// `\n` or `\r\n`.
+    Newline,
+    /// `--` up to the end of the line, without the line ending.
+    Comment,
+}
+
+/// Text which doesn't change what a file means.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia<'a> {
+    pub kind: TriviaKind,
+    pub text: &'a str,
+    /// Where the text starts in the source, in bytes.
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Identifier,
+    TypeIdentifier,
+    /// e.g. `#hello`
+    TagIdentifier,
+    Integer,
+    Float,
+    StringLiteral,
+    /// The quoted path of an import.
+    ImportPath,
+    /// e.g. `if`, `Map` or `@export`
+    Keyword,
+    /// Operators and brackets, e.g. `**` or `(`.
+    Punctuation,
+    /// An empty token which holds the trivia at the end of the file.
+    EndOfFile,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxToken<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// Where the text starts in the source, in bytes.
+    pub offset: usize,
+    /// The trivia between the previous token and this one, other than what the
+    /// previous token took as trailing trivia.
+    pub leading_trivia: Vec<Trivia<'a>>,
+    /// The trivia after this token up to the end of its line, such as a comment.
+    pub trailing_trivia: Vec<Trivia<'a>>,
+}
+
+impl<'a> SyntaxToken<'a> {
+    #[must_use]
+    pub const fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.text.len()
+    }
+
+    /// The leading and trailing trivia, in the order they appear in.
+    pub fn trivia(&self) -> impl Iterator<Item = &Trivia<'a>> {
+        self.leading_trivia.iter().chain(&self.trailing_trivia)
+    }
+}
+
+/// The syntax which an `ast` node was parsed from. Leaves such as identifiers and
+/// literals are tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxKind {
+    Document,
+    Import,
+    TypeDeclaration,
+    /// A variable declaration, including its `@export` decorator.
+    Declaration,
+    TupleDeclaration,
+    BinaryOperator,
+    Block,
+    EnumLiteral,
+    Function,
+    FunctionArgument,
+    FunctionApplicationArguments,
+    If,
+    List,
+    Map,
+    MapEntry,
+    Record,
+    RecordField,
+    RecordAssignment,
+    Set,
+    Tag,
+    Tuple,
+    UnaryOperator,
+    When,
+    WhenCase,
+    EnumType,
+    EnumVariantType,
+    FunctionType,
+    ListType,
+    MapType,
+    RecordType,
+    RecordFieldType,
+    SetType,
+    TagGroupType,
+    TagType,
+    TupleType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyntaxElement<'a> {
+    Node(SyntaxNode<'a>),
+    Token(SyntaxToken<'a>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxNode<'a> {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement<'a>>,
+}
+
+impl<'a> SyntaxNode<'a> {
+    /// The node's tokens in source order, including those of its descendants.
+    #[must_use]
+    pub fn tokens(&self) -> Vec<&SyntaxToken<'a>> {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    fn collect_tokens<'n>(&'n self, tokens: &mut Vec<&'n SyntaxToken<'a>>) {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(tokens),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+
+    /// The node's source text, including the trivia around its tokens.
+    #[must_use]
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for token in self.tokens() {
+            for trivia in &token.leading_trivia {
+                text.push_str(trivia.text);
+            }
+            text.push_str(token.text);
+            for trivia in &token.trailing_trivia {
+                text.push_str(trivia.text);
+            }
+        }
+        text
+    }
+
+    /// Where the node's tokens are in the source, without the trivia around them.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        let tokens = self.tokens();
+        match (tokens.first(), tokens.last()) {
+            (Some(first), Some(last)) => first.offset..last.range().end,
+            _ => 0..0,
+        }
+    }
+}
+
+/// A file's lossless syntax tree, along with the `ast` document it was built from.
+/// The document is parsed first, and the tree is derived from its source ranges,
+/// so both always describe the same code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxTree<'a> {
+    pub root: SyntaxNode<'a>,
+    pub document: DocumentNode<'a>,
+}
@@ -1,15 +1,20 @@
-use js_backend::print_js_document;
+use js_backend::{
+    check_integer_literals, check_tag_discriminants, fold_constants, inline_functions,
+    print_js_document, print_js_document_with_source_map, print_typescript_declarations,
+    remove_unused_declarations, specialize_generic_functions,
+};
 use parser::parse_buri_file;
 use type_checker_resolver::resolve_concrete_types;
 use type_checker_translator::apply_constraints;
+use typed_ast::ConcreteDocument;
 
-/// Compiles a single Buri file. Do not use to compile Buri programs with
-/// multiple files.
-///
-/// This function accepts the string contents of the Buri file, then returns
-/// the compiled JS output (or an error if the input is invalid). The caller
-/// must read the Buri file itself—this function does not do that.
-pub fn compile_buri_file(contents: &str) -> Result<String, String> {
+pub use js_backend::{
+    IntegerRepresentation, ModuleFormat, OutputStyle, Prelude, PrinterConfiguration, SourceMap,
+    TagRepresentation, DEFAULT_PRELUDE_SPECIFIER,
+};
+
+/// Parses and type checks the Buri file, without changing it for the JS backend.
+pub fn type_check_buri_file(contents: &str) -> Result<ConcreteDocument<'_>, String> {
     let parsed_ast = match parse_buri_file(contents) {
         Ok(ast) => ast,
         Err(error) => {
@@ -19,6 +24,104 @@ pub fn compile_buri_file(contents: &str) -> Result<String, String> {
         }
     };
     let (generic_document, type_schema) = apply_constraints(parsed_ast)?;
-    let concrete_document = resolve_concrete_types(type_schema, generic_document);
-    Ok(print_js_document(&concrete_document))
+    Ok(resolve_concrete_types(type_schema, generic_document))
+}
+
+/// Type checks the Buri file, folds its constants, then removes the declarations
+/// which nothing exported uses. Returns the document along with a warning for each removed declaration.
+///
+/// Generic functions are then copied for the calls which pass them `Float`s, and
+/// small functions are inlined. This happens after the warnings are collected, so
+/// the helpers which are removed once every call is inlined aren't reported as unused.
+///
+/// Fails if an integer literal or the tags can't be represented exactly in the
+/// configured representation.
+fn compile_to_concrete_document<'a>(
+    contents: &'a str,
+    configuration: &PrinterConfiguration,
+) -> Result<(ConcreteDocument<'a>, Vec<String>), String> {
+    let mut document = type_check_buri_file(contents)?;
+    if configuration.integer_representation == IntegerRepresentation::Number {
+        check_integer_literals(&document)?;
+    }
+    fold_constants(&mut document);
+    let warnings = remove_unused_declarations(&mut document);
+    specialize_generic_functions(&mut document);
+    inline_functions(&mut document);
+    fold_constants(&mut document);
+    remove_unused_declarations(&mut document);
+    if configuration.tag_representation == TagRepresentation::Integers {
+        check_tag_discriminants(&[&document])?;
+    }
+    Ok((document, warnings))
+}
+
+/// The paths of the files which the Buri file imports, as they are written in it.
+pub fn get_imported_paths(contents: &str) -> Result<Vec<String>, String> {
+    let parsed_ast =
+        parse_buri_file(contents).map_err(|error| format!("Parsing Error: {error}"))?;
+    Ok(parsed_ast
+        .value
+        .imports
+        .iter()
+        .map(|import| import.value.path.to_string())
+        .collect())
+}
+
+/// Checks that no two tags of a program's files have the same integer
+/// representation. Compiling a file with integer tags only checks its own tags, but
+/// the files of a program pass tags to each other.
+pub fn check_program_tag_discriminants(files: &[String]) -> Result<(), String> {
+    let documents = files
+        .iter()
+        .map(|contents| type_check_buri_file(contents))
+        .collect::<Result<Vec<_>, _>>()?;
+    check_tag_discriminants(&documents.iter().collect::<Vec<_>>())
+}
+
+/// Compiles a single Buri file. Do not use to compile Buri programs with
+/// multiple files.
+///
+/// This function accepts the string contents of the Buri file, then returns
+/// the compiled JS output (or an error if the input is invalid). The caller
+/// must read the Buri file itself—this function does not do that.
+pub fn compile_buri_file(
+    contents: &str,
+    configuration: &PrinterConfiguration,
+) -> Result<String, String> {
+    let (document, _) = compile_to_concrete_document(contents, configuration)?;
+    Ok(print_js_document(&document, configuration))
+}
+
+/// Everything which is written out when compiling a Buri file.
+pub struct CompiledBuriFile {
+    pub javascript: String,
+    /// Links the compiled JS back to the Buri file.
+    pub source_map: SourceMap,
+    /// Describes the exported values of the compiled JS to TypeScript.
+    pub typescript_declarations: String,
+    /// Problems which don't stop the file from compiling, such as unused declarations.
+    pub warnings: Vec<String>,
+}
+
+/// Compiles a single Buri file like `compile_buri_file`, and also returns the
+/// files which are written alongside the compiled JS.
+///
+/// `source` is the path of the Buri file relative to the source map, and `file`
+/// is the name of the JS file the output will be written to.
+pub fn compile_buri_file_with_artifacts(
+    contents: &str,
+    configuration: &PrinterConfiguration,
+    source: &str,
+    file: &str,
+) -> Result<CompiledBuriFile, String> {
+    let (document, warnings) = compile_to_concrete_document(contents, configuration)?;
+    let (javascript, source_map) =
+        print_js_document_with_source_map(&document, configuration, source, file);
+    Ok(CompiledBuriFile {
+        javascript,
+        source_map,
+        typescript_declarations: print_typescript_declarations(&document, configuration),
+        warnings,
+    })
 }
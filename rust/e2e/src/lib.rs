@@ -1,4 +1,4 @@
-use compiler::compile_buri_file;
+use compiler::{compile_buri_file, PrinterConfiguration, TagRepresentation};
 use std::fs;
 use std::io::Write;
 use std::{
@@ -68,7 +68,7 @@ fn build_valid_files(
     let mut passed_count = 0;
     for file_path in files {
         if let Ok(contents) = std::fs::read_to_string(file_path.path()) {
-            match compile_buri_file(&contents) {
+            match compile_buri_file(&contents, &get_test_configuration()) {
                 Ok(new_contents) => {
                     let mut output_path =
                         workspace_directory.join(PathBuf::from(".buri/dist").join(PathBuf::from(
@@ -127,7 +127,7 @@ fn build_invalid_files(workspace_directory: &Path, directory: &Path) -> (usize,
     let mut passed_count = 0;
     for file_path in files {
         if let Ok(contents) = std::fs::read_to_string(file_path.path()) {
-            if compile_buri_file(&contents).is_ok() {
+            if compile_buri_file(&contents, &get_test_configuration()).is_ok() {
                 successful_builds.push(dir_entry_to_string(&file_path, workspace_directory));
             } else {
                 println!(
@@ -143,6 +143,14 @@ fn build_invalid_files(workspace_directory: &Path, directory: &Path) -> (usize,
     (passed_count, successful_builds)
 }
 
+/// The JS tests build the tags which they compare with by name.
+fn get_test_configuration() -> PrinterConfiguration {
+    PrinterConfiguration {
+        tag_representation: TagRepresentation::Names,
+        ..PrinterConfiguration::default()
+    }
+}
+
 #[allow(clippy::result_unit_err)] // we just care if it passes or errors
 pub fn build_tests() -> Result<String, String> {
     let (workspace_directory, valid_directory, invalid_directory) = get_directories()?;
@@ -179,9 +187,483 @@ pub fn build_tests() -> Result<String, String> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use compiler::{compile_buri_file_with_artifacts, type_check_buri_file, Prelude};
+    use formatter::format_buri_file;
+    use interpreter::{evaluate_document, Value};
+    use parser::parse_syntax_tree;
+    use std::process::Command;
+    use typed_ast::ConcreteType;
+
+    /// The Buri files which the tests expect to build.
+    fn valid_buri_files() -> Vec<PathBuf> {
+        let (_, valid_directory, _) = get_directories().unwrap();
+        WalkDir::new(valid_directory)
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .filter(|item| {
+                matches!(
+                    item.path().extension(),
+                    Some(os_str) if os_str.eq_ignore_ascii_case("buri")
+                )
+            })
+            .map(DirEntry::into_path)
+            .collect()
+    }
+
+    fn get_file_url(path: &Path) -> String {
+        format!("file://{}", path.display())
+    }
+
+    /// Where the tests write the JS which they run.
+    fn get_temporary_directory() -> PathBuf {
+        let directory = std::env::temp_dir().join("buri-e2e");
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    /// The name of the JS module which a test file is compiled to.
+    fn get_module_name(file_path: &Path) -> String {
+        let (workspace_directory, _, _) = get_directories().unwrap();
+        let name = file_path
+            .strip_prefix(workspace_directory)
+            .unwrap()
+            .with_extension("mjs");
+        name.display().to_string().replace(['/', '\\'], "-")
+    }
+
+    /// Runs a JS module with Node, returning what it printed. Fails if the module
+    /// throws.
+    fn run_javascript(name: &str, source: &str) -> String {
+        let path = get_temporary_directory().join(name);
+        fs::write(&path, source).unwrap();
+        let output = Command::new("node").arg(&path).output().unwrap();
+        assert!(
+            output.status.success(),
+            "{name}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    fn print_javascript_string(value: &str) -> String {
+        let characters = value
+            .chars()
+            .map(|character| {
+                if character.is_ascii_alphanumeric() || " ./-_".contains(character) {
+                    character.to_string()
+                } else {
+                    format!("\\u{{{:x}}}", u32::from(character))
+                }
+            })
+            .collect::<String>();
+        format!("\"{characters}\"")
+    }
+
+    fn print_javascript_array<'v>(
+        elements: impl IntoIterator<Item = (&'v Value<'v>, &'v ConcreteType)>,
+        tag_representation: TagRepresentation,
+    ) -> Option<String> {
+        let elements = elements
+            .into_iter()
+            .map(|(element, element_type)| {
+                print_javascript_value(element, element_type, tag_representation)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(format!("[{}]", elements.join(",")))
+    }
+
+    /// Prints a value from the interpreter the way the compiled JS represents it.
+    /// Functions can't be compared, so they aren't printed. Integer tags are
+    /// converted by the prelude, which computes their integers like the compiler.
+    fn print_javascript_value<'v>(
+        value: &'v Value<'v>,
+        value_type: &'v ConcreteType,
+        tag_representation: TagRepresentation,
+    ) -> Option<String> {
+        match (value, value_type) {
+            (Value::Boolean(value), _) => Some(value.to_string()),
+            (Value::Integer(value), _) => Some(value.to_string()),
+            (Value::Float(value), _) if value.is_nan() => Some(String::from("NaN")),
+            (Value::Float(value), _) if value.is_infinite() => {
+                Some(String::from(if *value > 0.0 {
+                    "Infinity"
+                } else {
+                    "-Infinity"
+                }))
+            }
+            (Value::Float(value), _) => Some(format!("{value:?}")),
+            (Value::String(value), _) => Some(print_javascript_string(value)),
+            (Value::List(elements), ConcreteType::List(list_type)) => print_javascript_array(
+                elements
+                    .iter()
+                    .map(|element| (element, &list_type.element_type)),
+                tag_representation,
+            ),
+            (Value::Tuple(elements), ConcreteType::Tuple(tuple_type)) => print_javascript_array(
+                elements.iter().zip(&tuple_type.element_types),
+                tag_representation,
+            ),
+            (Value::Record(fields), ConcreteType::Record(record_type)) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, field)| {
+                        print_javascript_value(
+                            field,
+                            record_type.field_types.get(name)?,
+                            tag_representation,
+                        )
+                        .map(|field| format!("{name}:{field}"))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!("{{{}}}", fields.join(",")))
+            }
+            (Value::Map(entries), ConcreteType::Map(map_type)) => {
+                let entries = entries
+                    .iter()
+                    .map(|(key, value)| {
+                        print_javascript_array(
+                            [(key, &map_type.key_type), (value, &map_type.value_type)],
+                            tag_representation,
+                        )
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!("new $Map([{}])", entries.join(",")))
+            }
+            (Value::Set(elements), ConcreteType::Set(set_type)) => print_javascript_array(
+                elements
+                    .iter()
+                    .map(|element| (element, &set_type.element_type)),
+                tag_representation,
+            )
+            .map(|elements| format!("new $Set({elements})")),
+            (Value::Tag(name, contents), ConcreteType::TagUnion(tag_union_type)) => {
+                let contents = print_javascript_array(
+                    contents.iter().zip(tag_union_type.tag_types.get(name)?),
+                    tag_representation,
+                )?;
+                let name = print_javascript_string(name);
+                let tag = match contents.strip_prefix("[]") {
+                    Some(_) => format!("[{name}]"),
+                    None => format!("[{name},{}", &contents[1..]),
+                };
+                Some(match tag_representation {
+                    TagRepresentation::Names => tag,
+                    TagRepresentation::Integers => format!("$compactTag({tag})"),
+                })
+            }
+            (Value::Enum(name, payload), ConcreteType::Enum(enum_type)) => {
+                let mut names = enum_type.variants.keys().collect::<Vec<_>>();
+                names.sort();
+                let index = names.iter().position(|other_name| *other_name == name)?;
+                if enum_type.variants.values().all(Vec::is_empty) {
+                    return Some(index.to_string());
+                }
+                let payload = print_javascript_array(
+                    payload.iter().zip(enum_type.variants.get(name)?),
+                    tag_representation,
+                )?;
+                Some(match payload.strip_prefix("[]") {
+                    Some(_) => format!("[{index}]"),
+                    None => format!("[{index},{}", &payload[1..]),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the arguments of a call whose opening parenthesis ends `source`'s
+    /// prefix, or `None` if the parentheses aren't closed.
+    fn read_call_arguments(source: &str) -> Option<&str> {
+        let mut depth = 1;
+        for (index, character) in source.char_indices() {
+            match character {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return Some(&source[..index]);
+            }
+        }
+        None
+    }
+
+    /// Rewrites the tags which the JS tests build with the `tag` helper, such as
+    /// `tag("rgb", 255, 0, 0)`, as Buri tags.
+    fn replace_tag_helpers(arguments: &str) -> String {
+        let mut result = String::new();
+        let mut rest = arguments;
+        while let Some(start) = rest.find("tag(\"") {
+            result.push_str(&rest[..start]);
+            rest = &rest[start + "tag(\"".len()..];
+            let Some(end) = rest.find('"') else {
+                break;
+            };
+            result.push('#');
+            result.push_str(&rest[..end]);
+            rest = &rest[end + 1..];
+            if let Some(after) = rest.strip_prefix(')') {
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix(", ") {
+                result.push('(');
+                rest = after;
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Declares the results of the calls of the file's exported functions which its
+    /// JS test makes. The JS tests mostly call them with literals, which are Buri
+    /// literals too. Calls which aren't valid Buri, such as those with JS variables
+    /// as arguments, are left out.
+    fn add_tested_calls(file_path: &Path, contents: &str) -> String {
+        let Ok(test_source) = fs::read_to_string(file_path.with_extension("test.js")) else {
+            return contents.to_string();
+        };
+        let document = type_check_buri_file(contents).unwrap();
+        let mut calls = Vec::new();
+        for declaration in &document.variable_declarations {
+            if !declaration.is_exported {
+                continue;
+            }
+            let name = &declaration.declaration.identifier.name;
+            let call_start = format!("B{name}(");
+            for (index, _) in test_source.match_indices(&call_start) {
+                let is_whole_name = !test_source[..index]
+                    .ends_with(|character: char| character.is_ascii_alphanumeric());
+                let arguments = read_call_arguments(&test_source[index + call_start.len()..]);
+                if let (true, Some(arguments)) = (is_whole_name, arguments) {
+                    let call = format!("{name}({})", replace_tag_helpers(arguments));
+                    if !calls.contains(&call) {
+                        calls.push(call);
+                    }
+                }
+            }
+        }
+        let mut contents = contents.to_string();
+        let mut added_count = 0;
+        for call in calls {
+            let with_call = format!("{contents}\n\n@export\ntestedCall{added_count} = {call}\n");
+            if type_check_buri_file(&with_call).is_ok() {
+                contents = with_call;
+                added_count += 1;
+            }
+        }
+        contents
+    }
+
+    /// Writes the file compiled to JS next to the scripts which import it, and
+    /// returns a JS object of the values which the interpreter gives its exported
+    /// constants.
+    fn prepare_interpreted_values_check(
+        file_path: &Path,
+        module_name: &str,
+        contents: &str,
+        configuration: &PrinterConfiguration,
+    ) -> String {
+        let document = type_check_buri_file(contents).unwrap();
+        let values = evaluate_document(&document);
+        assert!(
+            values.is_ok(),
+            "{}: {:?}",
+            file_path.display(),
+            values.err()
+        );
+        let values = values.unwrap();
+        let expected_values = document
+            .variable_declarations
+            .iter()
+            .filter(|declaration| declaration.is_exported)
+            .filter_map(|declaration| {
+                let declaration = &declaration.declaration;
+                let name = &declaration.identifier.name;
+                print_javascript_value(
+                    &values[name],
+                    &declaration.declaration_type,
+                    configuration.tag_representation,
+                )
+                .map(|value| format!("B{name}:{value}"))
+            })
+            .collect::<Vec<_>>();
+        fs::write(
+            get_temporary_directory().join(module_name),
+            compile_buri_file(contents, configuration).unwrap(),
+        )
+        .unwrap();
+        format!("{{{}}}", expected_values.join(","))
+    }
 
     #[test]
     fn testing() {
         assert!(build_tests().is_ok());
     }
+
+    #[test]
+    fn valid_files_compile_to_the_same_output_every_time() {
+        for file_path in valid_buri_files() {
+            let contents = std::fs::read_to_string(&file_path).unwrap();
+            let compile = || {
+                compile_buri_file_with_artifacts(
+                    &contents,
+                    &get_test_configuration(),
+                    "source.buri",
+                    "source.mjs",
+                )
+                .map(|compiled| {
+                    (
+                        compiled.javascript,
+                        compiled.source_map.to_json(),
+                        compiled.typescript_declarations,
+                        compiled.warnings,
+                    )
+                })
+            };
+            assert_eq!(compile(), compile(), "{}", file_path.display());
+        }
+    }
+
+    /// Checks that the interpreter and the compiled JS agree on every exported value
+    /// of the valid files which don't import anything, and on the results of the
+    /// calls which their JS tests make.
+    fn check_interpreted_values(tag_representation: TagRepresentation, name: &str) {
+        let (workspace_directory, _, _) = get_directories().unwrap();
+        let prelude_directory = workspace_directory.join("packages/std/prelude");
+        let configuration = PrinterConfiguration {
+            prelude: Prelude::Import(get_file_url(&prelude_directory.join("index.js"))),
+            tag_representation,
+            ..PrinterConfiguration::default()
+        };
+        let mut script = vec![
+            format!(
+                "import {{ $Map, $Set, $compactTag }} from '{}'",
+                get_file_url(&prelude_directory.join("index.js"))
+            ),
+            format!(
+                "import {{ structuralKey }} from '{}'",
+                get_file_url(&prelude_directory.join("structural-key.js"))
+            ),
+        ];
+        let mut checks = Vec::new();
+        for file_path in valid_buri_files() {
+            let contents =
+                add_tested_calls(&file_path, &std::fs::read_to_string(&file_path).unwrap());
+            let document = type_check_buri_file(&contents).unwrap();
+            if document.imports.is_empty() {
+                // The tests run at the same time, so each writes its own modules.
+                let module_name = format!("{name}-{}", get_module_name(&file_path));
+                let expected_values = prepare_interpreted_values_check(
+                    &file_path,
+                    &module_name,
+                    &contents,
+                    &configuration,
+                );
+                script.push(format!(
+                    "import * as file{} from './{module_name}'",
+                    checks.len()
+                ));
+                checks.push(format!(
+                    "[{}, file{}, {expected_values}]",
+                    print_javascript_string(&file_path.display().to_string()),
+                    checks.len()
+                ));
+            }
+        }
+        script.push(format!("const checks = [{}]", checks.join(",\n")));
+        script.push(String::from(
+            "for (const [file, actual, expected] of checks) {
+    for (const [name, value] of Object.entries(expected)) {
+        if (structuralKey(actual[name]) !== structuralKey(value)) {
+            console.log(file + ': ' + name + ' is ' + structuralKey(actual[name]) + ' instead of ' + structuralKey(value))
+        }
+    }
+}",
+        ));
+        assert_eq!(
+            run_javascript(&format!("{name}.check.mjs"), &script.join("\n")),
+            ""
+        );
+    }
+
+    #[test]
+    fn valid_files_without_imports_can_be_evaluated() {
+        check_interpreted_values(TagRepresentation::Names, "interpreted-values");
+    }
+
+    #[test]
+    fn valid_files_without_imports_can_be_evaluated_with_integer_tags() {
+        check_interpreted_values(TagRepresentation::Integers, "integer-tags");
+    }
+
+    #[test]
+    fn valid_files_without_imports_run_with_the_inlined_prelude() {
+        let configuration = PrinterConfiguration {
+            prelude: Prelude::Inline,
+            ..get_test_configuration()
+        };
+        let mut script = Vec::new();
+        for file_path in valid_buri_files() {
+            let contents = std::fs::read_to_string(&file_path).unwrap();
+            let document = type_check_buri_file(&contents).unwrap();
+            if document.imports.is_empty() {
+                // The other tests write the same files with the prelude imported.
+                let module_name = format!("inline-prelude-{}", get_module_name(&file_path));
+                fs::write(
+                    get_temporary_directory().join(&module_name),
+                    compile_buri_file(&contents, &configuration).unwrap(),
+                )
+                .unwrap();
+                script.push(format!("import './{module_name}'"));
+            }
+        }
+        assert_eq!(
+            run_javascript("inline-prelude.check.mjs", &script.join("\n")),
+            ""
+        );
+    }
+
+    #[test]
+    fn formatting_valid_files_keeps_their_output() {
+        for file_path in valid_buri_files() {
+            let contents = std::fs::read_to_string(&file_path).unwrap();
+            let formatted = format_buri_file(&contents);
+            assert!(
+                formatted.is_ok(),
+                "{}: {:?}",
+                file_path.display(),
+                formatted.err()
+            );
+            let formatted = formatted.unwrap();
+            let compile = |contents: &str| {
+                compile_buri_file_with_artifacts(
+                    contents,
+                    &get_test_configuration(),
+                    "source.buri",
+                    "source.mjs",
+                )
+                .map(|compiled| (compiled.javascript, compiled.typescript_declarations))
+            };
+            assert_eq!(
+                compile(&formatted),
+                compile(&contents),
+                "{}",
+                file_path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn syntax_trees_of_valid_files_keep_every_byte() {
+        for file_path in valid_buri_files() {
+            let contents = std::fs::read_to_string(&file_path).unwrap();
+            let tree = parse_syntax_tree(&contents);
+            assert!(tree.is_ok(), "{}: {:?}", file_path.display(), tree.err());
+            assert_eq!(
+                tree.unwrap().root.text(),
+                contents,
+                "{}",
+                file_path.display()
+            );
+        }
+    }
 }
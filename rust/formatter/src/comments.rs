@@ -0,0 +1,130 @@
+use ast::{SyntaxNode, TriviaKind};
+use std::ops::Range;
+
+/// A `--` comment, from the trivia of the file's syntax tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comment<'a> {
+    /// Where the comment's `--` starts in the source.
+    pub offset: usize,
+    pub text: &'a str,
+}
+
+pub fn collect_comments<'a>(root: &SyntaxNode<'a>) -> Vec<Comment<'a>> {
+    root.tokens()
+        .iter()
+        .flat_map(|token| token.trivia())
+        .filter(|trivia| trivia.kind == TriviaKind::Comment)
+        .map(|trivia| Comment {
+            offset: trivia.offset,
+            text: trivia.text.trim_end(),
+        })
+        .collect()
+}
+
+/// Whether `offset` starts its line and the line before it is blank.
+pub fn follows_blank_line(source: &str, offset: usize) -> bool {
+    let Some(line_start) = source[..offset].rfind('\n') else {
+        return false;
+    };
+    if !source[line_start..offset].trim().is_empty() {
+        return false;
+    }
+    let previous_line_start = source[..line_start]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    source[previous_line_start..line_start].trim().is_empty()
+}
+
+/// The comments which haven't been printed yet, in the order they appear in.
+pub struct Comments<'a> {
+    source: &'a str,
+    all: Vec<Comment<'a>>,
+    next: usize,
+}
+
+impl<'a> Comments<'a> {
+    pub const fn new(source: &'a str, comments: Vec<Comment<'a>>) -> Self {
+        Self {
+            source,
+            all: comments,
+            next: 0,
+        }
+    }
+
+    /// Marks the comments which have been printed so far, so that printing can be
+    /// retried with a different layout.
+    pub const fn checkpoint(&self) -> usize {
+        self.next
+    }
+
+    pub const fn restore(&mut self, checkpoint: usize) {
+        self.next = checkpoint;
+    }
+
+    fn pending(&self) -> &[Comment<'a>] {
+        &self.all[self.next..]
+    }
+
+    pub fn any_within(&self, range: &Range<usize>) -> bool {
+        self.pending()
+            .iter()
+            .any(|comment| range.contains(&comment.offset))
+    }
+
+    /// Takes the comments which start before the offset, including any which
+    /// couldn't be printed where they were written.
+    pub fn take_before(&mut self, offset: usize) -> Vec<Comment<'a>> {
+        let count = self
+            .pending()
+            .iter()
+            .take_while(|comment| comment.offset < offset)
+            .count();
+        let comments = self.pending()[..count].to_vec();
+        self.next += count;
+        comments
+    }
+
+    /// Takes the comment at the end of the line which `offset` is on, if it is the
+    /// next one.
+    pub fn take_trailing(&mut self, offset: usize) -> Option<&'a str> {
+        let comment = self.pending().first().copied()?;
+        if comment.offset < offset || self.source[offset..comment.offset].contains('\n') {
+            return None;
+        }
+        self.next += 1;
+        Some(comment.text)
+    }
+
+    pub fn take_rest(&mut self) -> Vec<Comment<'a>> {
+        self.take_before(usize::MAX)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use parser::parse_syntax_tree;
+
+    fn comments(source: &str) -> Vec<Comment<'_>> {
+        collect_comments(&parse_syntax_tree(source).unwrap().root)
+    }
+
+    #[test]
+    fn dashes_in_strings_and_import_paths_are_not_comments() {
+        let source = "import a from \"a\\\"-- b\nc = \"--\\\" --\" -- comment  \n-- another";
+        let texts: Vec<_> = comments(source)
+            .iter()
+            .map(|comment| comment.text)
+            .collect();
+        assert_eq!(texts, ["-- b", "-- comment", "-- another"]);
+    }
+
+    #[test]
+    fn trailing_comments_are_on_the_same_line() {
+        let source = "a = 1 -- one\nb = 2\n-- two";
+        let mut comments = Comments::new(source, comments(source));
+        assert_eq!(comments.take_trailing(5), Some("-- one"));
+        assert_eq!(comments.take_trailing(18), None);
+        assert_eq!(comments.take_rest().len(), 1);
+    }
+}
@@ -0,0 +1,567 @@
+use crate::layout::{indent, Position};
+use crate::printer::{Delimiters, Printer};
+use crate::types::print_type;
+use ast::{
+    BinaryOperatorNode, BinaryOperatorSymbol, DeclarationNode, EnumLiteralNode, Expression,
+    FunctionArgumentNode, FunctionNode, IfNode, MapEntryValue, RecordAssignmentNode, RecordValue,
+    TagNode, TupleDeclarationNode, UnaryOperatorNode, UnaryOperatorSymbol, WhenCase, WhenNode,
+};
+use std::ops::Range;
+
+/// The part of the source which an expression was parsed from. Binary operators
+/// only keep the source of their operator and right operand, and blocks start with
+/// their indentation.
+pub fn expression_range(expression: &Expression) -> Range<usize> {
+    match expression {
+        Expression::BinaryOperator(node) => {
+            let end = node.source.to_range().end;
+            expression_range(&node.value.left_child).start
+                ..end.max(expression_range(&node.value.right_child).end)
+        }
+        Expression::Block(node) => match (node.value.first(), node.value.last()) {
+            (Some(first), Some(last)) => expression_range(first).start..expression_range(last).end,
+            _ => node.source.to_range(),
+        },
+        Expression::Declaration(node) => node.source.to_range(),
+        Expression::EnumLiteral(node) => node.source.to_range(),
+        Expression::Float(node) | Expression::StringLiteral(node) => node.source.to_range(),
+        Expression::Function(node) => node.source.to_range(),
+        Expression::FunctionApplicationArguments(node) => node.source.to_range(),
+        Expression::Identifier(node) => node.source.to_range(),
+        Expression::If(node) => node.source.to_range(),
+        Expression::Integer(node) => node.source.to_range(),
+        Expression::List(node) | Expression::Set(node) | Expression::Tuple(node) => {
+            node.source.to_range()
+        }
+        Expression::Map(node) => node.source.to_range(),
+        Expression::Record(node) => node.source.to_range(),
+        Expression::RecordAssignment(node) => node.source.to_range(),
+        Expression::Tag(node) => node.source.to_range(),
+        Expression::TupleDeclaration(node) => node.source.to_range(),
+        Expression::TypeDeclaration(node) => node.source.to_range(),
+        Expression::UnaryOperator(node) => node.source.to_range(),
+        Expression::When(node) => node.source.to_range(),
+    }
+}
+
+/// Mirrors the parser's order of operations, where lower numbers bind tighter.
+const fn order_of_operations(symbol: &BinaryOperatorSymbol) -> u8 {
+    match symbol {
+        BinaryOperatorSymbol::FunctionApplication
+        | BinaryOperatorSymbol::MethodLookup
+        | BinaryOperatorSymbol::FieldLookup => 1,
+        BinaryOperatorSymbol::Power => 2,
+        BinaryOperatorSymbol::Multiply
+        | BinaryOperatorSymbol::Divide
+        | BinaryOperatorSymbol::Modulus => 3,
+        BinaryOperatorSymbol::Add | BinaryOperatorSymbol::Subtract => 4,
+        BinaryOperatorSymbol::Concatenate => 5,
+        BinaryOperatorSymbol::EqualTo
+        | BinaryOperatorSymbol::NotEqualTo
+        | BinaryOperatorSymbol::LessThan
+        | BinaryOperatorSymbol::LessThanOrEqualTo
+        | BinaryOperatorSymbol::GreaterThan
+        | BinaryOperatorSymbol::GreaterThanOrEqualTo => 6,
+        BinaryOperatorSymbol::And => 7,
+        BinaryOperatorSymbol::Or => 8,
+    }
+}
+
+const fn symbol_text(symbol: &BinaryOperatorSymbol) -> &'static str {
+    match symbol {
+        BinaryOperatorSymbol::Add => "+",
+        BinaryOperatorSymbol::Subtract => "-",
+        BinaryOperatorSymbol::Multiply => "*",
+        BinaryOperatorSymbol::Divide => "/",
+        BinaryOperatorSymbol::Modulus => "%",
+        BinaryOperatorSymbol::Power => "**",
+        BinaryOperatorSymbol::Concatenate => "++",
+        BinaryOperatorSymbol::And => "and",
+        BinaryOperatorSymbol::Or => "or",
+        BinaryOperatorSymbol::EqualTo => "==",
+        BinaryOperatorSymbol::NotEqualTo => "!=",
+        BinaryOperatorSymbol::LessThan => "<",
+        BinaryOperatorSymbol::LessThanOrEqualTo => "<=",
+        BinaryOperatorSymbol::GreaterThan => ">",
+        BinaryOperatorSymbol::GreaterThanOrEqualTo => ">=",
+        BinaryOperatorSymbol::FunctionApplication => "",
+        BinaryOperatorSymbol::MethodLookup => ":",
+        BinaryOperatorSymbol::FieldLookup => ".",
+    }
+}
+
+/// Parentheses aren't kept in the syntax tree, so they are added wherever the
+/// operand would otherwise be parsed differently.
+fn operand_needs_parentheses(
+    operand: &Expression,
+    symbol: &BinaryOperatorSymbol,
+    is_left_operand: bool,
+) -> bool {
+    match operand {
+        Expression::BinaryOperator(node) => {
+            let operand_order = order_of_operations(&node.value.symbol);
+            let order = order_of_operations(symbol);
+            let is_right_associative = *symbol == BinaryOperatorSymbol::Power;
+            operand_order > order
+                || (operand_order == order && is_left_operand == is_right_associative)
+        }
+        // `not` takes the lookups and calls after it, e.g. `not a.b`.
+        Expression::UnaryOperator(node) if node.value.symbol == UnaryOperatorSymbol::Not => {
+            matches!(*node.value.child, Expression::BinaryOperator(_))
+                || (is_left_operand
+                    && matches!(
+                        symbol,
+                        BinaryOperatorSymbol::FunctionApplication
+                            | BinaryOperatorSymbol::MethodLookup
+                            | BinaryOperatorSymbol::FieldLookup
+                    ))
+        }
+        Expression::Declaration(_)
+        | Expression::Function(_)
+        | Expression::If(_)
+        | Expression::TupleDeclaration(_)
+        | Expression::TypeDeclaration(_)
+        | Expression::When(_) => true,
+        _ => false,
+    }
+}
+
+fn unary_operand_needs_parentheses(symbol: &UnaryOperatorSymbol, operand: &Expression) -> bool {
+    match operand {
+        Expression::BinaryOperator(node) => {
+            *symbol == UnaryOperatorSymbol::Negative
+                || !matches!(
+                    node.value.symbol,
+                    BinaryOperatorSymbol::FunctionApplication
+                        | BinaryOperatorSymbol::MethodLookup
+                        | BinaryOperatorSymbol::FieldLookup
+                )
+        }
+        // `--` starts a comment.
+        Expression::UnaryOperator(node) => node.value.symbol == UnaryOperatorSymbol::Negative,
+        Expression::If(_) | Expression::When(_) => true,
+        _ => false,
+    }
+}
+
+fn record_value_range(field: &RecordValue) -> Range<usize> {
+    field.identifier.source.to_range().start..expression_range(&field.value).end
+}
+
+fn map_entry_range(entry: &MapEntryValue) -> Range<usize> {
+    expression_range(&entry.key).start..expression_range(&entry.value).end
+}
+
+fn function_argument_range(argument: &FunctionArgumentNode) -> Range<usize> {
+    argument.source.to_range()
+}
+
+const fn brackets<'s>(open: &'s str, close: &'static str) -> Delimiters<'s> {
+    Delimiters {
+        open,
+        close,
+        padded: false,
+        trailing_comma: true,
+        resets_indentation: true,
+    }
+}
+
+const fn braces(open: &str) -> Delimiters<'_> {
+    Delimiters {
+        open,
+        close: "}",
+        padded: true,
+        trailing_comma: true,
+        resets_indentation: true,
+    }
+}
+
+impl Printer<'_> {
+    pub fn print_expression(&mut self, expression: &Expression, position: Position) -> String {
+        match expression {
+            Expression::BinaryOperator(node) => self.print_binary_operator(node, position),
+            Expression::Block(node) => self.print_block(&node.value, position.indentation + 1),
+            Expression::Declaration(node) => self.print_declaration(node, position),
+            Expression::EnumLiteral(node) => self.print_enum_literal(node, position),
+            Expression::Float(node) | Expression::StringLiteral(node) => {
+                node.source.value().to_owned()
+            }
+            Expression::Function(node) => self.print_function(node, position),
+            Expression::FunctionApplicationArguments(node) => self.print_expressions(
+                &Delimiters {
+                    resets_indentation: false,
+                    ..brackets("(", ")")
+                },
+                &node.value.arguments,
+                &node.source.to_range(),
+                position,
+            ),
+            Expression::Identifier(node) => node.value.name.clone(),
+            Expression::If(node) => self.print_if(node, position),
+            Expression::Integer(node) => node.source.value().to_owned(),
+            Expression::List(node) => self.print_expressions(
+                &brackets("[", "]"),
+                &node.value,
+                &node.source.to_range(),
+                position,
+            ),
+            Expression::Map(node) => self.print_delimited(
+                &brackets("Map[", "]"),
+                &node.value,
+                &node.source.to_range(),
+                position,
+                map_entry_range,
+                |printer, entry, position| {
+                    let key = printer.print_expression(&entry.key, position);
+                    let key = format!("{key} => ");
+                    let value = printer.print_expression(&entry.value, position.after(&key));
+                    format!("{key}{value}")
+                },
+            ),
+            Expression::Record(node) => self.print_record_values(
+                &braces("{"),
+                &node.value,
+                &node.source.to_range(),
+                position,
+            ),
+            Expression::RecordAssignment(node) => self.print_record_assignment(node, position),
+            Expression::Set(node) => self.print_expressions(
+                &brackets("Set[", "]"),
+                &node.value,
+                &node.source.to_range(),
+                position,
+            ),
+            Expression::Tag(node) => self.print_tag(node, position),
+            Expression::Tuple(node) => self.print_expressions(
+                &brackets("(", ")"),
+                &node.value,
+                &node.source.to_range(),
+                position,
+            ),
+            Expression::TupleDeclaration(node) => self.print_tuple_declaration(node, position),
+            Expression::TypeDeclaration(node) => format!(
+                "{} = {}",
+                node.value.identifier.value,
+                print_type(&node.value.type_expression)
+            ),
+            Expression::UnaryOperator(node) => self.print_unary_operator(node, position),
+            Expression::When(node) => self.print_when(node, position),
+        }
+    }
+
+    fn print_enum_literal(&mut self, node: &EnumLiteralNode, position: Position) -> String {
+        let name = format!("{}.{}", node.value.qualifier.value, node.value.variant_name);
+        if node.value.payload.is_empty() {
+            return name;
+        }
+        let open = format!("{name}(");
+        self.print_expressions(
+            &Delimiters {
+                resets_indentation: false,
+                ..brackets(&open, ")")
+            },
+            &node.value.payload,
+            &node.source.to_range(),
+            position,
+        )
+    }
+
+    fn print_record_assignment(
+        &mut self,
+        node: &RecordAssignmentNode,
+        position: Position,
+    ) -> String {
+        let open = format!("{{ {} |", node.value.identifier.value.name);
+        if node.value.new_values.is_empty() {
+            return format!("{open} }}");
+        }
+        self.print_record_values(
+            &braces(&open),
+            &node.value.new_values,
+            &node.source.to_range(),
+            position,
+        )
+    }
+
+    fn print_tuple_declaration(
+        &mut self,
+        node: &TupleDeclarationNode,
+        position: Position,
+    ) -> String {
+        let identifiers = node
+            .value
+            .identifiers
+            .iter()
+            .map(|identifier| identifier.value.name.as_str())
+            .collect::<Vec<_>>();
+        let type_annotation = node
+            .value
+            .type_expression
+            .as_ref()
+            .map_or_else(String::new, |type_expression| {
+                format!(": {}", print_type(type_expression))
+            });
+        let prefix = format!("({}){type_annotation} = ", identifiers.join(", "));
+        let value = self.print_expression(&node.value.expression, position.after(&prefix));
+        format!("{prefix}{value}")
+    }
+
+    fn print_unary_operator(&mut self, node: &UnaryOperatorNode, position: Position) -> String {
+        let symbol = match node.value.symbol {
+            UnaryOperatorSymbol::Not => "not ",
+            UnaryOperatorSymbol::Negative => "-",
+        };
+        let child_position = position.after(symbol);
+        let child = if unary_operand_needs_parentheses(&node.value.symbol, &node.value.child) {
+            self.print_parenthesized(&node.value.child, child_position)
+        } else {
+            self.print_expression(&node.value.child, child_position)
+        };
+        format!("{symbol}{child}")
+    }
+
+    fn print_expressions(
+        &mut self,
+        delimiters: &Delimiters,
+        expressions: &[Expression],
+        range: &Range<usize>,
+        position: Position,
+    ) -> String {
+        self.print_delimited(
+            delimiters,
+            expressions,
+            range,
+            position,
+            expression_range,
+            Self::print_expression,
+        )
+    }
+
+    fn print_record_values(
+        &mut self,
+        delimiters: &Delimiters,
+        fields: &[RecordValue],
+        range: &Range<usize>,
+        position: Position,
+    ) -> String {
+        self.print_delimited(
+            delimiters,
+            fields,
+            range,
+            position,
+            record_value_range,
+            |printer, field, position| {
+                let name = format!("{}: ", field.identifier.value.name);
+                let value = printer.print_expression(&field.value, position.after(&name));
+                format!("{name}{value}")
+            },
+        )
+    }
+
+    /// Parentheses reset the indentation which the parser expects.
+    fn print_parenthesized(&mut self, expression: &Expression, position: Position) -> String {
+        let inner = self.print_expression(expression, position.after("(").with_indentation(0));
+        format!("({inner})")
+    }
+
+    fn print_operand(
+        &mut self,
+        operand: &Expression,
+        symbol: &BinaryOperatorSymbol,
+        is_left_operand: bool,
+        position: Position,
+    ) -> String {
+        if operand_needs_parentheses(operand, symbol, is_left_operand) {
+            self.print_parenthesized(operand, position)
+        } else {
+            self.print_expression(operand, position)
+        }
+    }
+
+    fn print_binary_operator(&mut self, node: &BinaryOperatorNode, position: Position) -> String {
+        let symbol = &node.value.symbol;
+        let left = self.print_operand(&node.value.left_child, symbol, true, position);
+        let left = match symbol {
+            BinaryOperatorSymbol::FunctionApplication
+            | BinaryOperatorSymbol::MethodLookup
+            | BinaryOperatorSymbol::FieldLookup => format!("{left}{}", symbol_text(symbol)),
+            _ => format!("{left} {} ", symbol_text(symbol)),
+        };
+        let right = self.print_operand(
+            &node.value.right_child,
+            symbol,
+            false,
+            position.after(&left),
+        );
+        format!("{left}{right}")
+    }
+
+    pub fn print_declaration(&mut self, node: &DeclarationNode, position: Position) -> String {
+        let type_annotation = node
+            .value
+            .type_expression
+            .as_ref()
+            .map_or_else(String::new, |type_expression| {
+                format!(": {}", print_type(type_expression))
+            });
+        let prefix = format!("{}{type_annotation} = ", node.value.identifier.value.name);
+        let value = self.print_expression(&node.value.expression, position.after(&prefix));
+        format!("{prefix}{value}")
+    }
+
+    /// Prints the statements of a block, each on its own line, starting with a newline.
+    pub fn print_block(&mut self, statements: &[Expression], indentation: usize) -> String {
+        let lines = self.print_lines(
+            statements,
+            indentation,
+            expression_range,
+            |_| true,
+            Self::print_expression,
+        );
+        format!("\n{lines}")
+    }
+
+    /// Prints what follows `=>`, `do` or `else`: either a block, or an expression on
+    /// the same line.
+    fn print_body(&mut self, body: &Expression, prefix: &str, position: Position) -> String {
+        if let Expression::Block(node) = body {
+            let block = self.print_block(&node.value, position.indentation + 1);
+            format!("{prefix}{block}")
+        } else {
+            let prefix = format!("{prefix} ");
+            let body = self.print_expression(body, position.after(&prefix));
+            format!("{prefix}{body}")
+        }
+    }
+
+    fn print_function(&mut self, node: &FunctionNode, position: Position) -> String {
+        let arguments = &node.value.arguments;
+        let start = node.source.to_range().start;
+        let end = arguments
+            .last()
+            .map_or(start + 1, |argument| function_argument_range(argument).end);
+        let arguments = self.print_delimited(
+            &Delimiters {
+                resets_indentation: false,
+                ..brackets("(", ")")
+            },
+            arguments,
+            &(start..end),
+            position,
+            function_argument_range,
+            |_, argument, _| {
+                argument.value.argument_type.as_ref().map_or_else(
+                    || argument.value.argument_name.value.name.clone(),
+                    |argument_type| {
+                        format!(
+                            "{}: {}",
+                            argument.value.argument_name.value.name, argument_type.value
+                        )
+                    },
+                )
+            },
+        );
+        let return_type = node
+            .value
+            .return_type
+            .as_ref()
+            .map_or_else(String::new, |return_type| {
+                format!(": {}", return_type.value)
+            });
+        let prefix = format!("{arguments}{return_type} =>");
+        self.print_body(&node.value.body, &prefix, position.after(&arguments))
+    }
+
+    fn print_if(&mut self, node: &IfNode, position: Position) -> String {
+        let condition_position = position.after("if ");
+        let condition = match *node.value.condition {
+            Expression::Declaration(_)
+            | Expression::Function(_)
+            | Expression::If(_)
+            | Expression::TupleDeclaration(_)
+            | Expression::TypeDeclaration(_)
+            | Expression::When(_) => {
+                self.print_parenthesized(&node.value.condition, condition_position)
+            }
+            _ => self.print_expression(&node.value.condition, condition_position),
+        };
+        let prefix = format!("if {condition} do");
+        let mut text = self.print_body(&node.value.path_if_true, &prefix, position);
+        if let Some(path_if_false) = &node.value.path_if_false {
+            let prefix = if matches!(*node.value.path_if_true, Expression::Block(_)) {
+                format!("\n{}else", indent(position.indentation))
+            } else {
+                String::from(" else")
+            };
+            let else_position = position.after(&text);
+            text.push_str(&self.print_body(path_if_false, &prefix, else_position));
+        }
+        text
+    }
+
+    fn print_tag(&mut self, node: &TagNode, position: Position) -> String {
+        let name = format!("#{}", node.value.name.value);
+        if node.value.contents.is_empty() {
+            return name;
+        }
+        let open = format!("{name}(");
+        self.print_expressions(
+            &Delimiters {
+                trailing_comma: false,
+                ..brackets(&open, ")")
+            },
+            &node.value.contents,
+            &node.source.to_range(),
+            position,
+        )
+    }
+
+    fn print_when_case(&mut self, case: &WhenCase, position: Position) -> String {
+        let arguments = case
+            .case_arguments
+            .iter()
+            .map(|argument| {
+                argument.type_expression.as_ref().map_or_else(
+                    || argument.identifier.value.name.clone(),
+                    |type_expression| {
+                        format!(
+                            "{}: {}",
+                            argument.identifier.value.name,
+                            print_type(type_expression)
+                        )
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let arguments = if arguments.is_empty() {
+            String::new()
+        } else {
+            format!("({})", arguments.join(", "))
+        };
+        let prefix = format!("#{}{arguments} do", case.case_name.value);
+        self.print_body(&case.expression, &prefix, position)
+    }
+
+    /// Comments can only follow a `when` case on the same line, since the parser
+    /// doesn't allow comment lines between cases.
+    fn print_when(&mut self, node: &WhenNode, position: Position) -> String {
+        let condition = self.print_expression(&node.value.condition, position.after("when "));
+        let mut text = format!("when {condition} is");
+        self.push_trailing_comment(&mut text, expression_range(&node.value.condition).end);
+        let case_indentation = position.indentation + 1;
+        let case_position = Position::line(case_indentation);
+        for case in &node.value.cases {
+            text.push('\n');
+            text.push_str(&indent(case_indentation));
+            text.push_str(&self.print_when_case(case, case_position));
+            self.push_trailing_comment(&mut text, expression_range(&case.expression).end);
+        }
+        if let Some(default_case) = &node.value.default_case {
+            text.push('\n');
+            text.push_str(&indent(case_indentation));
+            text.push_str(&self.print_body(default_case, "_ do", case_position));
+            self.push_trailing_comment(&mut text, expression_range(default_case).end);
+        }
+        text
+    }
+}
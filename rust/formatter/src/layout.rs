@@ -0,0 +1,56 @@
+/// Lists, records and arguments which would make a line longer than this are split
+/// onto one line per item.
+pub const LINE_WIDTH: usize = 100;
+const INDENTATION: &str = "    ";
+
+/// Where an expression is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// The indentation which the parser expects for the blocks and `when` cases in
+    /// the expression. Brackets reset it to zero, so it can be less than the line's.
+    pub indentation: usize,
+    /// The indentation of the line which the expression starts on.
+    pub line_indentation: usize,
+    /// How many characters come before the expression on its line.
+    pub column: usize,
+}
+
+impl Position {
+    /// The start of a line, such as a statement in a block.
+    pub const fn line(indentation: usize) -> Self {
+        Self {
+            indentation,
+            line_indentation: indentation,
+            column: indentation * INDENTATION.len(),
+        }
+    }
+
+    /// The position right after some printed text.
+    pub fn after(self, text: &str) -> Self {
+        let column = text.rfind('\n').map_or_else(
+            || self.column + width(text),
+            |index| width(&text[index + 1..]),
+        );
+        Self { column, ..self }
+    }
+
+    pub const fn with_indentation(self, indentation: usize) -> Self {
+        Self {
+            indentation,
+            ..self
+        }
+    }
+}
+
+pub fn indent(indentation: usize) -> String {
+    INDENTATION.repeat(indentation)
+}
+
+fn width(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Whether the text fits on the rest of the line.
+pub fn fits(text: &str, column: usize) -> bool {
+    !text.contains('\n') && column + width(text) <= LINE_WIDTH
+}
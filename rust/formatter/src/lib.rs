@@ -0,0 +1,260 @@
+mod comments;
+mod expressions;
+mod layout;
+mod printer;
+mod types;
+
+use ast::{
+    DeclarationNode, Expression, ImportNode, ImportedIdentifier, TopLevelDeclaration,
+    TypeDeclarationNode,
+};
+use comments::{collect_comments, Comments};
+use expressions::expression_range;
+use layout::Position;
+use parser::parse_syntax_tree;
+use printer::Printer;
+use std::ops::Range;
+use types::print_type;
+
+pub use layout::LINE_WIDTH;
+
+const EXPORT_DECORATOR: &str = "@export";
+
+enum DocumentElement<'d, 'a> {
+    Import(&'d ImportNode<'a>),
+    TypeDeclaration(&'d TopLevelDeclaration<TypeDeclarationNode<'a>>),
+    VariableDeclaration(&'d TopLevelDeclaration<DeclarationNode<'a>>),
+    Expression(&'d Expression<'a>),
+}
+
+impl DocumentElement<'_, '_> {
+    /// Exported declarations start at their `@export` decorator.
+    fn range(&self, source: &str) -> Range<usize> {
+        let (range, is_exported) = match self {
+            Self::Import(node) => (node.source.to_range(), false),
+            Self::TypeDeclaration(declaration) => (
+                declaration.declaration.source.to_range(),
+                declaration.is_exported,
+            ),
+            Self::VariableDeclaration(declaration) => (
+                declaration.declaration.source.to_range(),
+                declaration.is_exported,
+            ),
+            Self::Expression(expression) => (expression_range(expression), false),
+        };
+        if is_exported {
+            let start = source[..range.start]
+                .rfind(EXPORT_DECORATOR)
+                .unwrap_or(range.start);
+            start..range.end
+        } else {
+            range
+        }
+    }
+}
+
+fn print_import(import: &ImportNode) -> String {
+    let identifiers = import
+        .value
+        .identifiers
+        .iter()
+        .map(|identifier| match identifier {
+            ImportedIdentifier::Identifier(node) => node.value.name.as_str(),
+            ImportedIdentifier::TypeIdentifier(node) => node.value.as_str(),
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "import {} from \"{}\"",
+        identifiers.join(", "),
+        import.value.path
+    )
+}
+
+fn print_export(is_exported: bool, declaration: &str) -> String {
+    if is_exported {
+        format!("{EXPORT_DECORATOR}\n{declaration}")
+    } else {
+        declaration.to_owned()
+    }
+}
+
+impl Printer<'_> {
+    fn print_document_element(&mut self, element: &DocumentElement, position: Position) -> String {
+        match element {
+            DocumentElement::Import(node) => print_import(node),
+            DocumentElement::TypeDeclaration(declaration) => {
+                let node = &declaration.declaration;
+                print_export(
+                    declaration.is_exported,
+                    &format!(
+                        "{} = {}",
+                        node.value.identifier.value,
+                        print_type(&node.value.type_expression)
+                    ),
+                )
+            }
+            DocumentElement::VariableDeclaration(declaration) => print_export(
+                declaration.is_exported,
+                &self.print_declaration(&declaration.declaration, position),
+            ),
+            DocumentElement::Expression(expression) => self.print_expression(expression, position),
+        }
+    }
+}
+
+/// Prints the document in the canonical style, and counts the comments in it.
+fn print_document(source: &str) -> Result<(String, usize), String> {
+    let tree = parse_syntax_tree(source)?;
+    let document = &tree.document.value;
+    let comments = collect_comments(&tree.root);
+    let comment_count = comments.len();
+    let mut printer = Printer {
+        source,
+        comments: Comments::new(source, comments),
+    };
+    // The parser sorts the document's elements by kind, so they are put back in the
+    // order they were written in.
+    let mut elements = document
+        .imports
+        .iter()
+        .map(DocumentElement::Import)
+        .chain(
+            document
+                .type_declarations
+                .iter()
+                .map(DocumentElement::TypeDeclaration),
+        )
+        .chain(
+            document
+                .variable_declarations
+                .iter()
+                .map(DocumentElement::VariableDeclaration),
+        )
+        .chain(document.expressions.iter().map(DocumentElement::Expression))
+        .collect::<Vec<_>>();
+    elements.sort_by_key(|element| element.range(source).start);
+    let mut text = printer.print_lines(
+        &elements,
+        0,
+        |element| element.range(source),
+        // The parser doesn't allow spaces between an import and a comment after it.
+        |element| !matches!(element, DocumentElement::Import(_)),
+        Printer::print_document_element,
+    );
+    printer.print_remaining_comments(&mut text);
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    Ok((text, comment_count))
+}
+
+/// Prints a Buri file in the canonical style, keeping its comments. Lists, records and
+/// arguments are split onto several lines when they don't fit in `LINE_WIDTH`.
+pub fn format_buri_file(source: &str) -> Result<String, String> {
+    let (formatted, comment_count) = print_document(source)?;
+    // Formatting the result again checks that it parses the same way as the source.
+    let is_unchanged = matches!(
+        print_document(&formatted),
+        Ok((reformatted, reformatted_count))
+            if reformatted == formatted && reformatted_count == comment_count
+    );
+    if is_unchanged {
+        Ok(formatted)
+    } else {
+        Err(String::from(
+            "Formatting Error: the formatted file doesn't parse the same way as the source",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_formats(source: &str, expected: &str) {
+        assert_eq!(format_buri_file(source), Ok(String::from(expected)));
+        assert_eq!(format_buri_file(expected), Ok(String::from(expected)));
+    }
+
+    #[test]
+    fn spacing_is_made_consistent() {
+        assert_formats(
+            "add=(a,b)=>a+b\nresult = add(1,   2)*3",
+            "add = (a, b) => a + b\nresult = add(1, 2) * 3\n",
+        );
+    }
+
+    #[test]
+    fn parentheses_are_kept_where_they_are_needed() {
+        assert_formats(
+            "a = ((1 + 2)) * (3 * 4)\nb = 2 ** (3 ** 4)\nc = (2 ** 3) ** 4\nd = -(x.y)\ne = (not x).y",
+            "a = (1 + 2) * (3 * 4)\nb = 2 ** 3 ** 4\nc = (2 ** 3) ** 4\nd = -(x.y)\ne = (not x).y\n",
+        );
+    }
+
+    #[test]
+    fn records_and_lists_get_canonical_spacing() {
+        assert_formats(
+            "a = {b:1,c:[1,2,],d:{}}\nb = { a | b: 2 }\nc = Map[1=>2]",
+            "a = { b: 1, c: [1, 2], d: {} }\nb = { a | b: 2 }\nc = Map[1 => 2]\n",
+        );
+    }
+
+    #[test]
+    fn long_lists_are_split_onto_several_lines() {
+        let items = (0..30).map(|index| index.to_string()).collect::<Vec<_>>();
+        let source = format!("numbers = [{}]", items.join(", "));
+        let expected = format!("numbers = [\n    {},\n]\n", items.join(",\n    "));
+        assert_formats(&source, &expected);
+    }
+
+    #[test]
+    fn blocks_and_cases_are_indented() {
+        assert_formats(
+            "f = (x) =>\n    y = when x is\n        #a do 1\n        #b(n) do\n            n\n        _ do 0\n    if y > 0 do y else\n        0",
+            "f = (x) =>\n    y = when x is\n        #a do 1\n        #b(n) do\n            n\n        _ do 0\n    if y > 0 do y else\n        0\n",
+        );
+    }
+
+    #[test]
+    fn comments_and_single_blank_lines_are_kept() {
+        assert_formats(
+            "-- header\n\n\n\nimport a from \"a.buri\"-- trailing\n\n@export\nb = a -- b\nf = () =>\n    -- inside\n    1\n-- end",
+            "-- header\n\nimport a from \"a.buri\"\n-- trailing\n\n@export\nb = a -- b\nf = () =>\n    -- inside\n    1\n-- end\n",
+        );
+    }
+
+    #[test]
+    fn comments_in_lists_keep_them_split() {
+        assert_formats(
+            "a = [1, -- one\n    2]",
+            "a = [\n    1, -- one\n    2,\n]\n",
+        );
+    }
+
+    #[test]
+    fn split_items_are_not_printed_after_other_items() {
+        let fields = (0..20)
+            .map(|index| format!("field{index}: {index}"))
+            .collect::<Vec<_>>();
+        let source = format!("a = {{ b: 1, c: {{ {} }} }}", fields.join(", "));
+        let expected = format!(
+            "a = {{\n    b: 1,\n    c: {{\n        {},\n    }},\n}}\n",
+            fields.join(",\n        ")
+        );
+        assert_formats(&source, &expected);
+    }
+
+    #[test]
+    fn the_last_argument_can_be_a_function_with_a_block() {
+        assert_formats(
+            "a = list:map((x) =>\n    x + 1\n)",
+            "a = list:map((x) =>\n    x + 1\n)\n",
+        );
+    }
+
+    #[test]
+    fn parsing_errors_are_reported() {
+        assert!(format_buri_file("a = (").is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::comments::{follows_blank_line, Comments};
+use crate::layout::{fits, indent, Position};
+use std::ops::Range;
+
+pub struct Printer<'a> {
+    pub source: &'a str,
+    pub comments: Comments<'a>,
+}
+
+/// How the items of a list, record or argument list are wrapped.
+pub struct Delimiters<'s> {
+    pub open: &'s str,
+    pub close: &'static str,
+    /// Records have spaces inside their braces, e.g. `{ a: 1 }`.
+    pub padded: bool,
+    /// Tags can't have a comma after their last item.
+    pub trailing_comma: bool,
+    /// Most brackets reset the indentation which the parser expects, but function
+    /// arguments keep it.
+    pub resets_indentation: bool,
+}
+
+/// Whether the printed text ends with a line which closes brackets, which means that
+/// it was split because of its own items rather than a block.
+fn ends_with_closing_delimiter(text: &str) -> bool {
+    matches!(
+        text.lines().last(),
+        Some(line) if line.trim_start().starts_with([']', ')', '}'])
+    )
+}
+
+impl Printer<'_> {
+    /// Prints items on their own lines, such as the statements in a block, along with
+    /// the comments around them. At most one blank line is kept between items.
+    /// Comments after items which can't be followed by one are moved to the next line.
+    pub fn print_lines<T>(
+        &mut self,
+        items: &[T],
+        indentation: usize,
+        item_range: impl Fn(&T) -> Range<usize>,
+        allows_trailing_comment: impl Fn(&T) -> bool,
+        print_item: impl Fn(&mut Self, &T, Position) -> String,
+    ) -> String {
+        let mut lines = String::new();
+        for item in items {
+            let range = item_range(item);
+            for comment in self.comments.take_before(range.start) {
+                self.start_line(&mut lines, comment.offset, indentation);
+                lines.push_str(comment.text);
+            }
+            self.start_line(&mut lines, range.start, indentation);
+            lines.push_str(&print_item(self, item, Position::line(indentation)));
+            if allows_trailing_comment(item) {
+                self.push_trailing_comment(&mut lines, range.end);
+            }
+        }
+        lines
+    }
+
+    /// Prints the comments which couldn't be printed earlier, such as those at the
+    /// end of the file, on their own lines.
+    pub fn print_remaining_comments(&mut self, lines: &mut String) {
+        for comment in self.comments.take_rest() {
+            self.start_line(lines, comment.offset, 0);
+            lines.push_str(comment.text);
+        }
+    }
+
+    fn start_line(&self, lines: &mut String, offset: usize, indentation: usize) {
+        if !lines.is_empty() {
+            lines.push('\n');
+            if follows_blank_line(self.source, offset) {
+                lines.push('\n');
+            }
+        }
+        lines.push_str(&indent(indentation));
+    }
+
+    pub fn push_trailing_comment(&mut self, text: &mut String, offset: usize) {
+        if let Some(comment) = self.comments.take_trailing(offset) {
+            text.push(' ');
+            text.push_str(comment);
+        }
+    }
+
+    /// Prints the items on one line if they fit, and otherwise on one line each. The
+    /// last item may span several lines when it ends with a block, like a function.
+    pub fn print_delimited<T>(
+        &mut self,
+        delimiters: &Delimiters,
+        items: &[T],
+        range: &Range<usize>,
+        position: Position,
+        item_range: impl Fn(&T) -> Range<usize>,
+        print_item: impl Fn(&mut Self, &T, Position) -> String,
+    ) -> String {
+        let item_position = if delimiters.resets_indentation {
+            position.with_indentation(0)
+        } else {
+            position
+        };
+        if !self.comments.any_within(range) {
+            let checkpoint = self.comments.checkpoint();
+            if let Some(text) =
+                self.print_on_one_line(delimiters, items, item_position, &print_item)
+            {
+                return text;
+            }
+            self.comments.restore(checkpoint);
+        }
+        let item_line_indentation = position.line_indentation + 1;
+        let mut text = String::from(delimiters.open);
+        for (index, item) in items.iter().enumerate() {
+            let range = item_range(item);
+            for comment in self.comments.take_before(range.start) {
+                text.push('\n');
+                text.push_str(&indent(item_line_indentation));
+                text.push_str(comment.text);
+            }
+            text.push('\n');
+            text.push_str(&indent(item_line_indentation));
+            text.push_str(&print_item(
+                self,
+                item,
+                Position::line(item_line_indentation).with_indentation(item_position.indentation),
+            ));
+            if index + 1 < items.len() || delimiters.trailing_comma {
+                text.push(',');
+            }
+            self.push_trailing_comment(&mut text, range.end);
+        }
+        for comment in self.comments.take_before(range.end) {
+            text.push('\n');
+            text.push_str(&indent(item_line_indentation));
+            text.push_str(comment.text);
+        }
+        text.push('\n');
+        text.push_str(&indent(position.line_indentation));
+        text.push_str(delimiters.close);
+        text
+    }
+
+    fn print_on_one_line<T>(
+        &mut self,
+        delimiters: &Delimiters,
+        items: &[T],
+        position: Position,
+        print_item: &impl Fn(&mut Self, &T, Position) -> String,
+    ) -> Option<String> {
+        let padding = if delimiters.padded && !items.is_empty() {
+            " "
+        } else {
+            ""
+        };
+        let mut text = format!("{}{padding}", delimiters.open);
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                if text.contains('\n') {
+                    return None;
+                }
+                text.push_str(", ");
+            }
+            let item_text = print_item(self, item, position.after(&text));
+            text.push_str(&item_text);
+        }
+        if text.contains('\n') {
+            // Items which are split themselves would be hard to read after other items.
+            if ends_with_closing_delimiter(&text) {
+                return None;
+            }
+            text.push('\n');
+            text.push_str(&indent(position.line_indentation));
+        } else {
+            text.push_str(padding);
+        }
+        text.push_str(delimiters.close);
+        let first_line = text.lines().next().unwrap_or_default();
+        fits(first_line, position.column).then_some(text)
+    }
+}
@@ -0,0 +1,75 @@
+use ast::{TagTypeNode, TypeExpression};
+
+fn print_types(types: &[TypeExpression]) -> String {
+    types.iter().map(print_type).collect::<Vec<_>>().join(", ")
+}
+
+fn print_payload(payload: &[TypeExpression]) -> String {
+    if payload.is_empty() {
+        String::new()
+    } else {
+        format!("({})", print_types(payload))
+    }
+}
+
+fn print_tag_type(tag: &TagTypeNode) -> String {
+    format!(
+        "#{}{}",
+        tag.value.name.value,
+        print_payload(&tag.value.contents)
+    )
+}
+
+/// Types are always printed on one line.
+pub fn print_type(type_expression: &TypeExpression) -> String {
+    match type_expression {
+        TypeExpression::Enum(node) => node
+            .value
+            .variants
+            .iter()
+            .map(|variant| {
+                format!(
+                    ".{}{}",
+                    variant.value.variant_name,
+                    print_payload(&variant.value.payload)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" | "),
+        TypeExpression::Function(node) => format!(
+            "({}) => {}",
+            print_types(&node.value.arguments),
+            print_type(&node.value.return_type)
+        ),
+        TypeExpression::Identifier(node) => node.value.clone(),
+        TypeExpression::List(node) => format!("[{}]", print_type(&node.value)),
+        TypeExpression::Map(node) => format!(
+            "Map({}, {})",
+            print_type(&node.value.key_type),
+            print_type(&node.value.value_type)
+        ),
+        TypeExpression::Record(node) if node.value.is_empty() => String::from("{}"),
+        TypeExpression::Record(node) => {
+            let fields = node
+                .value
+                .iter()
+                .map(|field| {
+                    format!(
+                        "{}: {}",
+                        field.identifier.value.name,
+                        print_type(&field.value)
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        TypeExpression::Set(node) => format!("Set({})", print_type(&node.value)),
+        TypeExpression::TagGroup(node) => node
+            .value
+            .iter()
+            .map(print_tag_type)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        TypeExpression::Tuple(node) => format!("({})", print_types(&node.value)),
+    }
+}
@@ -0,0 +1,389 @@
+use crate::{
+    methods::call_method,
+    operators::{apply_binary_operator, apply_unary_operator},
+    scope::Scope,
+    value::{Closure, Value},
+};
+use ast::BinaryOperatorSymbol;
+use indexmap::IndexMap;
+use std::rc::Rc;
+use typed_ast::{
+    ConcreteBinaryOperatorExpression, ConcreteBlockExpression, ConcreteExpression,
+    ConcreteIdentifierExpression, ConcreteIfExpression, ConcreteRecordAssignmentExpression,
+    ConcreteTupleDeclarationExpression, ConcreteWhenCaseName, ConcreteWhenExpression,
+};
+
+/// Roughly how deep JS engines let functions recurse. Deeper recursion is reported
+/// as an error, like a JS `RangeError`.
+pub const MAX_CALL_DEPTH: usize = 10_000;
+
+/// Calls which aren't in tail position are evaluated recursively, so the stack is
+/// grown in chunks of this size whenever less than `STACK_RED_ZONE` is left.
+const STACK_CHUNK_SIZE: usize = 1024 * 1024;
+const STACK_RED_ZONE: usize = 64 * 1024;
+
+/// Calls in tail position are returned instead of made, so that recursive loops
+/// run in constant stack space like they do in the JS backend.
+enum Step<'a> {
+    Value(Value<'a>),
+    TailCall(Rc<Closure<'a>>, Vec<Value<'a>>),
+}
+
+fn empty_tuple<'a>() -> Value<'a> {
+    Value::Tuple(Rc::new(Vec::new()))
+}
+
+/// Calls the closure, along with any calls it makes in tail position.
+pub fn call_closure<'a>(
+    mut closure: Rc<Closure<'a>>,
+    mut arguments: Vec<Value<'a>>,
+    depth: usize,
+) -> Result<Value<'a>, String> {
+    if depth >= MAX_CALL_DEPTH {
+        return Err(format!(
+            "Functions are nested more than {MAX_CALL_DEPTH} calls deep"
+        ));
+    }
+    stacker::maybe_grow(STACK_RED_ZONE, STACK_CHUNK_SIZE, || loop {
+        let function = closure.function;
+        if function.argument_names.len() != arguments.len() {
+            return Err(format!(
+                "The function expects {} arguments but was given {}",
+                function.argument_names.len(),
+                arguments.len()
+            ));
+        }
+        let scope = Scope::new_child(&closure.scope);
+        for (name, argument) in function.argument_names.iter().zip(arguments) {
+            scope.declare(name, argument);
+        }
+        match evaluate_tail(&function.body, &scope, depth + 1)? {
+            Step::Value(value) => return Ok(value),
+            Step::TailCall(next_closure, next_arguments) => {
+                closure = next_closure;
+                arguments = next_arguments;
+            }
+        }
+    })
+}
+
+/// Makes the call that a step ends with, if any.
+fn finish_step(step: Step<'_>, depth: usize) -> Result<Value<'_>, String> {
+    match step {
+        Step::Value(value) => Ok(value),
+        Step::TailCall(closure, arguments) => call_closure(closure, arguments, depth),
+    }
+}
+
+pub fn evaluate<'a>(
+    expression: &'a ConcreteExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Value<'a>, String> {
+    finish_step(evaluate_tail(expression, scope, depth)?, depth)
+}
+
+fn evaluate_all<'a>(
+    expressions: &'a [ConcreteExpression],
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Vec<Value<'a>>, String> {
+    expressions
+        .iter()
+        .map(|expression| evaluate(expression, scope, depth))
+        .collect()
+}
+
+fn evaluate_block<'a>(
+    block: &'a ConcreteBlockExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Step<'a>, String> {
+    let scope = Scope::new_child(scope);
+    let Some((last, rest)) = block.contents.split_last() else {
+        return Ok(Step::Value(empty_tuple()));
+    };
+    for expression in rest {
+        evaluate(expression, &scope, depth)?;
+    }
+    evaluate_tail(last, &scope, depth)
+}
+
+fn expect_boolean(value: &Value) -> Result<bool, String> {
+    match value {
+        Value::Boolean(value) => Ok(*value),
+        _ => Err(format!("Expected a boolean but got {}", value.kind())),
+    }
+}
+
+fn evaluate_if<'a>(
+    expression: &'a ConcreteIfExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Step<'a>, String> {
+    let condition = expect_boolean(&evaluate(&expression.condition, scope, depth)?)?;
+    match (condition, &expression.path_if_false) {
+        (true, Some(_)) => evaluate_tail(&expression.path_if_true, scope, depth),
+        (false, Some(path_if_false)) => evaluate_tail(path_if_false, scope, depth),
+        // Without an else, the result is optional.
+        (true, None) => Ok(Step::Value(Value::some(evaluate(
+            &expression.path_if_true,
+            scope,
+            depth,
+        )?))),
+        (false, None) => Ok(Step::Value(Value::none())),
+    }
+}
+
+fn declare_identifiers<'a>(
+    scope: &Scope<'a>,
+    identifiers: &[ConcreteIdentifierExpression],
+    values: &[Value<'a>],
+) {
+    for (identifier, value) in identifiers.iter().zip(values) {
+        if !identifier.is_disregarded {
+            scope.declare(&identifier.name, value.clone());
+        }
+    }
+}
+
+fn evaluate_when<'a>(
+    expression: &'a ConcreteWhenExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Step<'a>, String> {
+    let condition = evaluate(&expression.condition, scope, depth)?;
+    let (Value::Tag(name, contents) | Value::Enum(name, contents)) = &condition else {
+        return Err(format!("Expected a tag but got {}", condition.kind()));
+    };
+    let case = expression
+        .cases
+        .iter()
+        .find(|case| match &case.case_name {
+            ConcreteWhenCaseName::Name(case_name) => case_name == name,
+            ConcreteWhenCaseName::DefaultCase => true,
+        })
+        .ok_or_else(|| format!("No case matches {condition}"))?;
+    let scope = Scope::new_child(scope);
+    declare_identifiers(&scope, &case.case_arguments, contents);
+    evaluate_tail(&case.case_expression, &scope, depth)
+}
+
+fn evaluate_tuple_declaration<'a>(
+    declaration: &'a ConcreteTupleDeclarationExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Value<'a>, String> {
+    let value = evaluate(&declaration.value, scope, depth)?;
+    match &value {
+        Value::Tuple(elements) => declare_identifiers(scope, &declaration.identifiers, elements),
+        _ => return Err(format!("Expected a tuple but got {}", value.kind())),
+    }
+    Ok(value)
+}
+
+fn evaluate_record_assignment<'a>(
+    expression: &'a ConcreteRecordAssignmentExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Value<'a>, String> {
+    let record = scope.get(&expression.identifier.name)?;
+    let Value::Record(fields) = &record else {
+        return Err(format!("Expected a record but got {}", record.kind()));
+    };
+    let mut fields = fields.as_ref().clone();
+    for (name, value) in &expression.contents.contents {
+        fields.insert(name.clone(), evaluate(value, scope, depth)?);
+    }
+    Ok(Value::Record(Rc::new(fields)))
+}
+
+/// Looks up a record field by name, or a tuple element by index.
+fn look_up_field<'a>(value: &Value<'a>, field: &ConcreteExpression) -> Result<Value<'a>, String> {
+    match (value, field) {
+        (Value::Record(fields), ConcreteExpression::Identifier(identifier)) => fields
+            .get(&identifier.name)
+            .cloned()
+            .ok_or_else(|| format!("The record doesn't have a {} field", identifier.name)),
+        (Value::Tuple(elements), ConcreteExpression::Integer(index)) => {
+            usize::try_from(index.value)
+                .ok()
+                .and_then(|index| elements.get(index))
+                .cloned()
+                .ok_or_else(|| format!("The tuple doesn't have an element {}", index.value))
+        }
+        _ => Err(format!("Can't look up a field of {}", value.kind())),
+    }
+}
+
+fn evaluate_function_application<'a>(
+    expression: &'a ConcreteBinaryOperatorExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Step<'a>, String> {
+    let ConcreteExpression::FunctionArguments(arguments) = &expression.right_child else {
+        return Err(String::from("Functions can only be called with arguments"));
+    };
+    if let ConcreteExpression::BinaryOperator(method) = &expression.left_child {
+        if let (BinaryOperatorSymbol::MethodLookup, ConcreteExpression::Identifier(name)) =
+            (&method.symbol, &method.right_child)
+        {
+            let receiver = evaluate(&method.left_child, scope, depth)?;
+            let arguments = evaluate_all(arguments, scope, depth)?;
+            return call_method(&receiver, &name.name, &arguments).map(Step::Value);
+        }
+    }
+    match evaluate(&expression.left_child, scope, depth)? {
+        Value::Function(closure) => Ok(Step::TailCall(
+            closure,
+            evaluate_all(arguments, scope, depth)?,
+        )),
+        function => Err(format!("Can't call {}", function.kind())),
+    }
+}
+
+fn evaluate_binary_operator<'a>(
+    expression: &'a ConcreteBinaryOperatorExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Step<'a>, String> {
+    let value = match expression.symbol {
+        BinaryOperatorSymbol::FunctionApplication => {
+            return evaluate_function_application(expression, scope, depth)
+        }
+        BinaryOperatorSymbol::And | BinaryOperatorSymbol::Or => {
+            let left = expect_boolean(&evaluate(&expression.left_child, scope, depth)?)?;
+            if left == (expression.symbol == BinaryOperatorSymbol::Or) {
+                Value::Boolean(left)
+            } else {
+                let right = evaluate(&expression.right_child, scope, depth)?;
+                Value::Boolean(expect_boolean(&right)?)
+            }
+        }
+        BinaryOperatorSymbol::FieldLookup => {
+            let value = evaluate(&expression.left_child, scope, depth)?;
+            look_up_field(&value, &expression.right_child)?
+        }
+        BinaryOperatorSymbol::MethodLookup => {
+            return Err(String::from(
+                "Methods can only be called, not used as values",
+            ))
+        }
+        _ => apply_binary_operator(
+            &expression.symbol,
+            &evaluate(&expression.left_child, scope, depth)?,
+            &evaluate(&expression.right_child, scope, depth)?,
+        )?,
+    };
+    Ok(Step::Value(value))
+}
+
+fn evaluate_tail<'a>(
+    expression: &'a ConcreteExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Step<'a>, String> {
+    match expression {
+        ConcreteExpression::BinaryOperator(node) => evaluate_binary_operator(node, scope, depth),
+        ConcreteExpression::Block(node) => evaluate_block(node, scope, depth),
+        ConcreteExpression::If(node) => evaluate_if(node, scope, depth),
+        ConcreteExpression::When(node) => evaluate_when(node, scope, depth),
+        _ => evaluate_value(expression, scope, depth).map(Step::Value),
+    }
+}
+
+/// Evaluates the expressions which can't end with a call in tail position.
+fn evaluate_value<'a>(
+    expression: &'a ConcreteExpression,
+    scope: &Rc<Scope<'a>>,
+    depth: usize,
+) -> Result<Value<'a>, String> {
+    let value = match expression {
+        ConcreteExpression::BinaryOperator(_)
+        | ConcreteExpression::Block(_)
+        | ConcreteExpression::If(_)
+        | ConcreteExpression::When(_) => return evaluate(expression, scope, depth),
+        ConcreteExpression::Boolean(node) => Value::Boolean(node.value),
+        ConcreteExpression::Declaration(node) => {
+            let value = evaluate(&node.value, scope, depth)?;
+            if node.is_reassignment {
+                scope.reassign(&node.identifier.name, value.clone())?;
+            } else {
+                scope.declare(&node.identifier.name, value.clone());
+            }
+            value
+        }
+        ConcreteExpression::Float(node) => Value::Float(
+            node.value
+                .parse()
+                .map_err(|_| format!("{} isn't a valid float", node.value))?,
+        ),
+        ConcreteExpression::Function(node) => Value::Function(Rc::new(Closure {
+            function: node,
+            scope: Rc::clone(scope),
+        })),
+        ConcreteExpression::FunctionArguments(_) => {
+            return Err(String::from(
+                "Arguments can only be used in a function call",
+            ))
+        }
+        ConcreteExpression::Identifier(node) => scope.get(&node.name)?,
+        ConcreteExpression::Integer(node) => Value::Integer(
+            i64::try_from(node.value)
+                .map_err(|_| format!("Integer literal {} is too large", node.value))?,
+        ),
+        ConcreteExpression::List(node) => {
+            Value::List(Rc::new(evaluate_all(&node.contents, scope, depth)?))
+        }
+        ConcreteExpression::Map(node) => {
+            let mut map = Value::Map(Rc::new(Vec::new()));
+            for entry in &node.contents {
+                let key = evaluate(&entry.key, scope, depth)?;
+                let value = evaluate(&entry.value, scope, depth)?;
+                map = call_method(&map, "insert", &[key, value])?;
+            }
+            map
+        }
+        ConcreteExpression::Record(node) => {
+            let mut fields = IndexMap::new();
+            for (name, value) in &node.contents {
+                fields.insert(name.clone(), evaluate(value, scope, depth)?);
+            }
+            Value::Record(Rc::new(fields))
+        }
+        ConcreteExpression::RecordAssignment(node) => {
+            evaluate_record_assignment(node, scope, depth)?
+        }
+        ConcreteExpression::Set(node) => {
+            let mut set = Value::Set(Rc::new(Vec::new()));
+            for element in &node.contents {
+                set = call_method(&set, "insert", &[evaluate(element, scope, depth)?])?;
+            }
+            set
+        }
+        ConcreteExpression::StringLiteral(node) => Value::string(&node.value),
+        ConcreteExpression::Tag(node) => Value::Tag(
+            node.name.clone(),
+            evaluate_all(&node.contents, scope, depth)?,
+        ),
+        ConcreteExpression::Enum(node) => Value::Enum(
+            node.name.clone(),
+            evaluate_all(&node.payload, scope, depth)?,
+        ),
+        ConcreteExpression::Tuple(node) => {
+            Value::Tuple(Rc::new(evaluate_all(&node.contents, scope, depth)?))
+        }
+        ConcreteExpression::TupleDeclaration(node) => {
+            evaluate_tuple_declaration(node, scope, depth)?
+        }
+        // Types only matter to the type checker.
+        ConcreteExpression::TypeDeclaration(_) => empty_tuple(),
+        ConcreteExpression::TypeIdentifier(node) => {
+            return Err(format!("{} is a type, not a value", node.name))
+        }
+        ConcreteExpression::UnaryOperator(node) => {
+            apply_unary_operator(&node.symbol, &evaluate(&node.child, scope, depth)?)?
+        }
+    };
+    Ok(value)
+}
@@ -0,0 +1,151 @@
+mod evaluator;
+mod methods;
+mod operators;
+mod scope;
+mod value;
+
+use evaluator::{call_closure, evaluate};
+use indexmap::IndexMap;
+use std::rc::Rc;
+use typed_ast::{ConcreteDeclarationExpression, ConcreteDocument, ConcreteExpression};
+
+pub use evaluator::MAX_CALL_DEPTH;
+pub use scope::Scope;
+pub use value::{Closure, Value};
+
+/// Evaluates the top-level declarations of a type checked document in order,
+/// returning the value of each one by name.
+pub fn evaluate_document<'a>(
+    document: &'a ConcreteDocument,
+) -> Result<IndexMap<String, Value<'a>>, String> {
+    if let Some(import) = document.imports.first() {
+        return Err(format!(
+            "Imports can't be evaluated yet, but the document imports {}",
+            import.value.path
+        ));
+    }
+    let scope = Rc::new(Scope::default());
+    let mut values = IndexMap::new();
+    for declaration in &document.variable_declarations {
+        let declaration = &declaration.declaration;
+        let value = evaluate_declaration(declaration, &scope)?;
+        values.insert(declaration.identifier.name.clone(), value);
+    }
+    Ok(values)
+}
+
+/// Evaluates a top-level declaration into the scope, and returns its value. The
+/// scope can be kept to evaluate later declarations, like the REPL does.
+pub fn evaluate_declaration<'a>(
+    declaration: &'a ConcreteDeclarationExpression,
+    scope: &Rc<Scope<'a>>,
+) -> Result<Value<'a>, String> {
+    let value = evaluate(&declaration.value, scope, 0)?;
+    scope.declare(&declaration.identifier.name, value.clone());
+    Ok(value)
+}
+
+pub fn evaluate_expression<'a>(
+    expression: &'a ConcreteExpression,
+    scope: &Rc<Scope<'a>>,
+) -> Result<Value<'a>, String> {
+    evaluate(expression, scope, 0)
+}
+
+/// Calls a function value, such as one returned by `evaluate_document`.
+pub fn call_function<'a>(
+    function: &Value<'a>,
+    arguments: Vec<Value<'a>>,
+) -> Result<Value<'a>, String> {
+    match function {
+        Value::Function(closure) => call_closure(Rc::clone(closure), arguments, 0),
+        _ => Err(format!("Can't call {}", function.kind())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use compiler::type_check_buri_file;
+
+    fn call_for_test(
+        source: &'static str,
+        name: &str,
+        arguments: Vec<Value<'static>>,
+    ) -> Result<String, String> {
+        // The values borrow from the document, so it is kept for the rest of the test.
+        let document = Box::leak(Box::new(type_check_buri_file(source)?));
+        let values = evaluate_document(document)?;
+        call_function(&values[name], arguments).map(|value| value.to_string())
+    }
+
+    #[test]
+    fn declarations_are_evaluated_in_order() {
+        let document =
+            type_check_buri_file("a = 1 + 2\nb = [a, a * 2]\nc = #some({ b: b })").unwrap();
+        let values = evaluate_document(&document).unwrap();
+        assert_eq!(values["b"].to_string(), "[3, 6]");
+        assert_eq!(values["c"].to_string(), "#some({ b: [3, 6] })");
+    }
+
+    #[test]
+    fn recursive_functions() {
+        let source =
+            "fib = (n) =>\n    if n < 2 do\n        1\n    else\n        fib(n - 1) + fib(n - 2)";
+        assert_eq!(
+            call_for_test(source, "fib", vec![Value::Integer(10)]),
+            Ok(String::from("89"))
+        );
+    }
+
+    #[test]
+    fn tail_calls_do_not_grow_the_stack() {
+        let source = "sumTo = (n, total) =>\n    if n == 0 do\n        total\n    else\n        sumTo(n - 1, total + n)";
+        assert_eq!(
+            call_for_test(
+                source,
+                "sumTo",
+                vec![Value::Integer(100_000), Value::Integer(0)]
+            ),
+            Ok(String::from("5000050000"))
+        );
+    }
+
+    #[test]
+    fn deep_recursion_is_reported() {
+        let source =
+            "count = (n) =>\n    if n == 0 do\n        0\n    else\n        1 + count(n - 1)";
+        assert_eq!(
+            call_for_test(source, "count", vec![Value::Integer(100)]),
+            Ok(String::from("100"))
+        );
+        assert_eq!(
+            call_for_test(source, "count", vec![Value::Integer(100_000)]),
+            Err(format!(
+                "Functions are nested more than {MAX_CALL_DEPTH} calls deep"
+            ))
+        );
+    }
+
+    #[test]
+    fn closures_keep_their_scope() {
+        let source = "makeAdder = (n) =>\n    (x) => x + n\naddThree = makeAdder(3)";
+        assert_eq!(
+            call_for_test(source, "addThree", vec![Value::Integer(4)]),
+            Ok(String::from("7"))
+        );
+    }
+
+    #[test]
+    fn when_binds_tag_contents() {
+        let source = "describe = (shape) =>\n    when shape is\n        #circle(radius) do radius * 2\n        #square(side) do side\n        _ do 0";
+        assert_eq!(
+            call_for_test(
+                source,
+                "describe",
+                vec![Value::Tag("circle".to_string(), vec![Value::Integer(3)])]
+            ),
+            Ok(String::from("6"))
+        );
+    }
+}
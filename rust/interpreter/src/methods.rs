@@ -0,0 +1,279 @@
+use crate::value::Value;
+use std::rc::Rc;
+
+fn option(value: Option<Value<'_>>) -> Value<'_> {
+    value.map_or_else(Value::none, Value::some)
+}
+
+fn size<'a>(length: usize) -> Result<Value<'a>, String> {
+    i64::try_from(length)
+        .map(Value::Integer)
+        .map_err(|_| String::from("The size is too large to be an Int"))
+}
+
+/// Converts a rounded float to an `Int`. Like the prelude, `NaN` becomes `0`.
+fn float_to_integer<'a>(value: f64) -> Result<Value<'a>, String> {
+    if value.is_nan() {
+        return Ok(Value::Integer(0));
+    }
+    // Every `i64` rounds to a float in this range, so the conversion is exact.
+    if value < -(2_f64.powi(63)) || value >= 2_f64.powi(63) {
+        return Err(format!("{value} is too large to be an Int"));
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(Value::Integer(value as i64))
+}
+
+/// JS's `Math.round` rounds halves up, rather than away from zero.
+fn round_half_up(value: f64) -> f64 {
+    let floor = value.floor();
+    if value - floor >= 0.5 {
+        floor + 1.0
+    } else {
+        floor
+    }
+}
+
+/// Finds the code point of the nth character, counting from the end if `n` is
+/// negative.
+fn get_char_code<'a>(string: &str, n: i64) -> Option<Value<'a>> {
+    let character = if n >= 0 {
+        string.chars().nth(usize::try_from(n).ok()?)
+    } else {
+        string.chars().rev().nth(usize::try_from(-(n + 1)).ok()?)
+    };
+    character.map(|character| Value::Integer(i64::from(u32::from(character))))
+}
+
+fn find_entry<'a>(entries: &[(Value<'a>, Value<'a>)], key: &Value<'a>) -> Option<usize> {
+    entries.iter().position(|(entry_key, _)| entry_key == key)
+}
+
+fn call_list_method<'a>(
+    elements: &[Value<'a>],
+    name: &str,
+    arguments: &[Value<'a>],
+) -> Option<Result<Value<'a>, String>> {
+    let result = match (name, arguments) {
+        ("get", [Value::Integer(index)]) => Ok(option(
+            usize::try_from(*index)
+                .ok()
+                .and_then(|index| elements.get(index))
+                .cloned(),
+        )),
+        ("append", [element]) => {
+            let mut elements = elements.to_vec();
+            elements.push(element.clone());
+            Ok(Value::List(Rc::new(elements)))
+        }
+        ("size", []) => size(elements.len()),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn call_map_method<'a>(
+    entries: &[(Value<'a>, Value<'a>)],
+    name: &str,
+    arguments: &[Value<'a>],
+) -> Option<Result<Value<'a>, String>> {
+    let result = match (name, arguments) {
+        ("get", [key]) => Ok(option(
+            find_entry(entries, key).map(|index| entries[index].1.clone()),
+        )),
+        ("insert", [key, value]) => {
+            let mut entries = entries.to_vec();
+            match find_entry(&entries, key) {
+                Some(index) => entries[index].1 = value.clone(),
+                None => entries.push((key.clone(), value.clone())),
+            }
+            Ok(Value::Map(Rc::new(entries)))
+        }
+        ("remove", [key]) => Ok(Value::Map(Rc::new(
+            entries
+                .iter()
+                .filter(|(entry_key, _)| entry_key != key)
+                .cloned()
+                .collect(),
+        ))),
+        ("has", [key]) => Ok(Value::Boolean(find_entry(entries, key).is_some())),
+        ("size", []) => size(entries.len()),
+        ("keys", []) => Ok(Value::List(Rc::new(
+            entries.iter().map(|(key, _)| key.clone()).collect(),
+        ))),
+        ("values", []) => Ok(Value::List(Rc::new(
+            entries.iter().map(|(_, value)| value.clone()).collect(),
+        ))),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn call_set_method<'a>(
+    elements: &[Value<'a>],
+    name: &str,
+    arguments: &[Value<'a>],
+) -> Option<Result<Value<'a>, String>> {
+    let result = match (name, arguments) {
+        ("insert", [element]) => {
+            let mut elements = elements.to_vec();
+            if !elements.contains(element) {
+                elements.push(element.clone());
+            }
+            Ok(Value::Set(Rc::new(elements)))
+        }
+        ("remove", [element]) => Ok(Value::Set(Rc::new(
+            elements
+                .iter()
+                .filter(|other| *other != element)
+                .cloned()
+                .collect(),
+        ))),
+        ("has", [element]) => Ok(Value::Boolean(elements.contains(element))),
+        ("size", []) => size(elements.len()),
+        ("values", []) => Ok(Value::List(Rc::new(elements.to_vec()))),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn call_integer_method<'a>(value: i64, name: &str) -> Option<Result<Value<'a>, String>> {
+    match name {
+        // Like JS's `Number`, large `Int`s become the nearest float.
+        #[allow(clippy::cast_precision_loss)]
+        "toFloat" => Some(Ok(Value::Float(value as f64))),
+        _ => None,
+    }
+}
+
+fn call_float_method<'a>(value: f64, name: &str) -> Option<Result<Value<'a>, String>> {
+    let rounded = match name {
+        "floor" => value.floor(),
+        "ceil" => value.ceil(),
+        "round" => round_half_up(value),
+        "truncate" => value.trunc(),
+        _ => return None,
+    };
+    Some(float_to_integer(rounded))
+}
+
+fn call_string_method<'a>(
+    string: &str,
+    name: &str,
+    arguments: &[Value<'a>],
+) -> Option<Result<Value<'a>, String>> {
+    let result = match (name, arguments) {
+        ("size", []) => size(string.chars().count()),
+        ("getCharCode", [Value::Integer(n)]) => Ok(option(get_char_code(string, *n))),
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Calls one of the methods which `default_types.rs` gives the built-in types.
+pub fn call_method<'a>(
+    receiver: &Value<'a>,
+    name: &str,
+    arguments: &[Value<'a>],
+) -> Result<Value<'a>, String> {
+    let result = match receiver {
+        Value::List(elements) => call_list_method(elements, name, arguments),
+        Value::Map(entries) => call_map_method(entries, name, arguments),
+        Value::Set(elements) => call_set_method(elements, name, arguments),
+        Value::Integer(value) if arguments.is_empty() => call_integer_method(*value, name),
+        Value::Float(value) if arguments.is_empty() => call_float_method(*value, name),
+        Value::String(string) => call_string_method(string, name, arguments),
+        _ => None,
+    };
+    result.unwrap_or_else(|| Err(format!("Can't call {name} on {}", receiver.kind())))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn list_for_test(elements: &[i64]) -> Value<'static> {
+        Value::List(Rc::new(
+            elements.iter().copied().map(Value::Integer).collect(),
+        ))
+    }
+
+    #[test]
+    fn list_get_is_none_outside_the_list() {
+        let list = list_for_test(&[10, 20]);
+        assert_eq!(
+            call_method(&list, "get", &[Value::Integer(1)]),
+            Ok(Value::some(Value::Integer(20)))
+        );
+        assert_eq!(
+            call_method(&list, "get", &[Value::Integer(2)]),
+            Ok(Value::none())
+        );
+        assert_eq!(
+            call_method(&list, "get", &[Value::Integer(-1)]),
+            Ok(Value::none())
+        );
+    }
+
+    #[test]
+    fn strings_are_measured_in_characters() {
+        let string = Value::string("h🙂i");
+        assert_eq!(call_method(&string, "size", &[]), Ok(Value::Integer(3)));
+        assert_eq!(
+            call_method(&string, "getCharCode", &[Value::Integer(1)]),
+            Ok(Value::some(Value::Integer(0x1f642)))
+        );
+        assert_eq!(
+            call_method(&string, "getCharCode", &[Value::Integer(-1)]),
+            Ok(Value::some(Value::Integer(i64::from(u32::from('i')))))
+        );
+        assert_eq!(
+            call_method(&string, "getCharCode", &[Value::Integer(3)]),
+            Ok(Value::none())
+        );
+    }
+
+    #[test]
+    fn floats_round_like_js() {
+        let round = |value| call_method(&Value::Float(value), "round", &[]);
+        assert_eq!(round(2.5), Ok(Value::Integer(3)));
+        assert_eq!(round(-2.5), Ok(Value::Integer(-2)));
+        assert_eq!(
+            call_method(&Value::Float(-2.5), "truncate", &[]),
+            Ok(Value::Integer(-2))
+        );
+    }
+
+    #[test]
+    fn ints_are_converted_to_floats() {
+        assert_eq!(
+            call_method(&Value::Integer(-3), "toFloat", &[]),
+            Ok(Value::Float(-3.0))
+        );
+    }
+
+    #[test]
+    fn maps_keep_the_position_of_replaced_keys() {
+        let map = Value::Map(Rc::new(vec![
+            (Value::string("a"), Value::Integer(1)),
+            (Value::string("b"), Value::Integer(2)),
+        ]));
+        let map = call_method(&map, "insert", &[Value::string("a"), Value::Integer(3)]).unwrap();
+        assert_eq!(map.to_string(), "Map[\"a\" => 3, \"b\" => 2]");
+        assert_eq!(
+            call_method(&map, "keys", &[]),
+            Ok(Value::List(Rc::new(vec![
+                Value::string("a"),
+                Value::string("b")
+            ])))
+        );
+    }
+
+    #[test]
+    fn unknown_methods_are_reported() {
+        assert_eq!(
+            call_method(&Value::Integer(1), "size", &[]),
+            Err(String::from("Can't call size on an integer"))
+        );
+    }
+}
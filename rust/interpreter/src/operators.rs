@@ -0,0 +1,236 @@
+use crate::value::Value;
+use ast::{BinaryOperatorSymbol, UnaryOperatorSymbol};
+use std::{cmp::Ordering, rc::Rc};
+
+/// `Int`s are exact, so operations whose result doesn't fit are reported instead of
+/// being rounded.
+fn checked<'a>(result: Option<i64>, operation: &str) -> Result<Value<'a>, String> {
+    result.map_or_else(
+        || Err(format!("Integer overflow in {operation}")),
+        |value| Ok(Value::Integer(value)),
+    )
+}
+
+/// Like the prelude's `$modulo`, the result has the same sign as the divisor.
+fn integer_modulo(left: i64, right: i64) -> Option<i64> {
+    let remainder = left.checked_rem(right)?;
+    if remainder != 0 && (remainder < 0) != (right < 0) {
+        remainder.checked_add(right)
+    } else {
+        Some(remainder)
+    }
+}
+
+fn apply_integer_operator<'a>(
+    symbol: &BinaryOperatorSymbol,
+    left: i64,
+    right: i64,
+) -> Option<Result<Value<'a>, String>> {
+    let result = match symbol {
+        BinaryOperatorSymbol::Add => checked(left.checked_add(right), &format!("{left} + {right}")),
+        BinaryOperatorSymbol::Subtract => {
+            checked(left.checked_sub(right), &format!("{left} - {right}"))
+        }
+        BinaryOperatorSymbol::Multiply => {
+            checked(left.checked_mul(right), &format!("{left} * {right}"))
+        }
+        BinaryOperatorSymbol::Divide | BinaryOperatorSymbol::Modulus if right == 0 => {
+            Err(format!("{left} is divided by zero"))
+        }
+        // Both Rust and the compiled JS truncate towards zero.
+        BinaryOperatorSymbol::Divide => {
+            checked(left.checked_div(right), &format!("{left} / {right}"))
+        }
+        BinaryOperatorSymbol::Modulus => {
+            checked(integer_modulo(left, right), &format!("{left} % {right}"))
+        }
+        BinaryOperatorSymbol::Power => match u32::try_from(right) {
+            Ok(exponent) => checked(left.checked_pow(exponent), &format!("{left} ** {right}")),
+            Err(_) if right < 0 => Err(format!(
+                "{left} ** {right} has a negative exponent, so it isn't an Int"
+            )),
+            Err(_) => Err(format!("Integer overflow in {left} ** {right}")),
+        },
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn apply_float_operator<'a>(
+    symbol: &BinaryOperatorSymbol,
+    left: f64,
+    right: f64,
+) -> Option<Value<'a>> {
+    let result = match symbol {
+        BinaryOperatorSymbol::Add => left + right,
+        BinaryOperatorSymbol::Subtract => left - right,
+        BinaryOperatorSymbol::Multiply => left * right,
+        BinaryOperatorSymbol::Divide => left / right,
+        BinaryOperatorSymbol::Power => left.powf(right),
+        // The prelude's `$modulo` turns `-0` and `NaN` into `0`.
+        BinaryOperatorSymbol::Modulus => {
+            let remainder = ((left % right) + right) % right;
+            if remainder == 0.0 || remainder.is_nan() {
+                0.0
+            } else {
+                remainder
+            }
+        }
+        _ => return None,
+    };
+    Some(Value::Float(result))
+}
+
+/// JS compares strings by their UTF-16 code units.
+fn compare<'a>(left: &Value<'a>, right: &Value<'a>) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Integer(left), Value::Integer(right)) => Some(left.cmp(right)),
+        (Value::Float(left), Value::Float(right)) => left.partial_cmp(right),
+        (Value::String(left), Value::String(right)) => {
+            Some(left.encode_utf16().cmp(right.encode_utf16()))
+        }
+        _ => None,
+    }
+}
+
+fn apply_comparison<'a>(
+    symbol: &BinaryOperatorSymbol,
+    left: &Value<'a>,
+    right: &Value<'a>,
+) -> Option<Value<'a>> {
+    let is_true = match symbol {
+        BinaryOperatorSymbol::EqualTo => left == right,
+        BinaryOperatorSymbol::NotEqualTo => left != right,
+        // `NaN` isn't ordered, so every comparison with it is false.
+        BinaryOperatorSymbol::LessThan => compare(left, right) == Some(Ordering::Less),
+        BinaryOperatorSymbol::LessThanOrEqualTo => {
+            matches!(compare(left, right), Some(Ordering::Less | Ordering::Equal))
+        }
+        BinaryOperatorSymbol::GreaterThan => compare(left, right) == Some(Ordering::Greater),
+        BinaryOperatorSymbol::GreaterThanOrEqualTo => matches!(
+            compare(left, right),
+            Some(Ordering::Greater | Ordering::Equal)
+        ),
+        _ => return None,
+    };
+    Some(Value::Boolean(is_true))
+}
+
+/// Applies an operator whose operands are both evaluated first. `and`, `or` and
+/// lookups are handled by the evaluator.
+pub fn apply_binary_operator<'a>(
+    symbol: &BinaryOperatorSymbol,
+    left: &Value<'a>,
+    right: &Value<'a>,
+) -> Result<Value<'a>, String> {
+    let result = match (left, right) {
+        (Value::Integer(left), Value::Integer(right)) => {
+            apply_integer_operator(symbol, *left, *right)
+        }
+        (Value::Float(left), Value::Float(right)) => {
+            apply_float_operator(symbol, *left, *right).map(Ok)
+        }
+        (Value::String(left), Value::String(right))
+            if *symbol == BinaryOperatorSymbol::Concatenate =>
+        {
+            Some(Ok(Value::String(Rc::from(format!("{left}{right}")))))
+        }
+        _ => None,
+    };
+    result
+        .or_else(|| apply_comparison(symbol, left, right).map(Ok))
+        .unwrap_or_else(|| {
+            Err(format!(
+                "Can't apply {symbol:?} to {} and {}",
+                left.kind(),
+                right.kind()
+            ))
+        })
+}
+
+pub fn apply_unary_operator<'a>(
+    symbol: &UnaryOperatorSymbol,
+    child: &Value<'a>,
+) -> Result<Value<'a>, String> {
+    match (symbol, child) {
+        (UnaryOperatorSymbol::Not, Value::Boolean(value)) => Ok(Value::Boolean(!value)),
+        (UnaryOperatorSymbol::Negative, Value::Integer(value)) => {
+            checked(value.checked_neg(), &format!("-{value}"))
+        }
+        (UnaryOperatorSymbol::Negative, Value::Float(value)) => Ok(Value::Float(-value)),
+        _ => Err(format!("Can't apply {symbol:?} to {}", child.kind())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn apply(
+        symbol: &BinaryOperatorSymbol,
+        left: i64,
+        right: i64,
+    ) -> Result<Value<'static>, String> {
+        apply_binary_operator(symbol, &Value::Integer(left), &Value::Integer(right))
+    }
+
+    #[test]
+    fn integer_division_truncates_towards_zero() {
+        assert_eq!(
+            apply(&BinaryOperatorSymbol::Divide, -7, 2),
+            Ok(Value::Integer(-3))
+        );
+        assert_eq!(
+            apply(&BinaryOperatorSymbol::Divide, 7, 0),
+            Err(String::from("7 is divided by zero"))
+        );
+    }
+
+    #[test]
+    fn modulo_has_the_sign_of_the_divisor() {
+        assert_eq!(
+            apply(&BinaryOperatorSymbol::Modulus, -1, 3),
+            Ok(Value::Integer(2))
+        );
+        assert_eq!(
+            apply(&BinaryOperatorSymbol::Modulus, 1, -3),
+            Ok(Value::Integer(-2))
+        );
+        assert_eq!(
+            apply(&BinaryOperatorSymbol::Modulus, -3, 3),
+            Ok(Value::Integer(0))
+        );
+    }
+
+    #[test]
+    fn integer_overflow_is_reported() {
+        assert_eq!(
+            apply(&BinaryOperatorSymbol::Power, 2, 63),
+            Err(String::from("Integer overflow in 2 ** 63"))
+        );
+        assert_eq!(
+            apply(&BinaryOperatorSymbol::Power, 2, 62),
+            Ok(Value::Integer(1 << 62))
+        );
+    }
+
+    #[test]
+    fn float_modulo_never_returns_negative_zero() {
+        let result = apply_binary_operator(
+            &BinaryOperatorSymbol::Modulus,
+            &Value::Float(-3.0),
+            &Value::Float(3.0),
+        );
+        assert!(matches!(result, Ok(Value::Float(value)) if value.is_sign_positive()));
+    }
+
+    #[test]
+    fn strings_are_ordered_by_utf16_code_units() {
+        let result = apply_binary_operator(
+            &BinaryOperatorSymbol::LessThan,
+            &Value::string("\u{ffff}"),
+            &Value::string("🙂"),
+        );
+        assert_eq!(result, Ok(Value::Boolean(false)));
+    }
+}
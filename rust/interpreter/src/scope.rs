@@ -0,0 +1,63 @@
+use crate::value::Value;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// The variables declared in a block or function, which can be read by the scopes
+/// inside of it. Closures keep their scope alive, so it is shared.
+#[derive(Debug, Default)]
+pub struct Scope<'a> {
+    values: RefCell<HashMap<String, Value<'a>>>,
+    parent: Option<Rc<Self>>,
+}
+
+impl<'a> Scope<'a> {
+    pub fn new_child(parent: &Rc<Self>) -> Rc<Self> {
+        Rc::new(Self {
+            values: RefCell::new(HashMap::new()),
+            parent: Some(Rc::clone(parent)),
+        })
+    }
+
+    pub fn declare(&self, name: &str, value: Value<'a>) {
+        self.values.borrow_mut().insert(name.to_string(), value);
+    }
+
+    pub fn get(&self, name: &str) -> Result<Value<'a>, String> {
+        if let Some(value) = self.values.borrow().get(name) {
+            return Ok(value.clone());
+        }
+        self.parent.as_ref().map_or_else(
+            || Err(format!("{name} is used before it is declared")),
+            |parent| parent.get(name),
+        )
+    }
+
+    /// Changes the variable in the innermost scope which declares it.
+    pub fn reassign(&self, name: &str, value: Value<'a>) -> Result<(), String> {
+        if let Some(current_value) = self.values.borrow_mut().get_mut(name) {
+            *current_value = value;
+            return Ok(());
+        }
+        self.parent.as_ref().map_or_else(
+            || Err(format!("{name} is reassigned before it is declared")),
+            |parent| parent.reassign(name, value),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reassignments_change_the_declaring_scope() {
+        let outer = Rc::new(Scope::default());
+        outer.declare("count", Value::Integer(1));
+        let inner = Scope::new_child(&outer);
+        inner.reassign("count", Value::Integer(2)).unwrap();
+        assert_eq!(outer.get("count"), Ok(Value::Integer(2)));
+        assert_eq!(
+            inner.get("missing"),
+            Err(String::from("missing is used before it is declared"))
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use crate::scope::Scope;
+use indexmap::IndexMap;
+use std::{fmt, rc::Rc};
+use typed_ast::ConcreteFunctionExpression;
+
+/// A function along with the scope it was created in, which it can still read
+/// from and reassign after that scope has ended.
+pub struct Closure<'a> {
+    pub function: &'a ConcreteFunctionExpression,
+    pub scope: Rc<Scope<'a>>,
+}
+
+/// The scope usually contains the closure itself, so it isn't printed.
+impl fmt::Debug for Closure<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Closure")
+            .field("argument_names", &self.function.argument_names)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A Buri value. Buri values can't be changed, so collections are shared between
+/// copies instead of being cloned.
+#[derive(Debug, Clone)]
+pub enum Value<'a> {
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(Rc<str>),
+    List(Rc<Vec<Self>>),
+    Tuple(Rc<Vec<Self>>),
+    /// Fields are kept in the order they were written.
+    Record(Rc<IndexMap<String, Self>>),
+    /// Keys are compared by structure, so they can't be hashed. Entries are kept in
+    /// the order they were first inserted, like the prelude's `$Map`.
+    Map(Rc<Vec<(Self, Self)>>),
+    Set(Rc<Vec<Self>>),
+    Tag(String, Vec<Self>),
+    Enum(String, Vec<Self>),
+    Function(Rc<Closure<'a>>),
+}
+
+impl Value<'_> {
+    #[must_use]
+    pub fn some(value: Self) -> Self {
+        Self::Tag("some".to_string(), vec![value])
+    }
+
+    #[must_use]
+    pub fn none() -> Self {
+        Self::Tag("none".to_string(), vec![])
+    }
+
+    #[must_use]
+    pub fn string(value: &str) -> Self {
+        Self::String(Rc::from(value))
+    }
+
+    /// Describes the kind of value in error messages.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Boolean(_) => "a boolean",
+            Self::Integer(_) => "an integer",
+            Self::Float(_) => "a float",
+            Self::String(_) => "a string",
+            Self::List(_) => "a list",
+            Self::Tuple(_) => "a tuple",
+            Self::Record(_) => "a record",
+            Self::Map(_) => "a map",
+            Self::Set(_) => "a set",
+            Self::Tag(_, _) => "a tag",
+            Self::Enum(_, _) => "an enum",
+            Self::Function(_) => "a function",
+        }
+    }
+}
+
+/// Values are compared by structure, like `==` in the compiled JS.
+impl PartialEq for Value<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Boolean(left), Self::Boolean(right)) => left == right,
+            (Self::Integer(left), Self::Integer(right)) => left == right,
+            (Self::Float(left), Self::Float(right)) => left == right,
+            (Self::String(left), Self::String(right)) => left == right,
+            (Self::List(left), Self::List(right)) | (Self::Tuple(left), Self::Tuple(right)) => {
+                left == right
+            }
+            (Self::Record(left), Self::Record(right)) => left == right,
+            (Self::Map(left), Self::Map(right)) => {
+                left.len() == right.len()
+                    && left.iter().all(|(key, value)| {
+                        right.iter().any(|(other_key, other_value)| {
+                            key == other_key && value == other_value
+                        })
+                    })
+            }
+            (Self::Set(left), Self::Set(right)) => {
+                left.len() == right.len() && left.iter().all(|element| right.contains(element))
+            }
+            (Self::Tag(left_name, left_contents), Self::Tag(right_name, right_contents))
+            | (Self::Enum(left_name, left_contents), Self::Enum(right_name, right_contents)) => {
+                left_name == right_name && left_contents == right_contents
+            }
+            (Self::Function(left), Self::Function(right)) => Rc::ptr_eq(left, right),
+            _ => false,
+        }
+    }
+}
+
+fn write_separated<T>(
+    formatter: &mut fmt::Formatter,
+    items: impl IntoIterator<Item = T>,
+    mut write_item: impl FnMut(&mut fmt::Formatter, T) -> fmt::Result,
+) -> fmt::Result {
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            write!(formatter, ", ")?;
+        }
+        write_item(formatter, item)?;
+    }
+    Ok(())
+}
+
+fn write_contents(formatter: &mut fmt::Formatter, contents: &[Value]) -> fmt::Result {
+    if contents.is_empty() {
+        return Ok(());
+    }
+    write!(formatter, "(")?;
+    write_separated(formatter, contents, |formatter, value| {
+        write!(formatter, "{value}")
+    })?;
+    write!(formatter, ")")
+}
+
+/// Values are displayed the way they would be written in Buri.
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Boolean(value) => write!(formatter, "{value}"),
+            Self::Integer(value) => write!(formatter, "{value}"),
+            Self::Float(value) => write!(formatter, "{value:?}"),
+            Self::String(value) => write!(formatter, "{value:?}"),
+            Self::List(elements) => {
+                write!(formatter, "[")?;
+                write_separated(formatter, elements.iter(), |formatter, value| {
+                    write!(formatter, "{value}")
+                })?;
+                write!(formatter, "]")
+            }
+            Self::Tuple(elements) => {
+                write!(formatter, "(")?;
+                write_separated(formatter, elements.iter(), |formatter, value| {
+                    write!(formatter, "{value}")
+                })?;
+                write!(formatter, ")")
+            }
+            Self::Record(fields) if fields.is_empty() => write!(formatter, "{{}}"),
+            Self::Record(fields) => {
+                write!(formatter, "{{ ")?;
+                write_separated(formatter, fields.iter(), |formatter, (name, value)| {
+                    write!(formatter, "{name}: {value}")
+                })?;
+                write!(formatter, " }}")
+            }
+            Self::Map(entries) => {
+                write!(formatter, "Map[")?;
+                write_separated(formatter, entries.iter(), |formatter, (key, value)| {
+                    write!(formatter, "{key} => {value}")
+                })?;
+                write!(formatter, "]")
+            }
+            Self::Set(elements) => {
+                write!(formatter, "Set[")?;
+                write_separated(formatter, elements.iter(), |formatter, value| {
+                    write!(formatter, "{value}")
+                })?;
+                write!(formatter, "]")
+            }
+            Self::Tag(name, contents) => {
+                write!(formatter, "#{name}")?;
+                write_contents(formatter, contents)
+            }
+            Self::Enum(name, payload) => {
+                write!(formatter, ".{name}")?;
+                write_contents(formatter, payload)
+            }
+            Self::Function(closure) => write!(
+                formatter,
+                "({}) => ...",
+                closure.function.argument_names.join(", ")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn maps_and_sets_are_equal_in_any_order() {
+        let left = Value::Set(Rc::new(vec![Value::Integer(1), Value::Integer(2)]));
+        let right = Value::Set(Rc::new(vec![Value::Integer(2), Value::Integer(1)]));
+        assert_eq!(left, right);
+        let left = Value::Map(Rc::new(vec![
+            (Value::Integer(1), Value::string("one")),
+            (Value::Integer(2), Value::string("two")),
+        ]));
+        let right = Value::Map(Rc::new(vec![
+            (Value::Integer(2), Value::string("two")),
+            (Value::Integer(1), Value::string("one")),
+        ]));
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn values_are_displayed_as_buri() {
+        let record = Value::Record(Rc::new(IndexMap::from([
+            ("name".to_string(), Value::string("Sam")),
+            (
+                "friends".to_string(),
+                Value::List(Rc::new(vec![Value::some(Value::Float(1.0))])),
+            ),
+        ])));
+        assert_eq!(
+            record.to_string(),
+            "{ name: \"Sam\", friends: [#some(1.0)] }"
+        );
+        let map = Value::Map(Rc::new(vec![(
+            Value::Enum("red".to_string(), vec![]),
+            Value::Tuple(Rc::new(vec![Value::Integer(1), Value::none()])),
+        )]));
+        assert_eq!(map.to_string(), "Map[.red => (1, #none)]");
+    }
+}
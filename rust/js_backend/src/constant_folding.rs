@@ -0,0 +1,444 @@
+use crate::traversal::map_children;
+use ast::{BinaryOperatorSymbol, TopLevelDeclaration, UnaryOperatorSymbol};
+use typed_ast::{
+    ConcreteBinaryOperatorExpression, ConcreteBooleanExpression, ConcreteDeclarationExpression,
+    ConcreteDocument, ConcreteExpression, ConcreteIfExpression, ConcreteIntegerLiteralExpression,
+    ConcreteStringLiteralExpression, ConcreteTagExpression, ConcreteType,
+    ConcreteUnaryOperatorExpression, ConcreteWhenCaseName, ConcreteWhenExpression, PrimitiveType,
+};
+
+/// `Int`s can be JS numbers, which are only exact up to 2^53 - 1. Operations whose
+/// operands or results are any larger are left for JS to compute.
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+fn get_integer(expression: &ConcreteExpression) -> Option<i64> {
+    let value = match expression {
+        ConcreteExpression::Integer(integer) => i64::try_from(integer.value).ok()?,
+        ConcreteExpression::UnaryOperator(unary_operator)
+            if unary_operator.symbol == UnaryOperatorSymbol::Negative =>
+        {
+            -get_integer(&unary_operator.child)?
+        }
+        _ => return None,
+    };
+    (value.abs() <= MAX_SAFE_INTEGER).then_some(value)
+}
+
+/// Integer literals can't be negative, so negative results are negated literals.
+fn integer_literal(value: i64) -> Option<ConcreteExpression> {
+    if value.abs() > MAX_SAFE_INTEGER {
+        return None;
+    }
+    let literal = ConcreteExpression::Integer(Box::new(ConcreteIntegerLiteralExpression {
+        expression_type: ConcreteType::Primitive(PrimitiveType::Int),
+        value: value.unsigned_abs(),
+    }));
+    if value >= 0 {
+        return Some(literal);
+    }
+    Some(ConcreteExpression::UnaryOperator(Box::new(
+        ConcreteUnaryOperatorExpression {
+            expression_type: ConcreteType::Primitive(PrimitiveType::Int),
+            symbol: UnaryOperatorSymbol::Negative,
+            child: literal,
+        },
+    )))
+}
+
+fn boolean_literal(value: bool) -> ConcreteExpression {
+    ConcreteExpression::Boolean(Box::new(ConcreteBooleanExpression {
+        expression_type: ConcreteType::Primitive(PrimitiveType::CompilerBoolean),
+        value,
+    }))
+}
+
+/// Evaluates an operation on two `Int`s the way the compiled JS would.
+fn fold_integer_operation(
+    symbol: &BinaryOperatorSymbol,
+    left: i64,
+    right: i64,
+) -> Option<ConcreteExpression> {
+    match symbol {
+        BinaryOperatorSymbol::Add => integer_literal(left.checked_add(right)?),
+        BinaryOperatorSymbol::Subtract => integer_literal(left.checked_sub(right)?),
+        BinaryOperatorSymbol::Multiply => integer_literal(left.checked_mul(right)?),
+        // Both Rust and the compiled JS truncate towards zero, and dividing by zero
+        // is left to JS.
+        BinaryOperatorSymbol::Divide if right != 0 => integer_literal(left / right),
+        BinaryOperatorSymbol::Modulus if right != 0 => {
+            integer_literal(((left % right) + right) % right)
+        }
+        BinaryOperatorSymbol::Power if right >= 0 => {
+            integer_literal(left.checked_pow(u32::try_from(right).ok()?)?)
+        }
+        BinaryOperatorSymbol::EqualTo => Some(boolean_literal(left == right)),
+        BinaryOperatorSymbol::NotEqualTo => Some(boolean_literal(left != right)),
+        BinaryOperatorSymbol::LessThan => Some(boolean_literal(left < right)),
+        BinaryOperatorSymbol::LessThanOrEqualTo => Some(boolean_literal(left <= right)),
+        BinaryOperatorSymbol::GreaterThan => Some(boolean_literal(left > right)),
+        BinaryOperatorSymbol::GreaterThanOrEqualTo => Some(boolean_literal(left >= right)),
+        _ => None,
+    }
+}
+
+/// Strings are only compared for equality, since JS orders them by UTF-16 code units.
+fn fold_string_operation(
+    symbol: &BinaryOperatorSymbol,
+    left: &str,
+    right: &str,
+) -> Option<ConcreteExpression> {
+    match symbol {
+        BinaryOperatorSymbol::Concatenate => Some(ConcreteExpression::StringLiteral(Box::new(
+            ConcreteStringLiteralExpression {
+                expression_type: ConcreteType::Primitive(PrimitiveType::Str),
+                value: format!("{left}{right}"),
+            },
+        ))),
+        BinaryOperatorSymbol::EqualTo => Some(boolean_literal(left == right)),
+        BinaryOperatorSymbol::NotEqualTo => Some(boolean_literal(left != right)),
+        _ => None,
+    }
+}
+
+/// `and` and `or` only need their left operand to be known, since the right one is
+/// only evaluated depending on it.
+fn fold_boolean_operation(
+    symbol: &BinaryOperatorSymbol,
+    left: bool,
+    right: ConcreteExpression,
+) -> Result<ConcreteExpression, ConcreteExpression> {
+    match (symbol, left) {
+        (BinaryOperatorSymbol::And, true) | (BinaryOperatorSymbol::Or, false) => Ok(right),
+        (BinaryOperatorSymbol::And, false) | (BinaryOperatorSymbol::Or, true) => {
+            Ok(boolean_literal(left))
+        }
+        (BinaryOperatorSymbol::EqualTo, _) => match right {
+            ConcreteExpression::Boolean(right) => Ok(boolean_literal(left == right.value)),
+            right => Err(right),
+        },
+        (BinaryOperatorSymbol::NotEqualTo, _) => match right {
+            ConcreteExpression::Boolean(right) => Ok(boolean_literal(left != right.value)),
+            right => Err(right),
+        },
+        _ => Err(right),
+    }
+}
+
+/// Folds an operator whose operands are already folded.
+fn fold_binary_operator(binary_operator: ConcreteBinaryOperatorExpression) -> ConcreteExpression {
+    let left = binary_operator.left_child;
+    let right = binary_operator.right_child;
+    let folded = match (&left, &right) {
+        (ConcreteExpression::StringLiteral(left), ConcreteExpression::StringLiteral(right)) => {
+            fold_string_operation(&binary_operator.symbol, &left.value, &right.value)
+        }
+        _ => match (get_integer(&left), get_integer(&right)) {
+            (Some(left), Some(right)) => {
+                fold_integer_operation(&binary_operator.symbol, left, right)
+            }
+            _ => None,
+        },
+    };
+    if let Some(folded) = folded {
+        return folded;
+    }
+    let right = match &left {
+        ConcreteExpression::Boolean(boolean) => {
+            match fold_boolean_operation(&binary_operator.symbol, boolean.value, right) {
+                Ok(folded) => return folded,
+                Err(right) => right,
+            }
+        }
+        _ => right,
+    };
+    ConcreteExpression::BinaryOperator(Box::new(ConcreteBinaryOperatorExpression {
+        left_child: left,
+        right_child: right,
+        ..binary_operator
+    }))
+}
+
+fn fold_unary_operator(unary_operator: ConcreteUnaryOperatorExpression) -> ConcreteExpression {
+    let child = unary_operator.child;
+    match (&unary_operator.symbol, &child) {
+        (UnaryOperatorSymbol::Not, ConcreteExpression::Boolean(boolean)) => {
+            return boolean_literal(!boolean.value)
+        }
+        // Negative integers are written as negated literals, so only a negation of
+        // one of those can be folded.
+        (UnaryOperatorSymbol::Negative, ConcreteExpression::UnaryOperator(_)) => {
+            if let Some(folded) = get_integer(&child).and_then(|value| integer_literal(-value)) {
+                return folded;
+            }
+        }
+        _ => {}
+    }
+    ConcreteExpression::UnaryOperator(Box::new(ConcreteUnaryOperatorExpression {
+        child,
+        ..unary_operator
+    }))
+}
+
+/// Replaces an `if` whose condition is known with the path that is taken. An `if`
+/// without an `else` still returns an option.
+fn fold_if(if_expression: ConcreteIfExpression) -> ConcreteExpression {
+    let ConcreteExpression::Boolean(boolean) = &if_expression.condition else {
+        return ConcreteExpression::If(Box::new(if_expression));
+    };
+    match (boolean.value, if_expression.path_if_false) {
+        (true, Some(_)) => if_expression.path_if_true,
+        (false, Some(path_if_false)) => path_if_false,
+        (true, None) => ConcreteExpression::Tag(Box::new(ConcreteTagExpression {
+            expression_type: if_expression.expression_type,
+            name: "some".to_string(),
+            contents: vec![if_expression.path_if_true],
+        })),
+        (false, None) => ConcreteExpression::Tag(Box::new(ConcreteTagExpression {
+            expression_type: if_expression.expression_type,
+            name: "none".to_string(),
+            contents: vec![],
+        })),
+    }
+}
+
+/// When the condition is a tag literal, only the case which matches it is kept. If
+/// that case doesn't bind the tag's contents, it replaces the whole `when`.
+fn fold_when(mut when: ConcreteWhenExpression) -> ConcreteExpression {
+    if let ConcreteExpression::Tag(tag) = &when.condition {
+        let matching_case = when
+            .cases
+            .iter()
+            .position(|case| matches!(&case.case_name, ConcreteWhenCaseName::Name(name) if *name == tag.name))
+            .or_else(|| {
+                when.cases
+                    .iter()
+                    .position(|case| case.case_name == ConcreteWhenCaseName::DefaultCase)
+            });
+        if let Some(index) = matching_case {
+            let case = when.cases.swap_remove(index);
+            if case.case_arguments.is_empty() {
+                return case.case_expression;
+            }
+            when.cases = vec![case];
+        }
+    }
+    ConcreteExpression::When(Box::new(when))
+}
+
+/// Evaluates the parts of the expression which are known at compile time, and removes
+/// the branches which can't be taken.
+pub fn fold_expression(expression: ConcreteExpression) -> ConcreteExpression {
+    match map_children(expression, &mut fold_expression) {
+        ConcreteExpression::BinaryOperator(binary_operator) => {
+            fold_binary_operator(*binary_operator)
+        }
+        ConcreteExpression::UnaryOperator(unary_operator) => fold_unary_operator(*unary_operator),
+        ConcreteExpression::If(if_expression) => fold_if(*if_expression),
+        ConcreteExpression::When(when) => fold_when(*when),
+        expression => expression,
+    }
+}
+
+/// Folds the constants in every top-level declaration.
+pub fn fold_constants(document: &mut ConcreteDocument) {
+    document.variable_declarations = std::mem::take(&mut document.variable_declarations)
+        .into_iter()
+        .map(|declaration| TopLevelDeclaration {
+            declaration: ConcreteDeclarationExpression {
+                value: fold_expression(declaration.declaration.value),
+                ..declaration.declaration
+            },
+            ..declaration
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use typed_ast::{ConcreteWhenCase, SourceLocation};
+
+    fn binary_operator_for_test(
+        symbol: BinaryOperatorSymbol,
+        left_child: ConcreteExpression,
+        right_child: ConcreteExpression,
+    ) -> ConcreteExpression {
+        ConcreteExpression::BinaryOperator(Box::new(ConcreteBinaryOperatorExpression {
+            expression_type: ConcreteType::default_binary_operator_for_test(),
+            source: SourceLocation::default(),
+            symbol,
+            left_child,
+            right_child,
+        }))
+    }
+
+    fn if_for_test(
+        condition: bool,
+        path_if_false: Option<ConcreteExpression>,
+    ) -> ConcreteExpression {
+        ConcreteExpression::If(Box::new(ConcreteIfExpression {
+            expression_type: ConcreteType::default_for_test(),
+            condition: boolean_literal(condition),
+            path_if_true: ConcreteExpression::identifier_for_test("a"),
+            path_if_false,
+        }))
+    }
+
+    #[test]
+    fn integer_arithmetic_is_folded() {
+        let expression = binary_operator_for_test(
+            BinaryOperatorSymbol::Multiply,
+            binary_operator_for_test(
+                BinaryOperatorSymbol::Multiply,
+                ConcreteExpression::integer_for_test(60),
+                ConcreteExpression::integer_for_test(60),
+            ),
+            ConcreteExpression::integer_for_test(24),
+        );
+        assert_eq!(
+            fold_expression(expression),
+            ConcreteExpression::integer_for_test(86400)
+        );
+    }
+
+    #[test]
+    fn negative_results_are_negated_literals() {
+        let expression = binary_operator_for_test(
+            BinaryOperatorSymbol::Divide,
+            ConcreteExpression::negative_unary_operator_for_test(
+                ConcreteExpression::integer_for_test(7),
+            ),
+            ConcreteExpression::integer_for_test(2),
+        );
+        assert_eq!(
+            fold_expression(expression),
+            ConcreteExpression::negative_unary_operator_for_test(
+                ConcreteExpression::integer_for_test(3)
+            )
+        );
+    }
+
+    #[test]
+    fn modulo_is_never_negative() {
+        let expression = binary_operator_for_test(
+            BinaryOperatorSymbol::Modulus,
+            ConcreteExpression::negative_unary_operator_for_test(
+                ConcreteExpression::integer_for_test(1),
+            ),
+            ConcreteExpression::integer_for_test(3),
+        );
+        assert_eq!(
+            fold_expression(expression),
+            ConcreteExpression::integer_for_test(2)
+        );
+    }
+
+    #[test]
+    fn division_by_zero_is_left_to_js() {
+        let expression = binary_operator_for_test(
+            BinaryOperatorSymbol::Divide,
+            ConcreteExpression::integer_for_test(1),
+            ConcreteExpression::integer_for_test(0),
+        );
+        assert_eq!(fold_expression(expression.clone()), expression);
+    }
+
+    #[test]
+    fn integers_which_js_cant_represent_exactly_are_not_folded() {
+        let expression = binary_operator_for_test(
+            BinaryOperatorSymbol::Add,
+            ConcreteExpression::integer_for_test(1 << 53),
+            ConcreteExpression::integer_for_test(1),
+        );
+        assert_eq!(fold_expression(expression.clone()), expression);
+    }
+
+    #[test]
+    fn strings_are_concatenated() {
+        let expression = binary_operator_for_test(
+            BinaryOperatorSymbol::Concatenate,
+            ConcreteExpression::string_for_test("api/"),
+            ConcreteExpression::string_for_test("v2"),
+        );
+        assert_eq!(
+            fold_expression(expression),
+            ConcreteExpression::string_for_test("api/v2")
+        );
+    }
+
+    #[test]
+    fn comparisons_are_folded_to_booleans() {
+        let expression = binary_operator_for_test(
+            BinaryOperatorSymbol::LessThan,
+            ConcreteExpression::integer_for_test(1),
+            ConcreteExpression::integer_for_test(2),
+        );
+        assert_eq!(fold_expression(expression), boolean_literal(true));
+    }
+
+    #[test]
+    fn known_left_operands_short_circuit() {
+        let expression = binary_operator_for_test(
+            BinaryOperatorSymbol::And,
+            boolean_literal(true),
+            ConcreteExpression::identifier_for_test("a"),
+        );
+        assert_eq!(
+            fold_expression(expression),
+            ConcreteExpression::identifier_for_test("a")
+        );
+        let expression = binary_operator_for_test(
+            BinaryOperatorSymbol::Or,
+            boolean_literal(true),
+            ConcreteExpression::identifier_for_test("a"),
+        );
+        assert_eq!(fold_expression(expression), boolean_literal(true));
+    }
+
+    #[test]
+    fn ifs_with_known_conditions_take_one_path() {
+        let expression = if_for_test(false, Some(ConcreteExpression::identifier_for_test("b")));
+        assert_eq!(
+            fold_expression(expression),
+            ConcreteExpression::identifier_for_test("b")
+        );
+    }
+
+    #[test]
+    fn ifs_without_else_still_return_options() {
+        assert_eq!(
+            fold_expression(if_for_test(true, None)),
+            ConcreteExpression::Tag(Box::new(ConcreteTagExpression {
+                expression_type: ConcreteType::default_for_test(),
+                name: "some".to_string(),
+                contents: vec![ConcreteExpression::identifier_for_test("a")],
+            }))
+        );
+    }
+
+    #[test]
+    fn whens_on_known_tags_take_the_matching_case() {
+        let case = |case_name: ConcreteWhenCaseName, value: u64| ConcreteWhenCase {
+            expression_type: ConcreteType::default_for_test(),
+            case_name,
+            case_arguments: vec![],
+            case_expression: ConcreteExpression::integer_for_test(value),
+        };
+        let when = ConcreteExpression::When(Box::new(ConcreteWhenExpression {
+            expression_type: ConcreteType::default_for_test(),
+            condition: ConcreteExpression::Tag(Box::new(ConcreteTagExpression {
+                expression_type: ConcreteType::default_tag_union_for_test(),
+                name: "blue".to_string(),
+                contents: vec![],
+            })),
+            cases: vec![
+                case(ConcreteWhenCaseName::Name("red".to_string()), 1),
+                case(ConcreteWhenCaseName::DefaultCase, 2),
+            ],
+        }));
+        assert_eq!(
+            fold_expression(when),
+            ConcreteExpression::integer_for_test(2)
+        );
+    }
+}
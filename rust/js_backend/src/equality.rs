@@ -0,0 +1,351 @@
+use crate::{
+    helpers::Helper,
+    tags::{get_tag_discriminant, TagRepresentation},
+    PrinterConfiguration,
+};
+use std::collections::BTreeSet;
+use typed_ast::{ConcreteType, PrimitiveType};
+
+/// Values of these types are JS primitives (enums without payloads are
+/// numbers), so they can be compared with `===`. Values of unknown types could be
+/// anything, including lists and records.
+pub fn has_native_equality(concrete_type: &ConcreteType) -> bool {
+    match concrete_type {
+        ConcreteType::Primitive(PrimitiveType::Unknown) => false,
+        ConcreteType::Primitive(_) | ConcreteType::Function(_) => true,
+        ConcreteType::Enum(enum_type) => enum_type.variants.values().all(Vec::is_empty),
+        _ => false,
+    }
+}
+
+fn print_comparators(types: &[ConcreteType], configuration: &PrinterConfiguration) -> String {
+    let comparators = types
+        .iter()
+        .map(|element_type| print_equality_function(element_type, configuration))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("[{comparators}]")
+}
+
+/// Prints a JS function which takes two values of the given type and returns
+/// whether they are structurally equal. The function is built from the
+/// prelude's `$equals*` helpers so that every field, element and payload is
+/// compared with the comparator for its own type. Values of unknown types are
+/// compared by the structure they have at runtime.
+pub fn print_equality_function(
+    concrete_type: &ConcreteType,
+    configuration: &PrinterConfiguration,
+) -> String {
+    if has_native_equality(concrete_type) {
+        return Helper::EqualsPrimitive.name().to_string();
+    }
+    match concrete_type {
+        ConcreteType::Primitive(PrimitiveType::Unknown) => Helper::Equals.name().to_string(),
+        ConcreteType::Primitive(_) | ConcreteType::Function(_) => unreachable!(),
+        ConcreteType::List(list_type) => format!(
+            "{}({})",
+            Helper::EqualsList.name(),
+            print_equality_function(&list_type.element_type, configuration)
+        ),
+        ConcreteType::Tuple(tuple_type) => format!(
+            "{}({})",
+            Helper::EqualsTuple.name(),
+            print_comparators(&tuple_type.element_types, configuration)
+        ),
+        ConcreteType::Map(map_type) => format!(
+            "{}({})",
+            Helper::EqualsMap.name(),
+            print_equality_function(&map_type.value_type, configuration)
+        ),
+        ConcreteType::Set(_) => Helper::EqualsSet.name().to_string(),
+        ConcreteType::Record(record_type) => {
+            let mut field_names = record_type.field_types.keys().collect::<Vec<_>>();
+            field_names.sort();
+            let fields = field_names
+                .into_iter()
+                .map(|name| {
+                    format!(
+                        "{name}:{}",
+                        print_equality_function(&record_type.field_types[name], configuration)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("{}({{{fields}}})", Helper::EqualsRecord.name())
+        }
+        ConcreteType::TagUnion(tag_union_type) => {
+            let mut tag_names = tag_union_type.tag_types.keys().collect::<Vec<_>>();
+            tag_names.sort();
+            let tags = tag_names
+                .into_iter()
+                .map(|name| {
+                    let key = match configuration.tag_representation {
+                        TagRepresentation::Names => name.clone(),
+                        TagRepresentation::Integers => get_tag_discriminant(name).to_string(),
+                    };
+                    format!(
+                        "{key}:{}",
+                        print_comparators(&tag_union_type.tag_types[name], configuration)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("{}({{{tags}}})", Helper::EqualsTags.name())
+        }
+        // Enums with payloads are printed as `[variantIndex, ...payload]`, where
+        // the index is the position of the variant's name in sorted order.
+        ConcreteType::Enum(enum_type) => {
+            let mut variant_names = enum_type.variants.keys().collect::<Vec<_>>();
+            variant_names.sort();
+            let variants = variant_names
+                .into_iter()
+                .enumerate()
+                .map(|(index, name)| {
+                    format!(
+                        "{index}:{}",
+                        print_comparators(&enum_type.variants[name], configuration)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("{}({{{variants}}})", Helper::EqualsTags.name())
+        }
+    }
+}
+
+/// Adds the helpers which `print_equality_function` uses for the given type.
+pub fn collect_equality_helpers(concrete_type: &ConcreteType, helpers: &mut BTreeSet<Helper>) {
+    if has_native_equality(concrete_type) {
+        helpers.insert(Helper::EqualsPrimitive);
+        return;
+    }
+    match concrete_type {
+        ConcreteType::Primitive(PrimitiveType::Unknown) => {
+            helpers.insert(Helper::Equals);
+        }
+        ConcreteType::Primitive(_) | ConcreteType::Function(_) => unreachable!(),
+        ConcreteType::List(list_type) => {
+            helpers.insert(Helper::EqualsList);
+            collect_equality_helpers(&list_type.element_type, helpers);
+        }
+        ConcreteType::Tuple(tuple_type) => {
+            helpers.insert(Helper::EqualsTuple);
+            for element_type in &tuple_type.element_types {
+                collect_equality_helpers(element_type, helpers);
+            }
+        }
+        ConcreteType::Map(map_type) => {
+            helpers.insert(Helper::EqualsMap);
+            collect_equality_helpers(&map_type.value_type, helpers);
+        }
+        ConcreteType::Set(_) => {
+            helpers.insert(Helper::EqualsSet);
+        }
+        ConcreteType::Record(record_type) => {
+            helpers.insert(Helper::EqualsRecord);
+            for field_type in record_type.field_types.values() {
+                collect_equality_helpers(field_type, helpers);
+            }
+        }
+        ConcreteType::TagUnion(tag_union_type) => {
+            helpers.insert(Helper::EqualsTags);
+            for content_type in tag_union_type.tag_types.values().flatten() {
+                collect_equality_helpers(content_type, helpers);
+            }
+        }
+        ConcreteType::Enum(enum_type) => {
+            helpers.insert(Helper::EqualsTags);
+            for payload_type in enum_type.variants.values().flatten() {
+                collect_equality_helpers(payload_type, helpers);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layout::compact_for_test;
+    use indexmap::IndexMap;
+    use typed_ast::{ConcreteEnumType, ConcreteListType, ConcreteRecordType, ConcreteTagUnionType};
+
+    #[test]
+    fn primitives_are_compared_natively() {
+        let concrete_type = ConcreteType::Primitive(PrimitiveType::Int);
+        assert!(has_native_equality(&concrete_type));
+        assert_eq!(
+            print_equality_function(&concrete_type, &PrinterConfiguration::default()),
+            "$equalsPrimitive"
+        );
+    }
+
+    #[test]
+    fn unknown_types_are_compared_by_their_runtime_structure() {
+        let concrete_type = ConcreteType::Primitive(PrimitiveType::Unknown);
+        assert!(!has_native_equality(&concrete_type));
+        assert_eq!(
+            print_equality_function(&concrete_type, &PrinterConfiguration::default()),
+            "$equals"
+        );
+    }
+
+    #[test]
+    fn elements_and_fields_of_unknown_types_are_compared_by_their_runtime_structure() {
+        let unknown = ConcreteType::Primitive(PrimitiveType::Unknown);
+        let concrete_type = ConcreteType::Record(Box::new(ConcreteRecordType {
+            field_types: IndexMap::from([
+                (
+                    "values".to_string(),
+                    ConcreteType::List(Box::new(ConcreteListType {
+                        element_type: unknown.clone(),
+                    })),
+                ),
+                ("value".to_string(), unknown),
+            ]),
+        }));
+        assert_eq!(
+            print_equality_function(&concrete_type, &PrinterConfiguration::default()),
+            "$equalsRecord({value:$equals,values:$equalsList($equals)})"
+        );
+        let mut helpers = BTreeSet::new();
+        collect_equality_helpers(&concrete_type, &mut helpers);
+        assert_eq!(
+            helpers,
+            BTreeSet::from([Helper::Equals, Helper::EqualsList, Helper::EqualsRecord])
+        );
+    }
+
+    #[test]
+    fn enums_without_payloads_are_compared_natively() {
+        let concrete_type = ConcreteType::Enum(Box::new(ConcreteEnumType {
+            variants: IndexMap::from([("red".to_string(), vec![]), ("blue".to_string(), vec![])]),
+        }));
+        assert!(has_native_equality(&concrete_type));
+    }
+
+    #[test]
+    fn lists_compare_their_elements() {
+        let concrete_type = ConcreteType::List(Box::new(ConcreteListType {
+            element_type: ConcreteType::default_list_for_test(),
+        }));
+        assert_eq!(
+            print_equality_function(&concrete_type, &PrinterConfiguration::default()),
+            "$equalsList($equalsList($equalsPrimitive))"
+        );
+    }
+
+    #[test]
+    fn tuples_compare_each_element() {
+        assert_eq!(
+            print_equality_function(
+                &ConcreteType::default_tuple_for_test(),
+                &PrinterConfiguration::default()
+            ),
+            "$equalsTuple([$equalsPrimitive,$equalsPrimitive])"
+        );
+    }
+
+    #[test]
+    fn maps_compare_their_values() {
+        assert_eq!(
+            print_equality_function(
+                &ConcreteType::default_map_for_test(),
+                &PrinterConfiguration::default()
+            ),
+            "$equalsMap($equalsPrimitive)"
+        );
+    }
+
+    #[test]
+    fn records_compare_every_field_in_sorted_order() {
+        let concrete_type = ConcreteType::Record(Box::new(ConcreteRecordType {
+            field_types: IndexMap::from([
+                ("name".to_string(), ConcreteType::default_string_for_test()),
+                ("friends".to_string(), ConcreteType::default_list_for_test()),
+            ]),
+        }));
+        assert_eq!(
+            print_equality_function(&concrete_type, &PrinterConfiguration::default()),
+            "$equalsRecord({friends:$equalsList($equalsPrimitive),name:$equalsPrimitive})"
+        );
+    }
+
+    #[test]
+    fn tags_compare_their_contents() {
+        let concrete_type = ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
+            tag_types: IndexMap::from([
+                ("none".to_string(), vec![]),
+                (
+                    "some".to_string(),
+                    vec![ConcreteType::default_integer_for_test()],
+                ),
+            ]),
+        }));
+        assert_eq!(
+            compact_for_test(&print_equality_function(
+                &concrete_type,
+                &PrinterConfiguration::default()
+            )),
+            "$equalsTags({none:[],some:[$equalsPrimitive]})"
+        );
+        assert_eq!(
+            print_equality_function(
+                &concrete_type,
+                &PrinterConfiguration {
+                    tag_representation: TagRepresentation::Integers,
+                    ..PrinterConfiguration::default()
+                }
+            ),
+            format!(
+                "$equalsTags({{{}:[],{}:[$equalsPrimitive]}})",
+                get_tag_discriminant("none"),
+                get_tag_discriminant("some")
+            )
+        );
+    }
+
+    #[test]
+    fn enums_with_payloads_are_keyed_by_variant_index() {
+        let concrete_type = ConcreteType::Enum(Box::new(ConcreteEnumType {
+            variants: IndexMap::from([
+                (
+                    "some".to_string(),
+                    vec![ConcreteType::default_string_for_test()],
+                ),
+                ("none".to_string(), vec![]),
+            ]),
+        }));
+        assert!(!has_native_equality(&concrete_type));
+        assert_eq!(
+            print_equality_function(&concrete_type, &PrinterConfiguration::default()),
+            "$equalsTags({0:[],1:[$equalsPrimitive]})"
+        );
+    }
+
+    #[test]
+    fn collected_helpers_are_the_ones_printed() {
+        let concrete_type = ConcreteType::Record(Box::new(ConcreteRecordType {
+            field_types: IndexMap::from([
+                ("name".to_string(), ConcreteType::default_string_for_test()),
+                ("friends".to_string(), ConcreteType::default_list_for_test()),
+                (
+                    "tag".to_string(),
+                    ConcreteType::default_tag_union_for_test(),
+                ),
+            ]),
+        }));
+        let mut helpers = BTreeSet::new();
+        collect_equality_helpers(&concrete_type, &mut helpers);
+        assert_eq!(
+            helpers,
+            BTreeSet::from([
+                Helper::EqualsList,
+                Helper::EqualsPrimitive,
+                Helper::EqualsRecord,
+                Helper::EqualsTags
+            ])
+        );
+        let printed = print_equality_function(&concrete_type, &PrinterConfiguration::default());
+        assert!(helpers.iter().all(|helper| printed.contains(helper.name())));
+    }
+}
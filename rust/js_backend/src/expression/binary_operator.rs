@@ -1,52 +1,74 @@
+use crate::{
+    equality::{has_native_equality, print_equality_function},
+    helpers::{get_method_helper, is_method_call, Helper},
+    integers::IntegerRepresentation,
+    tags::TagRepresentation,
+    PrinterConfiguration,
+};
 use ast::BinaryOperatorSymbol;
-use typed_ast::{ConcreteBinaryOperatorExpression, ConcreteExpression};
+use typed_ast::{
+    get_concrete_type, ConcreteBinaryOperatorExpression, ConcreteExpression, ConcreteType,
+    PrimitiveType,
+};
 
 enum OperatorFormat {
     /// Do no formatting to the operator. Simply {left}{operator}{right}
     Naked,
     /// Add parenthesis around the entire expression. ({left}{operator}{right})
     Parenthesized,
-    /// Call the operator like a method. ({left}).{operator}({right})
-    Method,
+    /// Call one of the prelude's helper functions. {operator}({left},{right})
+    Helper,
 }
 
-fn print_operator(operator: &BinaryOperatorSymbol) -> String {
+fn print_operator(
+    operator: &BinaryOperatorSymbol,
+    operand_type: &ConcreteType,
+    configuration: &PrinterConfiguration,
+) -> String {
     match operator {
-        BinaryOperatorSymbol::Add => "add".to_string(),
-        BinaryOperatorSymbol::Subtract => "subtract".to_string(),
-        BinaryOperatorSymbol::Multiply => "multiply".to_string(),
-        BinaryOperatorSymbol::Divide => "divide".to_string(),
-        BinaryOperatorSymbol::Power => "power".to_string(),
-        BinaryOperatorSymbol::Modulus => "modulo".to_string(),
-        BinaryOperatorSymbol::EqualTo => "equals".to_string(),
-        BinaryOperatorSymbol::NotEqualTo => "notEquals".to_string(),
-        BinaryOperatorSymbol::LessThan => "lessThan".to_string(),
-        BinaryOperatorSymbol::LessThanOrEqualTo => "lessThanOrEquals".to_string(),
-        BinaryOperatorSymbol::GreaterThan => "greaterThan".to_string(),
-        BinaryOperatorSymbol::GreaterThanOrEqualTo => "greaterThanOrEquals".to_string(),
+        BinaryOperatorSymbol::Add | BinaryOperatorSymbol::Concatenate => "+".to_string(),
+        BinaryOperatorSymbol::Subtract => "-".to_string(),
+        BinaryOperatorSymbol::Multiply => "*".to_string(),
+        BinaryOperatorSymbol::Divide => "/".to_string(),
+        BinaryOperatorSymbol::Power => "**".to_string(),
+        BinaryOperatorSymbol::Modulus => Helper::Modulo.name().to_string(),
+        BinaryOperatorSymbol::EqualTo if has_native_equality(operand_type) => "===".to_string(),
+        BinaryOperatorSymbol::EqualTo => print_equality_function(operand_type, configuration),
+        BinaryOperatorSymbol::NotEqualTo if has_native_equality(operand_type) => "!==".to_string(),
+        BinaryOperatorSymbol::NotEqualTo => {
+            format!("!{}", print_equality_function(operand_type, configuration))
+        }
+        BinaryOperatorSymbol::LessThan => "<".to_string(),
+        BinaryOperatorSymbol::LessThanOrEqualTo => "<=".to_string(),
+        BinaryOperatorSymbol::GreaterThan => ">".to_string(),
+        BinaryOperatorSymbol::GreaterThanOrEqualTo => ">=".to_string(),
         BinaryOperatorSymbol::And => "&&".to_string(),
         BinaryOperatorSymbol::Or => "||".to_string(),
-        BinaryOperatorSymbol::Concatenate => "+".to_string(),
         BinaryOperatorSymbol::MethodLookup | BinaryOperatorSymbol::FieldLookup => ".".to_string(),
         BinaryOperatorSymbol::FunctionApplication => String::new(),
     }
 }
 
-const fn get_format(operator: &BinaryOperatorSymbol) -> OperatorFormat {
+fn get_format(operator: &BinaryOperatorSymbol, operand_type: &ConcreteType) -> OperatorFormat {
     match operator {
+        BinaryOperatorSymbol::Modulus => OperatorFormat::Helper,
+        BinaryOperatorSymbol::EqualTo | BinaryOperatorSymbol::NotEqualTo
+            if !has_native_equality(operand_type) =>
+        {
+            OperatorFormat::Helper
+        }
         BinaryOperatorSymbol::Add
         | BinaryOperatorSymbol::Subtract
         | BinaryOperatorSymbol::Multiply
         | BinaryOperatorSymbol::Divide
         | BinaryOperatorSymbol::Power
-        | BinaryOperatorSymbol::Modulus
         | BinaryOperatorSymbol::EqualTo
         | BinaryOperatorSymbol::NotEqualTo
         | BinaryOperatorSymbol::LessThan
         | BinaryOperatorSymbol::LessThanOrEqualTo
         | BinaryOperatorSymbol::GreaterThan
-        | BinaryOperatorSymbol::GreaterThanOrEqualTo => OperatorFormat::Method,
-        BinaryOperatorSymbol::Concatenate
+        | BinaryOperatorSymbol::GreaterThanOrEqualTo
+        | BinaryOperatorSymbol::Concatenate
         | BinaryOperatorSymbol::And
         | BinaryOperatorSymbol::Or
         | BinaryOperatorSymbol::FunctionApplication => OperatorFormat::Parenthesized,
@@ -58,18 +80,77 @@ const fn get_format(operator: &BinaryOperatorSymbol) -> OperatorFormat {
 
 fn maybe_parenthesize_left(string: &str, expression: &ConcreteExpression) -> String {
     match expression {
-        ConcreteExpression::Integer(_) | ConcreteExpression::UnaryOperator(_) => {
+        ConcreteExpression::Integer(_)
+        | ConcreteExpression::Float(_)
+        | ConcreteExpression::UnaryOperator(_) => {
             format!("({string})")
         }
         _ => string.to_string(),
     }
 }
 
-pub fn print_binary_operator(expression: &ConcreteBinaryOperatorExpression) -> String {
-    let operator = print_operator(&expression.symbol);
-    let left = super::print_expression(&expression.left_child);
+/// Keeps `1 - -1` from printing as `1--1` and `-2 ** 2` from being a syntax error.
+fn maybe_parenthesize_operand(string: String, expression: &ConcreteExpression) -> String {
+    match expression {
+        ConcreteExpression::UnaryOperator(_) => format!("({string})"),
+        _ => string,
+    }
+}
+
+/// The arguments which a method's helper takes before the method's own: the
+/// receiver, and the method's name for `$callMethod`.
+fn print_method_helper_arguments(
+    helper: Helper,
+    method_lookup: &ConcreteBinaryOperatorExpression,
+    configuration: &PrinterConfiguration,
+) -> Vec<String> {
+    let mut arguments = vec![super::print_expression(
+        &method_lookup.left_child,
+        configuration,
+    )];
+    if let (Helper::CallMethod, ConcreteExpression::Identifier(method)) =
+        (helper, &method_lookup.right_child)
+    {
+        arguments.push(format!("\"{}\"", method.name));
+    }
+    arguments
+}
+
+/// Calls the helper which a method of a list, string or float is compiled to.
+fn print_method_helper_call(
+    method_lookup: &ConcreteBinaryOperatorExpression,
+    arguments: &ConcreteExpression,
+    configuration: &PrinterConfiguration,
+) -> Option<String> {
+    let helper = get_method_helper(method_lookup)?;
+    let mut arguments_text = print_method_helper_arguments(helper, method_lookup, configuration);
+    if let ConcreteExpression::FunctionArguments(arguments) = arguments {
+        arguments_text.extend(
+            arguments
+                .iter()
+                .map(|argument| super::print_expression(argument, configuration)),
+        );
+    }
+    Some(format!("{}({})", helper.name(), arguments_text.join(",")))
+}
+
+pub fn print_binary_operator(
+    expression: &ConcreteBinaryOperatorExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let left = super::print_expression(&expression.left_child, configuration);
+    // Tuples are arrays, so their elements are looked up by index.
+    if let (BinaryOperatorSymbol::FieldLookup, ConcreteExpression::Integer(index)) =
+        (&expression.symbol, &expression.right_child)
+    {
+        return format!(
+            "{}[{}]",
+            maybe_parenthesize_left(&left, &expression.left_child),
+            index.value
+        );
+    }
     let right = {
-        let right_child_text = super::print_expression(&expression.right_child);
+        let right_child_text = super::print_expression(&expression.right_child, configuration);
         match &expression.symbol {
             BinaryOperatorSymbol::MethodLookup | BinaryOperatorSymbol::FieldLookup => {
                 right_child_text[1..].to_owned()
@@ -77,42 +158,113 @@ pub fn print_binary_operator(expression: &ConcreteBinaryOperatorExpression) -> S
             _ => right_child_text,
         }
     };
-    match get_format(&expression.symbol) {
-        OperatorFormat::Naked => format!(
-            "{}{operator}{right}",
-            maybe_parenthesize_left(&left, &expression.left_child)
-        ),
-        OperatorFormat::Parenthesized => format!("({left}{operator}{right})"),
-        OperatorFormat::Method => {
-            format!(
-                "{}.{}({})",
-                maybe_parenthesize_left(&left, &expression.left_child),
-                operator,
-                right
-            )
+    let operand_type = match &expression.symbol {
+        BinaryOperatorSymbol::MethodLookup
+        | BinaryOperatorSymbol::FieldLookup
+        | BinaryOperatorSymbol::FunctionApplication => &expression.expression_type,
+        _ => get_concrete_type(&expression.left_child),
+    };
+    let operator = print_operator(&expression.symbol, operand_type, configuration);
+    match get_format(&expression.symbol, operand_type) {
+        // Helpers aren't properties of the receiver, so a method which isn't called is
+        // the helper with the receiver bound to it.
+        OperatorFormat::Naked => match get_method_helper(expression) {
+            Some(helper) if expression.symbol == BinaryOperatorSymbol::MethodLookup => format!(
+                "{}.bind(null,{})",
+                helper.name(),
+                print_method_helper_arguments(helper, expression, configuration).join(",")
+            ),
+            _ => format!(
+                "{}{operator}{right}",
+                maybe_parenthesize_left(&left, &expression.left_child)
+            ),
+        },
+        OperatorFormat::Parenthesized => {
+            let left = maybe_parenthesize_operand(left, &expression.left_child);
+            let right = maybe_parenthesize_operand(right, &expression.right_child);
+            let is_number = operand_type != &ConcreteType::Primitive(PrimitiveType::Float)
+                && configuration.integer_representation == IntegerRepresentation::Number;
+            match &expression.symbol {
+                // Generic functions may divide `Int`s or `Float`s, which can only be told
+                // apart when they are divided.
+                BinaryOperatorSymbol::Divide
+                    if operand_type == &ConcreteType::Primitive(PrimitiveType::Num) =>
+                {
+                    format!("{}({left},{right})", Helper::Divide.name())
+                }
+                // `Int`s are truncated towards zero when divided. `||0` turns `-0` into `0`.
+                // BigInts are already truncated, and don't have `-0`.
+                BinaryOperatorSymbol::Divide if is_number => {
+                    format!("(Math.trunc({left}/{right})||0)")
+                }
+                BinaryOperatorSymbol::Multiply if is_number => format!("({left}*{right}||0)"),
+                // The prelude's methods return tags with their names, and `Int`s as
+                // JS numbers.
+                BinaryOperatorSymbol::FunctionApplication if is_method_call(expression) => {
+                    let conversion = match expression.expression_type {
+                        ConcreteType::TagUnion(_)
+                            if configuration.tag_representation == TagRepresentation::Integers =>
+                        {
+                            Helper::CompactTag.name().to_string()
+                        }
+                        ConcreteType::Primitive(PrimitiveType::Int)
+                            if configuration.integer_representation
+                                == IntegerRepresentation::BigInt =>
+                        {
+                            "BigInt".to_string()
+                        }
+                        _ => String::new(),
+                    };
+                    let call = match &expression.left_child {
+                        ConcreteExpression::BinaryOperator(method_lookup) => {
+                            print_method_helper_call(
+                                method_lookup,
+                                &expression.right_child,
+                                configuration,
+                            )
+                        }
+                        _ => None,
+                    };
+                    format!(
+                        "{conversion}({})",
+                        call.unwrap_or_else(|| format!("{left}{right}"))
+                    )
+                }
+                _ => format!("({left}{operator}{right})"),
+            }
         }
+        OperatorFormat::Helper => format!("{operator}({left},{right})"),
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use typed_ast::{ConcreteExpression, ConcreteType};
+    use crate::layout::compact_for_test;
+    use typed_ast::{ConcreteExpression, ConcreteIdentifierExpression, SourceLocation};
 
     #[test]
     fn addition() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Add,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).add(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1+2)"
+        );
     }
 
     #[test]
     fn addition_with_unary_operator_has_parenthesis() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Add,
             left_child: ConcreteExpression::negative_unary_operator_for_test(
@@ -120,45 +272,73 @@ mod test {
             ),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(-1).add(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "((-1)+2)"
+        );
     }
 
     #[test]
-    fn addition_without_number_literals_do_not_have_parenthesis() {
+    fn addition_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Add,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.add(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo+Bbar)"
+        );
     }
 
     #[test]
     fn concatenate() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Concatenate,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1+2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1+2)"
+        );
     }
 
     #[test]
     fn subtraction() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Subtract,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).subtract(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1-2)"
+        );
     }
 
     #[test]
     fn subtraction_with_unary_operator_has_parenthesis() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Subtract,
             left_child: ConcreteExpression::negative_unary_operator_for_test(
@@ -166,34 +346,55 @@ mod test {
             ),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(-1).subtract(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "((-1)-2)"
+        );
     }
 
     #[test]
-    fn subtraction_without_number_literals_do_not_have_parenthesis() {
+    fn subtraction_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Subtract,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.subtract(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo-Bbar)"
+        );
     }
 
     #[test]
     fn multiplication() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Multiply,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).multiply(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1*2||0)"
+        );
     }
 
     #[test]
     fn multiplication_with_unary_operator_has_parenthesis() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Multiply,
             left_child: ConcreteExpression::negative_unary_operator_for_test(
@@ -201,34 +402,55 @@ mod test {
             ),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(-1).multiply(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "((-1)*2||0)"
+        );
     }
 
     #[test]
-    fn multiplication_without_number_literals_do_not_have_parenthesis() {
+    fn multiplication_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Multiply,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.multiply(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo*Bbar||0)"
+        );
     }
 
     #[test]
     fn division() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Divide,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).divide(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Math.trunc(1/2)||0)"
+        );
     }
 
     #[test]
     fn division_with_unary_operator_has_parenthesis() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Divide,
             left_child: ConcreteExpression::negative_unary_operator_for_test(
@@ -236,34 +458,135 @@ mod test {
             ),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(-1).divide(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Math.trunc((-1)/2)||0)"
+        );
     }
 
     #[test]
-    fn division_without_number_literals_do_not_have_parenthesis() {
+    fn division_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Divide,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.divide(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Math.trunc(Bfoo/Bbar)||0)"
+        );
+    }
+
+    #[test]
+    fn bigint_division_and_multiplication_use_the_operators() {
+        for (symbol, expected) in [
+            (BinaryOperatorSymbol::Divide, "(1n/2n)"),
+            (BinaryOperatorSymbol::Multiply, "(1n*2n)"),
+        ] {
+            let expression = ConcreteBinaryOperatorExpression {
+                source: SourceLocation::default(),
+                expression_type: ConcreteType::default_binary_operator_for_test(),
+                symbol,
+                left_child: ConcreteExpression::integer_for_test(1),
+                right_child: ConcreteExpression::integer_for_test(2),
+            };
+            let configuration = PrinterConfiguration {
+                integer_representation: IntegerRepresentation::BigInt,
+                ..PrinterConfiguration::default()
+            };
+            assert_eq!(
+                compact_for_test(&print_binary_operator(&expression, &configuration)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn float_division_does_not_truncate() {
+        let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_float_for_test(),
+            symbol: BinaryOperatorSymbol::Divide,
+            left_child: ConcreteExpression::float_for_test("1.5"),
+            right_child: ConcreteExpression::float_for_test("0.5"),
+        };
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1.5/0.5)"
+        );
+    }
+
+    #[test]
+    fn float_multiplication_does_not_normalize_negative_zero() {
+        let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_binary_operator_for_test(),
+            symbol: BinaryOperatorSymbol::Multiply,
+            left_child: ConcreteExpression::float_for_test("1.5"),
+            right_child: ConcreteExpression::float_for_test("0.5"),
+        };
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1.5*0.5)"
+        );
+    }
+
+    #[test]
+    fn unary_operator_on_the_right_has_parenthesis() {
+        let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_binary_operator_for_test(),
+            symbol: BinaryOperatorSymbol::Subtract,
+            left_child: ConcreteExpression::integer_for_test(1),
+            right_child: ConcreteExpression::negative_unary_operator_for_test(
+                ConcreteExpression::integer_for_test(1),
+            ),
+        };
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1-(-1))"
+        );
     }
 
     #[test]
     fn power() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Power,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).power(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1**2)"
+        );
     }
 
     #[test]
     fn power_with_unary_operator_has_parenthesis() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Power,
             left_child: ConcreteExpression::negative_unary_operator_for_test(
@@ -271,34 +594,55 @@ mod test {
             ),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(-1).power(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "((-1)**2)"
+        );
     }
 
     #[test]
-    fn power_without_number_literals_do_not_have_parenthesis() {
+    fn power_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Power,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.power(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo**Bbar)"
+        );
     }
 
     #[test]
     fn modulus() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Modulus,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).modulo(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "$modulo(1,2)"
+        );
     }
 
     #[test]
-    fn modulus_with_unary_operator_has_parenthesis() {
+    fn modulus_with_unary_operator_is_a_plain_argument() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Modulus,
             left_child: ConcreteExpression::negative_unary_operator_for_test(
@@ -306,227 +650,520 @@ mod test {
             ),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(-1).modulo(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "$modulo(-1,2)"
+        );
     }
 
     #[test]
-    fn modulus_without_number_literals_do_not_have_parenthesis() {
+    fn modulus_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Modulus,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.modulo(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "$modulo(Bfoo,Bbar)"
+        );
     }
 
     #[test]
     fn equal_to() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::EqualTo,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).equals(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1===2)"
+        );
     }
 
     #[test]
-    fn equal_to_without_number_literals_do_not_have_parenthesis() {
+    fn equal_to_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::EqualTo,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.equals(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo===Bbar)"
+        );
     }
 
     #[test]
     fn not_equal_to() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::NotEqualTo,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).notEquals(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1!==2)"
+        );
     }
 
     #[test]
-    fn not_equal_to_without_number_literals_do_not_have_parenthesis() {
+    fn not_equal_to_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::NotEqualTo,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.notEquals(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo!==Bbar)"
+        );
+    }
+
+    #[test]
+    fn structural_types_are_compared_with_a_helper() {
+        let list_identifier = |name: &str| {
+            ConcreteExpression::Identifier(Box::new(ConcreteIdentifierExpression {
+                expression_type: ConcreteType::default_list_for_test(),
+                ..ConcreteExpression::raw_identifier_for_test(name)
+            }))
+        };
+        let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_binary_operator_for_test(),
+            symbol: BinaryOperatorSymbol::EqualTo,
+            left_child: list_identifier("foo"),
+            right_child: list_identifier("bar"),
+        };
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "$equalsList($equalsPrimitive)(Bfoo,Bbar)"
+        );
+    }
+
+    #[test]
+    fn structural_types_are_compared_with_a_negated_helper() {
+        let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_binary_operator_for_test(),
+            symbol: BinaryOperatorSymbol::NotEqualTo,
+            left_child: ConcreteExpression::Identifier(Box::new(ConcreteIdentifierExpression {
+                expression_type: ConcreteType::default_record_for_test(),
+                ..ConcreteExpression::raw_identifier_for_test("foo")
+            })),
+            right_child: ConcreteExpression::identifier_for_test("bar"),
+        };
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "!$equalsRecord({})(Bfoo,Bbar)"
+        );
     }
 
     #[test]
     fn less_than() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::LessThan,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).lessThan(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1<2)"
+        );
     }
 
     #[test]
-    fn less_than_without_number_literals_do_not_have_parenthesis() {
+    fn less_than_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::LessThan,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.lessThan(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo<Bbar)"
+        );
     }
 
     #[test]
     fn less_than_or_equal_to() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::LessThanOrEqualTo,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
         assert_eq!(
-            print_binary_operator(&expression),
-            "(1).lessThanOrEquals(2)"
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1<=2)"
         );
     }
 
     #[test]
-    fn less_than_or_equal_to_without_number_literals_do_not_have_parenthesis() {
+    fn less_than_or_equal_to_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::LessThanOrEqualTo,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
         assert_eq!(
-            print_binary_operator(&expression),
-            "Bfoo.lessThanOrEquals(Bbar)"
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo<=Bbar)"
         );
     }
 
     #[test]
     fn greater_than() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::GreaterThan,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).greaterThan(2)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1>2)"
+        );
     }
 
     #[test]
-    fn greater_than_without_number_literals_do_not_have_parenthesis() {
+    fn greater_than_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::GreaterThan,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.greaterThan(Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo>Bbar)"
+        );
     }
 
     #[test]
     fn greater_than_or_equal_to() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::GreaterThanOrEqualTo,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::integer_for_test(2),
         };
         assert_eq!(
-            print_binary_operator(&expression),
-            "(1).greaterThanOrEquals(2)"
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1>=2)"
         );
     }
 
     #[test]
-    fn greater_than_or_equal_to_without_number_literals_do_not_have_parenthesis() {
+    fn greater_than_or_equal_to_of_identifiers() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::GreaterThanOrEqualTo,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
         assert_eq!(
-            print_binary_operator(&expression),
-            "Bfoo.greaterThanOrEquals(Bbar)"
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo>=Bbar)"
         );
     }
 
     #[test]
     fn and() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::And,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "(Bfoo&&Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo&&Bbar)"
+        );
     }
 
     #[test]
     fn or() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::Or,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "(Bfoo||Bbar)");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo||Bbar)"
+        );
     }
 
     #[test]
     fn method_lookup() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::MethodLookup,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.bar");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "Bfoo.bar"
+        );
     }
 
     #[test]
     fn wrap_left_in_parenthesis_if_left_is_integer_literal_in_method_lookup() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::MethodLookup,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::identifier_for_test("foo"),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).foo");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1).foo"
+        );
     }
 
     #[test]
     fn field_lookup() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::FieldLookup,
             left_child: ConcreteExpression::identifier_for_test("foo"),
             right_child: ConcreteExpression::identifier_for_test("bar"),
         };
-        assert_eq!(print_binary_operator(&expression), "Bfoo.bar");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "Bfoo.bar"
+        );
     }
 
     #[test]
     fn wrap_left_in_parenthesis_if_left_is_integer_literal_in_field_lookup() {
         let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_binary_operator_for_test(),
             symbol: BinaryOperatorSymbol::FieldLookup,
             left_child: ConcreteExpression::integer_for_test(1),
             right_child: ConcreteExpression::identifier_for_test("foo"),
         };
-        assert_eq!(print_binary_operator(&expression), "(1).foo");
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(1).foo"
+        );
+    }
+
+    #[test]
+    fn tuple_element_lookup() {
+        let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_integer_for_test(),
+            symbol: BinaryOperatorSymbol::FieldLookup,
+            left_child: ConcreteExpression::identifier_for_test("pair"),
+            right_child: ConcreteExpression::integer_for_test(1),
+        };
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "Bpair[1]"
+        );
+    }
+
+    fn receiver_for_test(name: &str, receiver_type: ConcreteType) -> ConcreteExpression {
+        ConcreteExpression::Identifier(Box::new(ConcreteIdentifierExpression {
+            expression_type: receiver_type,
+            ..ConcreteExpression::raw_identifier_for_test(name)
+        }))
+    }
+
+    fn method_call_for_test(
+        receiver: ConcreteExpression,
+        method_name: &str,
+    ) -> ConcreteBinaryOperatorExpression {
+        ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_tag_union_for_test(),
+            symbol: BinaryOperatorSymbol::FunctionApplication,
+            left_child: ConcreteExpression::BinaryOperator(Box::new(
+                ConcreteBinaryOperatorExpression {
+                    source: SourceLocation::default(),
+                    expression_type: ConcreteType::default_function_for_test(),
+                    symbol: BinaryOperatorSymbol::MethodLookup,
+                    left_child: receiver,
+                    right_child: ConcreteExpression::identifier_for_test(method_name),
+                },
+            )),
+            right_child: ConcreteExpression::FunctionArguments(vec![
+                ConcreteExpression::string_for_test("a"),
+            ]),
+        }
+    }
+
+    #[test]
+    fn list_methods_call_the_prelude_helpers() {
+        let expression = method_call_for_test(
+            receiver_for_test("names", ConcreteType::default_list_for_test()),
+            "get",
+        );
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "($listGet(Bnames,\"a\"))"
+        );
+        assert_eq!(
+            print_binary_operator(
+                &expression,
+                &PrinterConfiguration {
+                    tag_representation: TagRepresentation::Integers,
+                    ..PrinterConfiguration::default()
+                }
+            ),
+            "$compactTag($listGet(Bnames,\"a\"))"
+        );
+    }
+
+    #[test]
+    fn map_methods_are_called_on_the_map() {
+        let expression = method_call_for_test(
+            receiver_for_test("ages", ConcreteType::default_map_for_test()),
+            "get",
+        );
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bages.get(\"a\"))"
+        );
+    }
+
+    #[test]
+    fn helper_methods_which_are_not_called_are_bound_to_the_receiver() {
+        let expression = ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_function_for_test(),
+            symbol: BinaryOperatorSymbol::MethodLookup,
+            left_child: receiver_for_test("name", ConcreteType::default_string_for_test()),
+            right_child: ConcreteExpression::identifier_for_test("size"),
+        };
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "$stringSize.bind(null,Bname)"
+        );
+    }
+
+    #[test]
+    fn methods_of_generic_receivers_are_looked_up_when_called() {
+        let expression = method_call_for_test(
+            receiver_for_test("items", ConcreteType::Primitive(PrimitiveType::Unknown)),
+            "append",
+        );
+        assert_eq!(
+            compact_for_test(&print_binary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "($callMethod(Bitems,\"append\",\"a\"))"
+        );
     }
 }
@@ -1,28 +1,52 @@
+use super::statement::{print_statement, print_statements, Destination, Target};
+use crate::{
+    layout::print_braced,
+    markers::{NEWLINE, SPACE},
+    PrinterConfiguration,
+};
 use typed_ast::ConcreteBlockExpression;
 
-pub fn print_block(block: &ConcreteBlockExpression) -> String {
+pub fn print_block(
+    block: &ConcreteBlockExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
     if block.contents.is_empty() {
         return String::new();
     }
     if block.contents.len() == 1 {
-        return super::print_expression(&block.contents[0]);
+        return super::print_expression(&block.contents[0], configuration);
     }
+    format!(
+        "(()=>{SPACE}{})()",
+        print_braced(&print_block_statements(
+            block,
+            Destination::new(Target::Return),
+            configuration
+        ))
+    )
+}
+
+pub fn print_block_statements(
+    block: &ConcreteBlockExpression,
+    destination: Destination,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let Some((last, rest)) = block.contents.split_last() else {
+        return String::new();
+    };
     let mut result = String::new();
-    result.push_str("(()=>{");
-    for (index, expression) in block.contents.iter().enumerate() {
-        if index == &block.contents.len() - 1 {
-            result.push_str("return ");
-        }
-        result.push_str(super::print_expression(expression).as_str());
-        result.push(';');
+    for expression in rest {
+        result.push_str(&print_statement(expression, configuration));
+        result.push(NEWLINE);
     }
-    result.push_str("})()");
+    result.push_str(&print_statements(last, destination, configuration));
     result
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::layout::compact_for_test;
     use typed_ast::{ConcreteExpression, ConcreteType};
 
     #[test]
@@ -31,7 +55,10 @@ mod test {
             expression_type: ConcreteType::default_integer_for_test(),
             contents: vec![],
         };
-        assert_eq!(print_block(&block), "");
+        assert_eq!(
+            compact_for_test(&print_block(&block, &PrinterConfiguration::default())),
+            ""
+        );
     }
 
     #[test]
@@ -40,7 +67,10 @@ mod test {
             expression_type: ConcreteType::default_integer_for_test(),
             contents: vec![ConcreteExpression::integer_for_test(42)],
         };
-        assert_eq!(print_block(&block), "42");
+        assert_eq!(
+            compact_for_test(&print_block(&block, &PrinterConfiguration::default())),
+            "42"
+        );
     }
 
     #[test]
@@ -52,6 +82,28 @@ mod test {
                 ConcreteExpression::integer_for_test(43),
             ],
         };
-        assert_eq!(print_block(&block), "(()=>{42;return 43;})()");
+        assert_eq!(
+            compact_for_test(&print_block(&block, &PrinterConfiguration::default())),
+            "(()=>{42;return 43;})()"
+        );
+    }
+
+    #[test]
+    fn a_code_block_can_be_printed_as_statements() {
+        let block = ConcreteBlockExpression {
+            expression_type: ConcreteType::default_integer_for_test(),
+            contents: vec![
+                ConcreteExpression::integer_for_test(42),
+                ConcreteExpression::integer_for_test(43),
+            ],
+        };
+        assert_eq!(
+            compact_for_test(&print_block_statements(
+                &block,
+                Destination::new(Target::Assign("Bx")),
+                &PrinterConfiguration::default(),
+            )),
+            "42;Bx=43;"
+        );
     }
 }
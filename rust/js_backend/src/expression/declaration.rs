@@ -1,37 +1,207 @@
-use crate::{expression::print_expression, identifier::print_identifier};
-use typed_ast::ConcreteDeclarationExpression;
+use crate::{
+    expression::{
+        function_declaration::print_named_function_declaration,
+        jsdoc::print_declaration_annotation,
+        print_expression,
+        statement::{needs_statements, print_statements, Destination, Target},
+    },
+    identifier::print_identifier,
+    layout::print_braced,
+    markers::{NEWLINE, SPACE},
+    source_map::print_source_location,
+    PrinterConfiguration,
+};
+use typed_ast::{ConcreteDeclarationExpression, ConcreteExpression};
 
-pub fn print_declaration(declaration: &ConcreteDeclarationExpression) -> String {
+fn print_value(
+    declaration: &ConcreteDeclarationExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    match &declaration.value {
+        ConcreteExpression::Function(function) => format!(
+            "{}{}",
+            print_source_location(function.source),
+            print_named_function_declaration(&declaration.identifier.name, function, configuration)
+        ),
+        value => print_expression(value, configuration),
+    }
+}
+
+/// Values which can't be printed as a JS expression are assigned to the identifier
+/// from inside a JS block, which keeps their own declarations out of this scope.
+fn print_assignment_statements(
+    identifier: &str,
+    declaration: &ConcreteDeclarationExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    print_braced(&print_statements(
+        &declaration.value,
+        Destination::new(Target::Assign(identifier)),
+        configuration,
+    ))
+}
+
+pub fn print_declaration(
+    declaration: &ConcreteDeclarationExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let identifier = print_identifier(&declaration.identifier);
+    if needs_statements(&declaration.value) {
+        return format!(
+            "let {identifier};{NEWLINE}{}",
+            print_assignment_statements(&identifier, declaration, configuration)
+        );
+    }
+    let value = print_value(declaration, configuration);
+    format!("const {identifier}{SPACE}={SPACE}{value}")
+}
+
+/// Declarations inside of functions can be reassigned, so they are printed with `let`.
+pub fn print_local_declaration(
+    declaration: &ConcreteDeclarationExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let annotation = print_declaration_annotation(declaration, configuration);
     let identifier = print_identifier(&declaration.identifier);
-    let value = print_expression(&declaration.value);
-    format!("const {identifier}={value}")
+    if needs_statements(&declaration.value) {
+        let assignment = print_assignment_statements(&identifier, declaration, configuration);
+        if declaration.is_reassignment {
+            return assignment;
+        }
+        return format!("{annotation}let {identifier};{NEWLINE}{assignment}");
+    }
+    let value = print_value(declaration, configuration);
+    if declaration.is_reassignment {
+        format!("{identifier}{SPACE}={SPACE}{value}")
+    } else {
+        format!("{annotation}let {identifier}{SPACE}={SPACE}{value}")
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use typed_ast::{ConcreteExpression, ConcreteType};
+    use typed_ast::{ConcreteExpression, ConcreteType, SourceLocation};
 
     use super::*;
+    use crate::layout::compact_for_test;
 
     #[test]
     fn declare_an_integer() {
         let declaration = ConcreteDeclarationExpression {
+            source: SourceLocation::default(),
             declaration_type: ConcreteType::default_integer_for_test(),
             expression_type: ConcreteType::default_integer_for_test(),
             identifier: ConcreteExpression::raw_identifier_for_test("foo"),
             value: ConcreteExpression::integer_for_test(42),
+            is_reassignment: false,
         };
-        assert_eq!(print_declaration(&declaration), "const Bfoo=42");
+        assert_eq!(
+            compact_for_test(&print_declaration(
+                &declaration,
+                &PrinterConfiguration::default()
+            )),
+            "const Bfoo=42"
+        );
     }
 
     #[test]
     fn declare_a_string() {
         let declaration = ConcreteDeclarationExpression {
+            source: SourceLocation::default(),
             declaration_type: ConcreteType::default_integer_for_test(),
             expression_type: ConcreteType::default_string_for_test(),
             identifier: ConcreteExpression::raw_identifier_for_test("hello"),
             value: ConcreteExpression::string_for_test("world"),
+            is_reassignment: false,
+        };
+        assert_eq!(
+            compact_for_test(&print_declaration(
+                &declaration,
+                &PrinterConfiguration::default()
+            )),
+            "const Bhello=\"world\""
+        );
+    }
+
+    #[test]
+    fn declare_a_local_integer() {
+        let declaration = ConcreteDeclarationExpression {
+            source: SourceLocation::default(),
+            declaration_type: ConcreteType::default_integer_for_test(),
+            expression_type: ConcreteType::default_integer_for_test(),
+            identifier: ConcreteExpression::raw_identifier_for_test("foo"),
+            value: ConcreteExpression::integer_for_test(42),
+            is_reassignment: false,
+        };
+        assert_eq!(
+            compact_for_test(&print_local_declaration(
+                &declaration,
+                &PrinterConfiguration::default()
+            )),
+            "let Bfoo=42"
+        );
+    }
+
+    #[test]
+    fn reassign_a_local_integer() {
+        let declaration = ConcreteDeclarationExpression {
+            source: SourceLocation::default(),
+            declaration_type: ConcreteType::default_integer_for_test(),
+            expression_type: ConcreteType::default_integer_for_test(),
+            identifier: ConcreteExpression::raw_identifier_for_test("foo"),
+            value: ConcreteExpression::integer_for_test(42),
+            is_reassignment: true,
+        };
+        assert_eq!(
+            compact_for_test(&print_local_declaration(
+                &declaration,
+                &PrinterConfiguration::default()
+            )),
+            "Bfoo=42"
+        );
+    }
+
+    #[test]
+    fn declare_a_block_with_statements() {
+        let declaration = ConcreteDeclarationExpression {
+            source: SourceLocation::default(),
+            declaration_type: ConcreteType::default_integer_for_test(),
+            expression_type: ConcreteType::default_integer_for_test(),
+            identifier: ConcreteExpression::raw_identifier_for_test("foo"),
+            value: ConcreteExpression::block_for_test(vec![
+                ConcreteExpression::integer_for_test(42),
+                ConcreteExpression::integer_for_test(43),
+            ]),
+            is_reassignment: false,
+        };
+        assert_eq!(
+            compact_for_test(&print_declaration(
+                &declaration,
+                &PrinterConfiguration::default()
+            )),
+            "let Bfoo;{42;Bfoo=43;}"
+        );
+    }
+
+    #[test]
+    fn reassign_a_local_block_with_statements() {
+        let declaration = ConcreteDeclarationExpression {
+            source: SourceLocation::default(),
+            declaration_type: ConcreteType::default_integer_for_test(),
+            expression_type: ConcreteType::default_integer_for_test(),
+            identifier: ConcreteExpression::raw_identifier_for_test("foo"),
+            value: ConcreteExpression::block_for_test(vec![
+                ConcreteExpression::integer_for_test(42),
+                ConcreteExpression::integer_for_test(43),
+            ]),
+            is_reassignment: true,
         };
-        assert_eq!(print_declaration(&declaration), "const Bhello=\"world\"");
+        assert_eq!(
+            compact_for_test(&print_local_declaration(
+                &declaration,
+                &PrinterConfiguration::default()
+            )),
+            "{42;Bfoo=43;}"
+        );
     }
 }
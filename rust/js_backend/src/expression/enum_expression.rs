@@ -1,7 +1,10 @@
-use crate::expression::print_expression;
+use crate::{expression::print_expression, PrinterConfiguration};
 use typed_ast::{ConcreteEnumExpression, ConcreteType};
 
-pub fn print_enum(enum_expression: &ConcreteEnumExpression) -> String {
+pub fn print_enum(
+    enum_expression: &ConcreteEnumExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
     let variants = match &enum_expression.expression_type {
         ConcreteType::Enum(concrete_enum_type) => &concrete_enum_type.variants,
         _ => unreachable!("Expected enum type"),
@@ -28,7 +31,7 @@ pub fn print_enum(enum_expression: &ConcreteEnumExpression) -> String {
         output.push_str(variant_index.to_string().as_str());
         for payload_element in &enum_expression.payload {
             output.push(',');
-            output.push_str(&print_expression(payload_element));
+            output.push_str(&print_expression(payload_element, configuration));
         }
         output.push(']');
     } else {
@@ -40,7 +43,8 @@ pub fn print_enum(enum_expression: &ConcreteEnumExpression) -> String {
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::collections::HashMap;
+    use crate::layout::compact_for_test;
+    use indexmap::IndexMap;
     use typed_ast::{ConcreteEnumType, ConcreteExpression, PrimitiveType};
 
     #[test]
@@ -50,12 +54,15 @@ mod test {
             expression_type: ConcreteType::default_enum_for_test(),
             payload: vec![],
         };
-        assert_eq!(print_enum(&enum_expression), "0");
+        assert_eq!(
+            print_enum(&enum_expression, &PrinterConfiguration::default()),
+            "0"
+        );
     }
 
     #[test]
     fn enums_with_payload_are_arrays() {
-        let mut foo_enum_variants = HashMap::new();
+        let mut foo_enum_variants = IndexMap::new();
         foo_enum_variants.insert(
             "foo".to_string(),
             vec![ConcreteType::Primitive(PrimitiveType::Int)],
@@ -67,12 +74,18 @@ mod test {
             })),
             payload: vec![ConcreteExpression::integer_for_test(42)],
         };
-        assert_eq!(print_enum(&enum_expression), "[0,42]");
+        assert_eq!(
+            compact_for_test(&print_enum(
+                &enum_expression,
+                &PrinterConfiguration::default()
+            )),
+            "[0,42]"
+        );
     }
 
     #[test]
     fn alphabetically_first_of_two_variants_has_value_zero() {
-        let mut ab_enum_variants = HashMap::new();
+        let mut ab_enum_variants = IndexMap::new();
         ab_enum_variants.insert("a".to_string(), vec![]);
         ab_enum_variants.insert("b".to_string(), vec![]);
         let enum_expression = ConcreteEnumExpression {
@@ -82,12 +95,15 @@ mod test {
             })),
             payload: vec![],
         };
-        assert_eq!(print_enum(&enum_expression), "0");
+        assert_eq!(
+            print_enum(&enum_expression, &PrinterConfiguration::default()),
+            "0"
+        );
     }
 
     #[test]
     fn alphabetically_second_of_two_variants_has_value_one() {
-        let mut ab_enum_variants = HashMap::new();
+        let mut ab_enum_variants = IndexMap::new();
         ab_enum_variants.insert("a".to_string(), vec![]);
         ab_enum_variants.insert("b".to_string(), vec![]);
         let enum_expression = ConcreteEnumExpression {
@@ -97,6 +113,9 @@ mod test {
             })),
             payload: vec![],
         };
-        assert_eq!(print_enum(&enum_expression), "1");
+        assert_eq!(
+            print_enum(&enum_expression, &PrinterConfiguration::default()),
+            "1"
+        );
     }
 }
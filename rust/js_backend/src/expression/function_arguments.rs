@@ -1,11 +1,15 @@
 use super::print_expression;
+use crate::PrinterConfiguration;
 use typed_ast::ConcreteExpression;
 
-pub fn print_function_arguments(arguments: &Vec<ConcreteExpression>) -> String {
+pub fn print_function_arguments(
+    arguments: &Vec<ConcreteExpression>,
+    configuration: &PrinterConfiguration,
+) -> String {
     let mut result = String::new();
     result.push('(');
     for (index, item) in arguments.iter().enumerate() {
-        result.push_str(&print_expression(item));
+        result.push_str(&print_expression(item, configuration));
         if index < arguments.len() - 1 {
             result.push(',');
         }
@@ -17,6 +21,7 @@ pub fn print_function_arguments(arguments: &Vec<ConcreteExpression>) -> String {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::layout::compact_for_test;
     use typed_ast::ConcreteExpression;
 
     #[test]
@@ -25,13 +30,25 @@ mod test {
             ConcreteExpression::integer_for_test(42),
             ConcreteExpression::integer_for_test(43),
         ];
-        assert_eq!(print_function_arguments(&arguments), "(42,43)");
+        assert_eq!(
+            compact_for_test(&print_function_arguments(
+                &arguments,
+                &PrinterConfiguration::default()
+            )),
+            "(42,43)"
+        );
     }
 
     #[test]
     fn does_not_include_comma_with_one_item() {
         let arguments = vec![ConcreteExpression::integer_for_test(42)];
-        assert_eq!(print_function_arguments(&arguments), "(42)");
+        assert_eq!(
+            compact_for_test(&print_function_arguments(
+                &arguments,
+                &PrinterConfiguration::default()
+            )),
+            "(42)"
+        );
     }
 
     #[test]
@@ -40,6 +57,9 @@ mod test {
             ConcreteExpression::string_for_test("foo"),
             ConcreteExpression::string_for_test("bar"),
         ];
-        assert_eq!(print_function_arguments(&arguments), "(\"foo\",\"bar\")");
+        assert_eq!(
+            print_function_arguments(&arguments, &PrinterConfiguration::default()),
+            "(\"foo\",\"bar\")"
+        );
     }
 }
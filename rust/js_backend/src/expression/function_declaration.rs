@@ -1,63 +1,216 @@
-use crate::mangle_variable_name;
+use super::{
+    statement::{needs_statements, print_statements, Destination, Target},
+    tail_call::TailCall,
+};
+use crate::{layout::print_braced, mangle_variable_name, markers::SPACE, PrinterConfiguration};
 use typed_ast::ConcreteFunctionExpression;
 
-pub fn print_function_declaration(function: &ConcreteFunctionExpression) -> String {
-    let mut result = String::new();
-    result.push('(');
-    for (index, parameter) in function.argument_names.iter().enumerate() {
-        if index > 0 {
-            result.push(',');
+pub fn print_function_declaration(
+    function: &ConcreteFunctionExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let parameters = function
+        .argument_names
+        .iter()
+        .map(|parameter| mangle_variable_name(parameter.as_str()))
+        .collect::<Vec<_>>()
+        .join(&format!(",{SPACE}"));
+    if needs_statements(&function.body) {
+        return format!(
+            "({parameters}){SPACE}=>{SPACE}{}",
+            print_braced(&print_statements(
+                &function.body,
+                Destination::new(Target::Return),
+                configuration,
+            ))
+        );
+    }
+    format!(
+        "({parameters}){SPACE}=>{SPACE}({})",
+        super::print_expression(&function.body, configuration)
+    )
+}
+
+/// Returns the names of the function's JS parameters. A named function which loops
+/// instead of calling itself takes its arguments under different names.
+pub fn get_parameter_names(
+    function_name: Option<&str>,
+    function: &ConcreteFunctionExpression,
+) -> Vec<String> {
+    let loops = matches!(
+        function_name,
+        Some(function_name) if TailCall {
+            function_name,
+            argument_names: &function.argument_names,
         }
-        result.push_str(&mangle_variable_name(parameter.as_str()));
+        .is_called_in_tail_position(&function.body)
+    );
+    function
+        .argument_names
+        .iter()
+        .map(|name| {
+            let name = mangle_variable_name(name);
+            if loops {
+                format!("${name}")
+            } else {
+                name
+            }
+        })
+        .collect()
+}
+
+/// Prints a function which is assigned to a variable, so it can refer to itself.
+pub fn print_named_function_declaration(
+    function_name: &str,
+    function: &ConcreteFunctionExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let tail_call = TailCall {
+        function_name,
+        argument_names: &function.argument_names,
+    };
+    if !tail_call.is_called_in_tail_position(&function.body) {
+        return print_function_declaration(function, configuration);
     }
-    result.push_str(")=>(");
-    result.push_str(super::print_expression(&function.body).as_str());
-    result.push(')');
-    result
+    format!(
+        "({}){SPACE}=>{SPACE}{}",
+        tail_call.print_parameters(),
+        print_braced(&tail_call.print_loop(&print_statements(
+            &function.body,
+            Destination::returned_from_loop(tail_call),
+            configuration
+        )))
+    )
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use typed_ast::{ConcreteExpression, ConcreteType};
+    use crate::layout::compact_for_test;
+    use ast::BinaryOperatorSymbol;
+    use typed_ast::{
+        ConcreteBinaryOperatorExpression, ConcreteExpression, ConcreteType, SourceLocation,
+    };
 
     #[test]
     fn prints_a_function_with_no_arguments() {
         let function = ConcreteFunctionExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_function_for_test(),
             argument_names: vec![],
             body: ConcreteExpression::integer_for_test(42),
         };
-        assert_eq!(print_function_declaration(&function), "()=>(42)");
+        assert_eq!(
+            compact_for_test(&print_function_declaration(
+                &function,
+                &PrinterConfiguration::default()
+            )),
+            "()=>(42)"
+        );
     }
 
     #[test]
     fn prints_a_function_with_one_argument() {
         let function = ConcreteFunctionExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_function_for_test(),
             argument_names: vec!["x".to_string()],
             body: ConcreteExpression::integer_for_test(42),
         };
-        assert_eq!(print_function_declaration(&function), "(Bx)=>(42)");
+        assert_eq!(
+            compact_for_test(&print_function_declaration(
+                &function,
+                &PrinterConfiguration::default()
+            )),
+            "(Bx)=>(42)"
+        );
     }
 
     #[test]
     fn prints_a_function_with_two_arguments() {
         let function = ConcreteFunctionExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_function_for_test(),
             argument_names: vec!["x".to_string(), "y".to_string()],
             body: ConcreteExpression::integer_for_test(42),
         };
-        assert_eq!(print_function_declaration(&function), "(Bx,By)=>(42)");
+        assert_eq!(
+            compact_for_test(&print_function_declaration(
+                &function,
+                &PrinterConfiguration::default()
+            )),
+            "(Bx,By)=>(42)"
+        );
     }
 
     #[test]
     fn prints_a_function_with_three_arguments() {
         let function = ConcreteFunctionExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_function_for_test(),
             argument_names: vec!["x".to_string(), "y".to_string(), "z".to_string()],
             body: ConcreteExpression::integer_for_test(42),
         };
-        assert_eq!(print_function_declaration(&function), "(Bx,By,Bz)=>(42)");
+        assert_eq!(
+            compact_for_test(&print_function_declaration(
+                &function,
+                &PrinterConfiguration::default()
+            )),
+            "(Bx,By,Bz)=>(42)"
+        );
+    }
+
+    #[test]
+    fn functions_with_block_bodies_are_printed_with_statements() {
+        let function = ConcreteFunctionExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_function_for_test(),
+            argument_names: vec![],
+            body: ConcreteExpression::block_for_test(vec![
+                ConcreteExpression::integer_for_test(42),
+                ConcreteExpression::integer_for_test(43),
+            ]),
+        };
+        assert_eq!(
+            compact_for_test(&print_function_declaration(
+                &function,
+                &PrinterConfiguration::default()
+            )),
+            "()=>{42;return 43;}"
+        );
+    }
+
+    #[test]
+    fn named_functions_which_call_themselves_in_tail_position_loop() {
+        let function = ConcreteFunctionExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_function_for_test(),
+            argument_names: vec!["x".to_string()],
+            body: ConcreteExpression::BinaryOperator(Box::new(ConcreteBinaryOperatorExpression {
+                source: SourceLocation::default(),
+                expression_type: ConcreteType::default_for_test(),
+                symbol: BinaryOperatorSymbol::FunctionApplication,
+                left_child: ConcreteExpression::identifier_for_test("loop"),
+                right_child: ConcreteExpression::FunctionArguments(vec![
+                    ConcreteExpression::identifier_for_test("x"),
+                ]),
+            })),
+        };
+        assert_eq!(
+            compact_for_test(&print_named_function_declaration(
+                "loop",
+                &function,
+                &PrinterConfiguration::default()
+            )),
+            "($Bx)=>{while(true){let Bx=$Bx;$Bx=Bx;continue;}}"
+        );
+        assert_eq!(
+            compact_for_test(&print_named_function_declaration(
+                "other",
+                &function,
+                &PrinterConfiguration::default()
+            )),
+            "(Bx)=>((Bloop(Bx)))"
+        );
     }
 }
@@ -1,42 +1,89 @@
+use super::statement::{print_statements, Destination};
+use crate::{
+    layout::print_braced,
+    markers::SPACE,
+    tags::{print_tag_name, print_tag_without_contents},
+    PrinterConfiguration,
+};
 use typed_ast::ConcreteIfExpression;
 
-fn print_true_path(expression: &ConcreteIfExpression) -> String {
-    let has_else = expression.path_if_false.is_some();
-    let mut result = String::new();
-    if !has_else {
-        result.push_str("[\"some\",");
-    }
-    result.push_str(super::print_expression(&expression.path_if_true).as_str());
-    if !has_else {
-        result.push(']');
+fn print_true_path(
+    expression: &ConcreteIfExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let path_if_true = super::print_expression(&expression.path_if_true, configuration);
+    if expression.path_if_false.is_some() {
+        return path_if_true;
     }
-    result
+    format!(
+        "[{},{path_if_true}]",
+        print_tag_name("some", configuration.tag_representation)
+    )
 }
 
-fn print_false_path(expression: &ConcreteIfExpression) -> String {
-    expression
-        .path_if_false
-        .as_ref()
-        .map_or_else(|| "[\"none\"]".to_string(), super::print_expression)
+fn print_false_path(
+    expression: &ConcreteIfExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    expression.path_if_false.as_ref().map_or_else(
+        || print_tag_without_contents("none", configuration.tag_representation),
+        |path_if_false| super::print_expression(path_if_false, configuration),
+    )
 }
 
-pub fn print_if_expression(expression: &ConcreteIfExpression) -> String {
+pub fn print_if_expression(
+    expression: &ConcreteIfExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
     let mut result = String::new();
     result.push('(');
-    result.push_str(super::print_expression(&expression.condition).as_str());
+    result.push_str(super::print_expression(&expression.condition, configuration).as_str());
     result.push('?');
-    result.push_str(print_true_path(expression).as_str());
+    result.push_str(print_true_path(expression, configuration).as_str());
     result.push(':');
-    result.push_str(print_false_path(expression).as_str());
+    result.push_str(print_false_path(expression, configuration).as_str());
     result.push(')');
     result
 }
 
+pub fn print_if_statements(
+    expression: &ConcreteIfExpression,
+    destination: Destination,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let false_path = expression.path_if_false.as_ref().map_or_else(
+        || {
+            destination.deliver(
+                &print_tag_without_contents("none", configuration.tag_representation),
+                configuration,
+            )
+        },
+        |path_if_false| print_statements(path_if_false, destination, configuration),
+    );
+    let true_destination = if expression.path_if_false.is_some() {
+        destination
+    } else {
+        destination.wrapped_in_some()
+    };
+    format!(
+        "if{SPACE}({}){SPACE}{}{SPACE}else{SPACE}{}",
+        super::print_expression(&expression.condition, configuration),
+        print_braced(&print_statements(
+            &expression.path_if_true,
+            true_destination,
+            configuration
+        )),
+        print_braced(&false_path)
+    )
+}
+
 #[cfg(test)]
 mod test {
     use typed_ast::{ConcreteExpression, ConcreteIfExpression, ConcreteType};
 
     use super::*;
+    use crate::expression::statement::Target;
+    use crate::layout::compact_for_test;
 
     #[test]
     fn prints_if_with_all_paths() {
@@ -46,7 +93,13 @@ mod test {
             path_if_true: ConcreteExpression::identifier_for_test("bar"),
             path_if_false: Some(ConcreteExpression::identifier_for_test("baz")),
         };
-        assert_eq!(print_if_expression(&expression), "(Bfoo?Bbar:Bbaz)");
+        assert_eq!(
+            compact_for_test(&print_if_expression(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo?Bbar:Bbaz)"
+        );
     }
 
     #[test]
@@ -58,8 +111,47 @@ mod test {
             path_if_false: None,
         };
         assert_eq!(
-            print_if_expression(&expression),
+            compact_for_test(&print_if_expression(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
             "(Bfoo?[\"some\",Bbar]:[\"none\"])"
         );
     }
+
+    #[test]
+    fn prints_if_statements_with_all_paths() {
+        let expression = ConcreteIfExpression {
+            expression_type: ConcreteType::default_integer_for_test(),
+            condition: ConcreteExpression::identifier_for_test("foo"),
+            path_if_true: ConcreteExpression::identifier_for_test("bar"),
+            path_if_false: Some(ConcreteExpression::identifier_for_test("baz")),
+        };
+        assert_eq!(
+            compact_for_test(&print_if_statements(
+                &expression,
+                Destination::new(Target::Return),
+                &PrinterConfiguration::default(),
+            )),
+            "if(Bfoo){return Bbar;}else{return Bbaz;}"
+        );
+    }
+
+    #[test]
+    fn prints_if_statements_with_only_true_path() {
+        let expression = ConcreteIfExpression {
+            expression_type: ConcreteType::default_integer_for_test(),
+            condition: ConcreteExpression::identifier_for_test("foo"),
+            path_if_true: ConcreteExpression::identifier_for_test("bar"),
+            path_if_false: None,
+        };
+        assert_eq!(
+            compact_for_test(&print_if_statements(
+                &expression,
+                Destination::new(Target::Assign("Bx")),
+                &PrinterConfiguration::default(),
+            )),
+            "if(Bfoo){Bx=[\"some\",Bbar];}else{Bx=[\"none\"];}"
+        );
+    }
 }
@@ -0,0 +1,186 @@
+use super::function_declaration::get_parameter_names;
+use crate::{
+    layout::print_doc_comment,
+    markers::{NEWLINE, SPACE},
+    typescript::print_typescript_type,
+    PrinterConfiguration,
+};
+use typed_ast::{
+    ConcreteDeclarationExpression, ConcreteExpression, ConcreteFunctionExpression, ConcreteType,
+};
+
+fn get_function_tags(
+    function_type: &ConcreteType,
+    parameter_names: &[String],
+    configuration: &PrinterConfiguration,
+) -> Vec<String> {
+    let ConcreteType::Function(function_type) = function_type else {
+        return vec![];
+    };
+    let mut tags = parameter_names
+        .iter()
+        .zip(&function_type.argument_types)
+        .map(|(name, argument_type)| {
+            format!(
+                "@param {{{}}} {name}",
+                print_typescript_type(argument_type, configuration)
+            )
+        })
+        .collect::<Vec<_>>();
+    tags.push(format!(
+        "@returns {{{}}}",
+        print_typescript_type(&function_type.return_type, configuration)
+    ));
+    tags
+}
+
+/// Prints the `JSDoc` which goes before a declaration: the parameters and return
+/// type of a function, or the type of any other value. Reassignments keep the
+/// type they were declared with, so they aren't annotated.
+pub fn print_declaration_annotation(
+    declaration: &ConcreteDeclarationExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    if !configuration.jsdoc || declaration.is_reassignment {
+        return String::new();
+    }
+    let tags = match &declaration.value {
+        ConcreteExpression::Function(function) => get_function_tags(
+            &function.expression_type,
+            &get_parameter_names(Some(&declaration.identifier.name), function),
+            configuration,
+        ),
+        _ => vec![format!(
+            "@type {{{}}}",
+            print_typescript_type(&declaration.declaration_type, configuration)
+        )],
+    };
+    format!(
+        "{}{NEWLINE}",
+        print_doc_comment(&tags, configuration.output_style)
+    )
+}
+
+/// Prints the `JSDoc` which goes before a function that isn't assigned to a variable,
+/// such as an argument. It is kept on one line since it is inside an expression.
+pub fn print_function_annotation(
+    function: &ConcreteFunctionExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    if !configuration.jsdoc {
+        return String::new();
+    }
+    let tags = get_function_tags(
+        &function.expression_type,
+        &get_parameter_names(None, function),
+        configuration,
+    );
+    format!(
+        "{}{SPACE}",
+        print_doc_comment(&[tags.join(" ")], configuration.output_style)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layout::{render_layout, OutputStyle};
+    use typed_ast::{ConcreteFunctionType, SourceLocation};
+
+    fn pretty_jsdoc_for_test() -> PrinterConfiguration {
+        PrinterConfiguration {
+            output_style: OutputStyle::Pretty,
+            jsdoc: true,
+            ..PrinterConfiguration::default()
+        }
+    }
+
+    fn render_pretty_for_test(marked: &str) -> String {
+        render_layout(marked, OutputStyle::Pretty)
+    }
+
+    fn function_for_test() -> ConcreteFunctionExpression {
+        ConcreteFunctionExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::Function(Box::new(ConcreteFunctionType {
+                argument_types: vec![
+                    ConcreteType::default_integer_for_test(),
+                    ConcreteType::default_list_for_test(),
+                ],
+                return_type: ConcreteType::default_string_for_test(),
+            })),
+            argument_names: vec!["count".to_string(), "names".to_string()],
+            body: ConcreteExpression::string_for_test("many"),
+        }
+    }
+
+    fn declaration_for_test(value: ConcreteExpression) -> ConcreteDeclarationExpression {
+        ConcreteDeclarationExpression {
+            source: SourceLocation::default(),
+            declaration_type: ConcreteType::default_integer_for_test(),
+            expression_type: ConcreteType::default_integer_for_test(),
+            identifier: ConcreteExpression::raw_identifier_for_test("foo"),
+            value,
+            is_reassignment: false,
+        }
+    }
+
+    #[test]
+    fn values_are_annotated_with_their_type() {
+        let declaration = declaration_for_test(ConcreteExpression::integer_for_test(42));
+        assert_eq!(
+            render_pretty_for_test(&print_declaration_annotation(
+                &declaration,
+                &pretty_jsdoc_for_test()
+            )),
+            "/** @type {number} */\n"
+        );
+    }
+
+    #[test]
+    fn functions_are_annotated_with_their_parameters_and_return_type() {
+        let declaration =
+            declaration_for_test(ConcreteExpression::Function(Box::new(function_for_test())));
+        assert_eq!(
+            render_pretty_for_test(&print_declaration_annotation(
+                &declaration,
+                &pretty_jsdoc_for_test()
+            )),
+            "/**\n * @param {number} Bcount\n * @param {ReadonlyArray<string>} Bnames\n * @returns {string}\n */\n"
+        );
+    }
+
+    #[test]
+    fn function_expressions_are_annotated_on_one_line() {
+        assert_eq!(
+            render_pretty_for_test(&print_function_annotation(
+                &function_for_test(),
+                &pretty_jsdoc_for_test()
+            )),
+            "/** @param {number} Bcount @param {ReadonlyArray<string>} Bnames @returns {string} */ "
+        );
+    }
+
+    #[test]
+    fn reassignments_are_not_annotated() {
+        let mut declaration = declaration_for_test(ConcreteExpression::integer_for_test(42));
+        declaration.is_reassignment = true;
+        assert_eq!(
+            print_declaration_annotation(&declaration, &pretty_jsdoc_for_test()),
+            ""
+        );
+    }
+
+    #[test]
+    fn annotations_are_only_printed_when_enabled() {
+        let declaration = declaration_for_test(ConcreteExpression::integer_for_test(42));
+        assert_eq!(
+            print_declaration_annotation(&declaration, &PrinterConfiguration::default()),
+            ""
+        );
+        assert_eq!(
+            print_function_annotation(&function_for_test(), &PrinterConfiguration::default()),
+            ""
+        );
+    }
+}
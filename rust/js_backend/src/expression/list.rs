@@ -1,11 +1,12 @@
 use super::print_expression;
+use crate::PrinterConfiguration;
 use typed_ast::ConcreteListExpression;
 
-pub fn print_list(list: &ConcreteListExpression) -> String {
+pub fn print_list(list: &ConcreteListExpression, configuration: &PrinterConfiguration) -> String {
     let mut result = String::new();
     result.push('[');
     for (index, item) in list.contents.iter().enumerate() {
-        result.push_str(&print_expression(item));
+        result.push_str(&print_expression(item, configuration));
         if index < list.contents.len() - 1 {
             result.push(',');
         }
@@ -17,6 +18,7 @@ pub fn print_list(list: &ConcreteListExpression) -> String {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::layout::compact_for_test;
     use typed_ast::{ConcreteExpression, ConcreteType};
 
     #[test]
@@ -28,7 +30,10 @@ mod test {
                 ConcreteExpression::integer_for_test(43),
             ],
         };
-        assert_eq!(print_list(&list), "[42,43]");
+        assert_eq!(
+            compact_for_test(&print_list(&list, &PrinterConfiguration::default())),
+            "[42,43]"
+        );
     }
 
     #[test]
@@ -37,7 +42,10 @@ mod test {
             expression_type: ConcreteType::default_list_for_test(),
             contents: vec![ConcreteExpression::integer_for_test(42)],
         };
-        assert_eq!(print_list(&list), "[42]");
+        assert_eq!(
+            compact_for_test(&print_list(&list, &PrinterConfiguration::default())),
+            "[42]"
+        );
     }
 
     #[test]
@@ -49,7 +57,10 @@ mod test {
                 ConcreteExpression::string_for_test("bar"),
             ],
         };
-        assert_eq!(print_list(&list), "[\"foo\",\"bar\"]");
+        assert_eq!(
+            compact_for_test(&print_list(&list, &PrinterConfiguration::default())),
+            "[\"foo\",\"bar\"]"
+        );
     }
 
     #[test]
@@ -64,6 +75,9 @@ mod test {
                 ]),
             ],
         };
-        assert_eq!(print_list(&list), "[42,(()=>{43;return 44;})()]");
+        assert_eq!(
+            compact_for_test(&print_list(&list, &PrinterConfiguration::default())),
+            "[42,(()=>{43;return 44;})()]"
+        );
     }
 }
@@ -0,0 +1,60 @@
+use super::print_expression;
+use crate::{helpers::Helper, PrinterConfiguration};
+use typed_ast::ConcreteMapExpression;
+
+/// Maps are instances of the prelude's `$Map` class, constructed from `[key, value]` pairs.
+pub fn print_map(map: &ConcreteMapExpression, configuration: &PrinterConfiguration) -> String {
+    let entries = map
+        .contents
+        .iter()
+        .map(|entry| {
+            format!(
+                "[{},{}]",
+                print_expression(&entry.key, configuration),
+                print_expression(&entry.value, configuration)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    format!("new {}([{entries}])", Helper::Map.name())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layout::compact_for_test;
+    use typed_ast::{ConcreteExpression, ConcreteMapEntry, ConcreteType};
+
+    #[test]
+    fn can_print_empty_map() {
+        let map = ConcreteMapExpression {
+            expression_type: ConcreteType::default_map_for_test(),
+            contents: vec![],
+        };
+        assert_eq!(
+            print_map(&map, &PrinterConfiguration::default()),
+            "new $Map([])"
+        );
+    }
+
+    #[test]
+    fn can_print_map_of_strings_to_integers() {
+        let map = ConcreteMapExpression {
+            expression_type: ConcreteType::default_map_for_test(),
+            contents: vec![
+                ConcreteMapEntry {
+                    key: ConcreteExpression::string_for_test("one"),
+                    value: ConcreteExpression::integer_for_test(1),
+                },
+                ConcreteMapEntry {
+                    key: ConcreteExpression::string_for_test("two"),
+                    value: ConcreteExpression::integer_for_test(2),
+                },
+            ],
+        };
+        assert_eq!(
+            compact_for_test(&print_map(&map, &PrinterConfiguration::default())),
+            "new $Map([[\"one\",1],[\"two\",2]])"
+        );
+    }
+}
@@ -6,96 +6,147 @@ mod enum_expression;
 mod function_arguments;
 mod function_declaration;
 mod if_expression;
+mod jsdoc;
 mod list;
+mod map;
 mod record;
 mod record_assignment;
+mod set;
+mod statement;
 mod tag;
+mod tail_call;
+mod tuple;
 mod unary_operator;
 mod variable_name_mangling;
 mod when;
 
 use crate::{
     identifier::print_identifier,
-    literals::{print_integer_literal, print_string_literal},
+    literals::{print_float_literal, print_integer_literal, print_string_literal},
+    source_map::print_source_location,
+    PrinterConfiguration,
 };
 use typed_ast::ConcreteExpression;
 
 pub use declaration::print_declaration;
+pub use jsdoc::print_declaration_annotation;
 pub use variable_name_mangling::mangle_variable_name;
 
 use self::when::print_when;
 
-fn print_expression(expression: &ConcreteExpression) -> String {
+fn print_expression(
+    expression: &ConcreteExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
     match expression {
         ConcreteExpression::Identifier(identifier) => print_identifier(identifier),
-        ConcreteExpression::Integer(integer) => print_integer_literal(integer),
+        ConcreteExpression::Integer(integer) => print_integer_literal(integer, configuration),
+        ConcreteExpression::Float(float) => print_float_literal(float),
         ConcreteExpression::StringLiteral(string) => print_string_literal(string),
-        ConcreteExpression::Record(record) => record::print_record(record),
+        ConcreteExpression::Record(record) => record::print_record(record, configuration),
         ConcreteExpression::RecordAssignment(assignment) => {
-            record_assignment::print_record_assignment(assignment)
-        }
-        ConcreteExpression::List(list) => list::print_list(list),
-        ConcreteExpression::BinaryOperator(operator) => {
-            binary_operator::print_binary_operator(operator)
+            record_assignment::print_record_assignment(assignment, configuration)
         }
+        ConcreteExpression::List(list) => list::print_list(list, configuration),
+        ConcreteExpression::Map(map) => map::print_map(map, configuration),
+        ConcreteExpression::Set(set) => set::print_set(set, configuration),
+        ConcreteExpression::BinaryOperator(operator) => format!(
+            "{}{}",
+            print_source_location(operator.source),
+            binary_operator::print_binary_operator(operator, configuration)
+        ),
         ConcreteExpression::UnaryOperator(operator) => {
-            unary_operator::print_unary_operator(operator)
+            unary_operator::print_unary_operator(operator, configuration)
+        }
+        ConcreteExpression::Tag(tag) => tag::print_tag(tag, configuration),
+        ConcreteExpression::Tuple(tuple) => tuple::print_tuple(tuple, configuration),
+        ConcreteExpression::TupleDeclaration(declaration) => {
+            tuple::print_tuple_declaration(declaration, configuration)
         }
-        ConcreteExpression::Tag(tag) => tag::print_tag(tag),
-        ConcreteExpression::Enum(enum_expression) => enum_expression::print_enum(enum_expression),
-        ConcreteExpression::If(if_expression) => if_expression::print_if_expression(if_expression),
-        ConcreteExpression::Block(block) => block::print_block(block),
-        ConcreteExpression::Function(function) => {
-            function_declaration::print_function_declaration(function)
+        ConcreteExpression::Enum(enum_expression) => {
+            enum_expression::print_enum(enum_expression, configuration)
         }
+        ConcreteExpression::If(if_expression) => {
+            if_expression::print_if_expression(if_expression, configuration)
+        }
+        ConcreteExpression::Block(block) => block::print_block(block, configuration),
+        ConcreteExpression::Function(function) => format!(
+            "{}{}{}",
+            jsdoc::print_function_annotation(function, configuration),
+            print_source_location(function.source),
+            function_declaration::print_function_declaration(function, configuration)
+        ),
         ConcreteExpression::Boolean(boolean) => boolean::print_boolean(boolean),
-        ConcreteExpression::Declaration(declaration) => declaration::print_declaration(declaration),
+        ConcreteExpression::Declaration(declaration) => format!(
+            "{}{}",
+            print_source_location(declaration.source),
+            declaration::print_local_declaration(declaration, configuration)
+        ),
         ConcreteExpression::FunctionArguments(arguments) => {
-            function_arguments::print_function_arguments(arguments)
+            function_arguments::print_function_arguments(arguments, configuration)
         }
         ConcreteExpression::TypeDeclaration(_) | ConcreteExpression::TypeIdentifier(_) => {
             String::new()
         }
-        ConcreteExpression::When(when) => print_when(when),
+        ConcreteExpression::When(when) => print_when(when, configuration),
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::layout::compact_for_test;
     use ast::{BinaryOperatorSymbol, UnaryOperatorSymbol};
-    use std::collections::HashMap;
+    use indexmap::IndexMap;
     use typed_ast::{
         ConcreteBinaryOperatorExpression, ConcreteBlockExpression, ConcreteBooleanExpression,
         ConcreteDeclarationExpression, ConcreteFunctionExpression, ConcreteIfExpression,
         ConcreteListExpression, ConcreteRecordAssignmentExpression, ConcreteRecordExpression,
         ConcreteStringLiteralExpression, ConcreteTagExpression, ConcreteType,
-        ConcreteUnaryOperatorExpression, PrimitiveType,
+        ConcreteUnaryOperatorExpression, PrimitiveType, SourceLocation,
     };
 
     #[test]
     fn can_print_identifier() {
         let expression = ConcreteExpression::identifier_for_test("foo");
-        assert_eq!(print_expression(&expression), "Bfoo");
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "Bfoo"
+        );
     }
 
     #[test]
     fn can_print_integer_literal() {
         let expression = ConcreteExpression::integer_for_test(42);
-        assert_eq!(print_expression(&expression), "42");
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "42"
+        );
     }
 
     #[test]
     fn can_print_string_literal() {
         let expression = ConcreteExpression::string_for_test("foo");
-        assert_eq!(print_expression(&expression), "\"foo\"");
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "\"foo\""
+        );
     }
 
     #[test]
     fn can_print_record() {
         let expression = ConcreteExpression::Record(Box::new(ConcreteRecordExpression {
             expression_type: ConcreteType::default_record_for_test(),
-            contents: HashMap::from([
+            contents: IndexMap::from([
                 ("foo".to_string(), ConcreteExpression::integer_for_test(42)),
                 (
                     "bar".to_string(),
@@ -106,11 +157,12 @@ mod test {
                 ),
             ]),
         }));
-        // Because of the HashMap, the order of the keys is not guaranteed.
-        // However, the order doesn't matter so we can accept either one.
-        assert!(
-            print_expression(&expression) == "{bar: \"baz\", foo: 42}"
-                || print_expression(&expression) == "{foo: 42, bar: \"baz\"}"
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "{foo:42,bar:\"baz\"}"
         );
     }
 
@@ -120,19 +172,29 @@ mod test {
             expression_type: ConcreteType::default_list_for_test(),
             contents: vec![ConcreteExpression::integer_for_test(42)],
         }));
-        assert_eq!(print_expression(&list), "[42]");
+        assert_eq!(
+            compact_for_test(&print_expression(&list, &PrinterConfiguration::default())),
+            "[42]"
+        );
     }
 
     #[test]
     fn print_binary_operator() {
         let expression =
             ConcreteExpression::BinaryOperator(Box::new(ConcreteBinaryOperatorExpression {
+                source: SourceLocation::default(),
                 expression_type: ConcreteType::default_binary_operator_for_test(),
                 symbol: BinaryOperatorSymbol::FieldLookup,
                 left_child: ConcreteExpression::identifier_for_test("foo"),
                 right_child: ConcreteExpression::identifier_for_test("bar"),
             }));
-        assert_eq!(print_expression(&expression), "Bfoo.bar");
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "Bfoo.bar"
+        );
     }
 
     #[test]
@@ -143,7 +205,13 @@ mod test {
                 symbol: UnaryOperatorSymbol::Negative,
                 child: ConcreteExpression::integer_for_test(42),
             }));
-        assert_eq!(print_expression(&expression), "-42");
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "-42"
+        );
     }
 
     #[test]
@@ -153,7 +221,10 @@ mod test {
             expression_type: ConcreteType::default_tag_union_for_test(),
             contents: vec![],
         }));
-        assert_eq!(print_expression(&tag), "[\"foo\"]");
+        assert_eq!(
+            compact_for_test(&print_expression(&tag, &PrinterConfiguration::default())),
+            "[\"foo\"]"
+        );
     }
 
     #[test]
@@ -164,7 +235,13 @@ mod test {
             path_if_true: ConcreteExpression::identifier_for_test("bar"),
             path_if_false: Some(ConcreteExpression::identifier_for_test("baz")),
         }));
-        assert_eq!(print_expression(&expression), "(Bfoo?Bbar:Bbaz)");
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "(Bfoo?Bbar:Bbaz)"
+        );
     }
 
     #[test]
@@ -173,17 +250,27 @@ mod test {
             expression_type: ConcreteType::default_integer_for_test(),
             contents: vec![ConcreteExpression::integer_for_test(42)],
         }));
-        assert_eq!(print_expression(&block), "42");
+        assert_eq!(
+            compact_for_test(&print_expression(&block, &PrinterConfiguration::default())),
+            "42"
+        );
     }
 
     #[test]
     fn print_function_declaration() {
         let function = ConcreteExpression::Function(Box::new(ConcreteFunctionExpression {
+            source: SourceLocation::default(),
             expression_type: ConcreteType::default_function_for_test(),
             argument_names: vec![],
             body: ConcreteExpression::integer_for_test(42),
         }));
-        assert_eq!(print_expression(&function), "()=>(42)");
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &function,
+                &PrinterConfiguration::default()
+            )),
+            "()=>(42)"
+        );
     }
 
     #[test]
@@ -192,28 +279,42 @@ mod test {
             expression_type: ConcreteType::Primitive(PrimitiveType::CompilerBoolean),
             value: true,
         }));
-        assert_eq!(print_expression(&boolean), "true");
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &boolean,
+                &PrinterConfiguration::default()
+            )),
+            "true"
+        );
     }
 
     #[test]
     fn print_declaration() {
         let declaration =
             ConcreteExpression::Declaration(Box::new(ConcreteDeclarationExpression {
+                source: SourceLocation::default(),
                 declaration_type: ConcreteType::default_integer_for_test(),
                 expression_type: ConcreteType::default_integer_for_test(),
                 identifier: ConcreteExpression::raw_identifier_for_test("foo"),
                 value: ConcreteExpression::integer_for_test(42),
+                is_reassignment: false,
             }));
-        assert_eq!(print_expression(&declaration), "const Bfoo=42");
+        assert_eq!(
+            compact_for_test(&print_expression(
+                &declaration,
+                &PrinterConfiguration::default()
+            )),
+            "let Bfoo=42"
+        );
     }
 
     #[test]
     fn print_record_assignment() {
         let record = ConcreteRecordExpression {
             expression_type: ConcreteType::default_record_for_test(),
-            contents: HashMap::from([(
+            contents: IndexMap::from([(
                 "meaningOfLife".to_string(),
-                ConcreteExpression::integer_for_test(42),
+                ConcreteExpression::string_for_test("forty two"),
             )]),
         };
         let identifier = ConcreteExpression::raw_identifier_for_test("hello");
@@ -224,8 +325,11 @@ mod test {
                 identifier,
             }));
         assert_eq!(
-            print_expression(&assignment),
-            "Bhello.$set({meaningOfLife: 42})"
+            compact_for_test(&print_expression(
+                &assignment,
+                &PrinterConfiguration::default()
+            )),
+            "$updateRecord(Bhello,{meaningOfLife:\"forty two\"})"
         );
     }
 }
@@ -1,33 +1,38 @@
 use super::print_expression;
+use crate::{
+    layout::print_braced,
+    markers::{NEWLINE, SPACE},
+    PrinterConfiguration,
+};
 use typed_ast::ConcreteRecordExpression;
 
-pub fn print_record(record: &ConcreteRecordExpression) -> String {
-    let mut result = String::new();
-    result.push('{');
-    for (index, (key, value)) in record.contents.iter().enumerate() {
-        result.push_str(key);
-        result.push_str(": ");
-        result.push_str(&print_expression(value));
-        if index < record.contents.len() - 1 {
-            result.push_str(", ");
-        }
-    }
-    result.push('}');
-    result
+/// Each field is printed on its own line in pretty output.
+pub fn print_record(
+    record: &ConcreteRecordExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let fields = record
+        .contents
+        .iter()
+        .map(|(key, value)| format!("{key}:{SPACE}{}", print_expression(value, configuration)))
+        .collect::<Vec<_>>()
+        .join(&format!(",{NEWLINE}"));
+    print_braced(&fields)
 }
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
+    use indexmap::IndexMap;
 
     use super::*;
+    use crate::layout::{compact_for_test, render_layout, OutputStyle};
     use typed_ast::{ConcreteExpression, ConcreteType};
 
     #[test]
     fn can_print_record() {
         let record = ConcreteRecordExpression {
             expression_type: ConcreteType::default_record_for_test(),
-            contents: HashMap::from([
+            contents: IndexMap::from([
                 ("foo".to_string(), ConcreteExpression::integer_for_test(42)),
                 (
                     "bar".to_string(),
@@ -35,11 +40,9 @@ mod test {
                 ),
             ]),
         };
-        // Because of the HashMap, the order of the keys is not guaranteed.
-        // However, the order doesn't matter so we can accept either one.
-        assert!(
-            print_record(&record) == "{bar: \"baz\", foo: 42}"
-                || print_record(&record) == "{foo: 42, bar: \"baz\"}"
+        assert_eq!(
+            compact_for_test(&print_record(&record, &PrinterConfiguration::default())),
+            "{foo:42,bar:\"baz\"}"
         );
     }
 
@@ -47,11 +50,32 @@ mod test {
     fn does_not_include_comma_with_one_item() {
         let record = ConcreteRecordExpression {
             expression_type: ConcreteType::default_record_for_test(),
-            contents: HashMap::from([(
+            contents: IndexMap::from([(
+                "foo".to_string(),
+                ConcreteExpression::integer_for_test(42),
+            )]),
+        };
+        assert_eq!(
+            compact_for_test(&print_record(&record, &PrinterConfiguration::default())),
+            "{foo:42}"
+        );
+    }
+
+    #[test]
+    fn pretty_records_print_each_field_on_its_own_line() {
+        let record = ConcreteRecordExpression {
+            expression_type: ConcreteType::default_record_for_test(),
+            contents: IndexMap::from([(
                 "foo".to_string(),
                 ConcreteExpression::integer_for_test(42),
             )]),
         };
-        assert_eq!(print_record(&record), "{foo: 42}");
+        assert_eq!(
+            render_layout(
+                &print_record(&record, &PrinterConfiguration::default()),
+                OutputStyle::Pretty
+            ),
+            "{\n    foo: 42\n}"
+        );
     }
 }
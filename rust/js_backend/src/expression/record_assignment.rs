@@ -1,26 +1,32 @@
 use crate::expression::record::print_record;
+use crate::helpers::Helper;
 use crate::identifier::print_identifier;
+use crate::PrinterConfiguration;
 use typed_ast::ConcreteRecordAssignmentExpression;
 
-pub fn print_record_assignment(assignment: &ConcreteRecordAssignmentExpression) -> String {
+pub fn print_record_assignment(
+    assignment: &ConcreteRecordAssignmentExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
     let identifier = print_identifier(&assignment.identifier);
-    let new_values = print_record(&assignment.contents);
-    format!("{identifier}.$set({new_values})")
+    let new_values = print_record(&assignment.contents, configuration);
+    format!("{}({identifier},{new_values})", Helper::UpdateRecord.name())
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::collections::HashMap;
+    use crate::layout::compact_for_test;
+    use indexmap::IndexMap;
     use typed_ast::{ConcreteExpression, ConcreteRecordExpression, ConcreteType};
 
     #[test]
     fn assigns_a_single_value() {
         let record = ConcreteRecordExpression {
             expression_type: ConcreteType::default_record_for_test(),
-            contents: HashMap::from([(
+            contents: IndexMap::from([(
                 "meaningOfLife".to_string(),
-                ConcreteExpression::integer_for_test(42),
+                ConcreteExpression::string_for_test("forty two"),
             )]),
         };
         let identifier = ConcreteExpression::raw_identifier_for_test("hello");
@@ -29,15 +35,18 @@ mod test {
             contents: record,
             identifier,
         };
-        let result = print_record_assignment(&assignment);
-        assert_eq!(result, "Bhello.$set({meaningOfLife: 42})");
+        let result = print_record_assignment(&assignment, &PrinterConfiguration::default());
+        assert_eq!(
+            compact_for_test(&result),
+            "$updateRecord(Bhello,{meaningOfLife:\"forty two\"})"
+        );
     }
 
     #[test]
     fn assigns_multiple_values() {
         let record = ConcreteRecordExpression {
             expression_type: ConcreteType::default_record_for_test(),
-            contents: HashMap::from([
+            contents: IndexMap::from([
                 (
                     "meaningOfLife".to_string(),
                     ConcreteExpression::integer_for_test(42),
@@ -51,12 +60,10 @@ mod test {
             contents: record,
             identifier,
         };
-        let result = print_record_assignment(&assignment);
-        // Because of the HashMap, the order of the keys is not guaranteed.
-        // However, the order doesn't matter so we can accept either one.
-        assert!(
-            result == "Bhello.$set({meaningOfLife: 42, foo: 0})"
-                || result == "Bhello.$set({foo: 0, meaningOfLife: 42})"
-        );
+        let result = compact_for_test(&print_record_assignment(
+            &assignment,
+            &PrinterConfiguration::default(),
+        ));
+        assert_eq!(result, "$updateRecord(Bhello,{meaningOfLife:42,foo:0})");
     }
 }
@@ -0,0 +1,47 @@
+use super::print_expression;
+use crate::{helpers::Helper, PrinterConfiguration};
+use typed_ast::ConcreteSetExpression;
+
+/// Sets are instances of the prelude's `$Set` class, constructed from a list of elements.
+pub fn print_set(set: &ConcreteSetExpression, configuration: &PrinterConfiguration) -> String {
+    let elements = set
+        .contents
+        .iter()
+        .map(|element| print_expression(element, configuration))
+        .collect::<Vec<String>>()
+        .join(",");
+    format!("new {}([{elements}])", Helper::Set.name())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use typed_ast::{ConcreteExpression, ConcreteType};
+
+    #[test]
+    fn can_print_empty_set() {
+        let set = ConcreteSetExpression {
+            expression_type: ConcreteType::default_set_for_test(),
+            contents: vec![],
+        };
+        assert_eq!(
+            print_set(&set, &PrinterConfiguration::default()),
+            "new $Set([])"
+        );
+    }
+
+    #[test]
+    fn can_print_set_of_strings() {
+        let set = ConcreteSetExpression {
+            expression_type: ConcreteType::default_set_for_test(),
+            contents: vec![
+                ConcreteExpression::string_for_test("foo"),
+                ConcreteExpression::string_for_test("bar"),
+            ],
+        };
+        assert_eq!(
+            print_set(&set, &PrinterConfiguration::default()),
+            "new $Set([\"foo\",\"bar\"])"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use super::{block, declaration, if_expression, print_expression, tail_call::TailCall, when};
+use crate::{
+    markers::SPACE, source_map::print_source_location, tags::print_tag_name, PrinterConfiguration,
+};
+use typed_ast::ConcreteExpression;
+
+/// What happens to the value of an expression that is printed as statements.
+#[derive(Debug, Clone, Copy)]
+pub enum Target<'a> {
+    Return,
+    Assign(&'a str),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Destination<'a> {
+    pub target: Target<'a>,
+    /// `if`s without an `else` wrap their value in `#some`. When they are nested, the
+    /// value is wrapped once for each of them.
+    pub some_wrappers: usize,
+    /// Set when the value is returned from a function which loops instead of calling
+    /// itself.
+    pub tail_call: Option<TailCall<'a>>,
+}
+
+impl<'a> Destination<'a> {
+    pub const fn new(target: Target<'a>) -> Self {
+        Self {
+            target,
+            some_wrappers: 0,
+            tail_call: None,
+        }
+    }
+
+    pub const fn returned_from_loop(tail_call: TailCall<'a>) -> Self {
+        Self {
+            target: Target::Return,
+            some_wrappers: 0,
+            tail_call: Some(tail_call),
+        }
+    }
+
+    /// The value will be wrapped after it is computed, so a call isn't in tail position.
+    pub const fn wrapped_in_some(self) -> Self {
+        Self {
+            target: self.target,
+            some_wrappers: self.some_wrappers + 1,
+            tail_call: None,
+        }
+    }
+
+    pub fn deliver(self, value: &str, configuration: &PrinterConfiguration) -> String {
+        let mut value = value.to_string();
+        for _ in 0..self.some_wrappers {
+            value = format!(
+                "[{},{value}]",
+                print_tag_name("some", configuration.tag_representation)
+            );
+        }
+        match self.target {
+            Target::Return => format!("return {value};"),
+            Target::Assign(identifier) => format!("{identifier}{SPACE}={SPACE}{value};"),
+        }
+    }
+}
+
+/// Whether the expression would need an IIFE to be printed as a JS expression.
+/// Such expressions are printed as statements when their value is returned from a
+/// function or assigned to a variable.
+pub fn needs_statements(expression: &ConcreteExpression) -> bool {
+    match expression {
+        ConcreteExpression::Block(block) => {
+            block.contents.len() > 1
+                || matches!(block.contents.first(), Some(expression) if needs_statements(expression))
+        }
+        ConcreteExpression::If(if_expression) => {
+            needs_statements(&if_expression.path_if_true)
+                || matches!(
+                    &if_expression.path_if_false,
+                    Some(path_if_false) if needs_statements(path_if_false)
+                )
+        }
+        ConcreteExpression::When(_) => true,
+        _ => false,
+    }
+}
+
+/// Prints an expression whose value is only used for its effect on the scope, such as
+/// a declaration which isn't the last expression in a block.
+pub fn print_statement(
+    expression: &ConcreteExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    match expression {
+        ConcreteExpression::Declaration(declaration) => format!(
+            "{}{};",
+            print_source_location(declaration.source),
+            declaration::print_local_declaration(declaration, configuration)
+        ),
+        _ => format!("{};", print_expression(expression, configuration)),
+    }
+}
+
+/// Prints an expression as JS statements which send its value to the destination.
+pub fn print_statements(
+    expression: &ConcreteExpression,
+    destination: Destination,
+    configuration: &PrinterConfiguration,
+) -> String {
+    match expression {
+        ConcreteExpression::Block(block) => {
+            block::print_block_statements(block, destination, configuration)
+        }
+        ConcreteExpression::If(if_expression) => {
+            if_expression::print_if_statements(if_expression, destination, configuration)
+        }
+        ConcreteExpression::When(when) => {
+            when::print_when_statements(when, destination, configuration)
+        }
+        _ => destination
+            .tail_call
+            .and_then(|tail_call| tail_call.print_self_call(expression, configuration))
+            .unwrap_or_else(|| {
+                destination.deliver(&print_expression(expression, configuration), configuration)
+            }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layout::compact_for_test;
+
+    #[test]
+    fn returned_values_are_returned() {
+        let destination = Destination::new(Target::Return);
+        assert_eq!(
+            compact_for_test(&destination.deliver("42", &PrinterConfiguration::default())),
+            "return 42;"
+        );
+    }
+
+    #[test]
+    fn assigned_values_are_assigned_to_the_identifier() {
+        let destination = Destination::new(Target::Assign("Bx"));
+        assert_eq!(
+            compact_for_test(&destination.deliver("42", &PrinterConfiguration::default())),
+            "Bx=42;"
+        );
+    }
+
+    #[test]
+    fn values_are_wrapped_once_for_each_some_wrapper() {
+        let destination = Destination::new(Target::Return)
+            .wrapped_in_some()
+            .wrapped_in_some();
+        assert_eq!(
+            compact_for_test(&destination.deliver("42", &PrinterConfiguration::default())),
+            "return [\"some\",[\"some\",42]];"
+        );
+    }
+
+    #[test]
+    fn literals_do_not_need_statements() {
+        assert!(!needs_statements(&ConcreteExpression::integer_for_test(42)));
+    }
+
+    #[test]
+    fn blocks_with_several_expressions_need_statements() {
+        let block = ConcreteExpression::block_for_test(vec![
+            ConcreteExpression::integer_for_test(42),
+            ConcreteExpression::integer_for_test(43),
+        ]);
+        assert!(needs_statements(&block));
+    }
+
+    #[test]
+    fn blocks_with_one_expression_do_not_need_statements() {
+        let block =
+            ConcreteExpression::block_for_test(vec![ConcreteExpression::integer_for_test(42)]);
+        assert!(!needs_statements(&block));
+    }
+}
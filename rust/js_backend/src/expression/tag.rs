@@ -1,27 +1,29 @@
+use crate::{
+    tags::{print_tag_name, print_tag_without_contents},
+    PrinterConfiguration,
+};
 use typed_ast::ConcreteTagExpression;
 
-pub fn print_tag(tag: &ConcreteTagExpression) -> String {
-    let mut output = String::new();
-    output.push_str("[\"");
-    output.push_str(&tag.name);
-    output.push('"');
-    if !tag.contents.is_empty() {
-        let contents = tag
-            .contents
-            .iter()
-            .map(super::print_expression)
-            .collect::<Vec<_>>()
-            .join(", ");
-        output.push(',');
-        output.push_str(&contents);
+pub fn print_tag(tag: &ConcreteTagExpression, configuration: &PrinterConfiguration) -> String {
+    if tag.contents.is_empty() {
+        return print_tag_without_contents(&tag.name, configuration.tag_representation);
     }
-    output.push(']');
-    output
+    let contents = tag
+        .contents
+        .iter()
+        .map(|content| super::print_expression(content, configuration))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "[{},{contents}]",
+        print_tag_name(&tag.name, configuration.tag_representation)
+    )
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::layout::compact_for_test;
     use typed_ast::{ConcreteExpression, ConcreteType};
 
     #[test]
@@ -31,7 +33,10 @@ mod test {
             expression_type: ConcreteType::default_tag_union_for_test(),
             contents: vec![],
         };
-        assert_eq!(print_tag(&tag), "[\"foo\"]");
+        assert_eq!(
+            compact_for_test(&print_tag(&tag, &PrinterConfiguration::default())),
+            "[\"foo\"]"
+        );
     }
 
     #[test]
@@ -41,6 +46,9 @@ mod test {
             expression_type: ConcreteType::default_tag_union_for_test(),
             contents: vec![ConcreteExpression::integer_for_test(42)],
         };
-        assert_eq!(print_tag(&tag), "[\"foo\",42]");
+        assert_eq!(
+            compact_for_test(&print_tag(&tag, &PrinterConfiguration::default())),
+            "[\"foo\",42]"
+        );
     }
 }
@@ -0,0 +1,252 @@
+use super::print_expression;
+use crate::{
+    layout::print_braced,
+    mangle_variable_name,
+    markers::{NEWLINE, SPACE},
+    PrinterConfiguration,
+};
+use ast::BinaryOperatorSymbol;
+use typed_ast::ConcreteExpression;
+
+/// A function whose calls to itself in tail position are compiled to a loop, so that
+/// recursing over a long list doesn't overflow the stack.
+///
+/// ```buri
+/// sumTo = (n, total) => if n == 0 do total else sumTo(n - 1, total + n)
+/// ```
+///
+/// The arguments are bound to fresh variables on every iteration, so closures created
+/// in the body still capture the values from their own iteration.
+///
+/// ```js
+/// const BsumTo=($Bn,$Btotal)=>{while(true){let Bn=$Bn,Btotal=$Btotal;if((Bn===0)){return Btotal;}else{$Bn=(Bn-1);$Btotal=(Btotal+Bn);continue;}}}
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TailCall<'a> {
+    pub function_name: &'a str,
+    pub argument_names: &'a [String],
+}
+
+impl TailCall<'_> {
+    /// Returns the arguments of the call if the expression calls this function.
+    fn get_self_call_arguments(
+        self,
+        expression: &ConcreteExpression,
+    ) -> Option<&Vec<ConcreteExpression>> {
+        let ConcreteExpression::BinaryOperator(operator) = expression else {
+            return None;
+        };
+        match (
+            &operator.symbol,
+            &operator.left_child,
+            &operator.right_child,
+        ) {
+            (
+                BinaryOperatorSymbol::FunctionApplication,
+                ConcreteExpression::Identifier(identifier),
+                ConcreteExpression::FunctionArguments(arguments),
+            ) if identifier.name == self.function_name
+                && arguments.len() == self.argument_names.len()
+                // An argument with the same name as the function hides it.
+                && !self.argument_names.iter().any(|name| name == self.function_name) =>
+            {
+                Some(arguments)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether any tail position of the expression calls this function.
+    pub fn is_called_in_tail_position(self, expression: &ConcreteExpression) -> bool {
+        match expression {
+            ConcreteExpression::Block(block) => matches!(
+                block.contents.last(),
+                Some(last) if self.is_called_in_tail_position(last)
+            ),
+            // Without an `else`, the value of the `if` is wrapped in `#some`, so the call
+            // isn't the last thing that happens.
+            ConcreteExpression::If(if_expression) => {
+                matches!(
+                    &if_expression.path_if_false,
+                    Some(path) if self.is_called_in_tail_position(&if_expression.path_if_true)
+                        || self.is_called_in_tail_position(path)
+                )
+            }
+            ConcreteExpression::When(when) => when
+                .cases
+                .iter()
+                .any(|case| self.is_called_in_tail_position(&case.case_expression)),
+            _ => self.get_self_call_arguments(expression).is_some(),
+        }
+    }
+
+    /// Prints the loop's parameters, which the arguments are rebound from.
+    pub fn print_parameters(self) -> String {
+        self.argument_names
+            .iter()
+            .map(|name| format!("${}", mangle_variable_name(name)))
+            .collect::<Vec<_>>()
+            .join(&format!(",{SPACE}"))
+    }
+
+    /// Prints the loop around the body, which binds the arguments for each iteration.
+    pub fn print_loop(self, body: &str) -> String {
+        let bindings = self
+            .argument_names
+            .iter()
+            .map(|name| {
+                let argument = mangle_variable_name(name);
+                format!("{argument}{SPACE}={SPACE}${argument}")
+            })
+            .collect::<Vec<_>>()
+            .join(&format!(",{SPACE}"));
+        let contents = if bindings.is_empty() {
+            body.to_string()
+        } else {
+            format!("let {bindings};{NEWLINE}{body}")
+        };
+        format!("while{SPACE}(true){SPACE}{}", print_braced(&contents))
+    }
+
+    /// Prints the expression as the next iteration of the loop if it calls this function.
+    pub fn print_self_call(
+        self,
+        expression: &ConcreteExpression,
+        configuration: &PrinterConfiguration,
+    ) -> Option<String> {
+        let arguments = self.get_self_call_arguments(expression)?;
+        let assignments = self
+            .argument_names
+            .iter()
+            .zip(arguments)
+            .map(|(name, argument)| {
+                format!(
+                    "${}{SPACE}={SPACE}{};{NEWLINE}",
+                    mangle_variable_name(name),
+                    print_expression(argument, configuration)
+                )
+            })
+            .collect::<Vec<_>>()
+            .concat();
+        Some(format!("{assignments}continue;"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layout::{compact_for_test, render_layout, OutputStyle};
+    use typed_ast::{
+        ConcreteBinaryOperatorExpression, ConcreteIfExpression, ConcreteType, SourceLocation,
+    };
+
+    fn call_for_test(name: &str, arguments: Vec<ConcreteExpression>) -> ConcreteExpression {
+        ConcreteExpression::BinaryOperator(Box::new(ConcreteBinaryOperatorExpression {
+            source: SourceLocation::default(),
+            expression_type: ConcreteType::default_for_test(),
+            symbol: BinaryOperatorSymbol::FunctionApplication,
+            left_child: ConcreteExpression::identifier_for_test(name),
+            right_child: ConcreteExpression::FunctionArguments(arguments),
+        }))
+    }
+
+    fn if_for_test(
+        path_if_true: ConcreteExpression,
+        path_if_false: Option<ConcreteExpression>,
+    ) -> ConcreteExpression {
+        ConcreteExpression::If(Box::new(ConcreteIfExpression {
+            expression_type: ConcreteType::default_for_test(),
+            condition: ConcreteExpression::identifier_for_test("done"),
+            path_if_true,
+            path_if_false,
+        }))
+    }
+
+    const ARGUMENT_NAMES: [String; 0] = [];
+
+    #[test]
+    fn a_call_to_the_function_is_a_tail_call() {
+        let argument_names = vec!["n".to_string()];
+        let tail_call = TailCall {
+            function_name: "count",
+            argument_names: &argument_names,
+        };
+        let expression = call_for_test("count", vec![ConcreteExpression::integer_for_test(1)]);
+        assert!(tail_call.is_called_in_tail_position(&expression));
+        assert_eq!(
+            compact_for_test(
+                &tail_call
+                    .print_self_call(&expression, &PrinterConfiguration::default())
+                    .unwrap()
+            ),
+            "$Bn=1;continue;"
+        );
+    }
+
+    #[test]
+    fn calls_to_other_functions_are_not_tail_calls() {
+        let tail_call = TailCall {
+            function_name: "count",
+            argument_names: &ARGUMENT_NAMES,
+        };
+        let expression = call_for_test("other", vec![]);
+        assert!(!tail_call.is_called_in_tail_position(&expression));
+        assert!(tail_call
+            .print_self_call(&expression, &PrinterConfiguration::default())
+            .is_none());
+    }
+
+    #[test]
+    fn calls_in_either_branch_of_an_if_are_tail_calls() {
+        let tail_call = TailCall {
+            function_name: "count",
+            argument_names: &ARGUMENT_NAMES,
+        };
+        let expression = if_for_test(
+            ConcreteExpression::integer_for_test(1),
+            Some(call_for_test("count", vec![])),
+        );
+        assert!(tail_call.is_called_in_tail_position(&expression));
+    }
+
+    #[test]
+    fn calls_in_an_if_without_an_else_are_not_tail_calls() {
+        let tail_call = TailCall {
+            function_name: "count",
+            argument_names: &ARGUMENT_NAMES,
+        };
+        let expression = if_for_test(call_for_test("count", vec![]), None);
+        assert!(!tail_call.is_called_in_tail_position(&expression));
+    }
+
+    #[test]
+    fn calls_before_the_end_of_a_block_are_not_tail_calls() {
+        let tail_call = TailCall {
+            function_name: "count",
+            argument_names: &ARGUMENT_NAMES,
+        };
+        let expression = ConcreteExpression::block_for_test(vec![
+            call_for_test("count", vec![]),
+            ConcreteExpression::integer_for_test(1),
+        ]);
+        assert!(!tail_call.is_called_in_tail_position(&expression));
+    }
+
+    #[test]
+    fn the_loop_binds_each_argument() {
+        let argument_names = vec!["a".to_string(), "b".to_string()];
+        let tail_call = TailCall {
+            function_name: "count",
+            argument_names: &argument_names,
+        };
+        assert_eq!(compact_for_test(&tail_call.print_parameters()), "$Ba,$Bb");
+        assert_eq!(
+            compact_for_test(&tail_call.print_loop("continue;")),
+            "while(true){let Ba=$Ba,Bb=$Bb;continue;}"
+        );
+        assert_eq!(
+            render_layout(&tail_call.print_loop("continue;"), OutputStyle::Pretty),
+            "while (true) {\n    let Ba = $Ba, Bb = $Bb;\n    continue;\n}"
+        );
+    }
+}
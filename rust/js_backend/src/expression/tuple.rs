@@ -0,0 +1,91 @@
+use super::print_expression;
+use crate::{identifier::print_identifier, markers::SPACE, PrinterConfiguration};
+use typed_ast::{ConcreteTupleDeclarationExpression, ConcreteTupleExpression};
+
+/// Tuples are represented as arrays.
+pub fn print_tuple(
+    tuple: &ConcreteTupleExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let contents: Vec<String> = tuple
+        .contents
+        .iter()
+        .map(|element| print_expression(element, configuration))
+        .collect();
+    format!("[{}]", contents.join(","))
+}
+
+pub fn print_tuple_declaration(
+    declaration: &ConcreteTupleDeclarationExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let identifiers: Vec<String> = declaration
+        .identifiers
+        .iter()
+        .map(print_identifier)
+        .collect();
+    let value = print_expression(&declaration.value, configuration);
+    format!(
+        "let [{}]{SPACE}={SPACE}{value}",
+        identifiers.join(&format!(",{SPACE}"))
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layout::{compact_for_test, render_layout, OutputStyle};
+    use typed_ast::{ConcreteExpression, ConcreteType};
+
+    #[test]
+    fn can_print_tuple() {
+        let tuple = ConcreteTupleExpression {
+            expression_type: ConcreteType::default_tuple_for_test(),
+            contents: vec![
+                ConcreteExpression::integer_for_test(1),
+                ConcreteExpression::string_for_test("one"),
+            ],
+        };
+        assert_eq!(
+            compact_for_test(&print_tuple(&tuple, &PrinterConfiguration::default())),
+            "[1,\"one\"]"
+        );
+    }
+
+    fn tuple_declaration_for_test() -> ConcreteTupleDeclarationExpression {
+        ConcreteTupleDeclarationExpression {
+            declaration_type: ConcreteType::default_tuple_for_test(),
+            expression_type: ConcreteType::default_tuple_for_test(),
+            identifiers: vec![
+                ConcreteExpression::raw_identifier_for_test("a"),
+                ConcreteExpression::raw_identifier_for_test("b"),
+            ],
+            value: ConcreteExpression::identifier_for_test("pair"),
+        }
+    }
+
+    #[test]
+    fn can_print_tuple_declaration() {
+        assert_eq!(
+            compact_for_test(&print_tuple_declaration(
+                &tuple_declaration_for_test(),
+                &PrinterConfiguration::default()
+            )),
+            "let [Ba,Bb]=Bpair"
+        );
+    }
+
+    #[test]
+    fn tuple_declarations_are_spaced_in_pretty_output() {
+        assert_eq!(
+            render_layout(
+                &print_tuple_declaration(
+                    &tuple_declaration_for_test(),
+                    &PrinterConfiguration::default()
+                ),
+                OutputStyle::Pretty
+            ),
+            "let [Ba, Bb] = Bpair"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::PrinterConfiguration;
 use ast::UnaryOperatorSymbol;
 use typed_ast::ConcreteUnaryOperatorExpression;
 
@@ -8,17 +9,21 @@ fn print_unary_operator_symbol(operator: &UnaryOperatorSymbol) -> String {
     }
 }
 
-pub fn print_unary_operator(expression: &ConcreteUnaryOperatorExpression) -> String {
+pub fn print_unary_operator(
+    expression: &ConcreteUnaryOperatorExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
     format!(
         "{}{}",
         print_unary_operator_symbol(&expression.symbol),
-        super::print_expression(&expression.child)
+        super::print_expression(&expression.child, configuration)
     )
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::layout::compact_for_test;
     use ast::UnaryOperatorSymbol;
     use typed_ast::{ConcreteExpression, ConcreteType};
 
@@ -29,7 +34,13 @@ mod test {
             symbol: UnaryOperatorSymbol::Negative,
             child: ConcreteExpression::integer_for_test(42),
         };
-        assert_eq!(print_unary_operator(&expression), "-42");
+        assert_eq!(
+            compact_for_test(&print_unary_operator(
+                &expression,
+                &PrinterConfiguration::default()
+            )),
+            "-42"
+        );
     }
 
     #[test]
@@ -39,6 +50,9 @@ mod test {
             symbol: UnaryOperatorSymbol::Not,
             child: ConcreteExpression::identifier_for_test("foo"),
         };
-        assert_eq!(print_unary_operator(&expression), "!Bfoo");
+        assert_eq!(
+            print_unary_operator(&expression, &PrinterConfiguration::default()),
+            "!Bfoo"
+        );
     }
 }
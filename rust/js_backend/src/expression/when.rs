@@ -1,49 +1,327 @@
-use super::print_expression;
-use crate::expression::mangle_variable_name;
-use typed_ast::{ConcreteWhenCaseName, ConcreteWhenExpression};
+use super::{
+    print_expression,
+    statement::{print_statements, Destination, Target},
+};
+use crate::{
+    expression::mangle_variable_name,
+    layout::print_braced,
+    markers::{NEWLINE, SPACE},
+    tags::{print_tag_name, TagRepresentation},
+    PrinterConfiguration,
+};
+use typed_ast::{
+    get_concrete_type, ConcreteType, ConcreteWhenCase, ConcreteWhenCaseName, ConcreteWhenExpression,
+};
 
-pub fn print_when(when: &ConcreteWhenExpression) -> String {
-    let printed_when_condition = print_expression(&when.condition);
-    print_when_case(when, &printed_when_condition, 0)
+/// The condition is bound to this name so that it is only evaluated once.
+/// Buri identifiers are always mangled, so it can't shadow a user's variable.
+const SCRUTINEE: &str = "$when";
+
+pub fn print_when(when: &ConcreteWhenExpression, configuration: &PrinterConfiguration) -> String {
+    format!(
+        "(({SCRUTINEE}){SPACE}=>{SPACE}{})({})",
+        print_braced(&print_switch(
+            when,
+            Destination::new(Target::Return),
+            configuration
+        )),
+        print_expression(&when.condition, configuration)
+    )
 }
 
-fn print_when_case(
+pub fn print_when_statements(
+    when: &ConcreteWhenExpression,
+    destination: Destination,
+    configuration: &PrinterConfiguration,
+) -> String {
+    print_braced(&format!(
+        "const {SCRUTINEE}{SPACE}={SPACE}{};{NEWLINE}{}",
+        print_expression(&when.condition, configuration),
+        print_switch(when, destination, configuration)
+    ))
+}
+
+/// Reads which tag the condition is. Integer tags without contents are just their
+/// discriminant, so the condition's type decides where the discriminant is.
+fn print_discriminant(
     when: &ConcreteWhenExpression,
-    printed_when_condition: &String,
-    index: usize,
+    tag_representation: TagRepresentation,
 ) -> String {
-    when.cases.get(index).map_or_else(
-        || String::from("0"),
-        |case| {
-            let mut result = String::new();
-            result.push('(');
-            match &case.case_name {
-                ConcreteWhenCaseName::Name(name) => {
-                    result.push_str(&format!(
-                        "{}[0]==\"{}\"",
-                        printed_when_condition.clone(),
-                        name
-                    ));
-                }
-                ConcreteWhenCaseName::DefaultCase => result.push_str("true"),
-            };
-            result.push('?');
-            result.push_str("(()=>{");
-            for (index, argument) in case.case_arguments.iter().enumerate() {
-                result.push_str(&format!(
-                    "let {}={}[{}];",
-                    mangle_variable_name(&argument.name),
-                    printed_when_condition.clone(),
-                    index + 1
-                ));
-            }
-            result.push_str("return ");
-            result.push_str(&print_expression(&case.case_expression));
-            result.push_str("})()");
-            result.push(':');
-            result.push_str(&print_when_case(when, printed_when_condition, index + 1));
-            result.push(')');
-            result
-        },
+    let first_element = format!("{SCRUTINEE}[0]");
+    let (TagRepresentation::Integers, ConcreteType::TagUnion(tag_union_type)) =
+        (tag_representation, get_concrete_type(&when.condition))
+    else {
+        return first_element;
+    };
+    let tags_with_contents = tag_union_type
+        .tag_types
+        .values()
+        .filter(|contents| !contents.is_empty())
+        .count();
+    if tags_with_contents == 0 {
+        SCRUTINEE.to_string()
+    } else if tags_with_contents == tag_union_type.tag_types.len() {
+        first_element
+    } else {
+        format!("{first_element}??{SCRUTINEE}")
+    }
+}
+
+fn print_switch(
+    when: &ConcreteWhenExpression,
+    destination: Destination,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let cases = when
+        .cases
+        .iter()
+        .map(|case| print_when_case(case, destination, configuration))
+        .collect::<Vec<_>>()
+        .join(&NEWLINE.to_string());
+    format!(
+        "switch{SPACE}({}){SPACE}{}",
+        print_discriminant(when, configuration.tag_representation),
+        print_braced(&cases)
     )
 }
+
+fn print_when_case(
+    case: &ConcreteWhenCase,
+    destination: Destination,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let label = match &case.case_name {
+        ConcreteWhenCaseName::Name(name) => format!(
+            "case {}:",
+            print_tag_name(name, configuration.tag_representation)
+        ),
+        ConcreteWhenCaseName::DefaultCase => String::from("default:"),
+    };
+    let mut body = case
+        .case_arguments
+        .iter()
+        .enumerate()
+        .map(|(index, argument)| {
+            format!(
+                "let {}{SPACE}={SPACE}{SCRUTINEE}[{}];{NEWLINE}",
+                mangle_variable_name(&argument.name),
+                index + 1
+            )
+        })
+        .collect::<Vec<_>>()
+        .concat();
+    body.push_str(&print_statements(
+        &case.case_expression,
+        destination,
+        configuration,
+    ));
+    if let Target::Assign(_) = destination.target {
+        body.push(NEWLINE);
+        body.push_str("break;");
+    }
+    format!("{label}{SPACE}{}", print_braced(&body))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        layout::{compact_for_test, render_layout, OutputStyle},
+        tags::get_tag_discriminant,
+    };
+    use indexmap::IndexMap;
+    use typed_ast::{ConcreteExpression, ConcreteTagUnionType};
+
+    fn case_for_test(
+        case_name: ConcreteWhenCaseName,
+        case_arguments: &[&str],
+        case_expression: ConcreteExpression,
+    ) -> ConcreteWhenCase {
+        ConcreteWhenCase {
+            expression_type: ConcreteType::default_for_test(),
+            case_name,
+            case_arguments: case_arguments
+                .iter()
+                .map(|name| ConcreteExpression::raw_identifier_for_test(name))
+                .collect(),
+            case_expression,
+        }
+    }
+
+    #[test]
+    fn the_condition_is_only_printed_once() {
+        let when = ConcreteWhenExpression {
+            expression_type: ConcreteType::default_for_test(),
+            condition: ConcreteExpression::identifier_for_test("color"),
+            cases: vec![
+                case_for_test(
+                    ConcreteWhenCaseName::Name("red".to_string()),
+                    &[],
+                    ConcreteExpression::string_for_test("red"),
+                ),
+                case_for_test(
+                    ConcreteWhenCaseName::Name("green".to_string()),
+                    &[],
+                    ConcreteExpression::string_for_test("green"),
+                ),
+            ],
+        };
+        assert_eq!(
+            compact_for_test(&print_when(&when, &PrinterConfiguration::default())),
+            "(($when)=>{switch($when[0]){case \"red\":{return \"red\";}case \"green\":{return \"green\";}}})(Bcolor)"
+        );
+    }
+
+    #[test]
+    fn case_arguments_are_read_from_the_bound_condition() {
+        let when = ConcreteWhenExpression {
+            expression_type: ConcreteType::default_for_test(),
+            condition: ConcreteExpression::identifier_for_test("color"),
+            cases: vec![case_for_test(
+                ConcreteWhenCaseName::Name("rgb".to_string()),
+                &["r", "g"],
+                ConcreteExpression::identifier_for_test("g"),
+            )],
+        };
+        assert_eq!(
+            compact_for_test(&print_when(&when, &PrinterConfiguration::default())),
+            "(($when)=>{switch($when[0]){case \"rgb\":{let Br=$when[1];let Bg=$when[2];return Bg;}}})(Bcolor)"
+        );
+    }
+
+    #[test]
+    fn default_cases_are_the_switch_default() {
+        let when = ConcreteWhenExpression {
+            expression_type: ConcreteType::default_for_test(),
+            condition: ConcreteExpression::identifier_for_test("color"),
+            cases: vec![
+                case_for_test(
+                    ConcreteWhenCaseName::Name("red".to_string()),
+                    &[],
+                    ConcreteExpression::integer_for_test(1),
+                ),
+                case_for_test(
+                    ConcreteWhenCaseName::DefaultCase,
+                    &[],
+                    ConcreteExpression::integer_for_test(2),
+                ),
+            ],
+        };
+        assert_eq!(
+            compact_for_test(&print_when(&when, &PrinterConfiguration::default())),
+            "(($when)=>{switch($when[0]){case \"red\":{return 1;}default:{return 2;}}})(Bcolor)"
+        );
+    }
+
+    #[test]
+    fn assigned_cases_break_out_of_the_switch() {
+        let when = ConcreteWhenExpression {
+            expression_type: ConcreteType::default_for_test(),
+            condition: ConcreteExpression::identifier_for_test("color"),
+            cases: vec![case_for_test(
+                ConcreteWhenCaseName::Name("red".to_string()),
+                &[],
+                ConcreteExpression::integer_for_test(1),
+            )],
+        };
+        assert_eq!(
+            compact_for_test(&print_when_statements(
+                &when,
+                Destination::new(Target::Assign("Bx")),
+                &PrinterConfiguration::default()
+            )),
+            "{const $when=Bcolor;switch($when[0]){case \"red\":{Bx=1;break;}}}"
+        );
+    }
+
+    #[test]
+    fn pretty_cases_are_indented_inside_the_switch() {
+        let when = ConcreteWhenExpression {
+            expression_type: ConcreteType::default_for_test(),
+            condition: ConcreteExpression::identifier_for_test("color"),
+            cases: vec![case_for_test(
+                ConcreteWhenCaseName::Name("rgb".to_string()),
+                &["r"],
+                ConcreteExpression::identifier_for_test("r"),
+            )],
+        };
+        assert_eq!(
+            render_layout(
+                &print_when_statements(
+                    &when,
+                    Destination::new(Target::Return),
+                    &PrinterConfiguration::default()
+                ),
+                OutputStyle::Pretty
+            ),
+            "{\n    const $when = Bcolor;\n    switch ($when[0]) {\n        case \"rgb\": {\n            let Br = $when[1];\n            return Br;\n        }\n    }\n}"
+        );
+    }
+
+    fn when_for_test(tag_types: &[(&str, usize)]) -> ConcreteWhenExpression {
+        let mut condition = ConcreteExpression::raw_identifier_for_test("tag");
+        condition.expression_type = ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
+            tag_types: tag_types
+                .iter()
+                .map(|(name, content_count)| {
+                    (
+                        (*name).to_string(),
+                        vec![ConcreteType::default_integer_for_test(); *content_count],
+                    )
+                })
+                .collect::<IndexMap<_, _>>(),
+        }));
+        ConcreteWhenExpression {
+            expression_type: ConcreteType::default_for_test(),
+            condition: ConcreteExpression::Identifier(Box::new(condition)),
+            cases: vec![case_for_test(
+                ConcreteWhenCaseName::DefaultCase,
+                &[],
+                ConcreteExpression::integer_for_test(0),
+            )],
+        }
+    }
+
+    fn print_integer_tags_for_test(when: &ConcreteWhenExpression) -> String {
+        let configuration = PrinterConfiguration {
+            tag_representation: TagRepresentation::Integers,
+            ..PrinterConfiguration::default()
+        };
+        compact_for_test(&print_when(when, &configuration))
+    }
+
+    #[test]
+    fn integer_tags_are_compared_by_discriminant() {
+        let mut when = when_for_test(&[("red", 0)]);
+        when.cases.insert(
+            0,
+            case_for_test(
+                ConcreteWhenCaseName::Name("red".to_string()),
+                &[],
+                ConcreteExpression::integer_for_test(1),
+            ),
+        );
+        assert_eq!(
+            print_integer_tags_for_test(&when),
+            format!(
+                "(($when)=>{{switch($when){{case {}:{{return 1;}}default:{{return 0;}}}}}})(Btag)",
+                get_tag_discriminant("red")
+            )
+        );
+    }
+
+    #[test]
+    fn integer_tags_with_contents_start_with_their_discriminant() {
+        assert!(
+            print_integer_tags_for_test(&when_for_test(&[("rgb", 3)])).contains("switch($when[0])")
+        );
+    }
+
+    #[test]
+    fn integer_tags_with_and_without_contents_can_be_mixed() {
+        assert!(
+            print_integer_tags_for_test(&when_for_test(&[("none", 0), ("some", 1)]))
+                .contains("switch($when[0]??$when)")
+        );
+    }
+}
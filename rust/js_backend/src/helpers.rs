@@ -0,0 +1,306 @@
+use crate::{
+    equality::{collect_equality_helpers, has_native_equality},
+    tags::TagRepresentation,
+    traversal::for_each_child,
+};
+use ast::BinaryOperatorSymbol;
+use std::collections::BTreeSet;
+use typed_ast::{
+    get_concrete_type, ConcreteBinaryOperatorExpression, ConcreteExpression, ConcreteType,
+    PrimitiveType, TypedDocument,
+};
+
+/// A function or class which the prelude exports for the compiled JS to import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Helper {
+    Map,
+    Set,
+    CallMethod,
+    CompactTag,
+    Divide,
+    Equals,
+    EqualsList,
+    EqualsMap,
+    EqualsPrimitive,
+    EqualsRecord,
+    EqualsSet,
+    EqualsTags,
+    EqualsTuple,
+    FloatCeil,
+    FloatFloor,
+    FloatRound,
+    FloatTruncate,
+    IntToFloat,
+    ListAppend,
+    ListGet,
+    ListSize,
+    Modulo,
+    StringGetCharCode,
+    StringSize,
+    UpdateRecord,
+}
+
+impl Helper {
+    /// The name which the prelude exports the helper as.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Map => "$Map",
+            Self::Set => "$Set",
+            Self::CallMethod => "$callMethod",
+            Self::CompactTag => "$compactTag",
+            Self::Divide => "$divide",
+            Self::Equals => "$equals",
+            Self::EqualsList => "$equalsList",
+            Self::EqualsMap => "$equalsMap",
+            Self::EqualsPrimitive => "$equalsPrimitive",
+            Self::EqualsRecord => "$equalsRecord",
+            Self::EqualsSet => "$equalsSet",
+            Self::EqualsTags => "$equalsTags",
+            Self::EqualsTuple => "$equalsTuple",
+            Self::FloatCeil => "$floatCeil",
+            Self::FloatFloor => "$floatFloor",
+            Self::FloatRound => "$floatRound",
+            Self::FloatTruncate => "$floatTruncate",
+            Self::IntToFloat => "$intToFloat",
+            Self::ListAppend => "$listAppend",
+            Self::ListGet => "$listGet",
+            Self::ListSize => "$listSize",
+            Self::Modulo => "$modulo",
+            Self::StringGetCharCode => "$stringGetCharCode",
+            Self::StringSize => "$stringSize",
+            Self::UpdateRecord => "$updateRecord",
+        }
+    }
+
+    /// The file of the prelude which defines the helper.
+    pub const fn module(self) -> &'static str {
+        match self {
+            Self::Map => "map",
+            Self::Set => "set",
+            Self::CallMethod => "methods",
+            Self::CompactTag => "tags",
+            Self::Equals
+            | Self::EqualsList
+            | Self::EqualsMap
+            | Self::EqualsPrimitive
+            | Self::EqualsRecord
+            | Self::EqualsSet
+            | Self::EqualsTags
+            | Self::EqualsTuple => "equality",
+            Self::FloatCeil | Self::FloatFloor | Self::FloatRound | Self::FloatTruncate => "float",
+            Self::IntToFloat => "integer",
+            Self::ListAppend | Self::ListGet | Self::ListSize => "list",
+            Self::Divide | Self::Modulo => "operators",
+            Self::StringGetCharCode | Self::StringSize => "string",
+            Self::UpdateRecord => "record",
+        }
+    }
+
+    /// Lists, strings and numbers are JS values without Buri's methods, so their
+    /// methods are helpers which take the receiver as their first argument. Maps and
+    /// sets are instances of the prelude's classes, which have the methods.
+    ///
+    /// The type of a generic function's arguments is unknown, so their methods are
+    /// looked up at runtime.
+    pub fn for_method(receiver_type: &ConcreteType, method_name: &str) -> Option<Self> {
+        match (receiver_type, method_name) {
+            (ConcreteType::Primitive(PrimitiveType::Unknown), _) => Some(Self::CallMethod),
+            (ConcreteType::List(_), "get") => Some(Self::ListGet),
+            (ConcreteType::List(_), "append") => Some(Self::ListAppend),
+            (ConcreteType::List(_), "size") => Some(Self::ListSize),
+            (ConcreteType::Primitive(PrimitiveType::Str), "size") => Some(Self::StringSize),
+            (ConcreteType::Primitive(PrimitiveType::Str), "getCharCode") => {
+                Some(Self::StringGetCharCode)
+            }
+            (ConcreteType::Primitive(PrimitiveType::Int), "toFloat") => Some(Self::IntToFloat),
+            (ConcreteType::Primitive(PrimitiveType::Float), "floor") => Some(Self::FloatFloor),
+            (ConcreteType::Primitive(PrimitiveType::Float), "ceil") => Some(Self::FloatCeil),
+            (ConcreteType::Primitive(PrimitiveType::Float), "round") => Some(Self::FloatRound),
+            (ConcreteType::Primitive(PrimitiveType::Float), "truncate") => {
+                Some(Self::FloatTruncate)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The helper which a method lookup such as `list:get` is compiled to, if any.
+pub fn get_method_helper(method_lookup: &ConcreteBinaryOperatorExpression) -> Option<Helper> {
+    match &method_lookup.right_child {
+        ConcreteExpression::Identifier(method) => {
+            Helper::for_method(get_concrete_type(&method_lookup.left_child), &method.name)
+        }
+        _ => None,
+    }
+}
+
+pub fn is_method_call(expression: &ConcreteBinaryOperatorExpression) -> bool {
+    expression.symbol == BinaryOperatorSymbol::FunctionApplication
+        && matches!(
+            &expression.left_child,
+            ConcreteExpression::BinaryOperator(method)
+                if method.symbol == BinaryOperatorSymbol::MethodLookup
+        )
+}
+
+fn collect_expression_helpers(
+    expression: &ConcreteExpression,
+    tag_representation: TagRepresentation,
+    helpers: &mut BTreeSet<Helper>,
+) {
+    match expression {
+        ConcreteExpression::Map(_) => {
+            helpers.insert(Helper::Map);
+        }
+        ConcreteExpression::Set(_) => {
+            helpers.insert(Helper::Set);
+        }
+        ConcreteExpression::RecordAssignment(_) => {
+            helpers.insert(Helper::UpdateRecord);
+        }
+        ConcreteExpression::BinaryOperator(binary_operator) => match binary_operator.symbol {
+            BinaryOperatorSymbol::Modulus => {
+                helpers.insert(Helper::Modulo);
+            }
+            BinaryOperatorSymbol::Divide
+                if get_concrete_type(&binary_operator.left_child)
+                    == &ConcreteType::Primitive(PrimitiveType::Num) =>
+            {
+                helpers.insert(Helper::Divide);
+            }
+            BinaryOperatorSymbol::EqualTo | BinaryOperatorSymbol::NotEqualTo => {
+                let operand_type = get_concrete_type(&binary_operator.left_child);
+                if !has_native_equality(operand_type) {
+                    collect_equality_helpers(operand_type, helpers);
+                }
+            }
+            BinaryOperatorSymbol::MethodLookup => {
+                helpers.extend(get_method_helper(binary_operator));
+            }
+            // Compact builds convert the tags which the prelude's methods return.
+            BinaryOperatorSymbol::FunctionApplication
+                if is_method_call(binary_operator)
+                    && tag_representation == TagRepresentation::Integers
+                    && matches!(binary_operator.expression_type, ConcreteType::TagUnion(_)) =>
+            {
+                helpers.insert(Helper::CompactTag);
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+    for_each_child(expression, &mut |child| {
+        collect_expression_helpers(child, tag_representation, helpers);
+    });
+}
+
+/// Finds the helpers which the printed document calls, so only those are imported
+/// or inlined.
+pub fn collect_helpers(
+    document: &TypedDocument<ConcreteType>,
+    tag_representation: TagRepresentation,
+) -> BTreeSet<Helper> {
+    let mut helpers = BTreeSet::new();
+    for declaration in &document.variable_declarations {
+        collect_expression_helpers(
+            &declaration.declaration.value,
+            tag_representation,
+            &mut helpers,
+        );
+    }
+    helpers
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use typed_ast::{ConcreteIdentifierExpression, ConcreteRecordExpression, SourceLocation};
+
+    fn method_call_for_test(
+        receiver: ConcreteExpression,
+        method_name: &str,
+        expression_type: ConcreteType,
+    ) -> ConcreteExpression {
+        ConcreteExpression::BinaryOperator(Box::new(ConcreteBinaryOperatorExpression {
+            expression_type,
+            source: SourceLocation::default(),
+            symbol: BinaryOperatorSymbol::FunctionApplication,
+            left_child: ConcreteExpression::BinaryOperator(Box::new(
+                ConcreteBinaryOperatorExpression {
+                    expression_type: ConcreteType::default_function_for_test(),
+                    source: SourceLocation::default(),
+                    symbol: BinaryOperatorSymbol::MethodLookup,
+                    left_child: receiver,
+                    right_child: ConcreteExpression::identifier_for_test(method_name),
+                },
+            )),
+            right_child: ConcreteExpression::FunctionArguments(vec![
+                ConcreteExpression::integer_for_test(0),
+            ]),
+        }))
+    }
+
+    fn list_for_test() -> ConcreteExpression {
+        ConcreteExpression::Identifier(Box::new(ConcreteIdentifierExpression {
+            expression_type: ConcreteType::default_list_for_test(),
+            ..ConcreteExpression::raw_identifier_for_test("names")
+        }))
+    }
+
+    #[test]
+    fn methods_of_lists_strings_and_numbers_are_helpers() {
+        assert_eq!(
+            Helper::for_method(&ConcreteType::default_list_for_test(), "get"),
+            Some(Helper::ListGet)
+        );
+        assert_eq!(
+            Helper::for_method(&ConcreteType::Primitive(PrimitiveType::Str), "size"),
+            Some(Helper::StringSize)
+        );
+        assert_eq!(
+            Helper::for_method(&ConcreteType::Primitive(PrimitiveType::Float), "floor"),
+            Some(Helper::FloatFloor)
+        );
+        assert_eq!(
+            Helper::for_method(&ConcreteType::Primitive(PrimitiveType::Int), "toFloat"),
+            Some(Helper::IntToFloat)
+        );
+        assert_eq!(
+            Helper::for_method(&ConcreteType::Primitive(PrimitiveType::Int), "size"),
+            None
+        );
+    }
+
+    #[test]
+    fn helpers_are_collected_from_nested_expressions() {
+        let record = ConcreteExpression::Record(Box::new(ConcreteRecordExpression {
+            expression_type: ConcreteType::default_record_for_test(),
+            contents: indexmap::IndexMap::from([(
+                "first".to_string(),
+                method_call_for_test(
+                    list_for_test(),
+                    "get",
+                    ConcreteType::default_tag_union_for_test(),
+                ),
+            )]),
+        }));
+        let mut helpers = BTreeSet::new();
+        collect_expression_helpers(&record, TagRepresentation::Names, &mut helpers);
+        assert_eq!(helpers, BTreeSet::from([Helper::ListGet]));
+    }
+
+    #[test]
+    fn compact_builds_convert_the_tags_returned_by_methods() {
+        let call = method_call_for_test(
+            list_for_test(),
+            "get",
+            ConcreteType::default_tag_union_for_test(),
+        );
+        let mut helpers = BTreeSet::new();
+        collect_expression_helpers(&call, TagRepresentation::Integers, &mut helpers);
+        assert_eq!(
+            helpers,
+            BTreeSet::from([Helper::CompactTag, Helper::ListGet])
+        );
+    }
+}
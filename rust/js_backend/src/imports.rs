@@ -1,5 +1,35 @@
 use ast::{IdentifierNode, ImportNode, ImportedIdentifier};
 
+/// The module system which the compiled JS uses to import and export values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModuleFormat {
+    /// `import` and `export` statements, in `.mjs` files.
+    #[default]
+    EsModule,
+    /// `require` and `module.exports`, in `.cjs` files, for older Node consumers.
+    CommonJs,
+}
+
+impl ModuleFormat {
+    /// The extension of the compiled files, which imports of other Buri files point to.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::EsModule => "mjs",
+            Self::CommonJs => "cjs",
+        }
+    }
+
+    /// The extension of the TypeScript declarations for the compiled files.
+    #[must_use]
+    pub const fn declaration_extension(self) -> &'static str {
+        match self {
+            Self::EsModule => "d.mts",
+            Self::CommonJs => "d.cts",
+        }
+    }
+}
+
 fn filter_identifiers<'a>(
     imported_identifiers: &[ImportedIdentifier<'a>],
 ) -> Vec<IdentifierNode<'a>> {
@@ -12,36 +42,51 @@ fn filter_identifiers<'a>(
         .collect()
 }
 
-fn format_path(path: &str) -> String {
+fn format_path(path: &str, module_format: ModuleFormat) -> String {
     let mut result = String::new();
     result.push('"');
-    result.push_str(path.replace(".buri", ".mjs").as_str());
+    result.push_str(
+        path.replace(".buri", &format!(".{}", module_format.extension()))
+            .as_str(),
+    );
     result.push('"');
     result
 }
 
-fn print_import(import: &ImportNode) -> String {
+fn print_import(import: &ImportNode, module_format: ModuleFormat) -> String {
     let identifiers = filter_identifiers(&import.value.identifiers);
     if identifiers.is_empty() {
         return String::new();
     }
     let mut result = String::new();
-    result.push_str("import {");
+    match module_format {
+        ModuleFormat::EsModule => result.push_str("import {"),
+        ModuleFormat::CommonJs => result.push_str("const {"),
+    }
     for (index, identifier) in identifiers.iter().enumerate() {
         result.push_str(&identifier.value.name);
         if index < identifiers.len() - 1 {
             result.push(',');
         }
     }
-    result.push_str("} from ");
-    result.push_str(format_path(import.value.path).as_str());
+    match module_format {
+        ModuleFormat::EsModule => {
+            result.push_str("} from ");
+            result.push_str(format_path(import.value.path, module_format).as_str());
+        }
+        ModuleFormat::CommonJs => {
+            result.push_str("} = require(");
+            result.push_str(format_path(import.value.path, module_format).as_str());
+            result.push(')');
+        }
+    }
     result
 }
 
-pub fn print_imports(imports: &[ImportNode]) -> String {
+pub fn print_imports(imports: &[ImportNode], module_format: ModuleFormat) -> String {
     let mut result = String::new();
     for import in imports {
-        let import_statement = print_import(import);
+        let import_statement = print_import(import, module_format);
         if import_statement.is_empty() {
             continue;
         }
@@ -61,7 +106,7 @@ mod test {
         let file = "import foo from \"foo.buri\"";
         let document = parse_buri_file(file).unwrap();
         assert_eq!(
-            print_imports(&document.value.imports),
+            print_imports(&document.value.imports, ModuleFormat::EsModule),
             "import {foo} from \"foo.mjs\"\n"
         );
     }
@@ -71,7 +116,7 @@ mod test {
         let file = "import foo, bar from \"foo.buri\"";
         let document = parse_buri_file(file).unwrap();
         assert_eq!(
-            print_imports(&document.value.imports),
+            print_imports(&document.value.imports, ModuleFormat::EsModule),
             "import {foo,bar} from \"foo.mjs\"\n"
         );
     }
@@ -81,7 +126,7 @@ mod test {
         let file = "import foo, bar, Baz from \"foo.buri\"";
         let document = parse_buri_file(file).unwrap();
         assert_eq!(
-            print_imports(&document.value.imports),
+            print_imports(&document.value.imports, ModuleFormat::EsModule),
             "import {foo,bar} from \"foo.mjs\"\n"
         );
     }
@@ -91,7 +136,7 @@ mod test {
         let file = "import foo from \"foo.buri\"\nimport bar from \"bar.buri\"";
         let document = parse_buri_file(file).unwrap();
         assert_eq!(
-            print_imports(&document.value.imports),
+            print_imports(&document.value.imports, ModuleFormat::EsModule),
             "import {foo} from \"foo.mjs\"\nimport {bar} from \"bar.mjs\"\n"
         );
     }
@@ -100,6 +145,19 @@ mod test {
     fn if_only_types_are_imported_delete_the_import_statement() {
         let file = "import Baz from \"foo.buri\"";
         let document = parse_buri_file(file).unwrap();
-        assert_eq!(print_imports(&document.value.imports), "");
+        assert_eq!(
+            print_imports(&document.value.imports, ModuleFormat::EsModule),
+            ""
+        );
+    }
+
+    #[test]
+    fn prints_commonjs_imports_of_cjs_files() {
+        let file = "import foo, bar from \"foo.buri\"";
+        let document = parse_buri_file(file).unwrap();
+        assert_eq!(
+            print_imports(&document.value.imports, ModuleFormat::CommonJs),
+            "const {foo,bar} = require(\"foo.cjs\")\n"
+        );
     }
 }
@@ -0,0 +1,673 @@
+use crate::{
+    traversal::{for_each_child, map_children},
+    tree_shaking::collect_references,
+};
+use ast::BinaryOperatorSymbol;
+use std::collections::{HashMap, HashSet};
+use typed_ast::{
+    ConcreteBinaryOperatorExpression, ConcreteDocument, ConcreteExpression,
+    ConcreteIdentifierExpression,
+};
+
+/// Functions are only inlined if their body has at most this many nodes, so that
+/// inlining never makes the output much bigger.
+const INLINE_SIZE_LIMIT: usize = 12;
+
+fn count_nodes(expression: &ConcreteExpression) -> usize {
+    let mut count = 1;
+    for_each_child(expression, &mut |child| count += count_nodes(child));
+    count
+}
+
+/// Adds every name which is declared anywhere inside the expression.
+pub fn collect_bound_names(expression: &ConcreteExpression, names: &mut HashSet<String>) {
+    match expression {
+        ConcreteExpression::Function(function) => {
+            names.extend(function.argument_names.iter().cloned());
+        }
+        ConcreteExpression::Declaration(declaration) => {
+            names.insert(declaration.identifier.name.clone());
+        }
+        ConcreteExpression::TupleDeclaration(declaration) => {
+            names.extend(
+                declaration
+                    .identifiers
+                    .iter()
+                    .map(|identifier| identifier.name.clone()),
+            );
+        }
+        ConcreteExpression::When(when) => {
+            for case in &when.cases {
+                names.extend(
+                    case.case_arguments
+                        .iter()
+                        .map(|argument| argument.name.clone()),
+                );
+            }
+        }
+        _ => {}
+    }
+    for_each_child(expression, &mut |child| collect_bound_names(child, names));
+}
+
+fn count_references(expression: &ConcreteExpression, name: &str) -> usize {
+    let mut count = match expression {
+        ConcreteExpression::Identifier(identifier) if identifier.name == name => 1,
+        ConcreteExpression::RecordAssignment(record_assignment)
+            if record_assignment.identifier.name == name =>
+        {
+            1
+        }
+        _ => 0,
+    };
+    for_each_child(expression, &mut |child| {
+        count += count_references(child, name);
+    });
+    count
+}
+
+fn is_referenced_in_function(expression: &ConcreteExpression, name: &str) -> bool {
+    if let ConcreteExpression::Function(function) = expression {
+        return count_references(&function.body, name) > 0;
+    }
+    let mut is_referenced = false;
+    for_each_child(expression, &mut |child| {
+        is_referenced = is_referenced || is_referenced_in_function(child, name);
+    });
+    is_referenced
+}
+
+fn is_reassigned(expression: &ConcreteExpression, name: &str) -> bool {
+    if let ConcreteExpression::Declaration(declaration) = expression {
+        if declaration.is_reassignment && declaration.identifier.name == name {
+            return true;
+        }
+    }
+    let mut is_reassigned_in_child = false;
+    for_each_child(expression, &mut |child| {
+        is_reassigned_in_child = is_reassigned_in_child || is_reassigned(child, name);
+    });
+    is_reassigned_in_child
+}
+
+/// Whether a record is assigned to the name, which needs a variable rather than any
+/// expression.
+fn is_record_assignment_target(expression: &ConcreteExpression, name: &str) -> bool {
+    if let ConcreteExpression::RecordAssignment(record_assignment) = expression {
+        if record_assignment.identifier.name == name {
+            return true;
+        }
+    }
+    let mut is_target_in_child = false;
+    for_each_child(expression, &mut |child| {
+        is_target_in_child = is_target_in_child || is_record_assignment_target(child, name);
+    });
+    is_target_in_child
+}
+
+/// Arguments which are cheap to copy can be used any number of times.
+fn is_trivial(expression: &ConcreteExpression) -> bool {
+    match expression {
+        ConcreteExpression::Identifier(_)
+        | ConcreteExpression::Boolean(_)
+        | ConcreteExpression::Integer(_)
+        | ConcreteExpression::Float(_)
+        | ConcreteExpression::StringLiteral(_) => true,
+        ConcreteExpression::UnaryOperator(unary_operator) => is_trivial(&unary_operator.child),
+        _ => false,
+    }
+}
+
+/// A top-level function which is small enough to be copied into its calls.
+struct InlineFunction {
+    argument_names: Vec<String>,
+    body: ConcreteExpression,
+    /// The names the body refers to from outside of the function. A call can't be
+    /// inlined where a local variable hides one of them.
+    outside_names: HashSet<String>,
+}
+
+/// Whether the declaration with this name refers back to itself, directly or
+/// through other top-level declarations.
+fn is_recursive(document: &ConcreteDocument, name: &str) -> bool {
+    let mut references = HashSet::new();
+    let mut visited = HashSet::new();
+    for declaration in &document.variable_declarations {
+        if declaration.declaration.identifier.name == name {
+            collect_references(&declaration.declaration.value, &mut references);
+        }
+    }
+    while let Some(reference) = references.difference(&visited).next().cloned() {
+        if reference == name {
+            return true;
+        }
+        for declaration in &document.variable_declarations {
+            if declaration.declaration.identifier.name == reference {
+                collect_references(&declaration.declaration.value, &mut references);
+            }
+        }
+        visited.insert(reference);
+    }
+    false
+}
+
+fn find_inline_functions(document: &ConcreteDocument) -> HashMap<String, InlineFunction> {
+    let mut functions = HashMap::new();
+    for declaration in &document.variable_declarations {
+        let name = &declaration.declaration.identifier.name;
+        let ConcreteExpression::Function(function) = &declaration.declaration.value else {
+            continue;
+        };
+        let is_declared_once = document
+            .variable_declarations
+            .iter()
+            .filter(|other| other.declaration.identifier.name == *name)
+            .count()
+            == 1;
+        if declaration.is_exported
+            || !is_declared_once
+            || count_nodes(&function.body) > INLINE_SIZE_LIMIT
+            || function
+                .argument_names
+                .iter()
+                .any(|argument_name| is_reassigned(&function.body, argument_name))
+            || is_recursive(document, name)
+        {
+            continue;
+        }
+        let mut outside_names = HashSet::new();
+        collect_references(&function.body, &mut outside_names);
+        let mut bound_names = function.argument_names.iter().cloned().collect();
+        collect_bound_names(&function.body, &mut bound_names);
+        outside_names.retain(|outside_name| !bound_names.contains(outside_name));
+        functions.insert(
+            name.clone(),
+            InlineFunction {
+                argument_names: function.argument_names.clone(),
+                body: function.body.clone(),
+                outside_names,
+            },
+        );
+    }
+    functions
+}
+
+/// What a name in an inlined body is replaced with.
+#[derive(Clone)]
+enum Replacement {
+    /// A parameter is replaced with the argument it was called with.
+    Argument(ConcreteExpression),
+    /// A local variable is given a fresh name, so it can't hide or be hidden by a
+    /// variable at the call.
+    Rename(String),
+}
+
+struct Inliner<'a> {
+    functions: &'a HashMap<String, InlineFunction>,
+    /// The names declared anywhere in the top-level declaration being inlined into.
+    local_names: HashSet<String>,
+    fresh_name_count: usize,
+}
+
+impl Inliner<'_> {
+    /// Buri identifiers can't contain `$`, so fresh names never collide with a
+    /// user's names once they are mangled.
+    fn make_fresh_name(&mut self, name: &str) -> String {
+        self.fresh_name_count += 1;
+        format!("{name}${}", self.fresh_name_count)
+    }
+
+    fn declare(
+        &mut self,
+        identifier: &mut ConcreteIdentifierExpression,
+        scope: &mut HashMap<String, Replacement>,
+    ) {
+        let fresh_name = self.make_fresh_name(&identifier.name);
+        scope.insert(
+            std::mem::replace(&mut identifier.name, fresh_name.clone()),
+            Replacement::Rename(fresh_name),
+        );
+    }
+
+    fn rename(identifier: &mut ConcreteIdentifierExpression, scope: &HashMap<String, Replacement>) {
+        match scope.get(&identifier.name) {
+            Some(Replacement::Rename(name)) => identifier.name.clone_from(name),
+            Some(Replacement::Argument(ConcreteExpression::Identifier(argument))) => {
+                identifier.name.clone_from(&argument.name);
+            }
+            _ => {}
+        }
+    }
+
+    /// Copies the body of an inlined function, replacing its parameters with the
+    /// arguments and renaming everything it declares.
+    fn substitute(
+        &mut self,
+        expression: ConcreteExpression,
+        scope: &HashMap<String, Replacement>,
+    ) -> ConcreteExpression {
+        match expression {
+            ConcreteExpression::Identifier(mut identifier) => {
+                if let Some(Replacement::Argument(argument)) = scope.get(&identifier.name) {
+                    argument.clone()
+                } else {
+                    Self::rename(&mut identifier, scope);
+                    ConcreteExpression::Identifier(identifier)
+                }
+            }
+            // The right side of a lookup is the name of a field or method.
+            ConcreteExpression::BinaryOperator(mut binary_operator)
+                if matches!(
+                    binary_operator.symbol,
+                    BinaryOperatorSymbol::MethodLookup | BinaryOperatorSymbol::FieldLookup
+                ) =>
+            {
+                binary_operator.left_child = self.substitute(binary_operator.left_child, scope);
+                ConcreteExpression::BinaryOperator(binary_operator)
+            }
+            ConcreteExpression::RecordAssignment(mut record_assignment) => {
+                Self::rename(&mut record_assignment.identifier, scope);
+                map_children(
+                    ConcreteExpression::RecordAssignment(record_assignment),
+                    &mut |child| self.substitute(child, scope),
+                )
+            }
+            ConcreteExpression::Function(mut function) => {
+                let mut function_scope = scope.clone();
+                for argument_name in &mut function.argument_names {
+                    let fresh_name = self.make_fresh_name(argument_name);
+                    function_scope.insert(
+                        std::mem::replace(argument_name, fresh_name.clone()),
+                        Replacement::Rename(fresh_name),
+                    );
+                }
+                function.body = self.substitute(function.body, &function_scope);
+                ConcreteExpression::Function(function)
+            }
+            // Declarations are in scope for the rest of their block, and functions
+            // can refer to themselves.
+            ConcreteExpression::Block(mut block) => {
+                let mut block_scope = scope.clone();
+                block.contents = block
+                    .contents
+                    .into_iter()
+                    .map(|expression| match expression {
+                        ConcreteExpression::Declaration(mut declaration) => {
+                            if declaration.is_reassignment {
+                                Self::rename(&mut declaration.identifier, &block_scope);
+                            } else {
+                                self.declare(&mut declaration.identifier, &mut block_scope);
+                            }
+                            declaration.value = self.substitute(declaration.value, &block_scope);
+                            ConcreteExpression::Declaration(declaration)
+                        }
+                        ConcreteExpression::TupleDeclaration(mut declaration) => {
+                            declaration.value = self.substitute(declaration.value, &block_scope);
+                            for identifier in &mut declaration.identifiers {
+                                self.declare(identifier, &mut block_scope);
+                            }
+                            ConcreteExpression::TupleDeclaration(declaration)
+                        }
+                        expression => self.substitute(expression, &block_scope),
+                    })
+                    .collect();
+                ConcreteExpression::Block(block)
+            }
+            ConcreteExpression::When(mut when) => {
+                when.condition = self.substitute(when.condition, scope);
+                for case in &mut when.cases {
+                    let mut case_scope = scope.clone();
+                    for argument in &mut case.case_arguments {
+                        self.declare(argument, &mut case_scope);
+                    }
+                    let case_expression = std::mem::replace(
+                        &mut case.case_expression,
+                        ConcreteExpression::FunctionArguments(vec![]),
+                    );
+                    case.case_expression = self.substitute(case_expression, &case_scope);
+                }
+                ConcreteExpression::When(when)
+            }
+            expression => map_children(expression, &mut |child| self.substitute(child, scope)),
+        }
+    }
+
+    /// Returns the inlined body of the call, or the call itself if it can't be inlined.
+    fn inline_call(
+        &mut self,
+        call: ConcreteBinaryOperatorExpression,
+    ) -> Result<ConcreteExpression, ConcreteBinaryOperatorExpression> {
+        let (
+            BinaryOperatorSymbol::FunctionApplication,
+            ConcreteExpression::Identifier(callee),
+            ConcreteExpression::FunctionArguments(arguments),
+        ) = (&call.symbol, &call.left_child, &call.right_child)
+        else {
+            return Err(call);
+        };
+        let Some(function) = self.functions.get(&callee.name) else {
+            return Err(call);
+        };
+        let can_inline = !self.local_names.contains(&callee.name)
+            && function.outside_names.is_disjoint(&self.local_names)
+            && arguments.len() == function.argument_names.len()
+            && function
+                .argument_names
+                .iter()
+                .zip(arguments)
+                .all(|(name, argument)| {
+                    if is_record_assignment_target(&function.body, name) {
+                        return matches!(argument, ConcreteExpression::Identifier(_));
+                    }
+                    is_trivial(argument)
+                        || (count_references(&function.body, name) <= 1
+                            && !is_referenced_in_function(&function.body, name))
+                });
+        if !can_inline {
+            return Err(call);
+        }
+        let ConcreteExpression::FunctionArguments(arguments) = call.right_child else {
+            return Err(call);
+        };
+        let scope = function
+            .argument_names
+            .iter()
+            .cloned()
+            .zip(arguments.into_iter().map(Replacement::Argument))
+            .collect::<HashMap<_, _>>();
+        Ok(self.substitute(function.body.clone(), &scope))
+    }
+
+    fn inline(&mut self, expression: ConcreteExpression) -> ConcreteExpression {
+        match map_children(expression, &mut |child| self.inline(child)) {
+            ConcreteExpression::BinaryOperator(call) => match self.inline_call(*call) {
+                // The body may call other functions which can be inlined.
+                Ok(inlined) => self.inline(inlined),
+                Err(call) => ConcreteExpression::BinaryOperator(Box::new(call)),
+            },
+            expression => expression,
+        }
+    }
+}
+
+/// Replaces calls of small, non-recursive functions which aren't exported with the
+/// bodies of the functions. The functions are removed later on if nothing else uses
+/// them.
+pub fn inline_functions(document: &mut ConcreteDocument) {
+    let functions = find_inline_functions(document);
+    if functions.is_empty() {
+        return;
+    }
+    let mut fresh_name_count = 0;
+    for declaration in &mut document.variable_declarations {
+        let mut local_names = HashSet::new();
+        collect_bound_names(&declaration.declaration.value, &mut local_names);
+        let mut inliner = Inliner {
+            functions: &functions,
+            local_names,
+            fresh_name_count,
+        };
+        let value = std::mem::replace(
+            &mut declaration.declaration.value,
+            ConcreteExpression::FunctionArguments(vec![]),
+        );
+        declaration.declaration.value = inliner.inline(value);
+        fresh_name_count = inliner.fresh_name_count;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ast::TopLevelDeclaration;
+    use typed_ast::{
+        ConcreteDeclarationExpression, ConcreteFunctionExpression, ConcreteType, SourceLocation,
+        TypedDocument,
+    };
+
+    fn function_for_test(argument_names: &[&str], body: ConcreteExpression) -> ConcreteExpression {
+        ConcreteExpression::Function(Box::new(ConcreteFunctionExpression {
+            expression_type: ConcreteType::default_function_for_test(),
+            source: SourceLocation::default(),
+            argument_names: argument_names.iter().map(ToString::to_string).collect(),
+            body,
+        }))
+    }
+
+    fn call_for_test(name: &str, arguments: Vec<ConcreteExpression>) -> ConcreteExpression {
+        binary_operator_for_test(
+            BinaryOperatorSymbol::FunctionApplication,
+            ConcreteExpression::identifier_for_test(name),
+            ConcreteExpression::FunctionArguments(arguments),
+        )
+    }
+
+    fn binary_operator_for_test(
+        symbol: BinaryOperatorSymbol,
+        left_child: ConcreteExpression,
+        right_child: ConcreteExpression,
+    ) -> ConcreteExpression {
+        ConcreteExpression::BinaryOperator(Box::new(ConcreteBinaryOperatorExpression {
+            expression_type: ConcreteType::default_binary_operator_for_test(),
+            source: SourceLocation::default(),
+            symbol,
+            left_child,
+            right_child,
+        }))
+    }
+
+    fn add_for_test(
+        left_child: ConcreteExpression,
+        right_child: ConcreteExpression,
+    ) -> ConcreteExpression {
+        binary_operator_for_test(BinaryOperatorSymbol::Add, left_child, right_child)
+    }
+
+    fn local_declaration_for_test(
+        name: &str,
+        value: ConcreteExpression,
+    ) -> ConcreteDeclarationExpression {
+        ConcreteDeclarationExpression {
+            declaration_type: ConcreteType::default_for_test(),
+            expression_type: ConcreteType::default_for_test(),
+            identifier: ConcreteExpression::raw_identifier_for_test(name),
+            value,
+            is_reassignment: false,
+            source: SourceLocation::default(),
+        }
+    }
+
+    fn declaration_for_test(
+        name: &str,
+        value: ConcreteExpression,
+        is_exported: bool,
+    ) -> TopLevelDeclaration<ConcreteDeclarationExpression> {
+        TopLevelDeclaration {
+            declaration: local_declaration_for_test(name, value),
+            is_exported,
+        }
+    }
+
+    fn document_for_test(
+        declarations: Vec<TopLevelDeclaration<ConcreteDeclarationExpression>>,
+    ) -> ConcreteDocument<'static> {
+        TypedDocument {
+            imports: vec![],
+            type_declarations: vec![],
+            variable_declarations: declarations,
+            expressions: vec![],
+        }
+    }
+
+    fn get_value<'a>(document: &'a ConcreteDocument, name: &str) -> &'a ConcreteExpression {
+        &document
+            .variable_declarations
+            .iter()
+            .find(|declaration| declaration.declaration.identifier.name == name)
+            .unwrap()
+            .declaration
+            .value
+    }
+
+    fn increment_for_test() -> TopLevelDeclaration<ConcreteDeclarationExpression> {
+        declaration_for_test(
+            "increment",
+            function_for_test(
+                &["x"],
+                add_for_test(
+                    ConcreteExpression::identifier_for_test("x"),
+                    ConcreteExpression::integer_for_test(1),
+                ),
+            ),
+            false,
+        )
+    }
+
+    #[test]
+    fn small_functions_are_inlined() {
+        let mut document = document_for_test(vec![
+            increment_for_test(),
+            declaration_for_test(
+                "two",
+                call_for_test("increment", vec![ConcreteExpression::integer_for_test(1)]),
+                true,
+            ),
+        ]);
+        inline_functions(&mut document);
+        assert_eq!(
+            get_value(&document, "two"),
+            &add_for_test(
+                ConcreteExpression::integer_for_test(1),
+                ConcreteExpression::integer_for_test(1)
+            )
+        );
+    }
+
+    #[test]
+    fn exported_functions_are_not_inlined() {
+        let mut increment = increment_for_test();
+        increment.is_exported = true;
+        let call = call_for_test("increment", vec![ConcreteExpression::integer_for_test(1)]);
+        let mut document = document_for_test(vec![
+            increment,
+            declaration_for_test("two", call.clone(), true),
+        ]);
+        inline_functions(&mut document);
+        assert_eq!(get_value(&document, "two"), &call);
+    }
+
+    #[test]
+    fn recursive_functions_are_not_inlined() {
+        let call = call_for_test("countdown", vec![ConcreteExpression::integer_for_test(3)]);
+        let mut document = document_for_test(vec![
+            declaration_for_test(
+                "countdown",
+                function_for_test(
+                    &["n"],
+                    call_for_test(
+                        "countdown",
+                        vec![ConcreteExpression::identifier_for_test("n")],
+                    ),
+                ),
+                false,
+            ),
+            declaration_for_test("done", call.clone(), true),
+        ]);
+        inline_functions(&mut document);
+        assert_eq!(get_value(&document, "done"), &call);
+    }
+
+    #[test]
+    fn arguments_with_side_effects_used_twice_are_not_inlined() {
+        let call = call_for_test("double", vec![call_for_test("read", vec![])]);
+        let mut document = document_for_test(vec![
+            declaration_for_test(
+                "double",
+                function_for_test(
+                    &["x"],
+                    add_for_test(
+                        ConcreteExpression::identifier_for_test("x"),
+                        ConcreteExpression::identifier_for_test("x"),
+                    ),
+                ),
+                false,
+            ),
+            declaration_for_test("result", call.clone(), true),
+        ]);
+        inline_functions(&mut document);
+        assert_eq!(get_value(&document, "result"), &call);
+    }
+
+    #[test]
+    fn inlined_variables_are_renamed() {
+        let mut document = document_for_test(vec![
+            declaration_for_test(
+                "twice",
+                function_for_test(
+                    &["x"],
+                    ConcreteExpression::block_for_test(vec![
+                        ConcreteExpression::Declaration(Box::new(local_declaration_for_test(
+                            "y",
+                            ConcreteExpression::identifier_for_test("x"),
+                        ))),
+                        add_for_test(
+                            ConcreteExpression::identifier_for_test("y"),
+                            ConcreteExpression::identifier_for_test("y"),
+                        ),
+                    ]),
+                ),
+                false,
+            ),
+            declaration_for_test(
+                "result",
+                function_for_test(
+                    &["y"],
+                    call_for_test("twice", vec![ConcreteExpression::identifier_for_test("y")]),
+                ),
+                true,
+            ),
+        ]);
+        inline_functions(&mut document);
+        assert_eq!(
+            get_value(&document, "result"),
+            &function_for_test(
+                &["y"],
+                ConcreteExpression::block_for_test(vec![
+                    ConcreteExpression::Declaration(Box::new(local_declaration_for_test(
+                        "y$1",
+                        ConcreteExpression::identifier_for_test("y"),
+                    ))),
+                    add_for_test(
+                        ConcreteExpression::identifier_for_test("y$1"),
+                        ConcreteExpression::identifier_for_test("y$1"),
+                    ),
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn calls_are_not_inlined_where_a_local_variable_hides_a_name() {
+        let call = call_for_test("withOne", vec![ConcreteExpression::integer_for_test(2)]);
+        let mut document = document_for_test(vec![
+            declaration_for_test("one", ConcreteExpression::integer_for_test(1), false),
+            declaration_for_test(
+                "withOne",
+                function_for_test(
+                    &["x"],
+                    add_for_test(
+                        ConcreteExpression::identifier_for_test("x"),
+                        ConcreteExpression::identifier_for_test("one"),
+                    ),
+                ),
+                false,
+            ),
+            declaration_for_test("result", function_for_test(&["one"], call.clone()), true),
+        ]);
+        inline_functions(&mut document);
+        assert_eq!(
+            get_value(&document, "result"),
+            &function_for_test(&["one"], call)
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::traversal::for_each_child;
+use typed_ast::{ConcreteDocument, ConcreteExpression};
+
+/// The largest integer which JS numbers represent exactly, `Number.MAX_SAFE_INTEGER`.
+pub const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// How `Int`s are represented in the printed JS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntegerRepresentation {
+    /// `Int`s are JS numbers, which are fast but only exact up to 2^53 - 1.
+    #[default]
+    Number,
+    /// `Int`s are `BigInt`s, so arithmetic on them is always exact.
+    BigInt,
+}
+
+fn check_expression_literals(expression: &ConcreteExpression) -> Result<(), String> {
+    if let ConcreteExpression::Integer(integer) = expression {
+        if integer.value > MAX_SAFE_INTEGER {
+            return Err(format!(
+                "Integer literal {} is too large to be represented exactly by a JS number. Use a smaller value, or compile with --bigint.",
+                integer.value
+            ));
+        }
+    }
+    let mut result = Ok(());
+    for_each_child(expression, &mut |child| {
+        if result.is_ok() {
+            result = check_expression_literals(child);
+        }
+    });
+    result
+}
+
+/// JS numbers would silently round literals above 2^53 - 1, so they are rejected
+/// unless `Int`s are `BigInt`s.
+pub fn check_integer_literals(document: &ConcreteDocument) -> Result<(), String> {
+    for declaration in &document.variable_declarations {
+        check_expression_literals(&declaration.declaration.value)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn literals_above_the_largest_safe_integer_are_rejected() {
+        assert!(
+            check_expression_literals(&ConcreteExpression::integer_for_test(MAX_SAFE_INTEGER))
+                .is_ok()
+        );
+        let block = ConcreteExpression::block_for_test(vec![ConcreteExpression::integer_for_test(
+            MAX_SAFE_INTEGER + 2,
+        )]);
+        assert_eq!(
+            check_expression_literals(&block),
+            Err(String::from("Integer literal 9007199254740993 is too large to be represented exactly by a JS number. Use a smaller value, or compile with --bigint."))
+        );
+    }
+}
@@ -0,0 +1,159 @@
+// Printers emit layout markers where pretty output would break a line or add a
+// space. Doc comments are printed on one line in compact output and as a block in
+// pretty output.
+use crate::markers::{DEDENT, INDENT, NEWLINE, SPACE};
+
+const INDENTATION: &str = "    ";
+
+/// How the printed JS is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStyle {
+    /// Everything that isn't needed by JS is left out.
+    Compact,
+    /// Statements and fields are printed on their own lines and indented.
+    Pretty,
+}
+
+impl Default for OutputStyle {
+    /// Debug builds of the compiler print readable output, since they are used to
+    /// work on the compiler.
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Pretty
+        } else {
+            Self::Compact
+        }
+    }
+}
+
+/// Wraps the contents in braces, and indents them when pretty printing.
+pub fn print_braced(contents: &str) -> String {
+    format!("{{{INDENT}{contents}{DEDENT}}}")
+}
+
+/// Prints a doc comment, such as `JSDoc`, with one tag on each line when pretty
+/// printing. The printer follows it with the layout marker that separates it from
+/// the code.
+pub fn print_doc_comment(lines: &[String], output_style: OutputStyle) -> String {
+    if output_style == OutputStyle::Compact || lines.len() == 1 {
+        return format!("/** {} */", lines.join(" "));
+    }
+    let mut result = String::from("/**");
+    for line in lines {
+        result.push(NEWLINE);
+        result.push_str(" * ");
+        result.push_str(line);
+    }
+    result.push(NEWLINE);
+    result.push_str(" */");
+    result
+}
+
+fn push_line_break(result: &mut String, depth: usize) {
+    result.push('\n');
+    for _ in 0..depth {
+        result.push_str(INDENTATION);
+    }
+}
+
+fn render_pretty(marked: &str) -> String {
+    let mut result = String::with_capacity(marked.len());
+    let mut depth: usize = 0;
+    let mut characters = marked.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            // Empty braces stay on one line.
+            INDENT if characters.peek() == Some(&DEDENT) => {
+                characters.next();
+            }
+            INDENT => {
+                depth += 1;
+                push_line_break(&mut result, depth);
+            }
+            DEDENT => {
+                depth = depth.saturating_sub(1);
+                push_line_break(&mut result, depth);
+            }
+            NEWLINE => push_line_break(&mut result, depth),
+            SPACE => result.push(' '),
+            _ => result.push(character),
+        }
+    }
+    result
+}
+
+fn render_compact(marked: &str) -> String {
+    marked
+        .chars()
+        .filter(|character| !matches!(*character, INDENT | DEDENT | NEWLINE | SPACE))
+        .collect()
+}
+
+/// Renders or removes the layout markers once the whole document is printed, so
+/// each printer only has to be written once.
+pub fn render_layout(marked: &str, output_style: OutputStyle) -> String {
+    match output_style {
+        OutputStyle::Compact => render_compact(marked),
+        OutputStyle::Pretty => render_pretty(marked),
+    }
+}
+
+#[cfg(test)]
+pub fn compact_for_test(marked: &str) -> String {
+    render_layout(marked, OutputStyle::Compact)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn function_for_test() -> String {
+        format!(
+            "(){SPACE}=>{SPACE}{}",
+            print_braced(&format!(
+                "let Bx{SPACE}={SPACE}1;{NEWLINE}return {};",
+                print_braced("...Bx")
+            ))
+        )
+    }
+
+    #[test]
+    fn compact_output_has_no_layout() {
+        assert_eq!(
+            render_layout(&function_for_test(), OutputStyle::Compact),
+            "()=>{let Bx=1;return {...Bx};}"
+        );
+    }
+
+    #[test]
+    fn pretty_output_indents_nested_braces() {
+        assert_eq!(
+            render_layout(&function_for_test(), OutputStyle::Pretty),
+            "() => {\n    let Bx = 1;\n    return {\n        ...Bx\n    };\n}"
+        );
+    }
+
+    #[test]
+    fn doc_comments_are_blocks_in_pretty_output() {
+        let lines = ["@type {number}".to_string(), "@deprecated".to_string()];
+        let marked = |output_style| {
+            print_braced(&format!(
+                "{}{NEWLINE}let Bx{SPACE}={SPACE}1;",
+                print_doc_comment(&lines, output_style)
+            ))
+        };
+        assert_eq!(
+            render_layout(&marked(OutputStyle::Pretty), OutputStyle::Pretty),
+            "{\n    /**\n     * @type {number}\n     * @deprecated\n     */\n    let Bx = 1;\n}"
+        );
+        assert_eq!(
+            render_layout(&marked(OutputStyle::Compact), OutputStyle::Compact),
+            "{/** @type {number} @deprecated */let Bx=1;}"
+        );
+    }
+
+    #[test]
+    fn empty_braces_stay_on_one_line() {
+        assert_eq!(render_layout(&print_braced(""), OutputStyle::Pretty), "{}");
+    }
+}
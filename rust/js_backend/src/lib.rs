@@ -1,22 +1,270 @@
-use expression::{mangle_variable_name, print_declaration};
+use expression::{mangle_variable_name, print_declaration, print_declaration_annotation};
+use helpers::collect_helpers;
 use imports::print_imports;
+use layout::render_layout;
+use markers::{NEWLINE, SPACE};
+use prelude::print_prelude;
+use source_map::{build_source_map, print_source_location, remove_source_locations};
 use typed_ast::{ConcreteType, TypedDocument};
 
+mod constant_folding;
+mod equality;
 mod expression;
+mod helpers;
 mod identifier;
 mod imports;
+mod inlining;
+mod integers;
+mod layout;
 mod literals;
+mod markers;
+mod prelude;
+mod source_map;
+mod specialization;
+mod tags;
+mod traversal;
+mod tree_shaking;
+mod typescript;
 
-#[must_use]
-pub fn print_js_document(document: &TypedDocument<ConcreteType>) -> String {
-    let mut result = String::from("import '@packages/std/prelude/index.js'\n");
-    result.push_str(&print_imports(&document.imports));
-    for declaration in &document.variable_declarations {
+pub use constant_folding::fold_constants;
+pub use imports::ModuleFormat;
+pub use inlining::inline_functions;
+pub use integers::{check_integer_literals, IntegerRepresentation};
+pub use layout::OutputStyle;
+pub use prelude::{Prelude, DEFAULT_PRELUDE_SPECIFIER};
+pub use source_map::SourceMap;
+pub use specialization::specialize_generic_functions;
+pub use tags::{check_tag_discriminants, TagRepresentation};
+pub use tree_shaking::remove_unused_declarations;
+pub use typescript::print_typescript_declarations;
+
+/// Options which change how the JS is printed without changing what it does.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PrinterConfiguration {
+    pub output_style: OutputStyle,
+    /// Annotates declarations and functions with their types as `JSDoc` comments.
+    pub jsdoc: bool,
+    pub module_format: ModuleFormat,
+    pub prelude: Prelude,
+    pub tag_representation: TagRepresentation,
+    pub integer_representation: IntegerRepresentation,
+}
+
+/// `CommonJS` modules export all of their values at once, after they are declared.
+fn print_commonjs_exports(document: &TypedDocument<ConcreteType>) -> String {
+    let exported_names = document
+        .variable_declarations
+        .iter()
+        .filter(|declaration| declaration.is_exported)
+        .map(|declaration| mangle_variable_name(&declaration.declaration.identifier.name))
+        .collect::<Vec<_>>();
+    if exported_names.is_empty() {
+        return String::new();
+    }
+    format!(
+        "\n{NEWLINE}module.exports{SPACE}={SPACE}{{{}}}",
+        exported_names.join(&format!(",{SPACE}"))
+    )
+}
+
+/// Prints the document with its layout rendered, keeping the markers for the
+/// source locations of its nodes.
+fn print_marked_js_document(
+    document: &TypedDocument<ConcreteType>,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let declarations = document
+        .variable_declarations
+        .iter()
+        .map(|declaration| {
+            let mut result = print_declaration_annotation(&declaration.declaration, configuration);
+            result.push_str(&print_source_location(declaration.declaration.source));
+            if declaration.is_exported && configuration.module_format == ModuleFormat::EsModule {
+                result.push_str("export ");
+            }
+            result.push_str(&print_declaration(&declaration.declaration, configuration));
+            result
+        })
+        .collect::<Vec<_>>();
+    // Pretty output separates declarations with a blank line.
+    let mut body = declarations.join(&format!("\n{NEWLINE}"));
+    if configuration.module_format == ModuleFormat::CommonJs {
+        body.push_str(&print_commonjs_exports(document));
+    }
+    let mut result = print_prelude(
+        &configuration.prelude,
+        configuration.module_format,
+        &collect_helpers(document, configuration.tag_representation),
+    );
+    result.push_str(&print_imports(
+        &document.imports,
+        configuration.module_format,
+    ));
+    // The prelude and imports end with a line break, and are followed by one blank line.
+    if !result.is_empty() && !body.is_empty() {
+        result.push('\n');
+    }
+    result.push_str(&body);
+    result = render_layout(&result, configuration.output_style);
+    if configuration.output_style == OutputStyle::Pretty && !result.ends_with('\n') {
         result.push('\n');
-        if declaration.is_exported {
-            result.push_str("export ");
-        }
-        result.push_str(&print_declaration(&declaration.declaration));
     }
     result
 }
+
+#[must_use]
+pub fn print_js_document(
+    document: &TypedDocument<ConcreteType>,
+    configuration: &PrinterConfiguration,
+) -> String {
+    remove_source_locations(&print_marked_js_document(document, configuration))
+}
+
+/// Prints the document along with a source map which links the JS back to the
+/// Buri source. `source` is the path of the Buri file relative to the source map,
+/// and `file` is the name of the JS file.
+#[must_use]
+pub fn print_js_document_with_source_map(
+    document: &TypedDocument<ConcreteType>,
+    configuration: &PrinterConfiguration,
+    source: &str,
+    file: &str,
+) -> (String, SourceMap) {
+    build_source_map(
+        &print_marked_js_document(document, configuration),
+        source,
+        file,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::markers::MARKERS;
+    use ast::TopLevelDeclaration;
+    use indexmap::IndexMap;
+    use typed_ast::{
+        ConcreteDeclarationExpression, ConcreteExpression, ConcreteRecordExpression,
+        ConcreteSetExpression, ConcreteTagExpression, SourceLocation,
+    };
+
+    fn declaration_for_test(
+        name: &str,
+        value: ConcreteExpression,
+    ) -> TopLevelDeclaration<ConcreteDeclarationExpression> {
+        TopLevelDeclaration {
+            declaration: ConcreteDeclarationExpression {
+                declaration_type: ConcreteType::default_record_for_test(),
+                expression_type: ConcreteType::default_record_for_test(),
+                identifier: ConcreteExpression::raw_identifier_for_test(name),
+                value,
+                is_reassignment: false,
+                source: SourceLocation { line: 1, column: 1 },
+            },
+            is_exported: true,
+        }
+    }
+
+    /// A document whose string literals contain every control character, including
+    /// the ones the printers use as markers.
+    fn document_for_test() -> TypedDocument<'static, ConcreteType> {
+        let control_characters = ('\0'..='\x1f').chain(['\x7f']).collect::<String>();
+        let record = ConcreteExpression::Record(Box::new(ConcreteRecordExpression {
+            expression_type: ConcreteType::default_record_for_test(),
+            contents: IndexMap::from([
+                (
+                    "text".to_string(),
+                    ConcreteExpression::string_for_test(&control_characters),
+                ),
+                ("count".to_string(), ConcreteExpression::integer_for_test(1)),
+                (
+                    "tag".to_string(),
+                    ConcreteExpression::Tag(Box::new(ConcreteTagExpression {
+                        expression_type: ConcreteType::default_tag_union_for_test(),
+                        name: "some".to_string(),
+                        contents: vec![ConcreteExpression::string_for_test("\x07\x08")],
+                    })),
+                ),
+            ]),
+        }));
+        TypedDocument {
+            imports: vec![],
+            type_declarations: vec![],
+            variable_declarations: vec![
+                declaration_for_test(
+                    "text",
+                    ConcreteExpression::string_for_test(&control_characters),
+                ),
+                declaration_for_test("record", record),
+            ],
+            expressions: vec![],
+        }
+    }
+
+    #[test]
+    fn printed_documents_have_no_markers_left() {
+        let document = document_for_test();
+        for output_style in [OutputStyle::Compact, OutputStyle::Pretty] {
+            for jsdoc in [false, true] {
+                for module_format in [ModuleFormat::EsModule, ModuleFormat::CommonJs] {
+                    for tag_representation in
+                        [TagRepresentation::Names, TagRepresentation::Integers]
+                    {
+                        for integer_representation in
+                            [IntegerRepresentation::Number, IntegerRepresentation::BigInt]
+                        {
+                            let configuration = PrinterConfiguration {
+                                output_style,
+                                jsdoc,
+                                module_format,
+                                prelude: Prelude::Inline,
+                                tag_representation,
+                                integer_representation,
+                            };
+                            let javascript = print_js_document(&document, &configuration);
+                            let (mapped_javascript, _) = print_js_document_with_source_map(
+                                &document,
+                                &configuration,
+                                "a.buri",
+                                "a.mjs",
+                            );
+                            assert_eq!(javascript, mapped_javascript);
+                            assert!(
+                                !javascript.contains(MARKERS),
+                                "{configuration:?}: {javascript:?}"
+                            );
+                            assert!(javascript.contains(r#""\x00\x01\x02\x03\x04\x05\x06\x07\b"#));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    #[test]
+    fn pretty_output_has_one_blank_line_after_the_prelude_and_ends_with_a_line_break() {
+        let document = TypedDocument {
+            imports: vec![],
+            type_declarations: vec![],
+            variable_declarations: vec![
+                declaration_for_test("a", ConcreteExpression::integer_for_test(1)),
+                declaration_for_test(
+                    "b",
+                    ConcreteExpression::Set(Box::new(ConcreteSetExpression {
+                        expression_type: ConcreteType::default_set_for_test(),
+                        contents: vec![],
+                    })),
+                ),
+            ],
+            expressions: vec![],
+        };
+        let configuration = PrinterConfiguration {
+            output_style: OutputStyle::Pretty,
+            prelude: Prelude::Import("buri-prelude".to_string()),
+            ..PrinterConfiguration::default()
+        };
+        assert_eq!(
+            print_js_document(&document, &configuration),
+            "import {$Set} from 'buri-prelude'\n\nexport const Ba = 1\n\nexport const Bb = new $Set([])\n"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use typed_ast::ConcreteFloatLiteralExpression;
+
+pub fn print_float_literal(node: &ConcreteFloatLiteralExpression) -> String {
+    // JavaScript does not allow leading zeros in number literals.
+    let trimmed = node.value.trim_start_matches('0');
+    if trimmed.starts_with(|c: char| c.is_ascii_digit()) {
+        trimmed.to_string()
+    } else {
+        format!("0{trimmed}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use typed_ast::ConcreteType;
+
+    #[test]
+    fn test_print_float_literal() {
+        let node = ConcreteFloatLiteralExpression {
+            expression_type: ConcreteType::default_float_for_test(),
+            value: "1.5e-3".to_string(),
+        };
+        assert_eq!(print_float_literal(&node), "1.5e-3");
+    }
+
+    #[test]
+    fn leading_zeros_are_removed() {
+        let node = ConcreteFloatLiteralExpression {
+            expression_type: ConcreteType::default_float_for_test(),
+            value: "007.5".to_string(),
+        };
+        assert_eq!(print_float_literal(&node), "7.5");
+    }
+
+    #[test]
+    fn a_single_zero_is_kept_before_the_decimal_point() {
+        let node = ConcreteFloatLiteralExpression {
+            expression_type: ConcreteType::default_float_for_test(),
+            value: "00.5".to_string(),
+        };
+        assert_eq!(print_float_literal(&node), "0.5");
+    }
+}
@@ -1,15 +1,21 @@
+use crate::{integers::IntegerRepresentation, PrinterConfiguration};
 use typed_ast::ConcreteIntegerLiteralExpression;
 
-pub fn print_integer_literal(node: &ConcreteIntegerLiteralExpression) -> String {
-    let mut result = String::new();
-    result.push_str(&node.value.to_string());
-    result
+pub fn print_integer_literal(
+    node: &ConcreteIntegerLiteralExpression,
+    configuration: &PrinterConfiguration,
+) -> String {
+    match configuration.integer_representation {
+        IntegerRepresentation::Number => node.value.to_string(),
+        IntegerRepresentation::BigInt => format!("{}n", node.value),
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    use crate::layout::compact_for_test;
     use typed_ast::{ConcreteType, PrimitiveType};
 
     #[test]
@@ -18,7 +24,13 @@ mod test {
             expression_type: ConcreteType::Primitive(PrimitiveType::Int),
             value: 1,
         };
-        assert_eq!(print_integer_literal(&node), "1");
+        assert_eq!(
+            compact_for_test(&print_integer_literal(
+                &node,
+                &PrinterConfiguration::default()
+            )),
+            "1"
+        );
     }
 
     #[test]
@@ -27,6 +39,28 @@ mod test {
             expression_type: ConcreteType::Primitive(PrimitiveType::Int),
             value: 2,
         };
-        assert_eq!(print_integer_literal(&node), "2");
+        assert_eq!(
+            compact_for_test(&print_integer_literal(
+                &node,
+                &PrinterConfiguration::default()
+            )),
+            "2"
+        );
+    }
+
+    #[test]
+    fn bigint_literals_have_a_suffix() {
+        let node = ConcreteIntegerLiteralExpression {
+            expression_type: ConcreteType::Primitive(PrimitiveType::Int),
+            value: 9_007_199_254_740_993,
+        };
+        let configuration = PrinterConfiguration {
+            integer_representation: IntegerRepresentation::BigInt,
+            ..PrinterConfiguration::default()
+        };
+        assert_eq!(
+            print_integer_literal(&node, &configuration),
+            "9007199254740993n"
+        );
     }
 }
@@ -1,5 +1,7 @@
+mod float;
 mod integer;
 mod string;
 
+pub use float::print_float_literal;
 pub use integer::print_integer_literal;
 pub use string::print_string_literal;
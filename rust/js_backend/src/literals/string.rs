@@ -36,6 +36,7 @@ pub fn print_string_literal(node: &ConcreteStringLiteralExpression) -> String {
             '\\' => {
                 result.push_str("\\\\");
             }
+            // This also keeps the printers' markers out of string literals.
             '\0'..='\x1F' | '\x7F' => {
                 let unicode_codepoint = character as usize;
                 result.push_str("\\x");
@@ -0,0 +1,22 @@
+// Printers mark up the JS they print with these control characters where the
+// layout or the source map needs to know about it. Everything else that depends
+// on the configuration is decided while printing. Once the whole document is
+// printed, the layout markers are rendered or removed, and the source locations
+// are collected into the source map. String literals escape every control
+// character and identifiers can't contain them, so a marker never comes from
+// the Buri source.
+
+/// Wraps the line and column in the Buri source where a node starts, e.g. `2:5`.
+pub const SOURCE_LOCATION: char = '\0';
+/// Starts a new line which is indented one level deeper.
+pub const INDENT: char = '\x01';
+/// Starts a new line which is indented one level shallower.
+pub const DEDENT: char = '\x02';
+/// Starts a new line at the current indentation.
+pub const NEWLINE: char = '\x03';
+/// A space which is only needed for readability.
+pub const SPACE: char = '\x04';
+
+/// Every marker, none of which may be left in the final output.
+#[cfg(test)]
+pub const MARKERS: [char; 5] = [SOURCE_LOCATION, INDENT, DEDENT, NEWLINE, SPACE];
@@ -0,0 +1,234 @@
+use crate::{helpers::Helper, imports::ModuleFormat};
+use std::collections::BTreeSet;
+
+/// Where the prelude is found when the compiled JS is bundled inside this repository.
+pub const DEFAULT_PRELUDE_SPECIFIER: &str = "@packages/std/prelude/index.js";
+
+/// How the compiled JS gets the prelude, which it needs at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prelude {
+    /// The prelude is imported from this module specifier.
+    Import(String),
+    /// The parts of the prelude which the output uses are copied into it, so it
+    /// doesn't depend on any other module. Each file gets its own copy of the
+    /// prelude's classes, so this is only meant for programs with a single file.
+    Inline,
+}
+
+impl Default for Prelude {
+    fn default() -> Self {
+        Self::Import(DEFAULT_PRELUDE_SPECIFIER.to_string())
+    }
+}
+
+/// A file of the prelude, which `Helper::module` refers to by name.
+struct PreludeModule {
+    name: &'static str,
+    source: &'static str,
+    /// Other modules which this one imports.
+    dependencies: &'static [&'static str],
+}
+
+// The modules are listed in the order they are inlined, so dependencies go first.
+// Structural keys and the map and set classes depend on each other, but they only
+// use each other inside functions, so they can be inlined in either order.
+const PRELUDE_MODULES: &[PreludeModule] = &[
+    PreludeModule {
+        name: "structural-key",
+        source: include_str!("../../../packages/std/prelude/structural-key.js"),
+        dependencies: &["map", "set"],
+    },
+    PreludeModule {
+        name: "equality",
+        source: include_str!("../../../packages/std/prelude/equality.js"),
+        dependencies: &["structural-key"],
+    },
+    PreludeModule {
+        name: "float",
+        source: include_str!("../../../packages/std/prelude/float.js"),
+        dependencies: &[],
+    },
+    PreludeModule {
+        name: "integer",
+        source: include_str!("../../../packages/std/prelude/integer.js"),
+        dependencies: &[],
+    },
+    PreludeModule {
+        name: "list",
+        source: include_str!("../../../packages/std/prelude/list.js"),
+        dependencies: &[],
+    },
+    PreludeModule {
+        name: "map",
+        source: include_str!("../../../packages/std/prelude/map.js"),
+        dependencies: &["structural-key"],
+    },
+    PreludeModule {
+        name: "operators",
+        source: include_str!("../../../packages/std/prelude/operators.js"),
+        dependencies: &[],
+    },
+    PreludeModule {
+        name: "record",
+        source: include_str!("../../../packages/std/prelude/record.js"),
+        dependencies: &[],
+    },
+    PreludeModule {
+        name: "set",
+        source: include_str!("../../../packages/std/prelude/set.js"),
+        dependencies: &["structural-key"],
+    },
+    PreludeModule {
+        name: "string",
+        source: include_str!("../../../packages/std/prelude/string.js"),
+        dependencies: &[],
+    },
+    PreludeModule {
+        name: "methods",
+        source: include_str!("../../../packages/std/prelude/methods.js"),
+        dependencies: &["float", "integer", "list", "string"],
+    },
+    PreludeModule {
+        name: "tags",
+        source: include_str!("../../../packages/std/prelude/tags.js"),
+        dependencies: &[],
+    },
+];
+
+/// Removes the module syntax which links the prelude's files together, since all
+/// of the inlined files share one scope.
+fn strip_module_syntax(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    for line in source.lines() {
+        if line.starts_with("import ") {
+            continue;
+        }
+        result.push_str(line.strip_prefix("export ").unwrap_or(line));
+        result.push('\n');
+    }
+    result
+}
+
+/// Adds the module, and the modules which it depends on, to `used_modules`.
+fn use_module(name: &'static str, used_modules: &mut BTreeSet<&'static str>) {
+    if !used_modules.insert(name) {
+        return;
+    }
+    if let Some(module) = PRELUDE_MODULES.iter().find(|module| module.name == name) {
+        for dependency in module.dependencies {
+            use_module(dependency, used_modules);
+        }
+    }
+}
+
+fn print_inline_prelude(helpers: &BTreeSet<Helper>) -> String {
+    let mut used_modules = BTreeSet::new();
+    for helper in helpers {
+        use_module(helper.module(), &mut used_modules);
+    }
+    PRELUDE_MODULES
+        .iter()
+        .filter(|module| used_modules.contains(module.name))
+        .map(|module| strip_module_syntax(module.source))
+        .collect()
+}
+
+/// Prints the code which makes the `helpers` which the compiled file calls
+/// available to it.
+pub fn print_prelude(
+    prelude: &Prelude,
+    module_format: ModuleFormat,
+    helpers: &BTreeSet<Helper>,
+) -> String {
+    let names = helpers
+        .iter()
+        .map(|helper| helper.name())
+        .collect::<Vec<_>>()
+        .join(",");
+    match (prelude, module_format) {
+        (Prelude::Import(_), _) if helpers.is_empty() => String::new(),
+        (Prelude::Import(specifier), ModuleFormat::EsModule) => {
+            format!("import {{{names}}} from '{specifier}'\n")
+        }
+        (Prelude::Import(specifier), ModuleFormat::CommonJs) => {
+            format!("const {{{names}}} = require('{specifier}')\n")
+        }
+        (Prelude::Inline, _) => print_inline_prelude(helpers),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn used_helpers_are_imported_from_the_specifier() {
+        let prelude = Prelude::Import("buri-prelude".to_string());
+        let helpers = BTreeSet::from([Helper::ListGet, Helper::Map]);
+        assert_eq!(
+            print_prelude(&prelude, ModuleFormat::EsModule, &helpers),
+            "import {$Map,$listGet} from 'buri-prelude'\n"
+        );
+        assert_eq!(
+            print_prelude(&prelude, ModuleFormat::CommonJs, &helpers),
+            "const {$Map,$listGet} = require('buri-prelude')\n"
+        );
+    }
+
+    #[test]
+    fn prelude_is_not_imported_without_helpers() {
+        let prelude = Prelude::Import("buri-prelude".to_string());
+        assert_eq!(
+            print_prelude(&prelude, ModuleFormat::EsModule, &BTreeSet::new()),
+            ""
+        );
+    }
+
+    #[test]
+    fn unused_prelude_modules_are_not_inlined() {
+        assert_eq!(
+            print_prelude(&Prelude::Inline, ModuleFormat::EsModule, &BTreeSet::new()),
+            ""
+        );
+    }
+
+    #[test]
+    fn inlined_modules_bring_their_dependencies() {
+        let prelude = print_prelude(
+            &Prelude::Inline,
+            ModuleFormat::EsModule,
+            &BTreeSet::from([Helper::Set]),
+        );
+        assert!(prelude.contains("const structuralKey = (value) =>"));
+        assert!(prelude.contains("class $Set {"));
+        assert!(!prelude.contains("import "));
+        assert!(!prelude.contains("export "));
+    }
+
+    #[test]
+    fn inlined_modules_bring_the_dependencies_of_their_dependencies() {
+        let prelude = print_prelude(
+            &Prelude::Inline,
+            ModuleFormat::EsModule,
+            &BTreeSet::from([Helper::Map]),
+        );
+        // Structural keys check whether values are sets, even in files without sets.
+        assert!(prelude.contains("const structuralKey = (value) =>"));
+        assert!(prelude.contains("class $Map {"));
+        assert!(prelude.contains("class $Set {"));
+        assert!(!prelude.contains("$equalsPrimitive"));
+    }
+
+    #[test]
+    fn inlined_modules_are_chosen_by_the_helpers() {
+        let prelude = print_prelude(
+            &Prelude::Inline,
+            ModuleFormat::CommonJs,
+            &BTreeSet::from([Helper::Modulo, Helper::StringSize]),
+        );
+        assert!(prelude.contains("const $modulo = "));
+        assert!(prelude.contains("const $stringSize = "));
+        assert!(!prelude.contains("$equalsPrimitive"));
+        assert!(!prelude.contains("globalThis"));
+    }
+}
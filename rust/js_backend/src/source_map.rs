@@ -0,0 +1,293 @@
+use crate::markers::SOURCE_LOCATION;
+use typed_ast::SourceLocation;
+
+const BASE64_DIGITS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Prints a marker for the location, or nothing if the location is unknown.
+pub fn print_source_location(location: SourceLocation) -> String {
+    if !location.is_known() {
+        return String::new();
+    }
+    format!(
+        "{SOURCE_LOCATION}{}:{}{SOURCE_LOCATION}",
+        location.line, location.column
+    )
+}
+
+/// Links a position in the generated JS to a position in the Buri source. All of
+/// the lines and columns start at 0, and generated columns are counted in UTF-16
+/// code units like JS does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Mapping {
+    generated_line: u32,
+    generated_column: u32,
+    source_line: u32,
+    source_column: u32,
+}
+
+fn parse_marker(marker: &str) -> Option<(u32, u32)> {
+    let (line, column) = marker.split_once(':')?;
+    Some((line.parse().ok()?, column.parse().ok()?))
+}
+
+/// Removes the markers from printed JS, returning the JS along with the positions
+/// that the markers were found at.
+fn extract_mappings(marked: &str) -> (String, Vec<Mapping>) {
+    let mut output = String::with_capacity(marked.len());
+    let mut mappings: Vec<Mapping> = Vec::new();
+    let mut generated_line = 0;
+    let mut generated_column = 0;
+    let mut characters = marked.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            SOURCE_LOCATION => {
+                let location = characters
+                    .by_ref()
+                    .take_while(|character| *character != SOURCE_LOCATION)
+                    .collect::<String>();
+                let Some((line, column)) = parse_marker(&location) else {
+                    continue;
+                };
+                // Nested nodes can start at the same place, e.g. a call of a method
+                // lookup. The outermost node is the most useful one to point at.
+                if matches!(
+                    mappings.last(),
+                    Some(last) if last.generated_line == generated_line
+                        && last.generated_column == generated_column
+                ) {
+                    continue;
+                }
+                mappings.push(Mapping {
+                    generated_line,
+                    generated_column,
+                    source_line: line.saturating_sub(1),
+                    source_column: column.saturating_sub(1),
+                });
+            }
+            '\n' => {
+                output.push(character);
+                generated_line += 1;
+                generated_column = 0;
+            }
+            _ => {
+                output.push(character);
+                generated_column += u32::try_from(character.len_utf16()).unwrap_or(0);
+            }
+        }
+    }
+    (output, mappings)
+}
+
+/// Removes the markers from printed JS when no source map is wanted.
+pub fn remove_source_locations(marked: &str) -> String {
+    marked.split(SOURCE_LOCATION).step_by(2).collect()
+}
+
+/// Appends a number as a base 64 VLQ: the sign is stored in the lowest bit, then
+/// the number is written 5 bits at a time, least significant bits first.
+fn encode_vlq(value: i64, output: &mut String) {
+    let mut remaining = if value < 0 {
+        (value.unsigned_abs() << 1) | 1
+    } else {
+        value.unsigned_abs() << 1
+    };
+    loop {
+        let mut digit = remaining & 0b1_1111;
+        remaining >>= 5;
+        if remaining > 0 {
+            digit |= 0b10_0000;
+        }
+        output.push(char::from(
+            BASE64_DIGITS[usize::try_from(digit).unwrap_or(0)],
+        ));
+        if remaining == 0 {
+            break;
+        }
+    }
+}
+
+/// Encodes the `mappings` field of a source map. Each generated line is separated
+/// by `;`, and every field of a segment is relative to the previous segment.
+fn encode_mappings(mappings: &[Mapping]) -> String {
+    let mut result = String::new();
+    let mut current_line = 0;
+    let mut previous_generated_column = 0;
+    let mut previous_source_line = 0;
+    let mut previous_source_column = 0;
+    for mapping in mappings {
+        if mapping.generated_line == current_line {
+            if !result.is_empty() && !result.ends_with(';') {
+                result.push(',');
+            }
+        } else {
+            while current_line < mapping.generated_line {
+                result.push(';');
+                current_line += 1;
+            }
+            previous_generated_column = 0;
+        }
+        encode_vlq(
+            i64::from(mapping.generated_column) - i64::from(previous_generated_column),
+            &mut result,
+        );
+        // Every mapping points into the only source file.
+        encode_vlq(0, &mut result);
+        encode_vlq(
+            i64::from(mapping.source_line) - i64::from(previous_source_line),
+            &mut result,
+        );
+        encode_vlq(
+            i64::from(mapping.source_column) - i64::from(previous_source_column),
+            &mut result,
+        );
+        previous_generated_column = mapping.generated_column;
+        previous_source_line = mapping.source_line;
+        previous_source_column = mapping.source_column;
+    }
+    result
+}
+
+fn print_json_string(string: &str) -> String {
+    let escaped = string
+        .chars()
+        .map(|character| match character {
+            '"' => String::from("\\\""),
+            '\\' => String::from("\\\\"),
+            character if character.is_control() => format!("\\u{:04x}", u32::from(character)),
+            character => character.to_string(),
+        })
+        .collect::<String>();
+    format!("\"{escaped}\"")
+}
+
+/// A version 3 source map for a compiled Buri file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    /// The name of the generated JS file.
+    pub file: String,
+    /// The path of the Buri file, relative to the source map.
+    pub source: String,
+    mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"version\":3,\"file\":{},\"sources\":[{}],\"names\":[],\"mappings\":{}}}",
+            print_json_string(&self.file),
+            print_json_string(&self.source),
+            print_json_string(&encode_mappings(&self.mappings))
+        )
+    }
+}
+
+/// Removes the markers from printed JS and builds a source map from them.
+pub fn build_source_map(marked: &str, source: &str, file: &str) -> (String, SourceMap) {
+    let (output, mappings) = extract_mappings(marked);
+    (
+        output,
+        SourceMap {
+            file: file.to_string(),
+            source: source.to_string(),
+            mappings,
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encode_vlq_for_test(value: i64) -> String {
+        let mut result = String::new();
+        encode_vlq(value, &mut result);
+        result
+    }
+
+    #[test]
+    fn vlq_encodes_small_numbers_in_one_digit() {
+        assert_eq!(encode_vlq_for_test(0), "A");
+        assert_eq!(encode_vlq_for_test(1), "C");
+        assert_eq!(encode_vlq_for_test(-1), "D");
+        assert_eq!(encode_vlq_for_test(15), "e");
+    }
+
+    #[test]
+    fn vlq_continues_large_numbers_into_more_digits() {
+        assert_eq!(encode_vlq_for_test(16), "gB");
+        assert_eq!(encode_vlq_for_test(-17), "jB");
+        assert_eq!(encode_vlq_for_test(1000), "w+B");
+    }
+
+    #[test]
+    fn unknown_locations_are_not_marked() {
+        assert_eq!(print_source_location(SourceLocation::default()), "");
+    }
+
+    #[test]
+    fn markers_are_removed() {
+        let marked = format!(
+            "const Ba={}Bf(1)",
+            print_source_location(SourceLocation { line: 2, column: 5 })
+        );
+        assert_eq!(remove_source_locations(&marked), "const Ba=Bf(1)");
+    }
+
+    #[test]
+    fn markers_are_mapped_to_their_position_in_the_output() {
+        let marked = format!(
+            "import 'x'\n{}const Ba={}Bf(\"é\",{}Bg())",
+            print_source_location(SourceLocation { line: 1, column: 1 }),
+            print_source_location(SourceLocation { line: 1, column: 5 }),
+            print_source_location(SourceLocation { line: 2, column: 3 })
+        );
+        let (output, source_map) = build_source_map(&marked, "a.buri", "a.mjs");
+        assert_eq!(output, "import 'x'\nconst Ba=Bf(\"é\",Bg())");
+        assert_eq!(
+            source_map.mappings,
+            vec![
+                Mapping {
+                    generated_line: 1,
+                    generated_column: 0,
+                    source_line: 0,
+                    source_column: 0
+                },
+                Mapping {
+                    generated_line: 1,
+                    generated_column: 9,
+                    source_line: 0,
+                    source_column: 4
+                },
+                Mapping {
+                    generated_line: 1,
+                    generated_column: 16,
+                    source_line: 1,
+                    source_column: 2
+                },
+            ]
+        );
+        assert_eq!(
+            source_map.to_json(),
+            "{\"version\":3,\"file\":\"a.mjs\",\"sources\":[\"a.buri\"],\"names\":[],\"mappings\":\";AAAA,SAAI,OACF\"}"
+        );
+    }
+
+    #[test]
+    fn nested_nodes_at_the_same_position_are_mapped_once() {
+        let marked = format!(
+            "{}{}Bx.By()",
+            print_source_location(SourceLocation { line: 1, column: 1 }),
+            print_source_location(SourceLocation { line: 1, column: 2 })
+        );
+        let (_, source_map) = build_source_map(&marked, "a.buri", "a.mjs");
+        assert_eq!(source_map.mappings.len(), 1);
+        assert_eq!(source_map.mappings[0].source_column, 0);
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(print_json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\u000a\"");
+    }
+}
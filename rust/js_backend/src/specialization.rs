@@ -0,0 +1,434 @@
+use crate::{inlining::collect_bound_names, traversal::map_children};
+use ast::{BinaryOperatorSymbol, TopLevelDeclaration};
+use std::collections::{HashMap, HashSet};
+use typed_ast::{
+    get_concrete_type, get_concrete_type_mut, ConcreteBinaryOperatorExpression,
+    ConcreteDeclarationExpression, ConcreteDocument, ConcreteEnumType, ConcreteExpression,
+    ConcreteFunctionType, ConcreteListType, ConcreteMapType, ConcreteRecordType, ConcreteSetType,
+    ConcreteTagUnionType, ConcreteTupleType, ConcreteType, PrimitiveType,
+};
+
+/// The type with every `Num` in it replaced by `Float`.
+fn replace_num_with_float(concrete_type: &ConcreteType) -> ConcreteType {
+    match concrete_type {
+        ConcreteType::Primitive(PrimitiveType::Num) => {
+            ConcreteType::Primitive(PrimitiveType::Float)
+        }
+        ConcreteType::Primitive(_) => concrete_type.clone(),
+        ConcreteType::Function(function_type) => {
+            ConcreteType::Function(Box::new(ConcreteFunctionType {
+                argument_types: function_type
+                    .argument_types
+                    .iter()
+                    .map(replace_num_with_float)
+                    .collect(),
+                return_type: replace_num_with_float(&function_type.return_type),
+            }))
+        }
+        ConcreteType::TagUnion(tag_union_type) => {
+            ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
+                tag_types: tag_union_type
+                    .tag_types
+                    .iter()
+                    .map(|(name, contents)| {
+                        (
+                            name.clone(),
+                            contents.iter().map(replace_num_with_float).collect(),
+                        )
+                    })
+                    .collect(),
+            }))
+        }
+        ConcreteType::Enum(enum_type) => ConcreteType::Enum(Box::new(ConcreteEnumType {
+            variants: enum_type
+                .variants
+                .iter()
+                .map(|(name, payload)| {
+                    (
+                        name.clone(),
+                        payload.iter().map(replace_num_with_float).collect(),
+                    )
+                })
+                .collect(),
+        })),
+        ConcreteType::List(list_type) => ConcreteType::List(Box::new(ConcreteListType {
+            element_type: replace_num_with_float(&list_type.element_type),
+        })),
+        ConcreteType::Map(map_type) => ConcreteType::Map(Box::new(ConcreteMapType {
+            key_type: replace_num_with_float(&map_type.key_type),
+            value_type: replace_num_with_float(&map_type.value_type),
+        })),
+        ConcreteType::Record(record_type) => ConcreteType::Record(Box::new(ConcreteRecordType {
+            field_types: record_type
+                .field_types
+                .iter()
+                .map(|(name, field_type)| (name.clone(), replace_num_with_float(field_type)))
+                .collect(),
+        })),
+        ConcreteType::Set(set_type) => ConcreteType::Set(Box::new(ConcreteSetType {
+            element_type: replace_num_with_float(&set_type.element_type),
+        })),
+        ConcreteType::Tuple(tuple_type) => ConcreteType::Tuple(Box::new(ConcreteTupleType {
+            element_types: tuple_type
+                .element_types
+                .iter()
+                .map(replace_num_with_float)
+                .collect(),
+        })),
+    }
+}
+
+fn contains_num(concrete_type: &ConcreteType) -> bool {
+    replace_num_with_float(concrete_type) != *concrete_type
+}
+
+fn specialize_type(concrete_type: &mut ConcreteType) {
+    *concrete_type = replace_num_with_float(concrete_type);
+}
+
+/// Replaces `Num` with `Float` in the types of the expression and everything in it.
+fn specialize_types(expression: ConcreteExpression) -> ConcreteExpression {
+    let mut expression = map_children(expression, &mut specialize_types);
+    match &mut expression {
+        ConcreteExpression::FunctionArguments(_) => return expression,
+        ConcreteExpression::Declaration(declaration) => {
+            specialize_type(&mut declaration.declaration_type);
+            specialize_type(&mut declaration.identifier.expression_type);
+        }
+        ConcreteExpression::TupleDeclaration(declaration) => {
+            specialize_type(&mut declaration.declaration_type);
+            for identifier in &mut declaration.identifiers {
+                specialize_type(&mut identifier.expression_type);
+            }
+        }
+        ConcreteExpression::RecordAssignment(record_assignment) => {
+            specialize_type(&mut record_assignment.identifier.expression_type);
+            specialize_type(&mut record_assignment.contents.expression_type);
+        }
+        ConcreteExpression::When(when) => {
+            for case in &mut when.cases {
+                specialize_type(&mut case.expression_type);
+                for argument in &mut case.case_arguments {
+                    specialize_type(&mut argument.expression_type);
+                }
+            }
+        }
+        ConcreteExpression::TypeDeclaration(declaration) => {
+            specialize_type(&mut declaration.declaration_type);
+        }
+        _ => {}
+    }
+    specialize_type(get_concrete_type_mut(&mut expression));
+    expression
+}
+
+/// Buri identifiers can't contain `$`, so the copy's name never collides with a
+/// user's names once it is mangled.
+fn get_specialized_name(name: &str) -> String {
+    format!("{name}$Float")
+}
+
+/// Finds the top-level functions which have `Num`s in their types.
+fn find_generic_functions(
+    document: &ConcreteDocument,
+) -> HashMap<String, ConcreteDeclarationExpression> {
+    let mut functions = HashMap::new();
+    for declaration in &document.variable_declarations {
+        let name = &declaration.declaration.identifier.name;
+        let is_declared_once = document
+            .variable_declarations
+            .iter()
+            .filter(|other| other.declaration.identifier.name == *name)
+            .count()
+            == 1;
+        if is_declared_once
+            && matches!(
+                declaration.declaration.value,
+                ConcreteExpression::Function(_)
+            )
+            && contains_num(get_concrete_type(&declaration.declaration.value))
+        {
+            functions.insert(name.clone(), declaration.declaration.clone());
+        }
+    }
+    functions
+}
+
+struct Specializer<'a> {
+    functions: &'a HashMap<String, ConcreteDeclarationExpression>,
+    /// The names declared anywhere in the top-level declaration being specialized.
+    /// A call of one of them may not call the top-level function.
+    local_names: HashSet<String>,
+    /// The functions whose copies with `Float`s are called.
+    called_functions: HashSet<String>,
+}
+
+impl Specializer<'_> {
+    /// Calls the copy of the function if a `Float` is passed where it takes a `Num`.
+    fn specialize_call(&mut self, call: &mut ConcreteBinaryOperatorExpression) {
+        let (
+            BinaryOperatorSymbol::FunctionApplication,
+            ConcreteExpression::Identifier(callee),
+            ConcreteExpression::FunctionArguments(arguments),
+        ) = (&call.symbol, &mut call.left_child, &call.right_child)
+        else {
+            return;
+        };
+        if self.local_names.contains(&callee.name) {
+            return;
+        }
+        let Some(declaration) = self.functions.get(&callee.name) else {
+            return;
+        };
+        let function_type = get_concrete_type(&declaration.value);
+        let ConcreteType::Function(function) = function_type else {
+            return;
+        };
+        let is_called_with_floats =
+            function
+                .argument_types
+                .iter()
+                .zip(arguments)
+                .any(|(argument_type, argument)| {
+                    contains_num(argument_type)
+                        && replace_num_with_float(argument_type) == *get_concrete_type(argument)
+                });
+        if !is_called_with_floats {
+            return;
+        }
+        self.called_functions.insert(callee.name.clone());
+        callee.name = get_specialized_name(&callee.name);
+        callee.expression_type = replace_num_with_float(function_type);
+        call.expression_type = replace_num_with_float(&function.return_type);
+    }
+
+    fn specialize(&mut self, expression: ConcreteExpression) -> ConcreteExpression {
+        match map_children(expression, &mut |child| self.specialize(child)) {
+            ConcreteExpression::BinaryOperator(mut call) => {
+                self.specialize_call(&mut call);
+                ConcreteExpression::BinaryOperator(call)
+            }
+            expression => expression,
+        }
+    }
+
+    fn specialize_declaration(&mut self, declaration: &mut ConcreteDeclarationExpression) {
+        self.local_names.clear();
+        collect_bound_names(&declaration.value, &mut self.local_names);
+        let value = std::mem::replace(
+            &mut declaration.value,
+            ConcreteExpression::FunctionArguments(vec![]),
+        );
+        declaration.value = self.specialize(value);
+    }
+}
+
+/// Calls which pass `Float`s to generic functions call a copy of the function
+/// whose `Num`s are `Float`s instead, declared right after the function.
+///
+/// Generic functions which only do arithmetic with their arguments take `Num`s.
+/// `Int`s and `Float`s are both JS numbers, so a `Float` with a whole value can't
+/// be told apart from an `Int` when it is divided.
+///
+/// Only calls of top-level functions by name are specialized. Values which a
+/// generic function returns keep its types once they are stored in a variable,
+/// unless the type checker narrowed them to `Float` by using them as one.
+pub fn specialize_generic_functions(document: &mut ConcreteDocument) {
+    let functions = find_generic_functions(document);
+    if functions.is_empty() {
+        return;
+    }
+    let mut specializer = Specializer {
+        functions: &functions,
+        local_names: HashSet::new(),
+        called_functions: HashSet::new(),
+    };
+    for declaration in &mut document.variable_declarations {
+        specializer.specialize_declaration(&mut declaration.declaration);
+    }
+    // The copies may pass their `Float`s on to other generic functions.
+    let mut specialized_functions = HashSet::new();
+    while let Some(name) = specializer
+        .called_functions
+        .difference(&specialized_functions)
+        .next()
+        .cloned()
+    {
+        let mut declaration = functions[&name].clone();
+        declaration.identifier.name = get_specialized_name(&name);
+        specialize_type(&mut declaration.identifier.expression_type);
+        specialize_type(&mut declaration.declaration_type);
+        specialize_type(&mut declaration.expression_type);
+        declaration.value = specialize_types(declaration.value);
+        specializer.specialize_declaration(&mut declaration);
+        let position = document
+            .variable_declarations
+            .iter()
+            .position(|other| other.declaration.identifier.name == name)
+            .map_or(document.variable_declarations.len(), |position| {
+                position + 1
+            });
+        document.variable_declarations.insert(
+            position,
+            TopLevelDeclaration {
+                declaration,
+                is_exported: false,
+            },
+        );
+        specialized_functions.insert(name);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use typed_ast::{ConcreteFunctionExpression, ConcreteIdentifierExpression, SourceLocation};
+
+    const NUM: ConcreteType = ConcreteType::Primitive(PrimitiveType::Num);
+
+    fn identifier_for_test(name: &str, expression_type: ConcreteType) -> ConcreteExpression {
+        ConcreteExpression::Identifier(Box::new(ConcreteIdentifierExpression {
+            expression_type,
+            ..ConcreteExpression::raw_identifier_for_test(name)
+        }))
+    }
+
+    fn function_type_for_test() -> ConcreteType {
+        ConcreteType::Function(Box::new(ConcreteFunctionType {
+            argument_types: vec![NUM, NUM],
+            return_type: NUM,
+        }))
+    }
+
+    /// `divide = (a, b) => a / b`
+    fn divide_for_test() -> TopLevelDeclaration<ConcreteDeclarationExpression> {
+        let body = ConcreteExpression::BinaryOperator(Box::new(ConcreteBinaryOperatorExpression {
+            expression_type: NUM,
+            source: SourceLocation::default(),
+            symbol: BinaryOperatorSymbol::Divide,
+            left_child: identifier_for_test("a", NUM),
+            right_child: identifier_for_test("b", NUM),
+        }));
+        declaration_for_test(
+            "divide",
+            ConcreteExpression::Function(Box::new(ConcreteFunctionExpression {
+                expression_type: function_type_for_test(),
+                source: SourceLocation::default(),
+                argument_names: vec!["a".to_string(), "b".to_string()],
+                body,
+            })),
+        )
+    }
+
+    fn declaration_for_test(
+        name: &str,
+        value: ConcreteExpression,
+    ) -> TopLevelDeclaration<ConcreteDeclarationExpression> {
+        let value_type = get_concrete_type(&value).clone();
+        TopLevelDeclaration {
+            declaration: ConcreteDeclarationExpression {
+                declaration_type: value_type.clone(),
+                expression_type: value_type.clone(),
+                identifier: ConcreteIdentifierExpression {
+                    expression_type: value_type,
+                    ..ConcreteExpression::raw_identifier_for_test(name)
+                },
+                value,
+                is_reassignment: false,
+                source: SourceLocation::default(),
+            },
+            is_exported: true,
+        }
+    }
+
+    fn call_for_test(argument: &ConcreteExpression) -> ConcreteExpression {
+        ConcreteExpression::BinaryOperator(Box::new(ConcreteBinaryOperatorExpression {
+            expression_type: NUM,
+            source: SourceLocation::default(),
+            symbol: BinaryOperatorSymbol::FunctionApplication,
+            left_child: identifier_for_test("divide", function_type_for_test()),
+            right_child: ConcreteExpression::FunctionArguments(vec![
+                argument.clone(),
+                argument.clone(),
+            ]),
+        }))
+    }
+
+    fn document_for_test(argument: &ConcreteExpression) -> ConcreteDocument<'static> {
+        ConcreteDocument {
+            imports: vec![],
+            type_declarations: vec![],
+            variable_declarations: vec![
+                divide_for_test(),
+                declaration_for_test("half", call_for_test(argument)),
+            ],
+            expressions: vec![],
+        }
+    }
+
+    fn get_declared_names(document: &ConcreteDocument) -> Vec<String> {
+        document
+            .variable_declarations
+            .iter()
+            .map(|declaration| declaration.declaration.identifier.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn calls_with_floats_call_a_copy_with_floats() {
+        let mut document = document_for_test(&ConcreteExpression::float_for_test("7.0"));
+        specialize_generic_functions(&mut document);
+        assert_eq!(
+            get_declared_names(&document),
+            vec!["divide", "divide$Float", "half"]
+        );
+        let ConcreteExpression::Function(copy) =
+            &document.variable_declarations[1].declaration.value
+        else {
+            panic!("Expected a function");
+        };
+        assert_eq!(
+            *get_concrete_type(&copy.body),
+            ConcreteType::Primitive(PrimitiveType::Float)
+        );
+        assert!(!document.variable_declarations[1].is_exported);
+        let ConcreteExpression::BinaryOperator(call) =
+            &document.variable_declarations[2].declaration.value
+        else {
+            panic!("Expected a call");
+        };
+        assert_eq!(
+            call.expression_type,
+            ConcreteType::Primitive(PrimitiveType::Float)
+        );
+        assert!(
+            matches!(&call.left_child, ConcreteExpression::Identifier(callee) if callee.name == "divide$Float")
+        );
+    }
+
+    #[test]
+    fn calls_with_ints_call_the_function() {
+        let mut document = document_for_test(&ConcreteExpression::integer_for_test(7));
+        let original = document.clone();
+        specialize_generic_functions(&mut document);
+        assert_eq!(document, original);
+    }
+
+    #[test]
+    fn local_variables_are_not_specialized() {
+        let shadowing = ConcreteExpression::Function(Box::new(ConcreteFunctionExpression {
+            expression_type: ConcreteType::default_function_for_test(),
+            source: SourceLocation::default(),
+            argument_names: vec!["divide".to_string()],
+            body: call_for_test(&ConcreteExpression::float_for_test("7.0")),
+        }));
+        let mut document = ConcreteDocument {
+            imports: vec![],
+            type_declarations: vec![],
+            variable_declarations: vec![divide_for_test(), declaration_for_test("f", shadowing)],
+            expressions: vec![],
+        };
+        specialize_generic_functions(&mut document);
+        assert_eq!(get_declared_names(&document), vec!["divide", "f"]);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::traversal::for_each_child;
+use std::collections::{BTreeSet, HashMap};
+use typed_ast::{get_concrete_type, ConcreteDocument, ConcreteExpression, ConcreteType};
+
+/// How tags are represented in the printed JS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TagRepresentation {
+    /// Tags are arrays of their name followed by their contents, e.g. `["some",1]`.
+    #[default]
+    Names,
+    /// Tags are represented by an integer computed from their name. Tags without
+    /// contents are just the integer, and others are arrays of the integer followed
+    /// by their contents.
+    Integers,
+}
+
+/// A 32-bit FNV-1a hash of the name's UTF-16 code units, cut down to 30 bits so JS
+/// engines store it unboxed. The prelude's `$getTagDiscriminant` computes the same
+/// value, so every module agrees on it without sharing a table.
+///
+/// Enums number their variants densely, since an enum value only ever has its
+/// enum's type. Tag unions are structural instead: `#none` belongs to every union
+/// which lists it, and a value moves between unions without being converted, so a
+/// tag needs the same integer in all of them.
+pub fn get_tag_discriminant(name: &str) -> u32 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for code_unit in name.encode_utf16() {
+        hash = (hash ^ u32::from(code_unit)).wrapping_mul(0x0100_0193);
+    }
+    hash & 0x3fff_ffff
+}
+
+/// Prints the first element of a tag with contents.
+pub fn print_tag_name(name: &str, representation: TagRepresentation) -> String {
+    match representation {
+        TagRepresentation::Names => format!("\"{name}\""),
+        TagRepresentation::Integers => get_tag_discriminant(name).to_string(),
+    }
+}
+
+/// Prints a whole tag which has no contents.
+pub fn print_tag_without_contents(name: &str, representation: TagRepresentation) -> String {
+    match representation {
+        TagRepresentation::Names => format!("[\"{name}\"]"),
+        TagRepresentation::Integers => get_tag_discriminant(name).to_string(),
+    }
+}
+
+fn collect_type_tag_names(concrete_type: &ConcreteType, names: &mut BTreeSet<String>) {
+    match concrete_type {
+        ConcreteType::Primitive(_) => {}
+        ConcreteType::Function(function_type) => {
+            for argument_type in &function_type.argument_types {
+                collect_type_tag_names(argument_type, names);
+            }
+            collect_type_tag_names(&function_type.return_type, names);
+        }
+        ConcreteType::TagUnion(tag_union_type) => {
+            for (name, content_types) in &tag_union_type.tag_types {
+                names.insert(name.clone());
+                for content_type in content_types {
+                    collect_type_tag_names(content_type, names);
+                }
+            }
+        }
+        ConcreteType::Enum(enum_type) => {
+            for payload_type in enum_type.variants.values().flatten() {
+                collect_type_tag_names(payload_type, names);
+            }
+        }
+        ConcreteType::List(list_type) => collect_type_tag_names(&list_type.element_type, names),
+        ConcreteType::Map(map_type) => {
+            collect_type_tag_names(&map_type.key_type, names);
+            collect_type_tag_names(&map_type.value_type, names);
+        }
+        ConcreteType::Record(record_type) => {
+            for field_type in record_type.field_types.values() {
+                collect_type_tag_names(field_type, names);
+            }
+        }
+        ConcreteType::Set(set_type) => collect_type_tag_names(&set_type.element_type, names),
+        ConcreteType::Tuple(tuple_type) => {
+            for element_type in &tuple_type.element_types {
+                collect_type_tag_names(element_type, names);
+            }
+        }
+    }
+}
+
+fn collect_tag_names(expression: &ConcreteExpression, names: &mut BTreeSet<String>) {
+    // Function arguments don't have a type of their own.
+    if !matches!(expression, ConcreteExpression::FunctionArguments(_)) {
+        collect_type_tag_names(get_concrete_type(expression), names);
+    }
+    for_each_child(expression, &mut |child| collect_tag_names(child, names));
+}
+
+/// Integer tags can only be told apart if no two tags have the same discriminant.
+/// Files pass tags to each other, so the documents are all the files of a program.
+pub fn check_tag_discriminants(documents: &[&ConcreteDocument]) -> Result<(), String> {
+    let mut names = BTreeSet::new();
+    for document in documents {
+        for declaration in &document.variable_declarations {
+            collect_type_tag_names(&declaration.declaration.declaration_type, &mut names);
+            collect_tag_names(&declaration.declaration.value, &mut names);
+        }
+    }
+    let mut names_by_discriminant = HashMap::new();
+    for name in names {
+        if let Some(other_name) =
+            names_by_discriminant.insert(get_tag_discriminant(&name), name.clone())
+        {
+            return Err(format!(
+                "Tags #{other_name} and #{name} have the same integer representation. Rename one of them, or compile with --tag-names."
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ast::TopLevelDeclaration;
+    use indexmap::IndexMap;
+    use typed_ast::{
+        ConcreteDeclarationExpression, ConcreteTagExpression, ConcreteTagUnionType, SourceLocation,
+    };
+
+    #[test]
+    fn discriminants_match_the_prelude() {
+        assert_eq!(get_tag_discriminant("none"), 0x2da7_afdb);
+        assert_eq!(get_tag_discriminant("some"), 0x3361_1c71);
+    }
+
+    #[test]
+    fn tags_are_printed_with_names_or_integers() {
+        assert_eq!(print_tag_name("some", TagRepresentation::Names), "\"some\"");
+        assert_eq!(
+            print_tag_name("some", TagRepresentation::Integers),
+            0x3361_1c71.to_string()
+        );
+    }
+
+    #[test]
+    fn tags_without_contents_are_just_the_integer() {
+        assert_eq!(
+            print_tag_without_contents("none", TagRepresentation::Names),
+            "[\"none\"]"
+        );
+        assert_eq!(
+            print_tag_without_contents("none", TagRepresentation::Integers),
+            0x2da7_afdb.to_string()
+        );
+    }
+
+    fn document_for_test(tag_name: &str) -> ConcreteDocument<'static> {
+        let tag_type = ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
+            tag_types: IndexMap::from([(tag_name.to_string(), vec![])]),
+        }));
+        ConcreteDocument {
+            imports: vec![],
+            type_declarations: vec![],
+            variable_declarations: vec![TopLevelDeclaration {
+                declaration: ConcreteDeclarationExpression {
+                    declaration_type: tag_type.clone(),
+                    expression_type: tag_type.clone(),
+                    identifier: ConcreteExpression::raw_identifier_for_test("status"),
+                    value: ConcreteExpression::Tag(Box::new(ConcreteTagExpression {
+                        expression_type: tag_type,
+                        name: tag_name.to_string(),
+                        contents: vec![],
+                    })),
+                    is_reassignment: false,
+                    source: SourceLocation::default(),
+                },
+                is_exported: true,
+            }],
+            expressions: vec![],
+        }
+    }
+
+    #[test]
+    fn tags_with_the_same_discriminant_are_reported_across_files() {
+        assert_eq!(get_tag_discriminant("dsbjm"), get_tag_discriminant("hraba"));
+        let first = document_for_test("dsbjm");
+        let second = document_for_test("hraba");
+        assert!(check_tag_discriminants(&[&first]).is_ok());
+        assert!(check_tag_discriminants(&[&second]).is_ok());
+        assert_eq!(
+            check_tag_discriminants(&[&first, &second]),
+            Err(String::from("Tags #dsbjm and #hraba have the same integer representation. Rename one of them, or compile with --tag-names."))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use typed_ast::{TypedExpression, TypedMapEntry, TypedRecordExpression, TypedWhenCase};
+
+/// Calls `visit` on each expression directly inside this one. The names which an
+/// expression binds, such as a declaration's identifier, aren't expressions of
+/// their own, so they aren't visited.
+pub fn for_each_child<T>(
+    expression: &TypedExpression<T>,
+    visit: &mut impl FnMut(&TypedExpression<T>),
+) {
+    match expression {
+        TypedExpression::BinaryOperator(binary_operator) => {
+            visit(&binary_operator.left_child);
+            visit(&binary_operator.right_child);
+        }
+        TypedExpression::UnaryOperator(unary_operator) => visit(&unary_operator.child),
+        TypedExpression::Declaration(declaration) => visit(&declaration.value),
+        TypedExpression::TupleDeclaration(declaration) => visit(&declaration.value),
+        TypedExpression::Function(function) => visit(&function.body),
+        TypedExpression::If(if_expression) => {
+            visit(&if_expression.condition);
+            visit(&if_expression.path_if_true);
+            if let Some(path_if_false) = &if_expression.path_if_false {
+                visit(path_if_false);
+            }
+        }
+        TypedExpression::When(when) => {
+            visit(&when.condition);
+            for case in &when.cases {
+                visit(&case.case_expression);
+            }
+        }
+        TypedExpression::Map(map) => {
+            for entry in &map.contents {
+                visit(&entry.key);
+                visit(&entry.value);
+            }
+        }
+        TypedExpression::Record(record) => record.contents.values().for_each(visit),
+        TypedExpression::RecordAssignment(record_assignment) => {
+            record_assignment.contents.contents.values().for_each(visit);
+        }
+        TypedExpression::Block(block) => block.contents.iter().for_each(visit),
+        TypedExpression::FunctionArguments(arguments) => arguments.iter().for_each(visit),
+        TypedExpression::List(list) => list.contents.iter().for_each(visit),
+        TypedExpression::Set(set) => set.contents.iter().for_each(visit),
+        TypedExpression::Tuple(tuple) => tuple.contents.iter().for_each(visit),
+        TypedExpression::Tag(tag) => tag.contents.iter().for_each(visit),
+        TypedExpression::Enum(enum_expression) => enum_expression.payload.iter().for_each(visit),
+        TypedExpression::Boolean(_)
+        | TypedExpression::Float(_)
+        | TypedExpression::Identifier(_)
+        | TypedExpression::Integer(_)
+        | TypedExpression::StringLiteral(_)
+        | TypedExpression::TypeDeclaration(_)
+        | TypedExpression::TypeIdentifier(_) => {}
+    }
+}
+
+fn map_each<T>(
+    expressions: Vec<TypedExpression<T>>,
+    transform: &mut impl FnMut(TypedExpression<T>) -> TypedExpression<T>,
+) -> Vec<TypedExpression<T>> {
+    expressions.into_iter().map(transform).collect()
+}
+
+fn map_record<T>(
+    record: &mut TypedRecordExpression<T>,
+    transform: &mut impl FnMut(TypedExpression<T>) -> TypedExpression<T>,
+) {
+    record.contents = std::mem::take(&mut record.contents)
+        .into_iter()
+        .map(|(name, value)| (name, transform(value)))
+        .collect();
+}
+
+/// Rebuilds the expression with `transform` applied to each expression directly
+/// inside it, so passes over the AST only have to handle the nodes they change.
+pub fn map_children<T>(
+    expression: TypedExpression<T>,
+    transform: &mut impl FnMut(TypedExpression<T>) -> TypedExpression<T>,
+) -> TypedExpression<T> {
+    match expression {
+        TypedExpression::BinaryOperator(mut binary_operator) => {
+            binary_operator.left_child = transform(binary_operator.left_child);
+            binary_operator.right_child = transform(binary_operator.right_child);
+            TypedExpression::BinaryOperator(binary_operator)
+        }
+        TypedExpression::UnaryOperator(mut unary_operator) => {
+            unary_operator.child = transform(unary_operator.child);
+            TypedExpression::UnaryOperator(unary_operator)
+        }
+        TypedExpression::Declaration(mut declaration) => {
+            declaration.value = transform(declaration.value);
+            TypedExpression::Declaration(declaration)
+        }
+        TypedExpression::TupleDeclaration(mut declaration) => {
+            declaration.value = transform(declaration.value);
+            TypedExpression::TupleDeclaration(declaration)
+        }
+        TypedExpression::Function(mut function) => {
+            function.body = transform(function.body);
+            TypedExpression::Function(function)
+        }
+        TypedExpression::If(mut if_expression) => {
+            if_expression.condition = transform(if_expression.condition);
+            if_expression.path_if_true = transform(if_expression.path_if_true);
+            if_expression.path_if_false = if_expression.path_if_false.map(&mut *transform);
+            TypedExpression::If(if_expression)
+        }
+        TypedExpression::When(mut when) => {
+            when.condition = transform(when.condition);
+            when.cases = when
+                .cases
+                .into_iter()
+                .map(|case| TypedWhenCase {
+                    case_expression: transform(case.case_expression),
+                    ..case
+                })
+                .collect();
+            TypedExpression::When(when)
+        }
+        TypedExpression::Map(mut map) => {
+            map.contents = map
+                .contents
+                .into_iter()
+                .map(|entry| TypedMapEntry {
+                    key: transform(entry.key),
+                    value: transform(entry.value),
+                })
+                .collect();
+            TypedExpression::Map(map)
+        }
+        TypedExpression::Record(mut record) => {
+            map_record(&mut record, transform);
+            TypedExpression::Record(record)
+        }
+        TypedExpression::RecordAssignment(mut record_assignment) => {
+            map_record(&mut record_assignment.contents, transform);
+            TypedExpression::RecordAssignment(record_assignment)
+        }
+        TypedExpression::Block(mut block) => {
+            block.contents = map_each(block.contents, transform);
+            TypedExpression::Block(block)
+        }
+        TypedExpression::FunctionArguments(arguments) => {
+            TypedExpression::FunctionArguments(map_each(arguments, transform))
+        }
+        TypedExpression::List(mut list) => {
+            list.contents = map_each(list.contents, transform);
+            TypedExpression::List(list)
+        }
+        TypedExpression::Set(mut set) => {
+            set.contents = map_each(set.contents, transform);
+            TypedExpression::Set(set)
+        }
+        TypedExpression::Tuple(mut tuple) => {
+            tuple.contents = map_each(tuple.contents, transform);
+            TypedExpression::Tuple(tuple)
+        }
+        TypedExpression::Tag(mut tag) => {
+            tag.contents = map_each(tag.contents, transform);
+            TypedExpression::Tag(tag)
+        }
+        TypedExpression::Enum(mut enum_expression) => {
+            enum_expression.payload = map_each(enum_expression.payload, transform);
+            TypedExpression::Enum(enum_expression)
+        }
+        TypedExpression::Boolean(_)
+        | TypedExpression::Float(_)
+        | TypedExpression::Identifier(_)
+        | TypedExpression::Integer(_)
+        | TypedExpression::StringLiteral(_)
+        | TypedExpression::TypeDeclaration(_)
+        | TypedExpression::TypeIdentifier(_) => expression,
+    }
+}
@@ -0,0 +1,172 @@
+use crate::traversal::for_each_child;
+use std::collections::HashSet;
+use typed_ast::{ConcreteDocument, TypedDocument, TypedExpression};
+
+/// Adds the names of every identifier that the expression refers to. Local variables
+/// aren't told apart from the top-level declarations they shadow, so a declaration
+/// may be kept without being needed, but never removed while it is needed.
+pub fn collect_references<T>(expression: &TypedExpression<T>, references: &mut HashSet<String>) {
+    match expression {
+        TypedExpression::Identifier(identifier) => {
+            references.insert(identifier.name.clone());
+        }
+        TypedExpression::RecordAssignment(record_assignment) => {
+            references.insert(record_assignment.identifier.name.clone());
+        }
+        _ => {}
+    }
+    for_each_child(expression, &mut |child| {
+        collect_references(child, references);
+    });
+}
+
+/// Finds the names of the top-level declarations which exported declarations use,
+/// directly or through other declarations.
+fn find_reachable_declarations<T>(document: &TypedDocument<T>) -> HashSet<String> {
+    let mut reachable = document
+        .variable_declarations
+        .iter()
+        .filter(|declaration| declaration.is_exported)
+        .map(|declaration| declaration.declaration.identifier.name.clone())
+        .collect::<HashSet<_>>();
+    let mut visited = HashSet::new();
+    while let Some(name) = reachable.difference(&visited).next().cloned() {
+        for declaration in &document.variable_declarations {
+            if declaration.declaration.identifier.name == name {
+                collect_references(&declaration.declaration.value, &mut reachable);
+            }
+        }
+        visited.insert(name);
+    }
+    reachable
+}
+
+/// Removes the top-level declarations which nothing exported uses, since they can't
+/// affect the program. Returns a warning for each declaration that was removed.
+pub fn remove_unused_declarations(document: &mut ConcreteDocument) -> Vec<String> {
+    let reachable = find_reachable_declarations(document);
+    let mut warnings = Vec::new();
+    document.variable_declarations.retain(|declaration| {
+        let identifier = &declaration.declaration.identifier;
+        if reachable.contains(&identifier.name) {
+            return true;
+        }
+        if !identifier.is_disregarded {
+            let source = declaration.declaration.source;
+            if source.is_known() {
+                warnings.push(format!(
+                    "Warning: {} is unused (line {}, column {})",
+                    identifier.name, source.line, source.column
+                ));
+            } else {
+                warnings.push(format!("Warning: {} is unused", identifier.name));
+            }
+        }
+        false
+    });
+    warnings
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ast::TopLevelDeclaration;
+    use typed_ast::{
+        ConcreteDeclarationExpression, ConcreteExpression, ConcreteFunctionExpression,
+        ConcreteType, SourceLocation,
+    };
+
+    fn declaration_for_test(
+        name: &str,
+        value: ConcreteExpression,
+        is_exported: bool,
+    ) -> TopLevelDeclaration<ConcreteDeclarationExpression> {
+        TopLevelDeclaration {
+            declaration: ConcreteDeclarationExpression {
+                declaration_type: ConcreteType::default_for_test(),
+                expression_type: ConcreteType::default_for_test(),
+                identifier: ConcreteExpression::raw_identifier_for_test(name),
+                value,
+                is_reassignment: false,
+                source: SourceLocation::default(),
+            },
+            is_exported,
+        }
+    }
+
+    fn document_for_test(
+        declarations: Vec<TopLevelDeclaration<ConcreteDeclarationExpression>>,
+    ) -> ConcreteDocument<'static> {
+        TypedDocument {
+            imports: vec![],
+            type_declarations: vec![],
+            variable_declarations: declarations,
+            expressions: vec![],
+        }
+    }
+
+    fn get_names(document: &ConcreteDocument) -> Vec<String> {
+        document
+            .variable_declarations
+            .iter()
+            .map(|declaration| declaration.declaration.identifier.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn declarations_used_by_exports_are_kept() {
+        let mut document = document_for_test(vec![
+            declaration_for_test("one", ConcreteExpression::integer_for_test(1), false),
+            declaration_for_test("two", ConcreteExpression::identifier_for_test("one"), false),
+            declaration_for_test(
+                "three",
+                ConcreteExpression::identifier_for_test("two"),
+                true,
+            ),
+        ]);
+        assert!(remove_unused_declarations(&mut document).is_empty());
+        assert_eq!(get_names(&document), vec!["one", "two", "three"]);
+    }
+
+    #[test]
+    fn declarations_only_used_by_unused_declarations_are_removed_with_a_warning() {
+        let mut declaration = declaration_for_test(
+            "unused",
+            ConcreteExpression::identifier_for_test("helper"),
+            false,
+        );
+        declaration.declaration.source = SourceLocation { line: 3, column: 1 };
+        let mut document = document_for_test(vec![
+            declaration_for_test("helper", ConcreteExpression::integer_for_test(1), false),
+            declaration,
+            declaration_for_test("exported", ConcreteExpression::integer_for_test(2), true),
+        ]);
+        assert_eq!(
+            remove_unused_declarations(&mut document),
+            vec![
+                "Warning: helper is unused",
+                "Warning: unused is unused (line 3, column 1)"
+            ]
+        );
+        assert_eq!(get_names(&document), vec!["exported"]);
+    }
+
+    #[test]
+    fn references_inside_functions_keep_declarations() {
+        let function = ConcreteExpression::Function(Box::new(ConcreteFunctionExpression {
+            expression_type: ConcreteType::default_function_for_test(),
+            source: SourceLocation::default(),
+            argument_names: vec!["x".to_string()],
+            body: ConcreteExpression::block_for_test(vec![
+                ConcreteExpression::identifier_for_test("helper"),
+                ConcreteExpression::identifier_for_test("recurse"),
+            ]),
+        }));
+        let mut document = document_for_test(vec![
+            declaration_for_test("helper", ConcreteExpression::integer_for_test(1), false),
+            declaration_for_test("recurse", function, true),
+        ]);
+        assert!(remove_unused_declarations(&mut document).is_empty());
+        assert_eq!(get_names(&document), vec!["helper", "recurse"]);
+    }
+}
@@ -0,0 +1,547 @@
+use crate::{
+    integers::IntegerRepresentation,
+    mangle_variable_name,
+    tags::{get_tag_discriminant, print_tag_name, TagRepresentation},
+    PrinterConfiguration,
+};
+use typed_ast::{
+    ConcreteDeclarationExpression, ConcreteExpression, ConcreteFunctionType, ConcreteType,
+    PrimitiveType, TypedDocument,
+};
+
+/// Maps and sets are instances of the prelude's `$Map` and `$Set` classes, so the
+/// declarations describe the parts of them which can be used from TypeScript.
+const MAP_INTERFACE: &str = "interface $Map<K, V> {
+    get(key: K): readonly [\"none\"] | readonly [\"some\", V];
+    insert(key: K, value: V): $Map<K, V>;
+    remove(key: K): $Map<K, V>;
+    has(key: K): boolean;
+    size(): number;
+    keys(): ReadonlyArray<K>;
+    values(): ReadonlyArray<V>;
+}
+";
+
+const SET_INTERFACE: &str = "interface $Set<T> {
+    insert(element: T): $Set<T>;
+    remove(element: T): $Set<T>;
+    has(element: T): boolean;
+    size(): number;
+    values(): ReadonlyArray<T>;
+}
+";
+
+/// Which of the prelude's classes the printed types refer to.
+#[derive(Debug, Default)]
+struct UsedPreludeTypes {
+    map: bool,
+    set: bool,
+}
+
+fn print_types(
+    types: &[ConcreteType],
+    configuration: &PrinterConfiguration,
+    used: &mut UsedPreludeTypes,
+) -> Vec<String> {
+    types
+        .iter()
+        .map(|element_type| print_type(element_type, configuration, used))
+        .collect()
+}
+
+/// Prints the type of a tag or enum variant, which is a tuple of its name (or
+/// index) followed by its payload.
+fn print_tagged_tuple(
+    tag: &str,
+    payload: &[ConcreteType],
+    configuration: &PrinterConfiguration,
+    used: &mut UsedPreludeTypes,
+) -> String {
+    let mut elements = vec![tag.to_string()];
+    elements.extend(print_types(payload, configuration, used));
+    format!("readonly [{}]", elements.join(", "))
+}
+
+fn print_union(members: &[String]) -> String {
+    if members.is_empty() {
+        return "never".to_string();
+    }
+    members.join(" | ")
+}
+
+fn print_function_type(
+    function_type: &ConcreteFunctionType,
+    argument_names: Option<&[String]>,
+    configuration: &PrinterConfiguration,
+    used: &mut UsedPreludeTypes,
+) -> String {
+    let arguments = function_type
+        .argument_types
+        .iter()
+        .enumerate()
+        .map(|(index, argument_type)| {
+            let name = argument_names
+                .and_then(|names| names.get(index))
+                .map_or_else(|| format!("arg{index}"), |name| mangle_variable_name(name));
+            format!("{name}: {}", print_type(argument_type, configuration, used))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "({arguments}) => {}",
+        print_type(&function_type.return_type, configuration, used)
+    )
+}
+
+/// `Int`s are JS numbers or `BigInt`s, depending on the configuration.
+fn print_primitive_type(
+    primitive_type: PrimitiveType,
+    integer_representation: IntegerRepresentation,
+) -> String {
+    match primitive_type {
+        PrimitiveType::CompilerBoolean => "boolean".to_string(),
+        PrimitiveType::Int => match integer_representation {
+            IntegerRepresentation::Number => "number".to_string(),
+            IntegerRepresentation::BigInt => "bigint".to_string(),
+        },
+        PrimitiveType::Float => "number".to_string(),
+        PrimitiveType::Str => "string".to_string(),
+        // Generic functions may be called with either `Int`s or `Float`s.
+        PrimitiveType::Num => match integer_representation {
+            IntegerRepresentation::Number => "number".to_string(),
+            IntegerRepresentation::BigInt => "number | bigint".to_string(),
+        },
+        PrimitiveType::Unknown => "unknown".to_string(),
+    }
+}
+
+/// Prints the TypeScript type of the JS values which a Buri type is compiled to.
+fn print_type(
+    concrete_type: &ConcreteType,
+    configuration: &PrinterConfiguration,
+    used: &mut UsedPreludeTypes,
+) -> String {
+    match concrete_type {
+        ConcreteType::Primitive(primitive_type) => {
+            print_primitive_type(*primitive_type, configuration.integer_representation)
+        }
+        ConcreteType::Function(function_type) => {
+            format!(
+                "({})",
+                print_function_type(function_type, None, configuration, used)
+            )
+        }
+        ConcreteType::List(list_type) => format!(
+            "ReadonlyArray<{}>",
+            print_type(&list_type.element_type, configuration, used)
+        ),
+        ConcreteType::Map(map_type) => {
+            used.map = true;
+            format!(
+                "$Map<{}, {}>",
+                print_type(&map_type.key_type, configuration, used),
+                print_type(&map_type.value_type, configuration, used)
+            )
+        }
+        ConcreteType::Set(set_type) => {
+            used.set = true;
+            format!(
+                "$Set<{}>",
+                print_type(&set_type.element_type, configuration, used)
+            )
+        }
+        ConcreteType::Tuple(tuple_type) => format!(
+            "readonly [{}]",
+            print_types(&tuple_type.element_types, configuration, used).join(", ")
+        ),
+        ConcreteType::Record(record_type) => {
+            let mut field_names = record_type.field_types.keys().collect::<Vec<_>>();
+            field_names.sort();
+            let fields = field_names
+                .into_iter()
+                .map(|name| {
+                    format!(
+                        "readonly {name}: {}",
+                        print_type(&record_type.field_types[name], configuration, used)
+                    )
+                })
+                .collect::<Vec<_>>();
+            if fields.is_empty() {
+                return "{}".to_string();
+            }
+            format!("{{ {} }}", fields.join("; "))
+        }
+        ConcreteType::TagUnion(tag_union_type) => {
+            let mut tag_names = tag_union_type.tag_types.keys().collect::<Vec<_>>();
+            tag_names.sort();
+            print_union(
+                &tag_names
+                    .into_iter()
+                    .map(|name| {
+                        let contents = &tag_union_type.tag_types[name];
+                        match configuration.tag_representation {
+                            TagRepresentation::Integers if contents.is_empty() => {
+                                get_tag_discriminant(name).to_string()
+                            }
+                            representation => print_tagged_tuple(
+                                &print_tag_name(name, representation),
+                                contents,
+                                configuration,
+                                used,
+                            ),
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        }
+        // Variants are numbered by the position of their name in sorted order.
+        // Enums without payloads are just the number.
+        ConcreteType::Enum(enum_type) => {
+            let mut variant_names = enum_type.variants.keys().collect::<Vec<_>>();
+            variant_names.sort();
+            let has_payload = enum_type
+                .variants
+                .values()
+                .any(|payload| !payload.is_empty());
+            print_union(
+                &variant_names
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, name)| {
+                        if has_payload {
+                            print_tagged_tuple(
+                                &index.to_string(),
+                                &enum_type.variants[name],
+                                configuration,
+                                used,
+                            )
+                        } else {
+                            index.to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        }
+    }
+}
+
+/// Prints a TypeScript type on its own, e.g. for a `JSDoc` annotation.
+pub fn print_typescript_type(
+    concrete_type: &ConcreteType,
+    configuration: &PrinterConfiguration,
+) -> String {
+    print_type(
+        concrete_type,
+        configuration,
+        &mut UsedPreludeTypes::default(),
+    )
+}
+
+fn print_declaration_type(
+    declaration: &ConcreteDeclarationExpression,
+    configuration: &PrinterConfiguration,
+    used: &mut UsedPreludeTypes,
+) -> String {
+    // Functions written in the declaration keep their argument names.
+    match (&declaration.declaration_type, &declaration.value) {
+        (ConcreteType::Function(function_type), ConcreteExpression::Function(function)) => {
+            print_function_type(
+                function_type,
+                Some(&function.argument_names),
+                configuration,
+                used,
+            )
+        }
+        (declaration_type, _) => print_type(declaration_type, configuration, used),
+    }
+}
+
+/// Prints a TypeScript declaration file which describes the exported values of
+/// the compiled JS. Tags and `Int`s are described the way the configuration prints
+/// them.
+#[must_use]
+pub fn print_typescript_declarations(
+    document: &TypedDocument<ConcreteType>,
+    configuration: &PrinterConfiguration,
+) -> String {
+    let mut used = UsedPreludeTypes::default();
+    let declarations = document
+        .variable_declarations
+        .iter()
+        .filter(|declaration| declaration.is_exported)
+        .map(|declaration| {
+            format!(
+                "export declare const {}: {};\n",
+                mangle_variable_name(&declaration.declaration.identifier.name),
+                print_declaration_type(&declaration.declaration, configuration, &mut used)
+            )
+        })
+        .collect::<Vec<_>>()
+        .concat();
+    let mut result = String::new();
+    if used.map {
+        result.push_str(MAP_INTERFACE);
+    }
+    if used.set {
+        result.push_str(SET_INTERFACE);
+    }
+    result.push_str(&declarations);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tags::TagRepresentation;
+    use ast::TopLevelDeclaration;
+    use indexmap::IndexMap;
+    use typed_ast::{
+        ConcreteEnumType, ConcreteFunctionExpression, ConcreteListType, ConcreteRecordType,
+        ConcreteTagUnionType, SourceLocation,
+    };
+
+    fn print_type_for_test(concrete_type: &ConcreteType) -> String {
+        print_type(
+            concrete_type,
+            &configuration_for_test(),
+            &mut UsedPreludeTypes::default(),
+        )
+    }
+
+    fn configuration_for_test() -> PrinterConfiguration {
+        PrinterConfiguration {
+            tag_representation: TagRepresentation::Names,
+            ..PrinterConfiguration::default()
+        }
+    }
+
+    fn document_for_test(
+        declarations: Vec<(ConcreteDeclarationExpression, bool)>,
+    ) -> TypedDocument<'static, ConcreteType> {
+        TypedDocument {
+            imports: vec![],
+            type_declarations: vec![],
+            variable_declarations: declarations
+                .into_iter()
+                .map(|(declaration, is_exported)| TopLevelDeclaration {
+                    declaration,
+                    is_exported,
+                })
+                .collect(),
+            expressions: vec![],
+        }
+    }
+
+    fn declaration_for_test(
+        name: &str,
+        declaration_type: ConcreteType,
+        value: ConcreteExpression,
+    ) -> ConcreteDeclarationExpression {
+        ConcreteDeclarationExpression {
+            declaration_type: declaration_type.clone(),
+            expression_type: declaration_type,
+            identifier: ConcreteExpression::raw_identifier_for_test(name),
+            value,
+            is_reassignment: false,
+            source: SourceLocation::default(),
+        }
+    }
+
+    #[test]
+    fn primitives_are_numbers_strings_and_booleans() {
+        assert_eq!(
+            print_type_for_test(&ConcreteType::default_integer_for_test()),
+            "number"
+        );
+        assert_eq!(
+            print_type_for_test(&ConcreteType::default_float_for_test()),
+            "number"
+        );
+        assert_eq!(
+            print_type_for_test(&ConcreteType::default_string_for_test()),
+            "string"
+        );
+        assert_eq!(
+            print_type_for_test(&ConcreteType::Primitive(PrimitiveType::CompilerBoolean)),
+            "boolean"
+        );
+    }
+
+    #[test]
+    fn unresolved_types_are_numbers_or_unknown() {
+        assert_eq!(
+            print_type_for_test(&ConcreteType::Primitive(PrimitiveType::Num)),
+            "number"
+        );
+        assert_eq!(
+            print_type_for_test(&ConcreteType::Primitive(PrimitiveType::Unknown)),
+            "unknown"
+        );
+    }
+
+    #[test]
+    fn lists_are_readonly_arrays() {
+        let concrete_type = ConcreteType::List(Box::new(ConcreteListType {
+            element_type: ConcreteType::default_list_for_test(),
+        }));
+        assert_eq!(
+            print_type_for_test(&concrete_type),
+            "ReadonlyArray<ReadonlyArray<string>>"
+        );
+    }
+
+    #[test]
+    fn records_are_objects_with_sorted_fields() {
+        let concrete_type = ConcreteType::Record(Box::new(ConcreteRecordType {
+            field_types: IndexMap::from([
+                ("name".to_string(), ConcreteType::default_string_for_test()),
+                ("age".to_string(), ConcreteType::default_integer_for_test()),
+            ]),
+        }));
+        assert_eq!(
+            print_type_for_test(&concrete_type),
+            "{ readonly age: number; readonly name: string }"
+        );
+    }
+
+    #[test]
+    fn tuples_are_readonly_tuples() {
+        assert_eq!(
+            print_type_for_test(&ConcreteType::default_tuple_for_test()),
+            "readonly [number, string]"
+        );
+    }
+
+    #[test]
+    fn tag_unions_are_unions_of_tagged_tuples() {
+        let concrete_type = ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
+            tag_types: IndexMap::from([
+                (
+                    "some".to_string(),
+                    vec![ConcreteType::default_integer_for_test()],
+                ),
+                ("none".to_string(), vec![]),
+            ]),
+        }));
+        assert_eq!(
+            print_type_for_test(&concrete_type),
+            "readonly [\"none\"] | readonly [\"some\", number]"
+        );
+    }
+
+    #[test]
+    fn integer_tags_are_discriminants() {
+        let concrete_type = ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
+            tag_types: IndexMap::from([
+                (
+                    "some".to_string(),
+                    vec![ConcreteType::default_integer_for_test()],
+                ),
+                ("none".to_string(), vec![]),
+            ]),
+        }));
+        assert_eq!(
+            print_type(
+                &concrete_type,
+                &PrinterConfiguration {
+                    tag_representation: TagRepresentation::Integers,
+                    ..configuration_for_test()
+                },
+                &mut UsedPreludeTypes::default()
+            ),
+            format!(
+                "{} | readonly [{}, number]",
+                get_tag_discriminant("none"),
+                get_tag_discriminant("some")
+            )
+        );
+    }
+
+    #[test]
+    fn enums_are_variant_indexes() {
+        let concrete_type = ConcreteType::Enum(Box::new(ConcreteEnumType {
+            variants: IndexMap::from([("red".to_string(), vec![]), ("blue".to_string(), vec![])]),
+        }));
+        assert_eq!(print_type_for_test(&concrete_type), "0 | 1");
+        let concrete_type = ConcreteType::Enum(Box::new(ConcreteEnumType {
+            variants: IndexMap::from([
+                (
+                    "some".to_string(),
+                    vec![ConcreteType::default_string_for_test()],
+                ),
+                ("none".to_string(), vec![]),
+            ]),
+        }));
+        assert_eq!(
+            print_type_for_test(&concrete_type),
+            "readonly [0] | readonly [1, string]"
+        );
+    }
+
+    #[test]
+    fn maps_declare_the_prelude_interface() {
+        let document = document_for_test(vec![(
+            declaration_for_test(
+                "ages",
+                ConcreteType::default_map_for_test(),
+                ConcreteExpression::integer_for_test(0),
+            ),
+            true,
+        )]);
+        let declarations = print_typescript_declarations(&document, &configuration_for_test());
+        assert!(declarations.starts_with("interface $Map<K, V> {"));
+        assert!(declarations.ends_with("export declare const Bages: $Map<string, number>;\n"));
+    }
+
+    #[test]
+    fn only_exported_declarations_are_declared() {
+        let function_type = ConcreteType::Function(Box::new(ConcreteFunctionType {
+            argument_types: vec![ConcreteType::default_integer_for_test()],
+            return_type: ConcreteType::default_string_for_test(),
+        }));
+        let function = ConcreteExpression::Function(Box::new(ConcreteFunctionExpression {
+            expression_type: function_type.clone(),
+            source: SourceLocation::default(),
+            argument_names: vec!["count".to_string()],
+            body: ConcreteExpression::string_for_test("many"),
+        }));
+        let document = document_for_test(vec![
+            (
+                declaration_for_test("describe", function_type, function),
+                true,
+            ),
+            (
+                declaration_for_test(
+                    "secret",
+                    ConcreteType::default_integer_for_test(),
+                    ConcreteExpression::integer_for_test(42),
+                ),
+                false,
+            ),
+        ]);
+        assert_eq!(
+            print_typescript_declarations(&document, &configuration_for_test()),
+            "export declare const Bdescribe: (Bcount: number) => string;\n"
+        );
+    }
+
+    #[test]
+    fn bigint_integers_are_declared_as_bigints() {
+        let document = document_for_test(vec![(
+            declaration_for_test(
+                "id",
+                ConcreteType::default_integer_for_test(),
+                ConcreteExpression::integer_for_test(42),
+            ),
+            true,
+        )]);
+        let configuration = PrinterConfiguration {
+            integer_representation: IntegerRepresentation::BigInt,
+            ..configuration_for_test()
+        };
+        assert_eq!(
+            print_typescript_declarations(&document, &configuration),
+            "export declare const Bid: bigint;\n"
+        );
+    }
+}
@@ -1,10 +1,61 @@
+use compiler::{
+    get_imported_paths, IntegerRepresentation, ModuleFormat, OutputStyle, Prelude,
+    TagRepresentation,
+};
+use std::collections::BTreeSet;
 use std::ffi::OsStr;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Appends a `sourceMappingURL` comment to the compiled JS, so that runtimes find the
+/// source map without being told where it is.
+const SOURCE_MAPPING_URL_FLAG: &str = "--source-mapping-url";
+/// Prints readable, indented JS. This is the default in debug builds.
+const PRETTY_FLAG: &str = "--pretty";
+/// Prints JS without any whitespace that isn't needed. This is the default in
+/// release builds.
+const COMPACT_FLAG: &str = "--compact";
+/// Annotates the compiled JS with `JSDoc` types, so editors can check code which uses it.
+const JSDOC_FLAG: &str = "--jsdoc";
+/// Prints a `CommonJS` module, which uses `require` and `module.exports`, to a `.cjs` file.
+const COMMONJS_FLAG: &str = "--commonjs";
+/// Imports the prelude from the given module specifier, e.g. `--prelude=buri-prelude`.
+const PRELUDE_FLAG: &str = "--prelude=";
+/// Copies the parts of the prelude which are used into the compiled JS, so it has no
+/// imports of its own.
+const INLINE_PRELUDE_FLAG: &str = "--inline-prelude";
+/// Represents tags as arrays of their name and contents. This is the default.
+const TAG_NAMES_FLAG: &str = "--tag-names";
+/// Represents tags by integers computed from their names, which are faster to compare.
+const INTEGER_TAGS_FLAG: &str = "--integer-tags";
+/// Compiles `Int`s to `BigInt`s, so arithmetic on them is exact beyond 2^53 - 1.
+const BIGINT_FLAG: &str = "--bigint";
+/// Lists the files which aren't formatted instead of formatting them.
+const CHECK_FLAG: &str = "--check";
 
 pub struct CliArguments {
     pub source: String,
     pub destination: String,
+    pub source_map: String,
+    /// TypeScript finds the declarations for `name.mjs` in `name.d.mts`, and for
+    /// `name.cjs` in `name.d.cts`.
+    pub typescript_declarations: String,
+    /// The Buri file's path relative to the source map.
+    pub source_map_source: String,
+    pub source_mapping_url: bool,
+    pub output_style: OutputStyle,
+    pub jsdoc: bool,
+    pub module_format: ModuleFormat,
+    pub prelude: Prelude,
+    pub tag_representation: TagRepresentation,
+    pub integer_representation: IntegerRepresentation,
+}
+
+pub struct FormatArguments {
+    pub check: bool,
+    /// The Buri files to format, including those in the directories which were given.
+    pub files: Vec<PathBuf>,
 }
 
 fn stringify_path(path: &Path) -> Result<String, String> {
@@ -52,11 +103,54 @@ fn get_workspace_directory() -> Result<PathBuf, String> {
         )
 }
 
-fn derive_destination_path_from_source_path(source_path: &Path) -> Result<String, String> {
+fn derive_destination_path_from_source_path(
+    source_path: &Path,
+    module_format: ModuleFormat,
+) -> Result<String, String> {
     let workspace_directory = get_workspace_directory()?;
     let output_path =
         &mut workspace_directory.join(PathBuf::from(".buri/dist").join(PathBuf::from(source_path)));
-    stringify_path(output_path.with_extension("mjs").as_path())
+    stringify_path(
+        output_path
+            .with_extension(module_format.extension())
+            .as_path(),
+    )
+}
+
+/// Finds the path which leads from a directory to a file. Both paths must be absolute.
+fn get_relative_path(from_directory: &Path, to: &Path) -> PathBuf {
+    let from_components = from_directory.components().collect::<Vec<_>>();
+    let to_components = to.components().collect::<Vec<_>>();
+    let shared_count = from_components
+        .iter()
+        .zip(&to_components)
+        .take_while(|(from, to)| from == to)
+        .count();
+    let mut result = PathBuf::new();
+    for _ in shared_count..from_components.len() {
+        result.push("..");
+    }
+    for component in &to_components[shared_count..] {
+        result.push(component);
+    }
+    result
+}
+
+fn derive_source_map_source(source_path: &Path, destination_path: &Path) -> Result<String, String> {
+    let absolute_source_path = match source_path.canonicalize() {
+        Ok(x) => x,
+        Err(e) => {
+            return Err(format!(
+                "Error reading source file {}: {e}",
+                stringify_path(source_path)?
+            ))
+        }
+    };
+    let destination_directory = destination_path.parent().unwrap_or(destination_path);
+    stringify_path(&get_relative_path(
+        destination_directory,
+        &absolute_source_path,
+    ))
 }
 
 fn create_destination_directory(destination_path: &Path) -> Result<(), String> {
@@ -73,20 +167,310 @@ fn create_destination_directory(destination_path: &Path) -> Result<(), String> {
     }
 }
 
-pub fn get_file_paths(arguments: &Vec<String>) -> Result<CliArguments, String> {
-    if arguments.len() > 2 {
+pub fn get_file_paths(arguments: &[String]) -> Result<CliArguments, String> {
+    let (flags, paths): (Vec<&String>, Vec<&String>) = arguments
+        .iter()
+        .skip(1)
+        .partition(|argument| argument.starts_with("--"));
+    let mut source_mapping_url = false;
+    let mut output_style = OutputStyle::default();
+    let mut jsdoc = false;
+    let mut module_format = ModuleFormat::default();
+    let mut prelude = Prelude::default();
+    let mut tag_representation = TagRepresentation::default();
+    let mut integer_representation = IntegerRepresentation::default();
+    for flag in flags {
+        match flag.as_str() {
+            SOURCE_MAPPING_URL_FLAG => source_mapping_url = true,
+            PRETTY_FLAG => output_style = OutputStyle::Pretty,
+            COMPACT_FLAG => output_style = OutputStyle::Compact,
+            JSDOC_FLAG => jsdoc = true,
+            COMMONJS_FLAG => module_format = ModuleFormat::CommonJs,
+            INLINE_PRELUDE_FLAG => prelude = Prelude::Inline,
+            TAG_NAMES_FLAG => tag_representation = TagRepresentation::Names,
+            INTEGER_TAGS_FLAG => tag_representation = TagRepresentation::Integers,
+            BIGINT_FLAG => integer_representation = IntegerRepresentation::BigInt,
+            _ => match flag.strip_prefix(PRELUDE_FLAG) {
+                Some(specifier) => prelude = Prelude::Import(specifier.to_string()),
+                None => return Err(format!("Unknown flag provided: {flag}")),
+            },
+        }
+    }
+    if paths.len() > 1 {
         return Err(String::from("Too many arguments provided"));
     }
-    let source_path = match arguments.get(1) {
+    let source_path = match paths.first() {
         Some(source_path) => Path::new(source_path),
         None => return Err(String::from("No source file provided")),
     };
     verify_path(source_path, "buri")?;
-    let derived_destination_path = derive_destination_path_from_source_path(source_path)?;
+    let derived_destination_path =
+        derive_destination_path_from_source_path(source_path, module_format)?;
     let destination_path = Path::new(&derived_destination_path);
     create_destination_directory(destination_path)?;
     Ok(CliArguments {
         source: stringify_path(source_path)?,
         destination: stringify_path(destination_path)?,
+        source_map: format!("{derived_destination_path}.map"),
+        typescript_declarations: stringify_path(
+            destination_path
+                .with_extension(module_format.declaration_extension())
+                .as_path(),
+        )?,
+        source_map_source: derive_source_map_source(source_path, destination_path)?,
+        source_mapping_url,
+        output_style,
+        jsdoc,
+        module_format,
+        prelude,
+        tag_representation,
+        integer_representation,
     })
 }
+
+/// Reads the Buri file and every Buri file which it imports, directly or through
+/// other files. Imports which aren't relative paths aren't files of the program.
+pub fn read_program_files(source: &Path) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    let mut read_paths = BTreeSet::new();
+    let mut unread_paths = vec![source.to_path_buf()];
+    while let Some(path) = unread_paths.pop() {
+        let path = path
+            .canonicalize()
+            .map_err(|e| format!("Error reading source file {}: {e}", path.display()))?;
+        if !read_paths.insert(path.clone()) {
+            continue;
+        }
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Error reading source file {}: {e}", path.display()))?;
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        for imported_path in get_imported_paths(&contents)? {
+            if imported_path.starts_with('.') {
+                unread_paths.push(directory.join(imported_path));
+            }
+        }
+        files.push(contents);
+    }
+    Ok(files)
+}
+
+fn get_buri_files(directory: &Path) -> Vec<PathBuf> {
+    WalkDir::new(directory)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry.file_type().is_file() && entry.path().extension() == Some(OsStr::new("buri"))
+        })
+        .map(walkdir::DirEntry::into_path)
+        .collect()
+}
+
+/// Parses the arguments of `mjolnirjs_spark fmt [--check] <paths>`.
+pub fn get_format_arguments(arguments: &[String]) -> Result<FormatArguments, String> {
+    let (flags, paths): (Vec<&String>, Vec<&String>) = arguments
+        .iter()
+        .skip(2)
+        .partition(|argument| argument.starts_with("--"));
+    let mut check = false;
+    for flag in flags {
+        match flag.as_str() {
+            CHECK_FLAG => check = true,
+            _ => return Err(format!("Unknown flag provided: {flag}")),
+        }
+    }
+    if paths.is_empty() {
+        return Err(String::from("No files or directories provided"));
+    }
+    let mut files = Vec::new();
+    for path in paths {
+        let path = Path::new(path);
+        if path.is_dir() {
+            files.extend(get_buri_files(path));
+        } else {
+            verify_path(path, "buri")?;
+            files.push(path.to_path_buf());
+        }
+    }
+    Ok(FormatArguments { check, files })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn relative_paths_go_up_to_the_shared_directory() {
+        assert_eq!(
+            get_relative_path(
+                Path::new("/workspace/.buri/dist/examples"),
+                Path::new("/workspace/examples/hello.buri")
+            ),
+            PathBuf::from("../../../examples/hello.buri")
+        );
+    }
+
+    #[test]
+    fn unknown_flags_are_rejected() {
+        let arguments = vec![
+            String::from("mjolnirjs_spark"),
+            String::from("--minify"),
+            String::from("hello.buri"),
+        ];
+        assert_eq!(
+            get_file_paths(&arguments).err(),
+            Some(String::from("Unknown flag provided: --minify"))
+        );
+    }
+
+    #[test]
+    fn commonjs_output_is_written_to_cjs_files() {
+        let arguments = vec![
+            String::from("mjolnirjs_spark"),
+            String::from("--commonjs"),
+            String::from("--prelude=buri-prelude"),
+            String::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../tests/js/valid/strings/size.buri"
+            )),
+        ];
+        let cli_arguments = get_file_paths(&arguments).unwrap();
+        assert_eq!(cli_arguments.module_format, ModuleFormat::CommonJs);
+        assert_eq!(
+            cli_arguments.prelude,
+            Prelude::Import(String::from("buri-prelude"))
+        );
+        assert!(cli_arguments.destination.ends_with("size.cjs"));
+        assert!(cli_arguments
+            .typescript_declarations
+            .ends_with("size.d.cts"));
+    }
+
+    #[test]
+    fn the_last_tag_flag_chooses_how_tags_are_printed() {
+        let arguments = vec![
+            String::from("mjolnirjs_spark"),
+            String::from("--tag-names"),
+            String::from("--integer-tags"),
+            String::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../tests/js/valid/strings/size.buri"
+            )),
+        ];
+        assert_eq!(
+            get_file_paths(&arguments).unwrap().tag_representation,
+            TagRepresentation::Integers
+        );
+    }
+
+    #[test]
+    fn tags_are_printed_with_names_by_default() {
+        let arguments = vec![
+            String::from("mjolnirjs_spark"),
+            String::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../tests/js/valid/strings/size.buri"
+            )),
+        ];
+        let cli_arguments = get_file_paths(&arguments).unwrap();
+        assert_eq!(cli_arguments.tag_representation, TagRepresentation::Names);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn debug_builds_print_pretty_output_unless_asked_for_compact_output() {
+        let mut arguments = vec![
+            String::from("mjolnirjs_spark"),
+            String::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../tests/js/valid/strings/size.buri"
+            )),
+        ];
+        assert_eq!(
+            get_file_paths(&arguments).unwrap().output_style,
+            OutputStyle::Pretty
+        );
+        arguments.insert(1, String::from("--compact"));
+        assert_eq!(
+            get_file_paths(&arguments).unwrap().output_style,
+            OutputStyle::Compact
+        );
+    }
+
+    #[test]
+    fn programs_are_read_through_their_relative_imports() {
+        let directory = std::env::temp_dir().join("buri-program-files");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(
+            directory.join("main.buri"),
+            "import first from \"./first.buri\"\nimport other from \"@std/other.buri\"\n\n@export\nsecond = #hraba\n",
+        )
+        .unwrap();
+        std::fs::write(
+            directory.join("first.buri"),
+            "import second from \"./main.buri\"\n\n@export\nfirst = #dsbjm\n",
+        )
+        .unwrap();
+        let files = read_program_files(&directory.join("main.buri")).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files[1].contains("#dsbjm"));
+        assert_eq!(
+            compiler::check_program_tag_discriminants(&files),
+            Err(String::from("Tags #dsbjm and #hraba have the same integer representation. Rename one of them, or compile with --tag-names."))
+        );
+    }
+
+    #[test]
+    fn bigint_flag_compiles_integers_to_bigints() {
+        let arguments = vec![
+            String::from("mjolnirjs_spark"),
+            String::from("--bigint"),
+            String::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../tests/js/valid/strings/size.buri"
+            )),
+        ];
+        assert_eq!(
+            get_file_paths(&arguments).unwrap().integer_representation,
+            IntegerRepresentation::BigInt
+        );
+    }
+
+    #[test]
+    fn formatting_directories_finds_their_buri_files() {
+        let arguments = vec![
+            String::from("mjolnirjs_spark"),
+            String::from("fmt"),
+            String::from("--check"),
+            String::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../tests/js/valid/strings"
+            )),
+        ];
+        let format_arguments = get_format_arguments(&arguments).unwrap();
+        assert!(format_arguments.check);
+        let names = format_arguments
+            .files
+            .iter()
+            .filter_map(|path| path.file_name()?.to_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "get-char-code.buri",
+                "size.buri",
+                "string.buri",
+                "type-declaration.buri"
+            ]
+        );
+    }
+
+    #[test]
+    fn formatting_needs_a_path() {
+        let arguments = vec![String::from("mjolnirjs_spark"), String::from("fmt")];
+        assert_eq!(
+            get_format_arguments(&arguments).err(),
+            Some(String::from("No files or directories provided"))
+        );
+    }
+}
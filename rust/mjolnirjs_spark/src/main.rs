@@ -1,48 +1,118 @@
-use compiler::compile_buri_file;
-use mjolnirjs_spark::get_file_paths;
+use compiler::{
+    check_program_tag_discriminants, compile_buri_file_with_artifacts, PrinterConfiguration,
+    TagRepresentation,
+};
+use formatter::format_buri_file;
+use mjolnirjs_spark::{get_file_paths, get_format_arguments, read_program_files};
 use std::env;
 use std::fs::File;
-use std::io::Write;
+use std::io::{self, Write};
+use std::path::Path;
 
-fn main_impl() -> Result<(), String> {
-    let arguments: Vec<String> = env::args().collect();
-    let file_paths = get_file_paths(&arguments)?;
-    let compiled_output = match std::fs::read_to_string(&file_paths.source) {
-        Ok(x) => compile_buri_file(&x)?,
-        Err(e) => {
-            return Err(format!(
-                "Error reading source file {}: {e}",
-                file_paths.source
-            ))
-        }
-    };
-    let mut output = match File::create(&file_paths.destination) {
+fn write_output_file(path: &str, contents: &str) -> Result<(), String> {
+    let mut output = match File::create(path) {
         Ok(x) => x,
-        Err(e) => {
-            return Err(format!(
-                "Error creating output file {}: {e}",
-                file_paths.destination
-            ))
-        }
+        Err(e) => return Err(format!("Error creating output file {path}: {e}")),
     };
-    match write!(output, "{compiled_output}") {
-        Ok(_) => {}
+    match write!(output, "{contents}") {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("Error writing to output file {path}: {e}")),
+    }
+}
+
+fn get_file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+}
+
+/// Formats Buri files in place, or with `--check` lists the ones which aren't formatted.
+fn format_files(arguments: &[String]) -> Result<(), String> {
+    let format_arguments = get_format_arguments(arguments)?;
+    let mut unformatted_count = 0;
+    for path in &format_arguments.files {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("Error reading source file {}: {e}", path.display()))?;
+        let formatted =
+            format_buri_file(&source).map_err(|e| format!("{}: {e}", path.display()))?;
+        if formatted == source {
+            continue;
+        }
+        if format_arguments.check {
+            println!("{}", path.display());
+            unformatted_count += 1;
+        } else {
+            std::fs::write(path, formatted)
+                .map_err(|e| format!("Error writing to file {}: {e}", path.display()))?;
+        }
+    }
+    match unformatted_count {
+        0 => Ok(()),
+        1 => Err(String::from("1 file is not formatted")),
+        count => Err(format!("{count} files are not formatted")),
+    }
+}
+
+fn main_impl(arguments: &[String]) -> Result<(), String> {
+    if arguments.get(1).map(String::as_str) == Some("repl") {
+        return repl::run(io::stdin().lock(), io::stdout())
+            .map_err(|e| format!("Error running the REPL: {e}"));
+    }
+    let file_paths = get_file_paths(arguments)?;
+    if file_paths.tag_representation == TagRepresentation::Integers {
+        check_program_tag_discriminants(&read_program_files(Path::new(&file_paths.source))?)?;
+    }
+    let mut compiled_file = match std::fs::read_to_string(&file_paths.source) {
+        Ok(x) => compile_buri_file_with_artifacts(
+            &x,
+            &PrinterConfiguration {
+                output_style: file_paths.output_style,
+                jsdoc: file_paths.jsdoc,
+                module_format: file_paths.module_format,
+                prelude: file_paths.prelude.clone(),
+                tag_representation: file_paths.tag_representation,
+                integer_representation: file_paths.integer_representation,
+            },
+            &file_paths.source_map_source,
+            &get_file_name(&file_paths.destination),
+        )?,
         Err(e) => {
             return Err(format!(
-                "Error writing to output file {}: {e}",
-                file_paths.destination
+                "Error reading source file {}: {e}",
+                file_paths.source
             ))
         }
     };
+    for warning in &compiled_file.warnings {
+        println!("{warning}");
+    }
+    if file_paths.source_mapping_url {
+        compiled_file.javascript.push_str("\n//# sourceMappingURL=");
+        compiled_file
+            .javascript
+            .push_str(&get_file_name(&file_paths.source_map));
+    }
+    write_output_file(&file_paths.destination, &compiled_file.javascript)?;
+    write_output_file(&file_paths.source_map, &compiled_file.source_map.to_json())?;
+    write_output_file(
+        &file_paths.typescript_declarations,
+        &compiled_file.typescript_declarations,
+    )?;
     Ok(())
 }
 
 fn main() -> Result<(), String> {
-    match main_impl() {
+    let arguments: Vec<String> = env::args().collect();
+    let (result, failure) = if arguments.get(1).map(String::as_str) == Some("fmt") {
+        (format_files(&arguments), "Could not format Buri files")
+    } else {
+        (main_impl(&arguments), "Could not compile Buri file")
+    };
+    match result {
         Ok(_) => Ok(()),
         Err(e) => {
             println!("{e}");
-            Err(String::from("Could not compile Buri file"))
+            Err(String::from(failure))
         }
     }
 }
@@ -1,9 +1,10 @@
 use crate::{
-    enum_literal::enum_literal, function::function, identifier::identifier, integer::integer,
-    list::list, parentheses::parentheses, record::record, record_assignment::record_assignment,
-    string_literal::string_literal, tag::tag, type_declaration::type_declaration,
-    unary_operator::unary_operator_expression, variable_declaration::variable_declaration,
-    ExpressionContext,
+    enum_literal::enum_literal, float::float, function::function, identifier::identifier,
+    integer::integer, list::list, map::map_literal, parentheses::parentheses, record::record,
+    record_assignment::record_assignment, set::set_literal, string_literal::string_literal,
+    tag::tag, tuple::tuple_literal, tuple_declaration::tuple_declaration,
+    type_declaration::type_declaration, unary_operator::unary_operator_expression,
+    variable_declaration::variable_declaration, ExpressionContext,
 };
 use ast::{Expression, IResult, ParserInput};
 use nom::{branch::alt, combinator::map};
@@ -13,6 +14,11 @@ pub fn basic_expression<'a>(
 ) -> impl FnMut(ParserInput<'a>) -> IResult<'a, Expression<'a>> {
     alt((
         map(move |input| function(context, input), Expression::Function),
+        map(
+            move |input| tuple_declaration(context, input),
+            Expression::TupleDeclaration,
+        ),
+        map(tuple_literal, Expression::Tuple),
         parentheses,
         map(type_declaration, Expression::TypeDeclaration),
         map(
@@ -24,10 +30,13 @@ pub fn basic_expression<'a>(
             Expression::UnaryOperator,
         ),
         map(identifier, Expression::Identifier),
+        map(float, Expression::Float),
         map(integer, Expression::Integer),
         map(string_literal, Expression::StringLiteral),
         map(enum_literal(context), Expression::EnumLiteral),
         map(list, Expression::List),
+        map(map_literal, Expression::Map),
+        map(set_literal, Expression::Set),
         map(record, Expression::Record),
         map(tag, Expression::Tag),
         map(
@@ -59,6 +68,16 @@ mod test {
         assert!(matches!(consumed, Expression::Integer(_)));
     }
 
+    #[test]
+    fn expression_can_be_float() {
+        let input = ParserInput::new("0.5");
+        let result =
+            basic_expression(ExpressionContext::new().allow_newlines_in_expressions())(input);
+        let (remainder, consumed) = result.unwrap();
+        assert!(remainder.is_empty());
+        assert!(matches!(consumed, Expression::Float(_)));
+    }
+
     #[test]
     fn expression_can_be_string_literal() {
         let input = ParserInput::new("\"hello\"");
@@ -77,6 +96,52 @@ mod test {
         assert!(matches!(consumed, Expression::List(_)));
     }
 
+    #[test]
+    fn expression_can_be_a_map() {
+        let input = ParserInput::new("Map[\"hello\" => 1]");
+        let result =
+            basic_expression(ExpressionContext::new().allow_newlines_in_expressions())(input);
+        let (_, consumed) = result.unwrap();
+        assert!(matches!(consumed, Expression::Map(_)));
+    }
+
+    #[test]
+    fn expression_can_be_a_set() {
+        let input = ParserInput::new("Set[\"hello\"]");
+        let result =
+            basic_expression(ExpressionContext::new().allow_newlines_in_expressions())(input);
+        let (_, consumed) = result.unwrap();
+        assert!(matches!(consumed, Expression::Set(_)));
+    }
+
+    #[test]
+    fn expression_can_be_tuple() {
+        let input = ParserInput::new("(1, \"hello\")");
+        let result =
+            basic_expression(ExpressionContext::new().allow_newlines_in_expressions())(input);
+        let (_, consumed) = result.unwrap();
+        assert!(matches!(consumed, Expression::Tuple(_)));
+    }
+
+    #[test]
+    fn expression_can_be_tuple_declaration() {
+        let input = ParserInput::new("(a, b) = pair");
+        let result =
+            basic_expression(ExpressionContext::new().allow_newlines_in_expressions())(input);
+        let (remainder, consumed) = result.unwrap();
+        assert_eq!(remainder, "");
+        assert!(matches!(consumed, Expression::TupleDeclaration(_)));
+    }
+
+    #[test]
+    fn parenthesized_expression_is_not_a_tuple() {
+        let input = ParserInput::new("(1)");
+        let result =
+            basic_expression(ExpressionContext::new().allow_newlines_in_expressions())(input);
+        let (_, consumed) = result.unwrap();
+        assert!(matches!(consumed, Expression::Integer(_)));
+    }
+
     #[test]
     fn expression_can_be_a_record() {
         let input = ParserInput::new("{a: 0}");
@@ -1,5 +1,5 @@
 use crate::{
-    basic_expression::basic_expression, expression, identifier::identifier,
+    basic_expression::basic_expression, expression, identifier::identifier, integer::integer,
     intra_expression_whitespace::intra_expression_whitespace, ExpressionContext,
 };
 use ast::{
@@ -93,18 +93,24 @@ fn binary_operator_segment_not_allowing_spaces(
     input: ParserInput,
 ) -> IResult<BinaryOperatorSegment> {
     map(
-        tuple((
-            consumed(alt((
-                // for correct parsing, check 2-character symbols before 1-character symbols
-                value(BinaryOperatorSymbol::MethodLookup, tag(":")),
-                value(BinaryOperatorSymbol::FieldLookup, tag(".")),
-            ))),
-            identifier,
+        alt((
+            tuple((
+                consumed(value(BinaryOperatorSymbol::MethodLookup, tag(":"))),
+                map(identifier, Expression::Identifier),
+            )),
+            tuple((
+                consumed(value(BinaryOperatorSymbol::FieldLookup, tag("."))),
+                alt((
+                    map(identifier, Expression::Identifier),
+                    // Tuple elements are looked up by their position, e.g. `pair.0`.
+                    map(integer, Expression::Integer),
+                )),
+            )),
         )),
-        |((source, symbol), identifier_node)| BinaryOperatorSegment {
+        |((source, symbol), expression)| BinaryOperatorSegment {
             source,
             symbol,
-            expression: Expression::Identifier(identifier_node),
+            expression,
         },
     )(input)
 }
@@ -244,9 +250,13 @@ pub fn binary_operator_expression<'a>(
                     alt((
                         binary_operator_segment_requiring_spaces(context),
                         binary_operator_segment_not_requiring_spaces(context),
-                        function_application(context),
                     )),
                 ),
+                // Arguments on a new line start a new expression, e.g. a tuple declaration.
+                preceded(
+                    intra_expression_whitespace(context.disallow_newlines_in_expressions()),
+                    function_application(context),
+                ),
                 binary_operator_segment_not_requiring_spaces(context),
                 binary_operator_segment_not_allowing_spaces,
                 function_application(context),
@@ -941,6 +951,38 @@ mod test {
         ));
     }
 
+    #[test]
+    fn recognize_tuple_element_lookup() {
+        let input = ParserInput::new("a.0");
+        let result = binary_operator_expression(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )(input);
+        let (remainder, expression) = result.unwrap();
+        assert_eq!(remainder, "");
+        let Expression::BinaryOperator(node) = expression else {
+            panic!("Expected a binary operator expression");
+        };
+        assert_eq!(node.value.symbol, BinaryOperatorSymbol::FieldLookup);
+        assert!(matches!(*node.value.right_child, Expression::Integer(_)));
+    }
+
+    #[test]
+    fn tuple_element_lookups_can_be_chained() {
+        let input = ParserInput::new("a.0.1");
+        let result = binary_operator_expression(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )(input);
+        let (remainder, expression) = result.unwrap();
+        assert_eq!(remainder, "");
+        let Expression::BinaryOperator(node) = expression else {
+            panic!("Expected a binary operator expression");
+        };
+        assert!(matches!(
+            *node.value.left_child,
+            Expression::BinaryOperator(_)
+        ));
+    }
+
     #[test]
     fn with_two_binary_operators_of_equal_order_the_second_is_the_root_of_the_expression() {
         let input = ParserInput::new("1+2-3");
@@ -1,12 +1,32 @@
 use crate::document::document;
 use ast::{DocumentNode, ParserInput};
-use nom::{combinator::eof, sequence::terminated};
+use nom::{combinator::eof, error::ErrorKind, sequence::terminated};
 
 pub fn parse_buri_file(source: &str) -> Result<DocumentNode, String> {
     let input = ParserInput::new(source);
     let result = terminated(document(), eof)(input);
     match result {
         Ok((_, document)) => Ok(document),
+        Err(nom::Err::Failure(error)) if error.code == ErrorKind::TooLarge => Err(format!(
+            "Integer literal {} is too large to fit in 64 bits at line {}, column {}",
+            error.input.value(),
+            error.input.location_line(),
+            error.input.get_utf8_column()
+        )),
         Err(error) => Err(error.to_string()),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integer_overflow_reports_the_location_of_the_literal() {
+        let result = parse_buri_file("a = 1\nb = 2 + 0x1_0000_0000_0000_0000\n");
+        assert_eq!(
+            result.unwrap_err(),
+            "Integer literal 0x1_0000_0000_0000_0000 is too large to fit in 64 bits at line 2, column 9"
+        );
+    }
+}
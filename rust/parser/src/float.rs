@@ -0,0 +1,110 @@
+use crate::integer::separated_digits;
+use ast::FloatNode;
+use ast::{IResult, ParserInput};
+use nom::{
+    branch::alt,
+    character::complete::{char, one_of},
+    combinator::{map, opt, recognize},
+    sequence::{pair, tuple},
+};
+
+fn decimal_digits(input: ParserInput) -> IResult<ParserInput> {
+    separated_digits(|c| c.is_ascii_digit())(input)
+}
+
+fn exponent(input: ParserInput) -> IResult<ParserInput> {
+    recognize(tuple((one_of("eE"), opt(one_of("+-")), decimal_digits)))(input)
+}
+
+/// Parses a float with a decimal point (e.g. `1.5`), an exponent (e.g. `1e3`),
+/// or both (e.g. `1.5e-3`). Digits are required on both sides of the decimal
+/// point. Digits may be separated by underscores, which are removed from the
+/// value. Negatives are not allowed (use the `-` unary operator instead).
+pub fn float(input: ParserInput) -> IResult<FloatNode> {
+    map(
+        recognize(pair(
+            decimal_digits,
+            alt((
+                recognize(tuple((char('.'), decimal_digits, opt(exponent)))),
+                exponent,
+            )),
+        )),
+        |consumed: ParserInput| FloatNode {
+            value: consumed.value().replace('_', ""),
+            source: consumed,
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integers_error() {
+        let input = ParserInput::new("1");
+        let result = float(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn a_negative_sign_errors() {
+        let input = ParserInput::new("-1.5");
+        let result = float(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn can_have_a_decimal_point() {
+        let input = ParserInput::new("1.5");
+        let (remainder, float_node) = float(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(float_node.value, "1.5");
+    }
+
+    #[test]
+    fn can_have_an_exponent() {
+        let input = ParserInput::new("1e3");
+        let (remainder, float_node) = float(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(float_node.value, "1e3");
+    }
+
+    #[test]
+    fn can_have_a_decimal_point_and_a_signed_exponent() {
+        let input = ParserInput::new("1.5E-3");
+        let (remainder, float_node) = float(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(float_node.value, "1.5E-3");
+    }
+
+    #[test]
+    fn underscores_are_removed_from_the_value() {
+        let input = ParserInput::new("1_234.567_89");
+        let (remainder, float_node) = float(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(float_node.value, "1234.56789");
+        assert_eq!(float_node.source.value(), "1_234.567_89");
+    }
+
+    #[test]
+    fn requires_digits_after_the_decimal_point() {
+        let input = ParserInput::new("1.a");
+        let result = float(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn requires_digits_in_the_exponent() {
+        let input = ParserInput::new("1e");
+        let result = float(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn requires_digits_before_the_decimal_point() {
+        let input = ParserInput::new(".5");
+        let result = float(input);
+        assert!(result.is_err());
+    }
+}
@@ -1,16 +1,50 @@
 use ast::IntegerNode;
 use ast::{IResult, ParserInput};
-use nom::{character::complete::digit1, combinator::map};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while, take_while1},
+    combinator::{consumed, recognize, success, value},
+    error::{Error, ErrorKind},
+    multi::many0,
+    sequence::pair,
+};
 
-/// Parses an integer with the digits 0-9. Underscores are not allowed.
+/// Parses a sequence of digits which may be separated by underscores. The
+/// sequence must start and end with a digit.
+pub fn separated_digits<'a>(
+    is_digit: fn(char) -> bool,
+) -> impl FnMut(ParserInput<'a>) -> IResult<'a, ParserInput<'a>> {
+    recognize(pair(
+        take_while1(is_digit),
+        many0(pair(take_while(|c| c == '_'), take_while1(is_digit))),
+    ))
+}
+
+fn radix_and_digits(input: ParserInput) -> IResult<(u32, ParserInput)> {
+    alt((
+        pair(
+            value(16, tag("0x")),
+            separated_digits(|c| c.is_ascii_hexdigit()),
+        ),
+        pair(value(8, tag("0o")), separated_digits(|c| c.is_digit(8))),
+        pair(value(2, tag("0b")), separated_digits(|c| c.is_digit(2))),
+        pair(success(10), separated_digits(|c| c.is_ascii_digit())),
+    ))(input)
+}
+
+/// Parses a decimal (`255`), hexadecimal (`0xFF`), octal (`0o377`) or binary
+/// (`0b1111_1111`) integer. Digits may be separated by underscores. The
+/// original spelling is kept in the node's source.
 /// Negatives are not allowed (use the `-` unary operator instead).
+///
+/// Integers too large to fit in a u64 are a failure rather than an error, so
+/// that the location of the literal is reported instead of backtracking.
 pub fn integer(input: ParserInput) -> IResult<IntegerNode> {
-    map(digit1, |consumed: ParserInput| IntegerNode {
-        // Will default to 0 if the integer is too large or small to fit in an
-        // i64.
-        value: consumed.value().parse::<u64>().unwrap_or(std::u64::MAX),
-        source: consumed,
-    })(input)
+    let (remainder, (source, (radix, digits))) = consumed(radix_and_digits)(input)?;
+    match u64::from_str_radix(&digits.value().replace('_', ""), radix) {
+        Ok(value) => Ok((remainder, IntegerNode { value, source })),
+        Err(_) => Err(nom::Err::Failure(Error::new(source, ErrorKind::TooLarge))),
+    }
 }
 
 #[cfg(test)]
@@ -56,8 +90,90 @@ mod test {
     }
 
     #[test]
-    fn integers_that_cannot_fit_in_an_i64_evaluate_to_zero() {
-        let input = ParserInput::new("9999999999999999999999999999");
+    fn can_be_hexadecimal() {
+        let input = ParserInput::new("0xFf");
+        let (remainder, integer_node) = integer(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(integer_node.value, 255);
+    }
+
+    #[test]
+    fn can_be_octal() {
+        let input = ParserInput::new("0o755");
+        let (remainder, integer_node) = integer(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(integer_node.value, 493);
+    }
+
+    #[test]
+    fn can_be_binary() {
+        let input = ParserInput::new("0b1010");
+        let (remainder, integer_node) = integer(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(integer_node.value, 10);
+    }
+
+    #[test]
+    fn digits_can_be_separated_by_underscores() {
+        let input = ParserInput::new("1_000__000");
+        let (remainder, integer_node) = integer(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(integer_node.value, 1_000_000);
+    }
+
+    #[test]
+    fn prefixed_digits_can_be_separated_by_underscores() {
+        let input = ParserInput::new("0b1111_0000");
+        let (remainder, integer_node) = integer(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(integer_node.value, 240);
+    }
+
+    #[test]
+    fn trailing_underscores_are_not_part_of_the_integer() {
+        let input = ParserInput::new("1_");
+        let (remainder, integer_node) = integer(input).unwrap();
+        assert_eq!(remainder.value(), "_");
+        assert_eq!(integer_node.value, 1);
+    }
+
+    #[test]
+    fn digits_must_follow_the_prefix() {
+        let input = ParserInput::new("0x_FF");
+        let (remainder, integer_node) = integer(input).unwrap();
+        assert_eq!(remainder.value(), "x_FF");
+        assert_eq!(integer_node.value, 0);
+    }
+
+    #[test]
+    fn digits_outside_of_the_radix_are_not_part_of_the_integer() {
+        let input = ParserInput::new("0b102");
+        let (remainder, integer_node) = integer(input).unwrap();
+        assert_eq!(remainder.value(), "2");
+        assert_eq!(integer_node.value, 2);
+    }
+
+    #[test]
+    fn preserves_the_original_spelling() {
+        let input = ParserInput::new("0xFF_FF");
+        let (_, integer_node) = integer(input).unwrap();
+        assert_eq!(integer_node.source.value(), "0xFF_FF");
+    }
+
+    #[test]
+    fn integers_that_cannot_fit_in_a_u64_fail_at_the_literal() {
+        let input = ParserInput::new("0x1_0000_0000_0000_0000");
+        let result = integer(input);
+        let Err(nom::Err::Failure(error)) = result else {
+            panic!("Expected a failure")
+        };
+        assert_eq!(error.code, ErrorKind::TooLarge);
+        assert_eq!(error.input.value(), "0x1_0000_0000_0000_0000");
+    }
+
+    #[test]
+    fn the_largest_u64_fits() {
+        let input = ParserInput::new("18446744073709551615");
         let (remainder, integer_node) = integer(input).unwrap();
         assert!(remainder.is_empty());
         assert_eq!(integer_node.value, std::u64::MAX);
@@ -7,6 +7,7 @@ mod enum_literal;
 mod enum_type;
 mod expression_context;
 mod file;
+mod float;
 mod function;
 mod function_argument;
 mod function_type;
@@ -19,16 +20,24 @@ mod intra_expression_whitespace;
 mod is_keyword;
 mod list;
 mod list_type;
+mod map;
+mod map_type;
 mod newline;
 mod parentheses;
 mod record;
 mod record_assignment;
 mod record_type;
+mod set;
+mod set_type;
 mod string_literal;
+mod syntax_tree;
 mod tag;
 mod tag_group_type;
 mod tag_identifier;
 mod tag_type;
+mod tuple;
+mod tuple_declaration;
+mod tuple_type;
 mod type_declaration;
 mod type_expression;
 mod type_identifier;
@@ -39,6 +48,7 @@ mod when_statement;
 use binary_operator_or_if::binary_operator_or_if as expression;
 use expression_context::ExpressionContext;
 pub use file::parse_buri_file;
+pub use syntax_tree::parse_syntax_tree;
 
 /// Parses an expression for use in unit tests.
 ///
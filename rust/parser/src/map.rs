@@ -0,0 +1,130 @@
+use crate::{
+    expression, intra_expression_whitespace::intra_expression_whitespace, ExpressionContext,
+};
+use ast::{Expression, MapEntryValue, MapNode};
+use ast::{IResult, ParsedNode, ParserInput};
+use nom::{
+    bytes::complete::tag,
+    combinator::{consumed, map, opt},
+    multi::many0,
+    sequence::{delimited, preceded, separated_pair, terminated, tuple},
+};
+
+fn padded_expression(input: ParserInput) -> IResult<Expression> {
+    preceded(
+        opt(intra_expression_whitespace(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )),
+        expression(ExpressionContext::new().allow_newlines_in_expressions()),
+    )(input)
+}
+
+fn padded_tag<'a>(
+    value: &'static str,
+) -> impl FnMut(ParserInput<'a>) -> IResult<'a, ParserInput<'a>> {
+    preceded(
+        opt(intra_expression_whitespace(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )),
+        tag(value),
+    )
+}
+
+fn map_entry(input: ParserInput) -> IResult<MapEntryValue> {
+    map(
+        separated_pair(padded_expression, padded_tag("=>"), padded_expression),
+        |(key, value)| MapEntryValue { key, value },
+    )(input)
+}
+
+fn map_contents(input: ParserInput) -> IResult<Vec<MapEntryValue>> {
+    map(
+        tuple((
+            map_entry,
+            many0(preceded(padded_tag(","), map_entry)),
+            opt(padded_tag(",")),
+        )),
+        |mut entries| {
+            let mut accumulator = Vec::new();
+            accumulator.push(entries.0);
+            accumulator.append(&mut entries.1);
+            accumulator
+        },
+    )(input)
+}
+
+/// Parses a map literal, e.g. `Map["one" => 1, "two" => 2]`.
+pub fn map_literal(input: ParserInput) -> IResult<MapNode> {
+    map(
+        consumed(delimited(
+            tag("Map["),
+            terminated(
+                opt(map_contents),
+                opt(intra_expression_whitespace(
+                    ExpressionContext::new().allow_newlines_in_expressions(),
+                )),
+            ),
+            tag("]"),
+        )),
+        |(consumed_input, produced_output)| ParsedNode {
+            source: consumed_input,
+            value: produced_output.unwrap_or_default(),
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn a_list_is_not_a_map() {
+        let input = ParserInput::new("[1, 2]");
+        let result = map_literal(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn empty_map_is_parsed() {
+        let input = ParserInput::new("Map[]");
+        let (remainder, consumed) = map_literal(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(consumed.value.len(), 0);
+    }
+
+    #[test]
+    fn map_with_one_entry_is_parsed() {
+        let input = ParserInput::new("Map[\"one\" => 1]");
+        let (remainder, consumed) = map_literal(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(consumed.value.len(), 1);
+        assert!(matches!(
+            consumed.value[0].key,
+            Expression::StringLiteral(_)
+        ));
+        assert!(matches!(consumed.value[0].value, Expression::Integer(_)));
+    }
+
+    #[test]
+    fn map_with_two_entries_and_trailing_comma_is_parsed() {
+        let input = ParserInput::new("Map[ \"one\" => 1, \"two\" => 2, ]");
+        let (remainder, consumed) = map_literal(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(consumed.value.len(), 2);
+    }
+
+    #[test]
+    fn map_can_span_multiple_lines() {
+        let input = ParserInput::new("Map[\n    1 => \"one\",\n    2 => \"two\",\n]");
+        let (remainder, consumed) = map_literal(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(consumed.value.len(), 2);
+    }
+
+    #[test]
+    fn map_entries_need_an_arrow() {
+        let input = ParserInput::new("Map[1, 2]");
+        let result = map_literal(input);
+        assert!(result.is_err());
+    }
+}
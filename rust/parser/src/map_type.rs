@@ -0,0 +1,77 @@
+use crate::{
+    intra_expression_whitespace::intra_expression_whitespace, type_expression::type_expression,
+    ExpressionContext,
+};
+use ast::{IResult, ParserInput};
+use ast::{MapTypeNode, MapTypeValue};
+use nom::{
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{consumed, map, opt},
+    sequence::{delimited, separated_pair, tuple},
+};
+
+pub fn map_type(input: ParserInput) -> IResult<MapTypeNode> {
+    map(
+        consumed(delimited(
+            tuple((
+                tag("Map("),
+                opt(intra_expression_whitespace(
+                    ExpressionContext::new().allow_newlines_in_expressions(),
+                )),
+            )),
+            separated_pair(
+                type_expression,
+                tuple((
+                    opt(intra_expression_whitespace(
+                        ExpressionContext::new().allow_newlines_in_expressions(),
+                    )),
+                    char(','),
+                    opt(intra_expression_whitespace(
+                        ExpressionContext::new().allow_newlines_in_expressions(),
+                    )),
+                )),
+                type_expression,
+            ),
+            tuple((
+                opt(intra_expression_whitespace(
+                    ExpressionContext::new().allow_newlines_in_expressions(),
+                )),
+                char(')'),
+            )),
+        )),
+        |(source, (key_type, value_type))| MapTypeNode {
+            source,
+            value: MapTypeValue {
+                key_type,
+                value_type,
+            },
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ast::TypeExpression;
+
+    #[test]
+    fn parses_key_and_value_types() {
+        let input = ParserInput::new("Map(Str, [Int])");
+        let (remainder, parsed) = map_type(input).unwrap();
+        assert!(remainder.is_empty());
+        assert!(matches!(
+            parsed.value.key_type,
+            TypeExpression::Identifier(_)
+        ));
+        assert!(matches!(parsed.value.value_type, TypeExpression::List(_)));
+    }
+
+    #[test]
+    fn needs_a_value_type() {
+        let input = ParserInput::new("Map(Str)");
+        let result = map_type(input);
+        assert!(result.is_err());
+    }
+}
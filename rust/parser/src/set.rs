@@ -0,0 +1,54 @@
+use crate::list::list;
+use ast::SetNode;
+use ast::{IResult, ParsedNode, ParserInput};
+use nom::{
+    bytes::complete::tag,
+    combinator::{consumed, map},
+    sequence::preceded,
+};
+
+/// Parses a set literal, e.g. `Set[1, 2, 3]`.
+pub fn set_literal(input: ParserInput) -> IResult<SetNode> {
+    map(
+        consumed(preceded(tag("Set"), list)),
+        |(consumed_input, list_node)| ParsedNode {
+            source: consumed_input,
+            value: list_node.value,
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn a_list_is_not_a_set() {
+        let input = ParserInput::new("[1, 2]");
+        let result = set_literal(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn empty_set_is_parsed() {
+        let input = ParserInput::new("Set[]");
+        let (remainder, consumed) = set_literal(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(consumed.value.len(), 0);
+    }
+
+    #[test]
+    fn set_with_elements_is_parsed() {
+        let input = ParserInput::new("Set[1, 2, 3,]");
+        let (remainder, consumed) = set_literal(input).unwrap();
+        assert!(remainder.is_empty());
+        assert_eq!(consumed.value.len(), 3);
+    }
+
+    #[test]
+    fn set_keyword_must_touch_the_brackets() {
+        let input = ParserInput::new("Set [1]");
+        let result = set_literal(input);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{
+    intra_expression_whitespace::intra_expression_whitespace, type_expression::type_expression,
+    ExpressionContext,
+};
+use ast::SetTypeNode;
+use ast::{IResult, ParserInput};
+use nom::{
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{consumed, map, opt},
+    sequence::delimited,
+};
+
+pub fn set_type(input: ParserInput) -> IResult<SetTypeNode> {
+    map(
+        consumed(delimited(
+            tag("Set("),
+            delimited(
+                opt(intra_expression_whitespace(
+                    ExpressionContext::new().allow_newlines_in_expressions(),
+                )),
+                type_expression,
+                opt(intra_expression_whitespace(
+                    ExpressionContext::new().allow_newlines_in_expressions(),
+                )),
+            ),
+            char(')'),
+        )),
+        |(source, expression)| SetTypeNode {
+            source,
+            value: expression,
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ast::TypeExpression;
+
+    #[test]
+    fn parses_element_type() {
+        let input = ParserInput::new("Set( Int )");
+        let (remainder, parsed) = set_type(input).unwrap();
+        assert!(remainder.is_empty());
+        assert!(matches!(parsed.value, TypeExpression::Identifier(_)));
+    }
+
+    #[test]
+    fn set_alone_is_not_a_set_type() {
+        let input = ParserInput::new("Set");
+        let result = set_type(input);
+        assert!(result.is_err());
+    }
+}
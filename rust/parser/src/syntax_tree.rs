@@ -0,0 +1,690 @@
+use crate::file::parse_buri_file;
+use ast::{
+    DeclarationNode, DocumentNode, Expression, ImportNode, ImportedIdentifier, ParserInput,
+    RecordValue, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, SyntaxTree, TokenKind, Trivia,
+    TriviaKind, TypeDeclarationNode, TypeExpression,
+};
+use std::ops::Range;
+
+const EXPORT_DECORATOR: &str = "@export";
+const TWO_CHARACTER_PUNCTUATION: [&str; 7] = ["**", "++", "==", "!=", "<=", ">=", "=>"];
+
+/// The parts of the source which the `ast` nodes cover. Leaves, such as identifiers and
+/// literals, become tokens, and the text between them is split into keywords,
+/// punctuation and trivia.
+#[derive(Default)]
+struct SourceRanges<'t> {
+    /// The source's tokens, once the leaves have been found.
+    tokens: &'t [SyntaxToken<'t>],
+    leaves: Vec<(TokenKind, Range<usize>)>,
+    /// The nodes in pre-order, so that a node comes before the nodes inside it.
+    nodes: Vec<(SyntaxKind, Range<usize>)>,
+}
+
+/// The quoted path is the last part of an import, and can't contain quotes.
+fn import_path_range(import: &ImportNode) -> Range<usize> {
+    let range = import.source.to_range();
+    let start = import.source.value()[..range.len() - 1]
+        .rfind('"')
+        .map_or(range.start, |index| range.start + index);
+    start..range.end
+}
+
+impl SourceRanges<'_> {
+    /// The `ast` has no node for parentheses, so the parentheses around an operand are
+    /// part of its operator, e.g. `(1 + 2) * 3`. Only parentheses can be between the
+    /// operands and the operator.
+    fn with_parentheses(
+        &self,
+        range: Range<usize>,
+        between_operands: Range<usize>,
+    ) -> Range<usize> {
+        let tokens = self.tokens;
+        let between = token_span(tokens, &between_operands);
+        let count = |text: &str| {
+            tokens[between.clone()]
+                .iter()
+                .filter(|token| token.kind == TokenKind::Punctuation && token.text == text)
+                .count()
+        };
+        let span = token_span(tokens, &range);
+        let mut start = span.start;
+        for _ in 0..count(")") {
+            if start > 0 && tokens[start - 1].text == "(" {
+                start -= 1;
+            }
+        }
+        let mut end = span.end;
+        for _ in 0..count("(") {
+            if end < tokens.len() && tokens[end].text == ")" {
+                end += 1;
+            }
+        }
+        if start < end {
+            tokens[start].offset..tokens[end - 1].range().end
+        } else {
+            range
+        }
+    }
+
+    fn leaf(&mut self, kind: TokenKind, source: &ParserInput) -> Range<usize> {
+        let range = source.to_range();
+        self.leaves.push((kind, range.clone()));
+        range
+    }
+
+    /// Adds a node, whose range is returned by `add_children` after adding the nodes
+    /// inside it.
+    fn node(
+        &mut self,
+        kind: SyntaxKind,
+        add_children: impl FnOnce(&mut Self) -> Range<usize>,
+    ) -> Range<usize> {
+        let index = self.nodes.len();
+        self.nodes.push((kind, 0..0));
+        let range = add_children(self);
+        self.nodes[index].1 = range.clone();
+        range
+    }
+
+    fn import(&mut self, import: &ImportNode) {
+        self.node(SyntaxKind::Import, |ranges| {
+            for identifier in &import.value.identifiers {
+                match identifier {
+                    ImportedIdentifier::Identifier(node) => {
+                        ranges.leaf(TokenKind::Identifier, &node.source)
+                    }
+                    ImportedIdentifier::TypeIdentifier(node) => {
+                        ranges.leaf(TokenKind::TypeIdentifier, &node.source)
+                    }
+                };
+            }
+            ranges
+                .leaves
+                .push((TokenKind::ImportPath, import_path_range(import)));
+            import.source.to_range()
+        });
+    }
+
+    fn type_declaration(&mut self, declaration: &TypeDeclarationNode, start: Option<usize>) {
+        self.node(SyntaxKind::TypeDeclaration, |ranges| {
+            ranges.leaf(
+                TokenKind::TypeIdentifier,
+                &declaration.value.identifier.source,
+            );
+            ranges.type_expression(&declaration.value.type_expression);
+            let range = declaration.source.to_range();
+            start.unwrap_or(range.start)..range.end
+        });
+    }
+
+    fn declaration(&mut self, declaration: &DeclarationNode, start: Option<usize>) {
+        self.node(SyntaxKind::Declaration, |ranges| {
+            ranges.leaf(TokenKind::Identifier, &declaration.value.identifier.source);
+            if let Some(type_expression) = &declaration.value.type_expression {
+                ranges.type_expression(type_expression);
+            }
+            ranges.expression(&declaration.value.expression);
+            let range = declaration.source.to_range();
+            start.unwrap_or(range.start)..range.end
+        });
+    }
+
+    fn record_field(&mut self, field: &RecordValue) {
+        self.node(SyntaxKind::RecordField, |ranges| {
+            let identifier = ranges.leaf(TokenKind::Identifier, &field.identifier.source);
+            let value = ranges.expression(&field.value);
+            identifier.start..value.end
+        });
+    }
+
+    fn expressions(&mut self, expressions: &[Expression]) {
+        for expression in expressions {
+            self.expression(expression);
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn expression(&mut self, expression: &Expression) -> Range<usize> {
+        match expression {
+            Expression::BinaryOperator(node) => self.node(SyntaxKind::BinaryOperator, |ranges| {
+                // The operator's source is only its symbol.
+                let left = ranges.expression(&node.value.left_child);
+                let right = ranges.expression(&node.value.right_child);
+                ranges.with_parentheses(left.start..right.end, left.end..right.start)
+            }),
+            Expression::Block(node) => self.node(SyntaxKind::Block, |ranges| {
+                ranges.expressions(&node.value);
+                node.source.to_range()
+            }),
+            Expression::Declaration(node) => {
+                self.declaration(node, None);
+                node.source.to_range()
+            }
+            Expression::EnumLiteral(node) => self.node(SyntaxKind::EnumLiteral, |ranges| {
+                ranges.leaf(TokenKind::TypeIdentifier, &node.value.qualifier.source);
+                ranges.expressions(&node.value.payload);
+                node.source.to_range()
+            }),
+            Expression::Float(node) => self.leaf(TokenKind::Float, &node.source),
+            Expression::Function(node) => self.node(SyntaxKind::Function, |ranges| {
+                for argument in &node.value.arguments {
+                    ranges.node(SyntaxKind::FunctionArgument, |ranges| {
+                        ranges.leaf(TokenKind::Identifier, &argument.value.argument_name.source);
+                        if let Some(argument_type) = &argument.value.argument_type {
+                            ranges.leaf(TokenKind::TypeIdentifier, &argument_type.source);
+                        }
+                        argument.source.to_range()
+                    });
+                }
+                if let Some(return_type) = &node.value.return_type {
+                    ranges.leaf(TokenKind::TypeIdentifier, &return_type.source);
+                }
+                ranges.expression(&node.value.body);
+                node.source.to_range()
+            }),
+            Expression::FunctionApplicationArguments(node) => {
+                self.node(SyntaxKind::FunctionApplicationArguments, |ranges| {
+                    ranges.expressions(&node.value.arguments);
+                    node.source.to_range()
+                })
+            }
+            Expression::Identifier(node) => self.leaf(TokenKind::Identifier, &node.source),
+            Expression::If(node) => self.node(SyntaxKind::If, |ranges| {
+                ranges.expression(&node.value.condition);
+                ranges.expression(&node.value.path_if_true);
+                if let Some(path_if_false) = &node.value.path_if_false {
+                    ranges.expression(path_if_false);
+                }
+                node.source.to_range()
+            }),
+            Expression::Integer(node) => self.leaf(TokenKind::Integer, &node.source),
+            Expression::List(node) => self.node(SyntaxKind::List, |ranges| {
+                ranges.expressions(&node.value);
+                node.source.to_range()
+            }),
+            Expression::Map(node) => self.node(SyntaxKind::Map, |ranges| {
+                for entry in &node.value {
+                    ranges.node(SyntaxKind::MapEntry, |ranges| {
+                        let key = ranges.expression(&entry.key);
+                        let value = ranges.expression(&entry.value);
+                        key.start..value.end
+                    });
+                }
+                node.source.to_range()
+            }),
+            Expression::Record(node) => self.node(SyntaxKind::Record, |ranges| {
+                for field in &node.value {
+                    ranges.record_field(field);
+                }
+                node.source.to_range()
+            }),
+            Expression::RecordAssignment(node) => {
+                self.node(SyntaxKind::RecordAssignment, |ranges| {
+                    ranges.leaf(TokenKind::Identifier, &node.value.identifier.source);
+                    for field in &node.value.new_values {
+                        ranges.record_field(field);
+                    }
+                    node.source.to_range()
+                })
+            }
+            Expression::Set(node) => self.node(SyntaxKind::Set, |ranges| {
+                ranges.expressions(&node.value);
+                node.source.to_range()
+            }),
+            Expression::StringLiteral(node) => self.leaf(TokenKind::StringLiteral, &node.source),
+            Expression::Tag(node) => self.node(SyntaxKind::Tag, |ranges| {
+                ranges.leaf(TokenKind::TagIdentifier, &node.value.name.source);
+                ranges.expressions(&node.value.contents);
+                node.source.to_range()
+            }),
+            Expression::Tuple(node) => self.node(SyntaxKind::Tuple, |ranges| {
+                ranges.expressions(&node.value);
+                node.source.to_range()
+            }),
+            Expression::TupleDeclaration(node) => {
+                self.node(SyntaxKind::TupleDeclaration, |ranges| {
+                    for identifier in &node.value.identifiers {
+                        ranges.leaf(TokenKind::Identifier, &identifier.source);
+                    }
+                    if let Some(type_expression) = &node.value.type_expression {
+                        ranges.type_expression(type_expression);
+                    }
+                    ranges.expression(&node.value.expression);
+                    node.source.to_range()
+                })
+            }
+            Expression::TypeDeclaration(node) => {
+                self.type_declaration(node, None);
+                node.source.to_range()
+            }
+            Expression::UnaryOperator(node) => self.node(SyntaxKind::UnaryOperator, |ranges| {
+                ranges.expression(&node.value.child);
+                node.source.to_range()
+            }),
+            Expression::When(node) => self.node(SyntaxKind::When, |ranges| {
+                ranges.expression(&node.value.condition);
+                for case in &node.value.cases {
+                    ranges.node(SyntaxKind::WhenCase, |ranges| {
+                        let name = ranges.leaf(TokenKind::TagIdentifier, &case.case_name.source);
+                        for argument in &case.case_arguments {
+                            ranges.leaf(TokenKind::Identifier, &argument.identifier.source);
+                            if let Some(type_expression) = &argument.type_expression {
+                                ranges.type_expression(type_expression);
+                            }
+                        }
+                        let expression = ranges.expression(&case.expression);
+                        name.start..expression.end
+                    });
+                }
+                if let Some(default_case) = &node.value.default_case {
+                    ranges.expression(default_case);
+                }
+                node.source.to_range()
+            }),
+        }
+    }
+
+    fn type_expressions(&mut self, type_expressions: &[TypeExpression]) {
+        for type_expression in type_expressions {
+            self.type_expression(type_expression);
+        }
+    }
+
+    fn type_expression(&mut self, type_expression: &TypeExpression) -> Range<usize> {
+        match type_expression {
+            TypeExpression::Enum(node) => self.node(SyntaxKind::EnumType, |ranges| {
+                for variant in &node.value.variants {
+                    ranges.node(SyntaxKind::EnumVariantType, |ranges| {
+                        ranges.type_expressions(&variant.value.payload);
+                        variant.source.to_range()
+                    });
+                }
+                node.source.to_range()
+            }),
+            TypeExpression::Function(node) => self.node(SyntaxKind::FunctionType, |ranges| {
+                ranges.type_expressions(&node.value.arguments);
+                ranges.type_expression(&node.value.return_type);
+                node.source.to_range()
+            }),
+            TypeExpression::Identifier(node) => self.leaf(TokenKind::TypeIdentifier, &node.source),
+            TypeExpression::List(node) => self.node(SyntaxKind::ListType, |ranges| {
+                ranges.type_expression(&node.value);
+                node.source.to_range()
+            }),
+            TypeExpression::Map(node) => self.node(SyntaxKind::MapType, |ranges| {
+                ranges.type_expression(&node.value.key_type);
+                ranges.type_expression(&node.value.value_type);
+                node.source.to_range()
+            }),
+            TypeExpression::Record(node) => self.node(SyntaxKind::RecordType, |ranges| {
+                for field in &node.value {
+                    ranges.node(SyntaxKind::RecordFieldType, |ranges| {
+                        let identifier =
+                            ranges.leaf(TokenKind::Identifier, &field.identifier.source);
+                        let value = ranges.type_expression(&field.value);
+                        identifier.start..value.end
+                    });
+                }
+                node.source.to_range()
+            }),
+            TypeExpression::Set(node) => self.node(SyntaxKind::SetType, |ranges| {
+                ranges.type_expression(&node.value);
+                node.source.to_range()
+            }),
+            TypeExpression::TagGroup(node) => self.node(SyntaxKind::TagGroupType, |ranges| {
+                for tag in &node.value {
+                    ranges.node(SyntaxKind::TagType, |ranges| {
+                        ranges.leaf(TokenKind::TagIdentifier, &tag.value.name.source);
+                        ranges.type_expressions(&tag.value.contents);
+                        tag.source.to_range()
+                    });
+                }
+                node.source.to_range()
+            }),
+            TypeExpression::Tuple(node) => self.node(SyntaxKind::TupleType, |ranges| {
+                ranges.type_expressions(&node.value);
+                node.source.to_range()
+            }),
+        }
+    }
+}
+
+/// Exported declarations start at their `@export` decorator.
+fn export_start(source: &str, is_exported: bool, declaration: &ParserInput) -> Option<usize> {
+    let start = declaration.location_offset();
+    is_exported
+        .then(|| source[..start].rfind(EXPORT_DECORATOR))
+        .flatten()
+}
+
+fn source_ranges<'t>(
+    source: &str,
+    document: &DocumentNode,
+    tokens: &'t [SyntaxToken<'t>],
+) -> SourceRanges<'t> {
+    let mut ranges = SourceRanges {
+        tokens,
+        ..SourceRanges::default()
+    };
+    for import in &document.value.imports {
+        ranges.import(import);
+    }
+    for declaration in &document.value.type_declarations {
+        let start = export_start(
+            source,
+            declaration.is_exported,
+            &declaration.declaration.source,
+        );
+        ranges.type_declaration(&declaration.declaration, start);
+    }
+    for declaration in &document.value.variable_declarations {
+        let start = export_start(
+            source,
+            declaration.is_exported,
+            &declaration.declaration.source,
+        );
+        ranges.declaration(&declaration.declaration, start);
+    }
+    ranges.expressions(&document.value.expressions);
+    // The document sorts its elements by kind, so the nodes are put back in the
+    // order they were written in. The sort is stable, so that a node still comes
+    // before the nodes inside it.
+    ranges
+        .nodes
+        .sort_by_key(|(_, range)| (range.start, std::cmp::Reverse(range.end)));
+    ranges.leaves.sort_by_key(|(_, range)| range.start);
+    ranges
+}
+
+/// A token or trivia, before trivia is attached to the tokens around it.
+enum Piece<'a> {
+    Token(TokenKind, Range<usize>),
+    Trivia(Trivia<'a>),
+}
+
+fn trivia_length(text: &str) -> Option<(TriviaKind, usize)> {
+    if text.starts_with("\r\n") {
+        Some((TriviaKind::Newline, 2))
+    } else if text.starts_with('\n') {
+        Some((TriviaKind::Newline, 1))
+    } else if text.starts_with("--") {
+        let length = text.find(['\r', '\n']).unwrap_or(text.len());
+        Some((TriviaKind::Comment, length))
+    } else if text.starts_with([' ', '\t']) {
+        let length = text
+            .find(|character| character != ' ' && character != '\t')
+            .unwrap_or(text.len());
+        Some((TriviaKind::Whitespace, length))
+    } else {
+        None
+    }
+}
+
+/// Splits the text between leaves into keywords, punctuation and trivia.
+fn split_gap<'a>(source: &'a str, range: Range<usize>, pieces: &mut Vec<Piece<'a>>) {
+    let mut offset = range.start;
+    while offset < range.end {
+        let text = &source[offset..range.end];
+        if let Some((kind, length)) = trivia_length(text) {
+            pieces.push(Piece::Trivia(Trivia {
+                kind,
+                text: &text[..length],
+                offset,
+            }));
+            offset += length;
+            continue;
+        }
+        let word_length = text
+            .find(|character: char| {
+                !(character.is_ascii_alphanumeric() || character == '_' || character == '@')
+            })
+            .unwrap_or(text.len());
+        let (kind, length) = if word_length > 0 {
+            // Enum variants are the only names which aren't leaves, e.g. `Color.red`.
+            let follows_dot = matches!(
+                pieces.iter().rev().find(|piece| matches!(piece, Piece::Token(..))),
+                Some(Piece::Token(TokenKind::Punctuation, previous)) if &source[previous.clone()] == "."
+            );
+            if follows_dot {
+                (TokenKind::Identifier, word_length)
+            } else {
+                (TokenKind::Keyword, word_length)
+            }
+        } else if TWO_CHARACTER_PUNCTUATION
+            .iter()
+            .any(|punctuation| text.starts_with(punctuation))
+        {
+            (TokenKind::Punctuation, 2)
+        } else {
+            let length = text.chars().next().map_or(1, char::len_utf8);
+            (TokenKind::Punctuation, length)
+        };
+        pieces.push(Piece::Token(kind, offset..offset + length));
+        offset += length;
+    }
+}
+
+/// Splits the whole source into tokens, each with the trivia around it. The last
+/// token is an empty end of file token.
+fn tokenize<'a>(source: &'a str, leaves: &[(TokenKind, Range<usize>)]) -> Vec<SyntaxToken<'a>> {
+    let mut pieces = Vec::new();
+    let mut offset = 0;
+    for (kind, range) in leaves {
+        split_gap(source, offset..range.start, &mut pieces);
+        pieces.push(Piece::Token(*kind, range.clone()));
+        offset = range.end;
+    }
+    split_gap(source, offset..source.len(), &mut pieces);
+    pieces.push(Piece::Token(
+        TokenKind::EndOfFile,
+        source.len()..source.len(),
+    ));
+
+    let mut tokens: Vec<SyntaxToken> = Vec::new();
+    let mut leading_trivia = Vec::new();
+    for piece in pieces {
+        match piece {
+            Piece::Token(kind, range) => tokens.push(SyntaxToken {
+                kind,
+                text: &source[range.clone()],
+                offset: range.start,
+                leading_trivia: std::mem::take(&mut leading_trivia),
+                trailing_trivia: Vec::new(),
+            }),
+            // Trivia up to the end of a token's line is the token's.
+            Piece::Trivia(trivia) => match tokens.last_mut() {
+                Some(token) if trivia.kind != TriviaKind::Newline && leading_trivia.is_empty() => {
+                    token.trailing_trivia.push(trivia);
+                }
+                _ => leading_trivia.push(trivia),
+            },
+        }
+    }
+    tokens
+}
+
+/// Which tokens a node covers, ignoring the trivia which its source may start or
+/// end with. The end of file token is the document's.
+fn token_span(tokens: &[SyntaxToken], range: &Range<usize>) -> Range<usize> {
+    let start = tokens.partition_point(|token| token.offset < range.start);
+    let end = tokens.partition_point(|token| {
+        token.kind != TokenKind::EndOfFile && token.range().end <= range.end
+    });
+    start..end.max(start)
+}
+
+struct TreeBuilder<'a, I: Iterator<Item = SyntaxToken<'a>>> {
+    tokens: I,
+    /// The nodes by the tokens they cover, in pre-order.
+    nodes: std::iter::Peekable<std::vec::IntoIter<(SyntaxKind, Range<usize>)>>,
+}
+
+impl<'a, I: Iterator<Item = SyntaxToken<'a>>> TreeBuilder<'a, I> {
+    fn push_tokens(&mut self, children: &mut Vec<SyntaxElement<'a>>, count: usize) {
+        children.extend(self.tokens.by_ref().take(count).map(SyntaxElement::Token));
+    }
+
+    fn build(&mut self, kind: SyntaxKind, span: Range<usize>) -> Result<SyntaxNode<'a>, String> {
+        let mut children = Vec::new();
+        let mut next = span.start;
+        while let Some((_, child_span)) = self.nodes.peek() {
+            if child_span.start >= span.end {
+                break;
+            }
+            if child_span.start < next || child_span.end > span.end {
+                return Err(format!(
+                    "Syntax Tree Error: {kind:?} overlaps with the node after it"
+                ));
+            }
+            let Some((child_kind, child_span)) = self.nodes.next() else {
+                break;
+            };
+            self.push_tokens(&mut children, child_span.start - next);
+            next = child_span.end;
+            children.push(SyntaxElement::Node(self.build(child_kind, child_span)?));
+        }
+        self.push_tokens(&mut children, span.end - next);
+        Ok(SyntaxNode { kind, children })
+    }
+}
+
+/// Parses a Buri file into a syntax tree which keeps every byte of the source, such
+/// as comments and whitespace, as trivia on its tokens.
+///
+/// The tree is built from the `ast` document rather than the other way around.
+/// The document's parsers are the only description of Buri's grammar, so the tree
+/// is made from the source ranges of the document's nodes instead of from a second
+/// grammar which would have to be kept in step with them. The cost is that the
+/// source is walked again after it is parsed, and that the tree only has the nodes
+/// which the document has, e.g. there is no node for parentheses.
+pub fn parse_syntax_tree(source: &str) -> Result<SyntaxTree<'_>, String> {
+    let document = parse_buri_file(source)?;
+    // The leaves don't depend on the tokens, but the nodes' parentheses do.
+    let leaves = source_ranges(source, &document, &[]).leaves;
+    let tokens = tokenize(source, &leaves);
+    let mut nodes = source_ranges(source, &document, &tokens).nodes;
+    for (_, range) in &mut nodes {
+        *range = token_span(&tokens, range);
+    }
+    nodes.retain(|(_, span)| !span.is_empty());
+    let token_count = tokens.len();
+    let mut builder = TreeBuilder {
+        tokens: tokens.into_iter(),
+        nodes: nodes.into_iter().peekable(),
+    };
+    let root = builder.build(SyntaxKind::Document, 0..token_count)?;
+    Ok(SyntaxTree { root, document })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn token_texts<'a>(node: &SyntaxNode<'a>) -> Vec<&'a str> {
+        node.tokens().iter().map(|token| token.text).collect()
+    }
+
+    fn child_nodes<'n, 'a>(node: &'n SyntaxNode<'a>) -> Vec<&'n SyntaxNode<'a>> {
+        node.children
+            .iter()
+            .filter_map(|child| match child {
+                SyntaxElement::Node(node) => Some(node),
+                SyntaxElement::Token(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_byte_is_kept() {
+        let source = "-- header\r\n\nimport a, B from \"a\\\"\n\n@export\nf = (x) =>\n    -- inside\n    x  +  1 -- trailing\n\n-- end";
+        let tree = parse_syntax_tree(source).unwrap();
+        assert_eq!(tree.root.text(), source);
+        let import = child_nodes(&tree.root)[0];
+        assert_eq!(import.kind, SyntaxKind::Import);
+        assert_eq!(
+            token_texts(import),
+            vec!["import", "a", ",", "B", "from", "\"a\\\""]
+        );
+    }
+
+    #[test]
+    fn comments_are_trailing_trivia_up_to_the_end_of_the_line() {
+        let tree = parse_syntax_tree("a = 1 -- one\n-- two\nb = 2").unwrap();
+        let tokens = tree.root.tokens();
+        let one = tokens.iter().find(|token| token.text == "1").unwrap();
+        let comments = one
+            .trailing_trivia
+            .iter()
+            .map(|trivia| trivia.text)
+            .collect::<Vec<_>>();
+        assert_eq!(comments, vec![" ", "-- one"]);
+        let b = tokens.iter().find(|token| token.text == "b").unwrap();
+        let kinds = b
+            .leading_trivia
+            .iter()
+            .map(|trivia| trivia.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                TriviaKind::Newline,
+                TriviaKind::Comment,
+                TriviaKind::Newline
+            ]
+        );
+    }
+
+    #[test]
+    fn nodes_are_in_source_order_and_contain_their_exports() {
+        let tree = parse_syntax_tree("A = #a\n@export\nb = (1 + 2) * 3\nc = Color.red").unwrap();
+        let declarations = child_nodes(&tree.root);
+        let kinds = declarations
+            .iter()
+            .map(|node| node.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                SyntaxKind::TypeDeclaration,
+                SyntaxKind::Declaration,
+                SyntaxKind::Declaration
+            ]
+        );
+        assert_eq!(
+            token_texts(declarations[1]),
+            vec!["@export", "b", "=", "(", "1", "+", "2", ")", "*", "3"]
+        );
+        let product = child_nodes(declarations[1])[0];
+        assert_eq!(product.kind, SyntaxKind::BinaryOperator);
+        assert_eq!(token_texts(product)[0], "(");
+        assert_eq!(child_nodes(product)[0].range(), 20..25);
+        let kinds = declarations[2]
+            .tokens()
+            .iter()
+            .map(|token| token.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Identifier,
+                TokenKind::Punctuation,
+                TokenKind::TypeIdentifier,
+                TokenKind::Punctuation,
+                TokenKind::Identifier
+            ]
+        );
+    }
+
+    #[test]
+    fn the_document_is_kept_alongside_the_tree() {
+        let source = "a = 1\nb = a";
+        let tree = parse_syntax_tree(source).unwrap();
+        assert_eq!(Ok(tree.document), parse_buri_file(source));
+    }
+
+    #[test]
+    fn parsing_errors_are_reported() {
+        assert!(parse_syntax_tree("a = (").is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    expression, intra_expression_whitespace::intra_expression_whitespace, ExpressionContext,
+};
+use ast::{Expression, TupleNode};
+use ast::{IResult, ParsedNode, ParserInput};
+use nom::{
+    bytes::complete::tag,
+    combinator::{consumed, map, opt},
+    multi::many1,
+    sequence::{delimited, preceded, terminated, tuple},
+};
+
+fn padded_expression(input: ParserInput) -> IResult<Expression> {
+    preceded(
+        opt(intra_expression_whitespace(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )),
+        expression(ExpressionContext::new().allow_newlines_in_expressions()),
+    )(input)
+}
+
+fn padded_comma(input: ParserInput) -> IResult<ParserInput> {
+    preceded(
+        opt(intra_expression_whitespace(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )),
+        tag(","),
+    )(input)
+}
+
+/// Parses a tuple literal, e.g. `(1, "one")`.
+///
+/// Tuples need at least two elements so that they can't be confused with an
+/// expression wrapped in parentheses.
+pub fn tuple_literal(input: ParserInput) -> IResult<TupleNode> {
+    map(
+        consumed(delimited(
+            tag("("),
+            terminated(
+                tuple((
+                    padded_expression,
+                    many1(preceded(padded_comma, padded_expression)),
+                    opt(padded_comma),
+                )),
+                opt(intra_expression_whitespace(
+                    ExpressionContext::new().allow_newlines_in_expressions(),
+                )),
+            ),
+            tag(")"),
+        )),
+        |(source, (first, mut rest, _))| {
+            let mut elements = vec![first];
+            elements.append(&mut rest);
+            ParsedNode {
+                source,
+                value: elements,
+            }
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn empty_parentheses_are_not_a_tuple() {
+        let input = ParserInput::new("()");
+        let result = tuple_literal(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn a_single_parenthesized_expression_is_not_a_tuple() {
+        let input = ParserInput::new("(1)");
+        let result = tuple_literal(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn a_single_element_with_a_trailing_comma_is_not_a_tuple() {
+        let input = ParserInput::new("(1,)");
+        let result = tuple_literal(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn can_have_two_elements() {
+        let input = ParserInput::new("(1, \"one\")");
+        let (remainder, parsed) = tuple_literal(input).unwrap();
+        assert_eq!(remainder, "");
+        assert_eq!(parsed.value.len(), 2);
+        assert!(matches!(parsed.value[0], Expression::Integer(_)));
+        assert!(matches!(parsed.value[1], Expression::StringLiteral(_)));
+    }
+
+    #[test]
+    fn can_have_more_than_two_elements() {
+        let input = ParserInput::new("(1, 2, 3)");
+        let (remainder, parsed) = tuple_literal(input).unwrap();
+        assert_eq!(remainder, "");
+        assert_eq!(parsed.value.len(), 3);
+    }
+
+    #[test]
+    fn can_have_a_trailing_comma() {
+        let input = ParserInput::new("(1, 2,)");
+        let (remainder, parsed) = tuple_literal(input).unwrap();
+        assert_eq!(remainder, "");
+        assert_eq!(parsed.value.len(), 2);
+    }
+
+    #[test]
+    fn can_be_split_across_multiple_lines() {
+        let input = ParserInput::new(indoc! {"
+            (
+                1,
+                2,
+            )"
+        });
+        let (remainder, parsed) = tuple_literal(input).unwrap();
+        assert_eq!(remainder, "");
+        assert_eq!(parsed.value.len(), 2);
+    }
+
+    #[test]
+    fn elements_can_be_tuples() {
+        let input = ParserInput::new("((1, 2), 3)");
+        let (remainder, parsed) = tuple_literal(input).unwrap();
+        assert_eq!(remainder, "");
+        assert!(matches!(parsed.value[0], Expression::Tuple(_)));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::binary_operator_or_if::binary_operator_or_if;
+use crate::{
+    identifier::identifier, intra_expression_whitespace::intra_expression_whitespace,
+    type_expression::type_expression, ExpressionContext,
+};
+use ast::{IResult, ParserInput};
+use ast::{IdentifierNode, TupleDeclarationNode, TupleDeclarationValue};
+use nom::{
+    character::complete::{char, space0},
+    combinator::{consumed, map, opt},
+    multi::many1,
+    sequence::{delimited, preceded, separated_pair, terminated, tuple},
+};
+
+fn padded_identifier(input: ParserInput) -> IResult<IdentifierNode> {
+    preceded(
+        opt(intra_expression_whitespace(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )),
+        identifier,
+    )(input)
+}
+
+fn padded_comma(input: ParserInput) -> IResult<char> {
+    preceded(
+        opt(intra_expression_whitespace(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )),
+        char(','),
+    )(input)
+}
+
+fn tuple_pattern(input: ParserInput) -> IResult<Vec<IdentifierNode>> {
+    map(
+        delimited(
+            char('('),
+            terminated(
+                tuple((
+                    padded_identifier,
+                    many1(preceded(padded_comma, padded_identifier)),
+                    opt(padded_comma),
+                )),
+                opt(intra_expression_whitespace(
+                    ExpressionContext::new().allow_newlines_in_expressions(),
+                )),
+            ),
+            char(')'),
+        ),
+        |(first, mut rest, _)| {
+            let mut identifiers = vec![first];
+            identifiers.append(&mut rest);
+            identifiers
+        },
+    )(input)
+}
+
+/// Parses a declaration that destructures a tuple, e.g. `(a, b) = pair`.
+pub fn tuple_declaration(
+    context: ExpressionContext,
+    input: ParserInput,
+) -> IResult<TupleDeclarationNode> {
+    map(
+        consumed(separated_pair(
+            tuple((
+                tuple_pattern,
+                opt(preceded(
+                    tuple((space0, char(':'), space0)),
+                    type_expression,
+                )),
+            )),
+            tuple((
+                space0,
+                char('='),
+                opt(intra_expression_whitespace(
+                    ExpressionContext::new().allow_newlines_in_expressions(),
+                )),
+            )),
+            binary_operator_or_if(context.allow_newlines_in_expressions()),
+        )),
+        |(consumed, ((identifiers, type_expression), expression))| TupleDeclarationNode {
+            value: TupleDeclarationValue {
+                identifiers,
+                type_expression,
+                expression: Box::new(expression),
+            },
+            source: consumed,
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_identifier_names() {
+        let input = ParserInput::new("(foo, bar) = pair");
+        let (remainder, node) = tuple_declaration(ExpressionContext::new(), input).unwrap();
+        assert_eq!(remainder, "");
+        let names: Vec<&str> = node
+            .value
+            .identifiers
+            .iter()
+            .map(|identifier| identifier.value.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn missing_type_expression_becomes_none() {
+        let input = ParserInput::new("(foo, bar) = pair");
+        let (_, node) = tuple_declaration(ExpressionContext::new(), input).unwrap();
+        assert_eq!(node.value.type_expression, None);
+    }
+
+    #[test]
+    fn parses_tuple_type() {
+        let input = ParserInput::new("(foo, bar): (Int, Str) = pair");
+        let (remainder, node) = tuple_declaration(ExpressionContext::new(), input).unwrap();
+        assert_eq!(remainder, "");
+        assert!(matches!(
+            node.value.type_expression,
+            Some(ast::TypeExpression::Tuple(_))
+        ));
+    }
+
+    #[test]
+    fn right_hand_side_can_be_a_tuple() {
+        let input = ParserInput::new("(foo, bar) = (1, 2)");
+        let (remainder, node) = tuple_declaration(ExpressionContext::new(), input).unwrap();
+        assert_eq!(remainder, "");
+        assert!(matches!(*node.value.expression, ast::Expression::Tuple(_)));
+    }
+
+    #[test]
+    fn errors_with_a_single_identifier() {
+        let input = ParserInput::new("(foo) = pair");
+        let result = tuple_declaration(ExpressionContext::new(), input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn errors_if_the_pattern_contains_a_non_identifier() {
+        let input = ParserInput::new("(foo, 1) = pair");
+        let result = tuple_declaration(ExpressionContext::new(), input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn errors_without_value() {
+        let input = ParserInput::new("(foo, bar) =");
+        let result = tuple_declaration(ExpressionContext::new(), input);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    intra_expression_whitespace::intra_expression_whitespace, type_expression::type_expression,
+    ExpressionContext,
+};
+use ast::TupleTypeNode;
+use ast::{IResult, ParserInput, TypeExpression};
+use nom::{
+    character::complete::char,
+    combinator::{consumed, map, opt},
+    multi::many1,
+    sequence::{delimited, preceded, terminated, tuple},
+};
+
+fn padded_type_expression(input: ParserInput) -> IResult<TypeExpression> {
+    preceded(
+        opt(intra_expression_whitespace(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )),
+        type_expression,
+    )(input)
+}
+
+fn padded_comma(input: ParserInput) -> IResult<char> {
+    preceded(
+        opt(intra_expression_whitespace(
+            ExpressionContext::new().allow_newlines_in_expressions(),
+        )),
+        char(','),
+    )(input)
+}
+
+/// Parses a tuple type, e.g. `(Int, Str)`.
+pub fn tuple_type(input: ParserInput) -> IResult<TupleTypeNode> {
+    map(
+        consumed(delimited(
+            char('('),
+            terminated(
+                tuple((
+                    padded_type_expression,
+                    many1(preceded(padded_comma, padded_type_expression)),
+                    opt(padded_comma),
+                )),
+                opt(intra_expression_whitespace(
+                    ExpressionContext::new().allow_newlines_in_expressions(),
+                )),
+            ),
+            char(')'),
+        )),
+        |(source, (first, mut rest, _))| {
+            let mut element_types = vec![first];
+            element_types.append(&mut rest);
+            TupleTypeNode {
+                source,
+                value: element_types,
+            }
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn a_single_type_is_not_a_tuple_type() {
+        let input = ParserInput::new("(Int)");
+        let result = tuple_type(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn can_have_two_element_types() {
+        let input = ParserInput::new("(Int, Str)");
+        let (remainder, parsed) = tuple_type(input).unwrap();
+        assert_eq!(remainder, "");
+        assert_eq!(parsed.value.len(), 2);
+        assert!(matches!(parsed.value[0], TypeExpression::Identifier(_)));
+    }
+
+    #[test]
+    fn can_have_a_trailing_comma() {
+        let input = ParserInput::new("(Int, Str,)");
+        let (remainder, parsed) = tuple_type(input).unwrap();
+        assert_eq!(remainder, "");
+        assert_eq!(parsed.value.len(), 2);
+    }
+
+    #[test]
+    fn element_types_can_be_tuple_types() {
+        let input = ParserInput::new("((Int, Int), Str)");
+        let (remainder, parsed) = tuple_type(input).unwrap();
+        assert_eq!(remainder, "");
+        assert!(matches!(parsed.value[0], TypeExpression::Tuple(_)));
+    }
+}
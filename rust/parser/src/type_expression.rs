@@ -1,5 +1,8 @@
 use crate::enum_type::enum_type;
+use crate::map_type::map_type;
+use crate::set_type::set_type;
 use crate::tag_group_type::tag_group_type;
+use crate::tuple_type::tuple_type;
 use crate::{
     function_type::function_type, list_type::list_type, record_type::record_type,
     type_identifier::type_identifier, ExpressionContext,
@@ -10,12 +13,15 @@ use nom::{branch::alt, combinator::map};
 
 pub fn type_expression(input: ParserInput) -> IResult<TypeExpression> {
     alt((
+        map(map_type, |map_type| TypeExpression::Map(Box::new(map_type))),
+        map(set_type, |set_type| TypeExpression::Set(Box::new(set_type))),
         map(type_identifier, TypeExpression::Identifier),
         map(list_type, |list| TypeExpression::List(Box::new(list))),
         map(enum_type(ExpressionContext::new()), TypeExpression::Enum),
         map(tag_group_type, TypeExpression::TagGroup),
         map(record_type, TypeExpression::Record),
         map(function_type, TypeExpression::Function),
+        map(tuple_type, TypeExpression::Tuple),
     ))(input)
 }
 
@@ -44,6 +50,35 @@ mod test {
         assert!(matches!(expression, TypeExpression::List(_)));
     }
 
+    #[test]
+    fn a_map_type_is_a_type_expression() {
+        let input = ParserInput::new("Map(Str, Int)");
+        let (_, expression) = type_expression(input.clone()).unwrap();
+        assert!(matches!(expression, TypeExpression::Map(_)));
+    }
+
+    #[test]
+    fn a_set_type_is_a_type_expression() {
+        let input = ParserInput::new("Set(Int)");
+        let (_, expression) = type_expression(input.clone()).unwrap();
+        assert!(matches!(expression, TypeExpression::Set(_)));
+    }
+
+    #[test]
+    fn a_tuple_type_is_a_type_expression() {
+        let input = ParserInput::new("(Int, Str)");
+        let (_, expression) = type_expression(input.clone()).unwrap();
+        assert!(matches!(expression, TypeExpression::Tuple(_)));
+    }
+
+    #[test]
+    fn a_function_returning_a_tuple_is_a_function_type() {
+        let input = ParserInput::new("(Int) => (Int, Str)");
+        let (remainder, expression) = type_expression(input.clone()).unwrap();
+        assert_eq!(remainder, "");
+        assert!(matches!(expression, TypeExpression::Function(_)));
+    }
+
     #[test]
     fn a_single_tag_type_is_a_type_expression() {
         let input = ParserInput::new("#hello");
@@ -0,0 +1,45 @@
+/// Words and symbols which can't end an expression, so a line ending with one of
+/// them is continued on the next line.
+const CONTINUING_WORDS: [&str; 3] = ["do", "is", "else"];
+const CONTINUING_SYMBOLS: [&str; 5] = ["=>", "(", "[", "{", ","];
+
+/// Whether the entry needs more lines before it can be evaluated. Once an entry
+/// spans several lines, it ends with a blank line, since the next line could
+/// always add another `else` or `when` case.
+pub fn needs_more_input(entry: &str) -> bool {
+    if entry.starts_with(':') {
+        return false;
+    }
+    if entry.contains('\n') {
+        return !entry.ends_with('\n');
+    }
+    let line = entry.trim_end();
+    CONTINUING_SYMBOLS
+        .iter()
+        .any(|symbol| line.ends_with(symbol))
+        || matches!(
+            line.rsplit(|character: char| character.is_whitespace()).next(),
+            Some(word) if CONTINUING_WORDS.contains(&word)
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn single_lines_continue_after_unfinished_expressions() {
+        assert!(!needs_more_input("x = 1 + 2"));
+        assert!(needs_more_input("double = (n) =>"));
+        assert!(needs_more_input("when shape is"));
+        assert!(needs_more_input("numbers = ["));
+        assert!(!needs_more_input("undo"));
+        assert!(!needs_more_input(":type ("));
+    }
+
+    #[test]
+    fn multi_line_entries_end_with_a_blank_line() {
+        assert!(needs_more_input("double = (n) =>\n    n * 2"));
+        assert!(!needs_more_input("double = (n) =>\n    n * 2\n"));
+    }
+}
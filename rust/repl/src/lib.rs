@@ -0,0 +1,81 @@
+mod input;
+mod session;
+
+use input::needs_more_input;
+use std::io::{self, BufRead, Write};
+
+pub use session::Session;
+
+const PROMPT: &str = "> ";
+const CONTINUATION_PROMPT: &str = "| ";
+const QUIT_COMMAND: &str = ":quit";
+
+/// Reads entries until `:quit` or the end of the input, and writes the result of
+/// each one. Errors are written too, and the session carries on without the entry.
+pub fn run(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut session = Session::default();
+    let mut entry = String::new();
+    write!(output, "{PROMPT}")?;
+    output.flush()?;
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim_end();
+        if entry.is_empty() {
+            if line.trim_start().is_empty() {
+                write!(output, "{PROMPT}")?;
+                output.flush()?;
+                continue;
+            }
+            entry.push_str(line);
+        } else if line.is_empty() {
+            entry.push('\n');
+        } else {
+            entry.push('\n');
+            entry.push_str(line);
+        }
+        if needs_more_input(&entry) {
+            write!(output, "{CONTINUATION_PROMPT}")?;
+            output.flush()?;
+            continue;
+        }
+        if entry.trim() == QUIT_COMMAND {
+            return Ok(());
+        }
+        match session.run(entry.trim_end()) {
+            Ok(result) if result.is_empty() => {}
+            Ok(result) => writeln!(output, "{result}")?,
+            Err(error) => writeln!(output, "{error}")?,
+        }
+        entry.clear();
+        write!(output, "{PROMPT}")?;
+        output.flush()?;
+    }
+    writeln!(output)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run_for_test(input: &str) -> String {
+        let mut output = Vec::new();
+        run(input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn entries_are_evaluated_in_a_session() {
+        assert_eq!(
+            run_for_test("x = 40\nx + 2\n:quit\nx\n"),
+            "> x: Int = 40\n> 42: Int\n> "
+        );
+    }
+
+    #[test]
+    fn multi_line_entries_use_a_continuation_prompt() {
+        assert_eq!(
+            run_for_test("double = (n) =>\n    n * 2\n\ndouble(4)\n"),
+            "> | | double: (Int) => Int = (n) => ...\n> 8: Int\n> \n"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use interpreter::{evaluate_declaration, evaluate_expression, Scope};
+use parser::parse_buri_file;
+use std::rc::Rc;
+use type_checker_resolver::resolve_entry_types;
+use type_checker_translator::apply_entry_constraints;
+use type_checker_types::type_schema::TypeSchema;
+use typed_ast::{get_concrete_type, ConcreteDocument};
+
+/// Shows the type of an expression without evaluating it, e.g. `:type [1, 2]`.
+const TYPE_COMMAND: &str = ":type ";
+/// Evaluates the declarations of a file, e.g. `:load examples/hello.buri`.
+const LOAD_COMMAND: &str = ":load ";
+
+/// The entries entered so far. Each entry is type checked against the schema of
+/// the entries before it, and evaluated after their declarations.
+pub struct Session {
+    schema: TypeSchema,
+    /// The entries which were evaluated without errors, in order.
+    documents: Vec<ConcreteDocument<'static>>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            schema: TypeSchema::new(),
+            documents: Vec::new(),
+        }
+    }
+}
+
+impl Session {
+    /// Type checks the entry in a copy of the schema, which the caller can keep if
+    /// it wants the entry's declarations to be used by later entries.
+    fn type_check(&self, entry: &str) -> Result<(ConcreteDocument<'static>, TypeSchema), String> {
+        let parsed_entry = parse_buri_file(entry)?;
+        if !parsed_entry.value.imports.is_empty() {
+            return Err(String::from(
+                "Imports can't be used in the REPL. Use :load to evaluate another file",
+            ));
+        }
+        let mut schema = self.schema.clone();
+        let generic_document = apply_entry_constraints(&mut schema, parsed_entry)?;
+        let document = resolve_entry_types(&mut schema, generic_document);
+        // The document only borrows from the entry for its imports, and there are none.
+        let document = ConcreteDocument {
+            imports: vec![],
+            type_declarations: document.type_declarations,
+            variable_declarations: document.variable_declarations,
+            expressions: document.expressions,
+        };
+        Ok((document, schema))
+    }
+
+    /// Evaluates the declarations and then the expressions of the entry, and
+    /// describes each of their values along with its type. If anything fails, the
+    /// entry's declarations can't be used by later entries.
+    pub fn evaluate(&mut self, entry: &str) -> Result<String, String> {
+        let (document, schema) = self.type_check(entry)?;
+        let results = self.evaluate_document(&document)?;
+        self.schema = schema;
+        self.documents.push(document);
+        Ok(results)
+    }
+
+    /// Values borrow the expressions they were made from, so the session can't
+    /// keep a scope next to the documents which it borrows from. Instead, each
+    /// entry gets a new scope, where the declarations of the earlier entries are
+    /// evaluated again. Evaluating them has no side effects, so they get the same
+    /// values as before.
+    fn evaluate_document(&self, document: &ConcreteDocument) -> Result<String, String> {
+        let scope = Rc::new(Scope::default());
+        for earlier_document in &self.documents {
+            for declaration in &earlier_document.variable_declarations {
+                evaluate_declaration(&declaration.declaration, &scope)?;
+            }
+        }
+        let mut results = Vec::new();
+        for declaration in &document.variable_declarations {
+            let declaration = &declaration.declaration;
+            let value = evaluate_declaration(declaration, &scope)?;
+            results.push(format!(
+                "{}: {} = {value}",
+                declaration.identifier.name, declaration.declaration_type
+            ));
+        }
+        for expression in &document.expressions {
+            let value = evaluate_expression(expression, &scope)?;
+            results.push(format!("{value}: {}", get_concrete_type(expression)));
+        }
+        Ok(results.join("\n"))
+    }
+
+    /// Describes the types of the entry's declarations and expressions, without
+    /// evaluating or keeping them.
+    pub fn describe_types(&self, entry: &str) -> Result<String, String> {
+        let (document, _) = self.type_check(entry)?;
+        let declarations = document.variable_declarations.iter().map(|declaration| {
+            format!(
+                "{}: {}",
+                declaration.declaration.identifier.name, declaration.declaration.declaration_type
+            )
+        });
+        let expressions = document
+            .expressions
+            .iter()
+            .map(|expression| get_concrete_type(expression).to_string());
+        Ok(declarations
+            .chain(expressions)
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    pub fn load(&mut self, path: &str) -> Result<String, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| format!("Error reading source file {path}: {error}"))?;
+        self.evaluate(&contents)
+    }
+
+    /// Runs a command, or evaluates the entry if it isn't one.
+    pub fn run(&mut self, entry: &str) -> Result<String, String> {
+        if let Some(expression) = entry.strip_prefix(TYPE_COMMAND) {
+            self.describe_types(expression)
+        } else if let Some(path) = entry.strip_prefix(LOAD_COMMAND) {
+            self.load(path.trim())
+        } else if entry.starts_with(':') {
+            Err(format!(
+                "Unknown command {entry}. The commands are :type <expression>, :load <file> and :quit"
+            ))
+        } else {
+            self.evaluate(entry)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn later_entries_use_earlier_declarations() {
+        let mut session = Session::default();
+        assert_eq!(
+            session.run("double = (n) => n * 2"),
+            Ok(String::from("double: (Int) => Int = (n) => ..."))
+        );
+        assert_eq!(session.run("double(21)"), Ok(String::from("42: Int")));
+    }
+
+    #[test]
+    fn failed_entries_are_forgotten() {
+        let mut session = Session::default();
+        assert!(session.run("name = 1 + \"one\"").is_err());
+        assert!(session.run("name").is_err());
+        assert_eq!(
+            session.run("name = \"Sam\""),
+            Ok(String::from("name: Str = \"Sam\""))
+        );
+    }
+
+    #[test]
+    fn entries_which_fail_while_being_evaluated_are_forgotten() {
+        let mut session = Session::default();
+        session.run("total = 1").unwrap();
+        assert_eq!(
+            session.run("doubled = 2\nhalf = 1 / 0"),
+            Err(String::from("1 is divided by zero"))
+        );
+        assert!(session.run("doubled").is_err());
+        assert!(session.run("half").is_err());
+        assert_eq!(session.run("total + 1"), Ok(String::from("2: Int")));
+    }
+
+    #[test]
+    fn functions_of_earlier_entries_can_be_called() {
+        let mut session = Session::default();
+        session.run("double = (n) => n * 2").unwrap();
+        session.run("quadruple = (n) => double(double(n))").unwrap();
+        assert_eq!(session.run("quadruple(5)"), Ok(String::from("20: Int")));
+    }
+
+    #[test]
+    fn type_command_does_not_evaluate() {
+        let mut session = Session::default();
+        session.run("numbers = [1, 2, 3]").unwrap();
+        assert_eq!(
+            session.run(":type numbers:get(5)"),
+            Ok(String::from("#some(Int) | #none"))
+        );
+        assert_eq!(session.run(":type numbers"), Ok(String::from("[Int]")));
+    }
+
+    #[test]
+    fn unknown_commands_are_reported() {
+        let mut session = Session::default();
+        assert_eq!(
+            session.run(":help"),
+            Err(String::from(
+                "Unknown command :help. The commands are :type <expression>, :load <file> and :quit"
+            ))
+        );
+    }
+}
@@ -1,3 +1,3 @@
 mod resolve_concrete_types;
 
-pub use resolve_concrete_types::resolve_concrete_types;
+pub use resolve_concrete_types::{resolve_concrete_types, resolve_entry_types};
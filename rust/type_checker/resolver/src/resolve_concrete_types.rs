@@ -3,10 +3,12 @@ use type_checker_types::{
     generic_nodes::{
         GenericBinaryOperatorExpression, GenericBlockExpression, GenericBooleanExpression,
         GenericDeclarationExpression, GenericDocument, GenericEnumExpression, GenericExpression,
-        GenericFunctionExpression, GenericIdentifierExpression, GenericIfExpression,
-        GenericIntegerLiteralExpression, GenericListExpression, GenericRecordAssignmentExpression,
-        GenericRecordExpression, GenericStringLiteralExpression, GenericTagExpression,
-        GenericUnaryOperatorExpression, GenericWhenExpression,
+        GenericFloatLiteralExpression, GenericFunctionExpression, GenericIdentifierExpression,
+        GenericIfExpression, GenericIntegerLiteralExpression, GenericListExpression,
+        GenericMapExpression, GenericRecordAssignmentExpression, GenericRecordExpression,
+        GenericSetExpression, GenericStringLiteralExpression, GenericTagExpression,
+        GenericTupleDeclarationExpression, GenericTupleExpression, GenericUnaryOperatorExpression,
+        GenericWhenExpression,
     },
     type_schema::TypeSchema,
     TypeId,
@@ -14,11 +16,13 @@ use type_checker_types::{
 use typed_ast::{
     ConcreteBinaryOperatorExpression, ConcreteBlockExpression, ConcreteBooleanExpression,
     ConcreteDeclarationExpression, ConcreteDocument, ConcreteEnumExpression, ConcreteExpression,
-    ConcreteFunctionExpression, ConcreteIdentifierExpression, ConcreteIfExpression,
-    ConcreteIntegerLiteralExpression, ConcreteListExpression, ConcreteRecordAssignmentExpression,
-    ConcreteRecordExpression, ConcreteStringLiteralExpression, ConcreteTagExpression, ConcreteType,
-    ConcreteUnaryOperatorExpression, ConcreteWhenCase, ConcreteWhenExpression, PrimitiveType,
-    TypedDeclarationExpression,
+    ConcreteFloatLiteralExpression, ConcreteFunctionExpression, ConcreteIdentifierExpression,
+    ConcreteIfExpression, ConcreteIntegerLiteralExpression, ConcreteListExpression,
+    ConcreteMapEntry, ConcreteMapExpression, ConcreteRecordAssignmentExpression,
+    ConcreteRecordExpression, ConcreteSetExpression, ConcreteStringLiteralExpression,
+    ConcreteTagExpression, ConcreteTupleDeclarationExpression, ConcreteTupleExpression,
+    ConcreteType, ConcreteUnaryOperatorExpression, ConcreteWhenCase, ConcreteWhenExpression,
+    PrimitiveType, TypedDeclarationExpression,
 };
 
 fn resolve_generic_type(schema: &mut TypeSchema, type_id: TypeId) -> ConcreteType {
@@ -34,6 +38,7 @@ fn resolve_binary_operator(
             simplified_schema,
             generic_binary_operator.expression_type.type_id,
         ),
+        source: generic_binary_operator.source,
         symbol: generic_binary_operator.symbol,
         left_child: resolve_expression(simplified_schema, generic_binary_operator.left_child),
         right_child: resolve_expression(simplified_schema, generic_binary_operator.right_child),
@@ -89,6 +94,8 @@ fn resolve_declaration(
             _ => unreachable!(),
         },
         value: resolve_expression(simplified_schema, generic_declaration.value),
+        is_reassignment: generic_declaration.is_reassignment,
+        source: generic_declaration.source,
     }))
 }
 
@@ -101,6 +108,7 @@ fn resolve_function(
             simplified_schema,
             generic_function.expression_type.type_id,
         ),
+        source: generic_function.source,
         argument_names: generic_function.argument_names,
         body: resolve_expression(simplified_schema, generic_function.body),
     }))
@@ -149,6 +157,19 @@ fn resolve_if(
     }))
 }
 
+fn resolve_float(
+    simplified_schema: &mut TypeSchema,
+    generic_float: GenericFloatLiteralExpression,
+) -> ConcreteExpression {
+    ConcreteExpression::Float(Box::new(ConcreteFloatLiteralExpression {
+        expression_type: resolve_generic_type(
+            simplified_schema,
+            generic_float.expression_type.type_id,
+        ),
+        value: generic_float.value,
+    }))
+}
+
 fn resolve_integer(
     simplified_schema: &mut TypeSchema,
     generic_integer: &GenericIntegerLiteralExpression,
@@ -193,6 +214,43 @@ fn resolve_list(
     }))
 }
 
+fn resolve_map(
+    simplified_schema: &mut TypeSchema,
+    generic_map: GenericMapExpression,
+) -> ConcreteExpression {
+    ConcreteExpression::Map(Box::new(ConcreteMapExpression {
+        expression_type: resolve_generic_type(
+            simplified_schema,
+            generic_map.expression_type.type_id,
+        ),
+        contents: generic_map
+            .contents
+            .into_iter()
+            .map(|entry| ConcreteMapEntry {
+                key: resolve_expression(simplified_schema, entry.key),
+                value: resolve_expression(simplified_schema, entry.value),
+            })
+            .collect(),
+    }))
+}
+
+fn resolve_set(
+    simplified_schema: &mut TypeSchema,
+    generic_set: GenericSetExpression,
+) -> ConcreteExpression {
+    ConcreteExpression::Set(Box::new(ConcreteSetExpression {
+        expression_type: resolve_generic_type(
+            simplified_schema,
+            generic_set.expression_type.type_id,
+        ),
+        contents: generic_set
+            .contents
+            .into_iter()
+            .map(|item| resolve_expression(simplified_schema, item))
+            .collect(),
+    }))
+}
+
 fn resolve_record(
     simplified_schema: &mut TypeSchema,
     generic_record: GenericRecordExpression,
@@ -246,6 +304,45 @@ fn resolve_tag(
     }))
 }
 
+fn resolve_tuple(
+    simplified_schema: &mut TypeSchema,
+    generic_tuple: GenericTupleExpression,
+) -> ConcreteExpression {
+    ConcreteExpression::Tuple(Box::new(ConcreteTupleExpression {
+        expression_type: resolve_generic_type(
+            simplified_schema,
+            generic_tuple.expression_type.type_id,
+        ),
+        contents: generic_tuple
+            .contents
+            .into_iter()
+            .map(|item| resolve_expression(simplified_schema, item))
+            .collect(),
+    }))
+}
+
+fn resolve_tuple_declaration(
+    simplified_schema: &mut TypeSchema,
+    generic_tuple_declaration: GenericTupleDeclarationExpression,
+) -> ConcreteExpression {
+    ConcreteExpression::TupleDeclaration(Box::new(ConcreteTupleDeclarationExpression {
+        declaration_type: resolve_generic_type(
+            simplified_schema,
+            generic_tuple_declaration.declaration_type.type_id,
+        ),
+        expression_type: resolve_generic_type(
+            simplified_schema,
+            generic_tuple_declaration.expression_type.type_id,
+        ),
+        identifiers: generic_tuple_declaration
+            .identifiers
+            .into_iter()
+            .map(|identifier| resolve_identifier(simplified_schema, identifier))
+            .collect(),
+        value: resolve_expression(simplified_schema, generic_tuple_declaration.value),
+    }))
+}
+
 fn resolve_enum(
     simplified_schema: &mut TypeSchema,
     generic_enum: GenericEnumExpression,
@@ -328,6 +425,7 @@ fn resolve_expression(
         GenericExpression::Declaration(generic_declaration) => {
             resolve_declaration(simplified_schema, *generic_declaration)
         }
+        GenericExpression::Float(generic_float) => resolve_float(simplified_schema, *generic_float),
         GenericExpression::Function(generic_function) => {
             resolve_function(simplified_schema, *generic_function)
         }
@@ -345,6 +443,8 @@ fn resolve_expression(
             resolve_record_assignment(simplified_schema, *generic_record_assignment)
         }
         GenericExpression::List(list) => resolve_list(simplified_schema, *list),
+        GenericExpression::Map(map) => resolve_map(simplified_schema, *map),
+        GenericExpression::Set(set) => resolve_set(simplified_schema, *set),
         GenericExpression::Record(record) => {
             ConcreteExpression::Record(Box::new(resolve_record(simplified_schema, *record)))
         }
@@ -352,6 +452,10 @@ fn resolve_expression(
             resolve_string_literal(simplified_schema, *generic_string_literal)
         }
         GenericExpression::Tag(tag) => resolve_tag(simplified_schema, *tag),
+        GenericExpression::Tuple(tuple) => resolve_tuple(simplified_schema, *tuple),
+        GenericExpression::TupleDeclaration(tuple_declaration) => {
+            resolve_tuple_declaration(simplified_schema, *tuple_declaration)
+        }
         GenericExpression::Enum(enum_expression) => {
             resolve_enum(simplified_schema, *enum_expression)
         }
@@ -374,6 +478,8 @@ fn resolve_variable_declaration_types(
             expression_type: resolved_type,
             identifier: resolve_identifier(schema, input.declaration.identifier),
             value: resolve_expression(schema, input.declaration.value),
+            is_reassignment: input.declaration.is_reassignment,
+            source: input.declaration.source,
         },
         is_exported: input.is_exported,
     }
@@ -398,3 +504,30 @@ pub fn resolve_concrete_types(mut schema: TypeSchema, input: GenericDocument) ->
         expressions: vec![],
     }
 }
+
+/// Resolves the types of an entry of the REPL, including its top-level expressions.
+/// The schema is borrowed so that later entries can be checked against it.
+#[must_use]
+pub fn resolve_entry_types<'a>(
+    schema: &mut TypeSchema,
+    input: GenericDocument<'a>,
+) -> ConcreteDocument<'a> {
+    let GenericDocument {
+        imports,
+        variable_declarations,
+        expressions,
+        ..
+    } = input;
+    ConcreteDocument {
+        imports,
+        type_declarations: vec![],
+        variable_declarations: variable_declarations
+            .into_iter()
+            .map(|input| resolve_variable_declaration_types(schema, input))
+            .collect(),
+        expressions: expressions
+            .into_iter()
+            .map(|expression| resolve_expression(schema, expression))
+            .collect(),
+    }
+}
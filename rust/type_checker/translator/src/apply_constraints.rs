@@ -1,7 +1,11 @@
 use crate::parsed_expression_to_generic_expression::{
-    translate_declaration, translate_type_declaration,
+    translate_declaration, translate_parsed_expression_to_generic_expression,
+    translate_type_declaration,
+};
+use ast::{
+    DeclarationValue, DocumentNode, DocumentValue, ParsedNode, TopLevelDeclaration,
+    TypeDeclarationNode,
 };
-use ast::{DeclarationValue, DocumentNode, ParsedNode, TopLevelDeclaration, TypeDeclarationNode};
 use type_checker_types::{
     generic_nodes::{
         GenericDeclarationExpression, GenericDocument, GenericTypeDeclarationExpression,
@@ -29,35 +33,58 @@ fn translate_top_level_type_declaration<'a>(
     })
 }
 
-pub fn apply_constraints(input: DocumentNode) -> Result<(GenericDocument, TypeSchema), String> {
-    let mut schema = TypeSchema::new();
+/// Type checks the declarations of a document in the schema, which may already
+/// contain declarations from elsewhere.
+fn translate_declarations<'a>(
+    schema: &mut TypeSchema,
+    input: DocumentValue<'a>,
+) -> Result<GenericDocument<'a>, String> {
     let mut type_declarations: Vec<TopLevelDeclaration<GenericTypeDeclarationExpression>> =
         Vec::new();
-    type_declarations.reserve_exact(input.value.type_declarations.len());
-    for type_declaration in input.value.type_declarations {
+    type_declarations.reserve_exact(input.type_declarations.len());
+    for type_declaration in input.type_declarations {
         type_declarations.push(translate_top_level_type_declaration(
-            &mut schema,
+            schema,
             type_declaration,
         )?);
     }
     let mut variable_declarations: Vec<TopLevelDeclaration<GenericDeclarationExpression>> =
         Vec::new();
-    variable_declarations.reserve_exact(input.value.variable_declarations.len());
-    for variable_declaration in input.value.variable_declarations {
+    variable_declarations.reserve_exact(input.variable_declarations.len());
+    for variable_declaration in input.variable_declarations {
         variable_declarations.push(translate_top_level_variable_declaration(
-            &mut schema,
+            schema,
             variable_declaration,
         )?);
     }
-    Ok((
-        GenericDocument {
-            imports: input.value.imports,
-            type_declarations,
-            variable_declarations,
-            // We don't need to check top-level expressions since they are
-            // inconsequential to the program.
-            expressions: vec![],
-        },
-        schema,
-    ))
+    Ok(GenericDocument {
+        imports: input.imports,
+        type_declarations,
+        variable_declarations,
+        // We don't need to check top-level expressions since they are
+        // inconsequential to the program.
+        expressions: vec![],
+    })
+}
+
+pub fn apply_constraints(input: DocumentNode) -> Result<(GenericDocument, TypeSchema), String> {
+    let mut schema = TypeSchema::new();
+    let document = translate_declarations(&mut schema, input.value)?;
+    Ok((document, schema))
+}
+
+/// Type checks an entry of the REPL in the schema of the entries before it, so that
+/// it can use their declarations. Unlike in a file, top-level expressions are
+/// checked too, after the declarations.
+pub fn apply_entry_constraints<'a>(
+    schema: &mut TypeSchema,
+    mut input: DocumentNode<'a>,
+) -> Result<GenericDocument<'a>, String> {
+    let expressions = std::mem::take(&mut input.value.expressions);
+    let mut document = translate_declarations(schema, input.value)?;
+    document.expressions = expressions
+        .into_iter()
+        .map(|expression| translate_parsed_expression_to_generic_expression(schema, expression))
+        .collect::<Result<_, _>>()?;
+    Ok(document)
 }
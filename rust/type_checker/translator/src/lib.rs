@@ -1,4 +1,4 @@
 mod apply_constraints;
 mod parsed_expression_to_generic_expression;
 
-pub use apply_constraints::apply_constraints;
+pub use apply_constraints::{apply_constraints, apply_entry_constraints};
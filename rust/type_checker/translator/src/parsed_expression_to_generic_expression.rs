@@ -1,24 +1,28 @@
 use ast::{
     BinaryOperatorNode, BinaryOperatorSymbol, BlockNode, DeclarationNode, EnumLiteralNode,
-    EnumTypeNode, Expression, FunctionNode, FunctionTypeNode, IdentifierNode, IfNode, IntegerNode,
-    ListNode, ListTypeNode, RecordAssignmentNode, RecordNode, RecordTypeNode, StringLiteralNode,
-    TagGroupTypeNode, TagNode, TypeDeclarationNode, TypeExpression, TypeIdentifierNode,
-    UnaryOperatorNode, UnaryOperatorSymbol, WhenNode,
+    EnumTypeNode, Expression, FloatNode, FunctionNode, FunctionTypeNode, IdentifierNode, IfNode,
+    IntegerNode, ListNode, ListTypeNode, MapNode, MapTypeNode, RecordAssignmentNode, RecordNode,
+    RecordTypeNode, SetNode, SetTypeNode, StringLiteralNode, TagGroupTypeNode, TagNode,
+    TupleDeclarationNode, TupleNode, TupleTypeNode, TypeDeclarationNode, TypeExpression,
+    TypeIdentifierNode, UnaryOperatorNode, UnaryOperatorSymbol, WhenNode,
 };
-use std::collections::HashMap;
+use indexmap::IndexMap;
 use type_checker_errors::generate_backtrace_error;
 use type_checker_types::{
     constraints::{
         Constraint, EnumExactConstraint, HasExactFieldsConstraint, HasFieldConstraint,
-        HasFunctionShape, HasTagConstraint, HasVariantConstraint, TagAtMostConstraint,
+        HasFunctionShape, HasTagConstraint, HasVariantConstraint, MapOfTypesConstraint,
+        TagAtMostConstraint,
     },
     generic_nodes::{
         get_generic_type_id, GenericBinaryOperatorExpression, GenericBlockExpression,
         GenericDeclarationExpression, GenericEnumExpression, GenericExpression,
-        GenericFunctionExpression, GenericIdentifierExpression, GenericIfExpression,
-        GenericIntegerLiteralExpression, GenericListExpression, GenericRecordAssignmentExpression,
-        GenericRecordExpression, GenericSourcedType, GenericStringLiteralExpression,
-        GenericTagExpression, GenericTypeDeclarationExpression, GenericTypeIdentifierExpression,
+        GenericFloatLiteralExpression, GenericFunctionExpression, GenericIdentifierExpression,
+        GenericIfExpression, GenericIntegerLiteralExpression, GenericListExpression,
+        GenericMapEntry, GenericMapExpression, GenericRecordAssignmentExpression,
+        GenericRecordExpression, GenericSetExpression, GenericSourcedType,
+        GenericStringLiteralExpression, GenericTagExpression, GenericTupleDeclarationExpression,
+        GenericTupleExpression, GenericTypeDeclarationExpression, GenericTypeIdentifierExpression,
         GenericUnaryOperatorExpression, GenericWhenCase, GenericWhenCaseName,
         GenericWhenExpression,
     },
@@ -26,12 +30,20 @@ use type_checker_types::{
     type_schema::TypeSchema,
     TypeId,
 };
-use typed_ast::PrimitiveType;
+use typed_ast::{PrimitiveType, SourceLocation};
 
-const fn constrain_equal_to_num() -> Constraint {
+const fn constrain_equal_to_int() -> Constraint {
     Constraint::EqualToPrimitive(PrimitiveType::Int)
 }
 
+const fn constrain_equal_to_float() -> Constraint {
+    Constraint::EqualToPrimitive(PrimitiveType::Float)
+}
+
+const fn constrain_numeric() -> Constraint {
+    Constraint::Numeric
+}
+
 const fn constrain_equal_to_str() -> Constraint {
     Constraint::EqualToPrimitive(PrimitiveType::Str)
 }
@@ -52,13 +64,13 @@ fn constrain_at_least_false() -> Constraint {
 
 fn constrain_at_most_boolean_tag() -> Constraint {
     Constraint::TagAtMost(TagAtMostConstraint {
-        tags: HashMap::from([("true".to_owned(), vec![]), ("false".to_owned(), vec![])]),
+        tags: IndexMap::from([("true".to_owned(), vec![]), ("false".to_owned(), vec![])]),
     })
 }
 
 fn constrain_at_most_none_tag() -> Constraint {
     Constraint::TagAtMost(TagAtMostConstraint {
-        tags: HashMap::from([("none".to_owned(), vec![])]),
+        tags: IndexMap::from([("none".to_owned(), vec![])]),
     })
 }
 
@@ -69,23 +81,25 @@ struct TranslateBinaryOperatorIdCollection {
     pub right_child_id: TypeId,
 }
 
+/// Both operands and the result must be the same kind of number, so `Int`s and
+/// `Float`s cannot be mixed without an explicit conversion.
 fn translate_binary_operator_add_arithmetic_constraints(
     schema: &mut TypeSchema,
     id_collection: &TranslateBinaryOperatorIdCollection,
 ) -> Result<(), String> {
     schema.add_constraint(
         id_collection.type_id,
-        constrain_equal_to_num(),
+        constrain_numeric(),
         &mut CheckedTypes::new(),
     )?;
-    schema.add_constraint(
+    schema.set_equal_to_canonical_type(
+        id_collection.type_id,
         id_collection.left_child_id,
-        constrain_equal_to_num(),
         &mut CheckedTypes::new(),
     )?;
-    schema.add_constraint(
+    schema.set_equal_to_canonical_type(
+        id_collection.type_id,
         id_collection.right_child_id,
-        constrain_equal_to_num(),
         &mut CheckedTypes::new(),
     )?;
     Ok(())
@@ -168,12 +182,12 @@ fn translate_binary_operator_add_comparison_constraints(
     )?;
     schema.add_constraint(
         id_collection.left_child_id,
-        constrain_equal_to_num(),
+        constrain_numeric(),
         &mut CheckedTypes::new(),
     )?;
-    schema.add_constraint(
+    schema.set_equal_to_canonical_type(
+        id_collection.left_child_id,
         id_collection.right_child_id,
-        constrain_equal_to_num(),
         &mut CheckedTypes::new(),
     )?;
     Ok(())
@@ -239,6 +253,17 @@ fn translate_binary_operator_add_field_lookup_constraints(
 ) -> Result<(), String> {
     let field_name = match right_child {
         GenericExpression::Identifier(identifier_expression) => identifier_expression.name.clone(),
+        GenericExpression::Integer(integer_expression) => {
+            let Ok(index) = usize::try_from(integer_expression.value) else {
+                return Err(generate_backtrace_error(format!("TupleIndexTooLarge: {}", integer_expression.value)))
+            };
+            return schema.set_equal_to_tuple_element(
+                id_collection.left_child_id,
+                index,
+                id_collection.type_id,
+                &mut CheckedTypes::new(),
+            );
+        }
         _ => {
             return Err(generate_backtrace_error(
                 "FieldLookupDoesNotUseIdentifier".to_owned(),
@@ -355,6 +380,7 @@ fn translate_binary_operator<'a>(
         }
     };
     Ok(GenericBinaryOperatorExpression {
+        source: SourceLocation::from(&node.source),
         expression_type: GenericSourcedType {
             type_id,
             source_of_type: node.source,
@@ -459,10 +485,20 @@ pub fn translate_declaration<'a>(
         &mut CheckedTypes::new(),
     )?;
 
-    let name_type_id = schema.make_id();
-    schema
+    let identifier_name = &node.value.identifier.value.name;
+    let is_reassignment = schema
         .scope
-        .declare_identifier(node.value.identifier.value.name.clone(), name_type_id)?;
+        .get_variable_declaration_type(identifier_name)
+        .is_some();
+    let name_type_id = if is_reassignment {
+        schema.scope.reassign_identifier(identifier_name)?
+    } else {
+        let name_type_id = schema.make_id();
+        schema
+            .scope
+            .declare_identifier(identifier_name.clone(), name_type_id)?;
+        name_type_id
+    };
     let identifier = translate_identifier(schema, node.value.identifier.clone())?;
 
     let expression = translate_declaration_expression(
@@ -473,6 +509,7 @@ pub fn translate_declaration<'a>(
     )?;
 
     Ok(GenericDeclarationExpression {
+        source: SourceLocation::from(&node.source),
         declaration_type: GenericSourcedType {
             type_id: name_type_id,
             source_of_type: node.source.clone(),
@@ -483,6 +520,7 @@ pub fn translate_declaration<'a>(
         },
         identifier,
         value: expression,
+        is_reassignment,
     })
 }
 
@@ -492,7 +530,7 @@ fn translate_function<'a>(
     declaration_type: Option<TypeId>,
 ) -> Result<GenericFunctionExpression<'a>, String> {
     let function_type = schema.make_id();
-    schema.scope.start_sub_scope();
+    schema.scope.start_function_scope();
     let mut argument_names = Vec::new();
     let mut argument_types = Vec::new();
     argument_names.reserve_exact(node.value.arguments.len());
@@ -569,6 +607,7 @@ fn translate_function<'a>(
     )?;
     schema.scope.end_sub_scope();
     Ok(GenericFunctionExpression {
+        source: SourceLocation::from(&node.source),
         expression_type: GenericSourcedType {
             type_id: function_type,
             source_of_type: node.source,
@@ -582,7 +621,7 @@ fn translate_identifier<'a>(
     schema: &mut TypeSchema,
     node: IdentifierNode<'a>,
 ) -> Result<GenericIdentifierExpression<'a>, String> {
-    let Some(type_id) = schema.scope.get_variable_declaration_type(&node.value.name) else {
+    let Some(type_id) = schema.scope.reference_identifier(&node.value.name) else {
         return Err(generate_backtrace_error(format!("IdentifierNotFound: {}", node.value.name)))
     };
     Ok(GenericIdentifierExpression {
@@ -657,12 +696,31 @@ fn translate_if<'a>(
     })
 }
 
+fn translate_float<'a>(
+    schema: &mut TypeSchema,
+    node: FloatNode<'a>,
+) -> Result<GenericFloatLiteralExpression<'a>, String> {
+    let type_id = schema.make_id();
+    schema.add_constraint(
+        type_id,
+        constrain_equal_to_float(),
+        &mut CheckedTypes::new(),
+    )?;
+    Ok(GenericFloatLiteralExpression {
+        expression_type: GenericSourcedType {
+            type_id,
+            source_of_type: node.source,
+        },
+        value: node.value,
+    })
+}
+
 fn translate_integer<'a>(
     schema: &mut TypeSchema,
     node: IntegerNode<'a>,
 ) -> Result<GenericIntegerLiteralExpression<'a>, String> {
     let type_id = schema.make_id();
-    schema.add_constraint(type_id, constrain_equal_to_num(), &mut CheckedTypes::new())?;
+    schema.add_constraint(type_id, constrain_equal_to_int(), &mut CheckedTypes::new())?;
     Ok(GenericIntegerLiteralExpression {
         expression_type: GenericSourcedType {
             type_id,
@@ -704,14 +762,59 @@ fn translate_list<'a>(
     })
 }
 
+fn translate_map<'a>(
+    schema: &mut TypeSchema,
+    node: MapNode<'a>,
+) -> Result<GenericMapExpression<'a>, String> {
+    let map_type_id = schema.make_id();
+    let key_type_id = schema.make_id();
+    let value_type_id = schema.make_id();
+    schema.add_constraint(
+        map_type_id,
+        Constraint::MapOfTypes(MapOfTypesConstraint {
+            key_type: key_type_id,
+            value_type: value_type_id,
+        }),
+        &mut CheckedTypes::new(),
+    )?;
+    let mut entry_translations = Vec::new();
+    entry_translations.reserve_exact(node.value.len());
+    for entry in node.value {
+        let key_translation = translate_parsed_expression_to_generic_expression(schema, entry.key)?;
+        schema.set_equal_to_canonical_type(
+            get_generic_type_id(&key_translation),
+            key_type_id,
+            &mut CheckedTypes::new(),
+        )?;
+        let value_translation =
+            translate_parsed_expression_to_generic_expression(schema, entry.value)?;
+        schema.set_equal_to_canonical_type(
+            get_generic_type_id(&value_translation),
+            value_type_id,
+            &mut CheckedTypes::new(),
+        )?;
+        entry_translations.push(GenericMapEntry {
+            key: key_translation,
+            value: value_translation,
+        });
+    }
+    Ok(GenericMapExpression {
+        expression_type: GenericSourcedType {
+            type_id: map_type_id,
+            source_of_type: node.source,
+        },
+        contents: entry_translations,
+    })
+}
+
 fn translate_record<'a>(
     schema: &mut TypeSchema,
     node: RecordNode<'a>,
 ) -> Result<GenericRecordExpression<'a>, String> {
     let record_type_id = schema.make_id();
-    let mut element_translations = HashMap::new();
+    let mut element_translations = IndexMap::new();
     element_translations.reserve(node.value.len());
-    let mut fields = HashMap::new();
+    let mut fields = IndexMap::new();
     for element in node.value {
         let field_type_id = schema.make_id();
         let field_name = element.identifier.value.name;
@@ -864,12 +967,12 @@ fn translate_unary_operator<'a>(
             translated_child
         }
         UnaryOperatorSymbol::Negative => {
-            schema.add_constraint(type_id, constrain_equal_to_num(), &mut CheckedTypes::new())?;
+            schema.add_constraint(type_id, constrain_numeric(), &mut CheckedTypes::new())?;
             let translated_child =
                 translate_parsed_expression_to_generic_expression(schema, *node.value.child)?;
-            schema.add_constraint(
+            schema.set_equal_to_canonical_type(
+                type_id,
                 get_generic_type_id(&translated_child),
-                constrain_equal_to_num(),
                 &mut CheckedTypes::new(),
             )?;
             translated_child
@@ -893,7 +996,7 @@ fn translate_record_assignment<'a>(
     let raw_translated_name = translate_identifier(schema, node.value.identifier)?;
     let translated_name = GenericExpression::Identifier(Box::new(raw_translated_name.clone()));
     let name_type_id = get_generic_type_id(&translated_name);
-    let mut field_translations = HashMap::new();
+    let mut field_translations = IndexMap::new();
     field_translations.reserve(node.value.new_values.len());
 
     for element in node.value.new_values {
@@ -937,6 +1040,38 @@ fn translate_record_assignment<'a>(
     })
 }
 
+fn translate_set<'a>(
+    schema: &mut TypeSchema,
+    node: SetNode<'a>,
+) -> Result<GenericSetExpression<'a>, String> {
+    let set_type_id = schema.make_id();
+    let element_type_id = schema.make_id();
+    schema.add_constraint(
+        set_type_id,
+        Constraint::SetOfType(element_type_id),
+        &mut CheckedTypes::new(),
+    )?;
+    let mut element_translations = Vec::new();
+    element_translations.reserve_exact(node.value.len());
+    for element in node.value {
+        let element_translation =
+            translate_parsed_expression_to_generic_expression(schema, element)?;
+        schema.set_equal_to_canonical_type(
+            get_generic_type_id(&element_translation),
+            element_type_id,
+            &mut CheckedTypes::new(),
+        )?;
+        element_translations.push(element_translation);
+    }
+    Ok(GenericSetExpression {
+        expression_type: GenericSourcedType {
+            type_id: set_type_id,
+            source_of_type: node.source,
+        },
+        contents: element_translations,
+    })
+}
+
 pub fn translate_type_declaration<'a>(
     schema: &mut TypeSchema,
     node: TypeDeclarationNode<'a>,
@@ -973,6 +1108,114 @@ pub fn translate_type_declaration<'a>(
     })
 }
 
+fn translate_tuple<'a>(
+    schema: &mut TypeSchema,
+    node: TupleNode<'a>,
+) -> Result<GenericTupleExpression<'a>, String> {
+    let tuple_type_id = schema.make_id();
+    let mut element_translations = Vec::new();
+    element_translations.reserve_exact(node.value.len());
+    for element in node.value {
+        element_translations.push(translate_parsed_expression_to_generic_expression(
+            schema, element,
+        )?);
+    }
+    schema.add_constraint(
+        tuple_type_id,
+        Constraint::TupleOfTypes(
+            element_translations
+                .iter()
+                .map(get_generic_type_id)
+                .collect(),
+        ),
+        &mut CheckedTypes::new(),
+    )?;
+    Ok(GenericTupleExpression {
+        expression_type: GenericSourcedType {
+            type_id: tuple_type_id,
+            source_of_type: node.source,
+        },
+        contents: element_translations,
+    })
+}
+
+fn translate_tuple_declaration<'a>(
+    schema: &mut TypeSchema,
+    node: TupleDeclarationNode<'a>,
+) -> Result<GenericTupleDeclarationExpression<'a>, String> {
+    let declaration_type_id = schema.make_id();
+    schema.add_constraint(
+        declaration_type_id,
+        constrain_at_most_none_tag(),
+        &mut CheckedTypes::new(),
+    )?;
+
+    let value = translate_parsed_expression_to_generic_expression(schema, *node.value.expression)?;
+    let value_type_id = get_generic_type_id(&value);
+    if let Some(type_expression) = node.value.type_expression {
+        let type_expression_id = translate_parsed_type_expression(schema, &type_expression)?;
+        schema.set_equal_to_canonical_type(
+            type_expression_id,
+            value_type_id,
+            &mut CheckedTypes::new(),
+        )?;
+    }
+
+    let element_type_ids: Vec<TypeId> = node
+        .value
+        .identifiers
+        .iter()
+        .map(|_| schema.make_id())
+        .collect();
+    // Check that the value has exactly as many elements as there are identifiers,
+    // then give each identifier the type of its element.
+    schema.add_constraint(
+        value_type_id,
+        Constraint::TupleOfTypes(element_type_ids.clone()),
+        &mut CheckedTypes::new(),
+    )?;
+    let mut identifiers = Vec::new();
+    identifiers.reserve_exact(element_type_ids.len());
+    for (index, (identifier, element_type_id)) in node
+        .value
+        .identifiers
+        .into_iter()
+        .zip(element_type_ids)
+        .enumerate()
+    {
+        schema.set_equal_to_tuple_element(
+            value_type_id,
+            index,
+            element_type_id,
+            &mut CheckedTypes::new(),
+        )?;
+        schema
+            .scope
+            .declare_identifier(identifier.value.name.clone(), element_type_id)?;
+        identifiers.push(GenericIdentifierExpression {
+            expression_type: GenericSourcedType {
+                type_id: element_type_id,
+                source_of_type: identifier.source,
+            },
+            name: identifier.value.name,
+            is_disregarded: identifier.value.is_disregarded,
+        });
+    }
+
+    Ok(GenericTupleDeclarationExpression {
+        declaration_type: GenericSourcedType {
+            type_id: value_type_id,
+            source_of_type: node.source.clone(),
+        },
+        expression_type: GenericSourcedType {
+            type_id: declaration_type_id,
+            source_of_type: node.source,
+        },
+        identifiers,
+        value,
+    })
+}
+
 fn translate_type_identifier<'a>(
     schema: &mut TypeSchema,
     node: TypeIdentifierNode<'a>,
@@ -1035,13 +1278,57 @@ fn translate_list_type(
     Ok(type_id)
 }
 
+fn translate_map_type(schema: &mut TypeSchema, expression: &MapTypeNode) -> Result<TypeId, String> {
+    let type_id = schema.make_id();
+    let key_type_id = translate_parsed_type_expression(schema, &expression.value.key_type)?;
+    let value_type_id = translate_parsed_type_expression(schema, &expression.value.value_type)?;
+    schema.add_constraint(
+        type_id,
+        Constraint::MapOfTypes(MapOfTypesConstraint {
+            key_type: key_type_id,
+            value_type: value_type_id,
+        }),
+        &mut CheckedTypes::new(),
+    )?;
+    Ok(type_id)
+}
+
+fn translate_set_type(schema: &mut TypeSchema, expression: &SetTypeNode) -> Result<TypeId, String> {
+    let type_id = schema.make_id();
+    let element_type_id = translate_parsed_type_expression(schema, &expression.value)?;
+    schema.add_constraint(
+        type_id,
+        Constraint::SetOfType(element_type_id),
+        &mut CheckedTypes::new(),
+    )?;
+    Ok(type_id)
+}
+
+fn translate_tuple_type(
+    schema: &mut TypeSchema,
+    expression: &TupleTypeNode,
+) -> Result<TypeId, String> {
+    let type_id = schema.make_id();
+    let mut element_type_ids = Vec::new();
+    element_type_ids.reserve_exact(expression.value.len());
+    for element_type in &expression.value {
+        element_type_ids.push(translate_parsed_type_expression(schema, element_type)?);
+    }
+    schema.add_constraint(
+        type_id,
+        Constraint::TupleOfTypes(element_type_ids),
+        &mut CheckedTypes::new(),
+    )?;
+    Ok(type_id)
+}
+
 fn translate_record_type(
     schema: &mut TypeSchema,
     expression: &RecordTypeNode,
 ) -> Result<TypeId, String> {
     let type_id = schema.make_id();
 
-    let mut fields = HashMap::new();
+    let mut fields = IndexMap::new();
 
     for field in &expression.value {
         let field_type_id = translate_parsed_type_expression(schema, &field.value)?;
@@ -1063,7 +1350,7 @@ fn translate_tag_group_type(
 ) -> Result<TypeId, String> {
     let type_id = schema.make_id();
 
-    let mut tags: HashMap<String, Vec<TypeId>> = HashMap::new();
+    let mut tags: IndexMap<String, Vec<TypeId>> = IndexMap::new();
     for tag in &expression.value {
         let tag_name = tag.value.name.value.clone();
         if tags.contains_key(&tag_name) {
@@ -1092,7 +1379,7 @@ fn translate_enum_type(
 ) -> Result<TypeId, String> {
     let type_id = schema.make_id();
 
-    let mut variants: HashMap<String, Vec<TypeId>> = HashMap::new();
+    let mut variants: IndexMap<String, Vec<TypeId>> = IndexMap::new();
     for variant in &expression.value.variants {
         let variant_name = variant.value.variant_name.clone();
         if variants.contains_key(&variant_name) {
@@ -1125,8 +1412,11 @@ fn translate_parsed_type_expression(
             translate_type_identifier_type(schema, identifier)
         }
         TypeExpression::List(list) => translate_list_type(schema, list),
+        TypeExpression::Map(map) => translate_map_type(schema, map),
+        TypeExpression::Set(set) => translate_set_type(schema, set),
         TypeExpression::Record(record) => translate_record_type(schema, record),
         TypeExpression::TagGroup(tags) => translate_tag_group_type(schema, tags),
+        TypeExpression::Tuple(tuple) => translate_tuple_type(schema, tuple),
         TypeExpression::Enum(enum_type) => translate_enum_type(schema, enum_type),
     }
 }
@@ -1143,7 +1433,7 @@ fn translate_when_expression<'a>(
 
     let mut cases = Vec::new();
     let is_open = expression.value.default_case.is_some();
-    let mut at_most_tags = HashMap::new();
+    let mut at_most_tags = IndexMap::new();
 
     for case in expression.value.cases {
         schema.scope.start_sub_scope();
@@ -1261,6 +1551,9 @@ pub fn translate_parsed_expression_to_generic_expression<'a>(
         Expression::EnumLiteral(node) => translate_enum(schema, node)
             .map(Box::new)
             .map(GenericExpression::Enum),
+        Expression::Float(node) => translate_float(schema, node)
+            .map(Box::new)
+            .map(GenericExpression::Float),
         Expression::Function(node) => translate_function(schema, node, None)
             .map(Box::new)
             .map(GenericExpression::Function),
@@ -1279,18 +1572,30 @@ pub fn translate_parsed_expression_to_generic_expression<'a>(
         Expression::List(node) => translate_list(schema, node)
             .map(Box::new)
             .map(GenericExpression::List),
+        Expression::Map(node) => translate_map(schema, node)
+            .map(Box::new)
+            .map(GenericExpression::Map),
         Expression::Record(node) => translate_record(schema, node)
             .map(Box::new)
             .map(GenericExpression::Record),
         Expression::RecordAssignment(node) => translate_record_assignment(schema, node)
             .map(Box::new)
             .map(GenericExpression::RecordAssignment),
+        Expression::Set(node) => translate_set(schema, node)
+            .map(Box::new)
+            .map(GenericExpression::Set),
         Expression::StringLiteral(node) => Ok(GenericExpression::StringLiteral(Box::new(
             translate_string(schema, node)?,
         ))),
         Expression::Tag(node) => translate_tag(schema, node)
             .map(Box::new)
             .map(GenericExpression::Tag),
+        Expression::Tuple(node) => translate_tuple(schema, node)
+            .map(Box::new)
+            .map(GenericExpression::Tuple),
+        Expression::TupleDeclaration(node) => translate_tuple_declaration(schema, node)
+            .map(Box::new)
+            .map(GenericExpression::TupleDeclaration),
         Expression::TypeDeclaration(node) => translate_type_declaration(schema, node)
             .map(Box::new)
             .map(GenericExpression::TypeDeclaration),
@@ -1309,26 +1614,26 @@ mod test {
     use ast::{FunctionApplicationArgumentsNode, FunctionApplicationArgumentsValue, ParserInput};
     use indoc::indoc;
     use parser::parse_test_expression;
-    use typed_ast::ConcreteType;
+    use typed_ast::{ConcreteTagUnionType, ConcreteTupleType, ConcreteType};
 
-    const INITIAL_CONSTRAINT_COUNT: usize = 5;
+    const INITIAL_CONSTRAINT_COUNT: usize = 13;
 
     #[test]
     fn binary_operator_increments_id_counter_by_one_more_than_total_number_of_ids_in_children() {
         let mut schema = TypeSchema::new();
         let expression = parse_test_expression("314 + 271");
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
-        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 5);
+        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 9);
     }
 
     #[test]
-    fn arithmetic_binary_operator_adds_three_constraints_beyond_those_added_by_its_children() {
+    fn arithmetic_binary_operator_shares_one_canonical_id_with_its_children() {
         let mut schema = TypeSchema::new();
         let expression = parse_test_expression("314 + 271");
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 5
+            INITIAL_CONSTRAINT_COUNT + 7
         );
     }
 
@@ -1363,7 +1668,7 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 4
+            INITIAL_CONSTRAINT_COUNT + 8
         );
     }
 
@@ -1374,19 +1679,18 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 4
+            INITIAL_CONSTRAINT_COUNT + 8
         );
     }
 
     #[test]
-    fn ordered_comparison_binary_operator_adds_four_constraints_beyond_those_added_by_its_children()
-    {
+    fn ordered_comparison_binary_operator_children_share_one_canonical_id() {
         let mut schema = TypeSchema::new();
         let expression = parse_test_expression("314 < 271");
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 5
+            INITIAL_CONSTRAINT_COUNT + 8
         );
     }
 
@@ -1399,7 +1703,7 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 11
+            INITIAL_CONSTRAINT_COUNT + 15
         );
     }
 
@@ -1412,7 +1716,7 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 11
+            INITIAL_CONSTRAINT_COUNT + 15
         );
     }
 
@@ -1506,7 +1810,7 @@ mod test {
             ],
         });
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
-        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 6);
+        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 12);
     }
 
     #[test]
@@ -1561,7 +1865,7 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 5
+            INITIAL_CONSTRAINT_COUNT + 11
         );
     }
 
@@ -1688,7 +1992,7 @@ mod test {
         let mut schema = TypeSchema::new();
         let expression = parse_test_expression("314");
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
-        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 3);
+        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 5);
     }
 
     #[test]
@@ -1698,7 +2002,7 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 3
+            INITIAL_CONSTRAINT_COUNT + 5
         );
     }
 
@@ -1720,7 +2024,7 @@ mod test {
         let mut schema = TypeSchema::new();
         let expression = parse_test_expression("[2, 3, 5]");
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
-        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 12);
+        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 18);
     }
 
     #[test]
@@ -1730,7 +2034,7 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 9
+            INITIAL_CONSTRAINT_COUNT + 15
         );
     }
 
@@ -1754,7 +2058,7 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 9
+            INITIAL_CONSTRAINT_COUNT + 15
         );
     }
 
@@ -1766,12 +2070,283 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn for_map_input_each_entry_has_corresponding_entry_in_translated_map() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("Map[\"one\" => 1, \"two\" => 2]");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        let GenericExpression::Map(map_node) = result else {
+            panic!("Expected a map")
+        };
+        assert_eq!(map_node.contents.len(), 2);
+        assert!(matches!(
+            map_node.contents[0].key,
+            GenericExpression::StringLiteral(_)
+        ));
+        assert!(matches!(
+            map_node.contents[0].value,
+            GenericExpression::Integer(_)
+        ));
+    }
+
+    #[test]
+    fn maps_with_mixed_key_types_error() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("Map[1 => 1, \"two\" => 2]");
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn maps_with_mixed_value_types_error() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("Map[1 => 1, 2 => \"two\"]");
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn map_get_returns_an_option_of_the_value_type() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("Map[1 => \"one\"]:get(1)");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        assert_eq!(
+            schema.get_concrete_type_from_id(get_generic_type_id(&result)),
+            ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
+                tag_types: IndexMap::from([
+                    (
+                        "some".to_owned(),
+                        vec![ConcreteType::Primitive(PrimitiveType::Str)]
+                    ),
+                    ("none".to_owned(), vec![]),
+                ])
+            }))
+        );
+    }
+
+    #[test]
+    fn sets_of_mixed_types_error() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("Set[1, \"two\"]");
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn set_has_returns_a_boolean() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("Set[1, 2]:has(1)");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        assert_eq!(
+            schema.get_concrete_type_from_id(get_generic_type_id(&result)),
+            ConcreteType::Primitive(PrimitiveType::CompilerBoolean)
+        );
+    }
+
+    #[test]
+    fn tuple_input_has_the_types_of_its_elements() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("(1, \"two\")");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        assert_eq!(
+            schema.get_concrete_type_from_id(get_generic_type_id(&result)),
+            ConcreteType::Tuple(Box::new(ConcreteTupleType {
+                element_types: vec![
+                    ConcreteType::Primitive(PrimitiveType::Int),
+                    ConcreteType::Primitive(PrimitiveType::Str),
+                ]
+            }))
+        );
+    }
+
+    #[test]
+    fn tuple_element_lookup_has_the_type_of_the_element() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("(1, \"two\").1");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        assert_eq!(
+            schema.get_concrete_type_from_id(get_generic_type_id(&result)),
+            ConcreteType::Primitive(PrimitiveType::Str)
+        );
+    }
+
+    #[test]
+    fn tuple_element_lookup_past_the_end_of_the_tuple_errors() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("(1, 2).2");
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn tuple_element_lookups_on_arguments_are_checked_against_each_other() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("(pair) => pair.0 + (pair.0 ++ \"!\")");
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn float_input_has_float_type() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("1.5e3");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        assert_eq!(
+            schema.get_concrete_type_from_id(get_generic_type_id(&result)),
+            ConcreteType::Primitive(PrimitiveType::Float)
+        );
+    }
+
+    #[test]
+    fn float_arithmetic_has_float_type() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("-1.5 / 2.0");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        assert_eq!(
+            schema.get_concrete_type_from_id(get_generic_type_id(&result)),
+            ConcreteType::Primitive(PrimitiveType::Float)
+        );
+    }
+
+    #[test]
+    fn int_to_float_has_float_type() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("1:toFloat() / 2.0");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        assert_eq!(
+            schema.get_concrete_type_from_id(get_generic_type_id(&result)),
+            ConcreteType::Primitive(PrimitiveType::Float)
+        );
+    }
+
+    #[test]
+    fn arithmetic_with_an_int_and_a_float_errors() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("1 + 1.5");
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn comparing_an_int_and_a_float_errors() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("1 < 1.5");
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn arithmetic_on_arguments_takes_the_type_of_the_other_operand() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("(a) => a * 0.5");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        let ConcreteType::Function(function_type) = schema.get_concrete_type_from_id(get_generic_type_id(&result)) else {
+            panic!("Expected a function")
+        };
+        assert_eq!(
+            function_type.argument_types[0],
+            ConcreteType::Primitive(PrimitiveType::Float)
+        );
+    }
+
+    #[test]
+    fn arithmetic_on_arguments_are_any_number() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("(a, b) => a - b");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        let ConcreteType::Function(function_type) = schema.get_concrete_type_from_id(get_generic_type_id(&result)) else {
+            panic!("Expected a function")
+        };
+        assert_eq!(
+            function_type.argument_types[0],
+            ConcreteType::Primitive(PrimitiveType::Num)
+        );
+    }
+
+    #[test]
+    fn rounding_a_float_returns_an_int() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression("1.5:round() + 1");
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        assert_eq!(
+            schema.get_concrete_type_from_id(get_generic_type_id(&result)),
+            ConcreteType::Primitive(PrimitiveType::Int)
+        );
+    }
+
+    #[test]
+    fn tuple_declaration_gives_each_identifier_the_type_of_its_element() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            () =>
+                (a, b) = (1, \"two\")
+                b
+        "});
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        let GenericExpression::Function(function) = result else {
+            panic!("Expected a function")
+        };
+        assert_eq!(
+            schema.get_concrete_type_from_id(get_generic_type_id(&function.body)),
+            ConcreteType::Primitive(PrimitiveType::Str)
+        );
+    }
+
+    #[test]
+    fn tuple_declaration_with_the_wrong_number_of_identifiers_errors() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            () =>
+                (a, b) = (1, 2, 3)
+                a
+        "});
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn tuple_declaration_with_an_incompatible_type_errors() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            () =>
+                (a, b): (Int, Int) = (1, \"two\")
+                a
+        "});
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn tuple_declaration_cannot_redeclare_an_identifier() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            () =>
+                a = 1
+                (a, b) = (1, 2)
+                a
+        "});
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn record_input_increments_id_counter_by_two_for_each_field_plus_one_for_the_record() {
         let mut schema = TypeSchema::new();
         let expression = parse_test_expression("{ a: 3, b: 4 }");
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
-        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 7);
+        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 11);
     }
 
     #[test]
@@ -1783,7 +2358,7 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 7
+            INITIAL_CONSTRAINT_COUNT + 11
         );
     }
 
@@ -1809,7 +2384,7 @@ mod test {
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 5
+            INITIAL_CONSTRAINT_COUNT + 9
         );
     }
 
@@ -1947,17 +2522,17 @@ mod test {
         let mut schema = TypeSchema::new();
         let expression = parse_test_expression("-314");
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
-        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 4);
+        assert_eq!(schema.count_ids(), INITIAL_CONSTRAINT_COUNT + 6);
     }
 
     #[test]
-    fn unary_operator_negative_input_adds_two_constraints_beyond_those_added_by_the_child() {
+    fn unary_operator_negative_input_shares_one_canonical_id_with_the_child() {
         let mut schema = TypeSchema::new();
         let expression = parse_test_expression("-314");
         translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
         assert_eq!(
             schema.get_total_canonical_ids(),
-            INITIAL_CONSTRAINT_COUNT + 4
+            INITIAL_CONSTRAINT_COUNT + 5
         );
     }
 
@@ -2040,6 +2615,110 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn can_reassign_local_variables() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            () =>
+                a = 1
+                a = a + 1
+                a
+        "});
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn can_reassign_arguments() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            (a) =>
+                a = a + 1
+                a
+        "});
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn reassignment_uses_the_type_of_the_original_declaration() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            () =>
+                a = 1
+                a = 2
+        "});
+        let result =
+            translate_parsed_expression_to_generic_expression(&mut schema, expression).unwrap();
+        let GenericExpression::Function(function) = result else {
+            panic!("Expected a function")
+        };
+        let GenericExpression::Block(block) = function.body else {
+            panic!("Expected a block")
+        };
+        let [GenericExpression::Declaration(first), GenericExpression::Declaration(second)] = block.contents.as_slice() else {
+            panic!("Expected two declarations")
+        };
+        assert!(!first.is_reassignment);
+        assert!(second.is_reassignment);
+        assert_eq!(
+            first.declaration_type.type_id,
+            second.declaration_type.type_id
+        );
+    }
+
+    #[test]
+    fn cannot_reassign_with_a_different_type() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            () =>
+                a = 1
+                a = \"hello\"
+                a
+        "});
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cannot_reassign_variables_outside_of_a_function() {
+        let mut schema = TypeSchema::new();
+        let first = parse_test_expression("a = 1");
+        translate_parsed_expression_to_generic_expression(&mut schema, first).unwrap();
+        let expression = parse_test_expression("a = 2");
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cannot_reassign_variables_from_an_outer_function() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            () =>
+                a = 1
+                inner = () =>
+                    a = 2
+                    a
+                inner
+        "});
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cannot_reassign_variables_referenced_by_an_inner_function() {
+        let mut schema = TypeSchema::new();
+        let expression = parse_test_expression(indoc! {"
+            () =>
+                a = 1
+                inner = () => a
+                a = 2
+                inner
+        "});
+        let result = translate_parsed_expression_to_generic_expression(&mut schema, expression);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn can_declare_a_type_identifier() {
         let mut schema = TypeSchema::new();
@@ -1,5 +1,5 @@
 use crate::TypeId;
-use std::collections::HashMap;
+use indexmap::IndexMap;
 use typed_ast::PrimitiveType;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -16,7 +16,7 @@ pub struct HasVariantConstraint {
 pub struct EnumExactConstraint {
     /// The keys are the names of the variants in the enum.
     /// The values are the types of the variant payloads.
-    pub variants: HashMap<String, Vec<TypeId>>,
+    pub variants: IndexMap<String, Vec<TypeId>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,7 +33,7 @@ pub struct HasTagConstraint {
 pub struct TagAtMostConstraint {
     /// The keys are the names of the tags in a tag union.
     /// The values are the types of the tag payloads.
-    pub tags: HashMap<String, Vec<TypeId>>,
+    pub tags: IndexMap<String, Vec<TypeId>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -44,9 +44,24 @@ pub struct HasFieldConstraint {
     pub field_type: TypeId,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Constrain that a generic type is a map from `key_type` to `value_type`.
+pub struct MapOfTypesConstraint {
+    pub key_type: TypeId,
+    pub value_type: TypeId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Constrain that a generic type be a tuple with an element at position `index`,
+/// the type of which is `element_type`.
+pub struct HasTupleElementConstraint {
+    pub index: usize,
+    pub element_type: TypeId,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HasExactFieldsConstraint {
-    pub fields: HashMap<String, TypeId>,
+    pub fields: IndexMap<String, TypeId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -67,8 +82,14 @@ pub struct HasFunctionShape {
 pub enum Constraint {
     /// Constrain that a generic type be equal to some primitive type.
     EqualToPrimitive(PrimitiveType),
+    /// Constrain that a generic type is a number, either an `Int` or a `Float`.
+    Numeric,
     /// Constrain that a generic type is a list whose contents have a particular type.
     ListOfType(TypeId),
+    /// Constrain that a generic type is a map whose keys and values have particular types.
+    MapOfTypes(MapOfTypesConstraint),
+    /// Constrain that a generic type is a set whose elements have a particular type.
+    SetOfType(TypeId),
     /// Constrain that a generic type is a tag union with at least a given set of tags.
     HasTag(HasTagConstraint),
     /// Constrain that a generic type is a tag union with at most a given set of tags.
@@ -79,6 +100,10 @@ pub enum Constraint {
     EnumExact(EnumExactConstraint),
     HasField(HasFieldConstraint),
     HasExactFields(HasExactFieldsConstraint),
+    /// Constrain that a generic type is a tuple with at least a given element.
+    HasTupleElement(HasTupleElementConstraint),
+    /// Constrain that a generic type is a tuple whose elements have exactly the given types.
+    TupleOfTypes(Vec<TypeId>),
     HasMethod(HasMethodConstraint),
     /// Constrain that a generic type is a function with a given return type.
     HasFunctionShape(HasFunctionShape),
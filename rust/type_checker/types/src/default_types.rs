@@ -1,10 +1,10 @@
 use crate::{
     constraints::{Constraint, HasFunctionShape, HasMethodConstraint, TagAtMostConstraint},
     type_checking_call_stack::CheckedTypes,
-    type_schema::{TypeSchema, INT_TYPE_ID},
+    type_schema::{TypeSchema, FLOAT_TYPE_ID, INT_TYPE_ID},
     TypeId,
 };
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 pub fn create_list_default_methods(
     schema: &mut TypeSchema,
@@ -33,6 +33,134 @@ pub fn create_list_default_methods(
     )
 }
 
+pub fn create_map_default_methods(
+    schema: &mut TypeSchema,
+    map_type_id: TypeId,
+    key_type: TypeId,
+    value_type: TypeId,
+) -> Result<(TypeId, Vec<HasMethodConstraint>), String> {
+    create_parsed_constraint_from_methods(
+        vec![
+            Method {
+                name: "get",
+                arguments: vec![key_type],
+                return_type: create_option_type(value_type, schema)?,
+            },
+            Method {
+                name: "insert",
+                arguments: vec![key_type, value_type],
+                return_type: map_type_id,
+            },
+            Method {
+                name: "remove",
+                arguments: vec![key_type],
+                return_type: map_type_id,
+            },
+            Method {
+                name: "has",
+                arguments: vec![key_type],
+                return_type: create_boolean_type(schema)?,
+            },
+            Method {
+                name: "size",
+                arguments: vec![],
+                return_type: INT_TYPE_ID,
+            },
+            Method {
+                name: "keys",
+                arguments: vec![],
+                return_type: create_list_type(key_type, schema)?,
+            },
+            Method {
+                name: "values",
+                arguments: vec![],
+                return_type: create_list_type(value_type, schema)?,
+            },
+        ],
+        schema,
+    )
+}
+
+pub fn create_set_default_methods(
+    schema: &mut TypeSchema,
+    set_type_id: TypeId,
+    element_type: TypeId,
+) -> Result<(TypeId, Vec<HasMethodConstraint>), String> {
+    create_parsed_constraint_from_methods(
+        vec![
+            Method {
+                name: "insert",
+                arguments: vec![element_type],
+                return_type: set_type_id,
+            },
+            Method {
+                name: "remove",
+                arguments: vec![element_type],
+                return_type: set_type_id,
+            },
+            Method {
+                name: "has",
+                arguments: vec![element_type],
+                return_type: create_boolean_type(schema)?,
+            },
+            Method {
+                name: "size",
+                arguments: vec![],
+                return_type: INT_TYPE_ID,
+            },
+            Method {
+                name: "values",
+                arguments: vec![],
+                return_type: create_list_type(element_type, schema)?,
+            },
+        ],
+        schema,
+    )
+}
+
+pub fn create_int_default_methods(
+    schema: &mut TypeSchema,
+) -> Result<(TypeId, Vec<HasMethodConstraint>), String> {
+    create_parsed_constraint_from_methods(
+        vec![Method {
+            name: "toFloat",
+            arguments: vec![],
+            return_type: FLOAT_TYPE_ID,
+        }],
+        schema,
+    )
+}
+
+pub fn create_float_default_methods(
+    schema: &mut TypeSchema,
+) -> Result<(TypeId, Vec<HasMethodConstraint>), String> {
+    create_parsed_constraint_from_methods(
+        vec![
+            Method {
+                name: "floor",
+                arguments: vec![],
+                return_type: INT_TYPE_ID,
+            },
+            Method {
+                name: "ceil",
+                arguments: vec![],
+                return_type: INT_TYPE_ID,
+            },
+            Method {
+                name: "round",
+                arguments: vec![],
+                return_type: INT_TYPE_ID,
+            },
+            Method {
+                name: "truncate",
+                arguments: vec![],
+                return_type: INT_TYPE_ID,
+            },
+        ],
+        schema,
+    )
+}
+
 pub fn create_string_default_methods(
     schema: &mut TypeSchema,
 ) -> Result<(TypeId, Vec<HasMethodConstraint>), String> {
@@ -95,7 +223,7 @@ fn create_option_type(content_type_id: TypeId, schema: &mut TypeSchema) -> Resul
     schema.add_constraint(
         type_id,
         Constraint::TagAtMost(TagAtMostConstraint {
-            tags: HashMap::from([
+            tags: IndexMap::from([
                 ("some".to_string(), vec![content_type_id]),
                 ("none".to_string(), vec![]),
             ]),
@@ -104,3 +232,25 @@ fn create_option_type(content_type_id: TypeId, schema: &mut TypeSchema) -> Resul
     )?;
     Ok(type_id)
 }
+
+fn create_boolean_type(schema: &mut TypeSchema) -> Result<TypeId, String> {
+    let type_id = schema.make_id();
+    schema.add_constraint(
+        type_id,
+        Constraint::TagAtMost(TagAtMostConstraint {
+            tags: IndexMap::from([("true".to_string(), vec![]), ("false".to_string(), vec![])]),
+        }),
+        &mut CheckedTypes::new(),
+    )?;
+    Ok(type_id)
+}
+
+fn create_list_type(element_type_id: TypeId, schema: &mut TypeSchema) -> Result<TypeId, String> {
+    let type_id = schema.make_id();
+    schema.add_constraint(
+        type_id,
+        Constraint::ListOfType(element_type_id),
+        &mut CheckedTypes::new(),
+    )?;
+    Ok(type_id)
+}
@@ -2,12 +2,13 @@ use crate::TypeId;
 use ast::{ImportNode, ParserInput, TopLevelDeclaration};
 use typed_ast::{
     TypedBinaryOperatorExpression, TypedBlockExpression, TypedBooleanLiteralExpression,
-    TypedDeclarationExpression, TypedEnumExpression, TypedExpression, TypedFunctionExpression,
-    TypedIdentifierExpression, TypedIfExpression, TypedIntegerLiteralExpression,
-    TypedListExpression, TypedRecordAssignmentExpression, TypedRecordExpression,
-    TypedStringLiteralExpression, TypedTagExpression, TypedTypeDeclarationExpression,
-    TypedTypeIdentifierExpression, TypedUnaryOperatorExpression, TypedWhenCase, TypedWhenCaseName,
-    TypedWhenExpression,
+    TypedDeclarationExpression, TypedEnumExpression, TypedExpression, TypedFloatLiteralExpression,
+    TypedFunctionExpression, TypedIdentifierExpression, TypedIfExpression,
+    TypedIntegerLiteralExpression, TypedListExpression, TypedMapEntry, TypedMapExpression,
+    TypedRecordAssignmentExpression, TypedRecordExpression, TypedSetExpression,
+    TypedStringLiteralExpression, TypedTagExpression, TypedTupleDeclarationExpression,
+    TypedTupleExpression, TypedTypeDeclarationExpression, TypedTypeIdentifierExpression,
+    TypedUnaryOperatorExpression, TypedWhenCase, TypedWhenCaseName, TypedWhenExpression,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,18 +24,25 @@ pub type GenericBinaryOperatorExpression<'a> =
 pub type GenericBlockExpression<'a> = TypedBlockExpression<GenericSourcedType<'a>>;
 pub type GenericBooleanExpression<'a> = TypedBooleanLiteralExpression<GenericSourcedType<'a>>;
 pub type GenericDeclarationExpression<'a> = TypedDeclarationExpression<GenericSourcedType<'a>>;
+pub type GenericFloatLiteralExpression<'a> = TypedFloatLiteralExpression<GenericSourcedType<'a>>;
 pub type GenericFunctionExpression<'a> = TypedFunctionExpression<GenericSourcedType<'a>>;
 pub type GenericIdentifierExpression<'a> = TypedIdentifierExpression<GenericSourcedType<'a>>;
 pub type GenericIfExpression<'a> = TypedIfExpression<GenericSourcedType<'a>>;
 pub type GenericIntegerLiteralExpression<'a> =
     TypedIntegerLiteralExpression<GenericSourcedType<'a>>;
 pub type GenericListExpression<'a> = TypedListExpression<GenericSourcedType<'a>>;
+pub type GenericMapEntry<'a> = TypedMapEntry<GenericSourcedType<'a>>;
+pub type GenericMapExpression<'a> = TypedMapExpression<GenericSourcedType<'a>>;
 pub type GenericRecordAssignmentExpression<'a> =
     TypedRecordAssignmentExpression<GenericSourcedType<'a>>;
 pub type GenericRecordExpression<'a> = TypedRecordExpression<GenericSourcedType<'a>>;
+pub type GenericSetExpression<'a> = TypedSetExpression<GenericSourcedType<'a>>;
 pub type GenericStringLiteralExpression<'a> = TypedStringLiteralExpression<GenericSourcedType<'a>>;
 pub type GenericTagExpression<'a> = TypedTagExpression<GenericSourcedType<'a>>;
 pub type GenericEnumExpression<'a> = TypedEnumExpression<GenericSourcedType<'a>>;
+pub type GenericTupleExpression<'a> = TypedTupleExpression<GenericSourcedType<'a>>;
+pub type GenericTupleDeclarationExpression<'a> =
+    TypedTupleDeclarationExpression<GenericSourcedType<'a>>;
 pub type GenericTypeDeclarationExpression<'a> =
     TypedTypeDeclarationExpression<GenericSourcedType<'a>>;
 pub type GenericTypeIdentifierExpression<'a> =
@@ -62,17 +70,22 @@ pub const fn get_generic_type_id(input: &GenericExpression) -> TypeId {
         GenericExpression::Block(node) => node.expression_type.type_id,
         GenericExpression::Boolean(node) => node.expression_type.type_id,
         GenericExpression::Declaration(node) => node.expression_type.type_id,
+        GenericExpression::Float(node) => node.expression_type.type_id,
         GenericExpression::Function(node) => node.expression_type.type_id,
         GenericExpression::FunctionArguments(_) => unreachable!(),
         GenericExpression::Identifier(node) => node.expression_type.type_id,
         GenericExpression::If(node) => node.expression_type.type_id,
         GenericExpression::Integer(node) => node.expression_type.type_id,
         GenericExpression::List(node) => node.expression_type.type_id,
+        GenericExpression::Map(node) => node.expression_type.type_id,
         GenericExpression::Record(node) => node.expression_type.type_id,
         GenericExpression::RecordAssignment(node) => node.expression_type.type_id,
+        GenericExpression::Set(node) => node.expression_type.type_id,
         GenericExpression::StringLiteral(node) => node.expression_type.type_id,
         GenericExpression::Tag(node) => node.expression_type.type_id,
         GenericExpression::Enum(node) => node.expression_type.type_id,
+        GenericExpression::Tuple(node) => node.expression_type.type_id,
+        GenericExpression::TupleDeclaration(node) => node.expression_type.type_id,
         GenericExpression::TypeDeclaration(node) => node.expression_type.type_id,
         GenericExpression::TypeIdentifier(node) => node.expression_type.type_id,
         GenericExpression::UnaryOperator(node) => node.expression_type.type_id,
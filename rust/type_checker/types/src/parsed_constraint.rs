@@ -1,39 +1,52 @@
 use crate::{
     constraints::{Constraint, HasMethodConstraint},
-    default_types::{create_list_default_methods, create_string_default_methods},
+    default_types::{
+        create_float_default_methods, create_int_default_methods, create_list_default_methods,
+        create_map_default_methods, create_set_default_methods, create_string_default_methods,
+    },
     type_checking_call_stack::CheckedTypes,
     type_schema::{CanonicalIds, TypeSchema},
     TypeId,
 };
+use indexmap::IndexMap;
 use std::collections::HashMap;
 use type_checker_errors::generate_backtrace_error;
 use typed_ast::{
-    ConcreteEnumType, ConcreteFunctionType, ConcreteListType, ConcreteRecordType,
-    ConcreteTagUnionType, ConcreteType, PrimitiveType,
+    ConcreteEnumType, ConcreteFunctionType, ConcreteListType, ConcreteMapType, ConcreteRecordType,
+    ConcreteSetType, ConcreteTagUnionType, ConcreteTupleType, ConcreteType, PrimitiveType,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum TagGroupConstraints {
     /// For tag unions that need to have at least these tags.
-    OpenTags(HashMap<String, Vec<TypeId>>),
+    OpenTags(IndexMap<String, Vec<TypeId>>),
     /// For tag unions that can accept at most these tags.
-    ClosedTags(HashMap<String, Vec<TypeId>>),
+    ClosedTags(IndexMap<String, Vec<TypeId>>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum EnumConstraints {
     /// For enums that need to have at least these variants.
-    OpenVariants(HashMap<String, Vec<TypeId>>),
+    OpenVariants(IndexMap<String, Vec<TypeId>>),
     /// For enums that can accept at exactly these variants.
-    ExactVariants(HashMap<String, Vec<TypeId>>),
+    ExactVariants(IndexMap<String, Vec<TypeId>>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum RecordConstraints {
     /// For records that need to have at least these fields.
-    OpenFields(HashMap<String, TypeId>),
+    OpenFields(IndexMap<String, TypeId>),
     /// For records that can accept at most these fields.
-    ExactFields(HashMap<String, TypeId>),
+    ExactFields(IndexMap<String, TypeId>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TupleConstraints {
+    /// For tuples that need to have at least these elements.
+    /// The keys are the positions of the elements.
+    OpenElements(HashMap<usize, TypeId>),
+    /// For tuples that have exactly these elements.
+    ExactElements(Vec<TypeId>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -42,16 +55,27 @@ struct FunctionConstraints {
     pub return_type: TypeId,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MapConstraints {
+    pub key_type: TypeId,
+    pub value_type: TypeId,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 /// Constraints that apply to a single data type.
 enum CategoryConstraints {
     #[default]
     Unknown,
+    /// An `Int` or a `Float`. Defaults to `Int` if nothing narrows it further.
+    Numeric,
     Primitive(PrimitiveType),
     List(TypeId),
+    Map(MapConstraints),
+    Set(TypeId),
     TagGroup(TagGroupConstraints),
     Enum(EnumConstraints),
     Record(RecordConstraints),
+    Tuple(TupleConstraints),
     Function(FunctionConstraints),
 }
 
@@ -64,13 +88,29 @@ impl CategoryConstraints {
         checked_types: &mut CheckedTypes,
     ) -> bool {
         match (self, other) {
-            (Self::Unknown, _) | (_, Self::Unknown) => true,
+            (Self::Unknown, _) | (_, Self::Unknown) | (Self::Numeric, Self::Numeric) => true,
             (Self::Primitive(self_primitive), Self::Primitive(constraint_primitive)) => {
                 self_primitive == constraint_primitive
             }
-            (Self::List(self_type), Self::List(constraint_type)) => {
+            (Self::Numeric, Self::Primitive(primitive))
+            | (Self::Primitive(primitive), Self::Numeric) => {
+                matches!(primitive, PrimitiveType::Int | PrimitiveType::Float)
+            }
+            (Self::List(self_type), Self::List(constraint_type))
+            | (Self::Set(self_type), Self::Set(constraint_type)) => {
                 schema.types_are_compatible(*self_type, *constraint_type, checked_types)
             }
+            (Self::Map(self_map), Self::Map(constraint_map)) => {
+                schema.types_are_compatible(
+                    self_map.key_type,
+                    constraint_map.key_type,
+                    checked_types,
+                ) && schema.types_are_compatible(
+                    self_map.value_type,
+                    constraint_map.value_type,
+                    checked_types,
+                )
+            }
             (
                 Self::TagGroup(TagGroupConstraints::ClosedTags(self_tags)),
                 Self::TagGroup(TagGroupConstraints::ClosedTags(other_tags)),
@@ -243,6 +283,43 @@ impl CategoryConstraints {
                     schema.types_are_compatible(*self_type_id, *type_id, checked_types)
                 })
             }),
+            (
+                Self::Tuple(TupleConstraints::ExactElements(self_elements)),
+                Self::Tuple(TupleConstraints::ExactElements(other_elements)),
+            ) => {
+                self_elements.len() == other_elements.len()
+                    && self_elements.iter().zip(other_elements.iter()).all(
+                        |(self_type_id, other_type_id)| {
+                            schema.types_are_compatible(
+                                *self_type_id,
+                                *other_type_id,
+                                checked_types,
+                            )
+                        },
+                    )
+            }
+            (
+                Self::Tuple(TupleConstraints::OpenElements(open_elements)),
+                Self::Tuple(TupleConstraints::ExactElements(exact_elements)),
+            )
+            | (
+                Self::Tuple(TupleConstraints::ExactElements(exact_elements)),
+                Self::Tuple(TupleConstraints::OpenElements(open_elements)),
+            ) => open_elements.iter().all(|(index, open_type_id)| {
+                matches!(
+                    exact_elements.get(*index),
+                    Some(exact_type_id)
+                        if schema.types_are_compatible(*exact_type_id, *open_type_id, checked_types)
+                )
+            }),
+            (
+                Self::Tuple(TupleConstraints::OpenElements(self_elements)),
+                Self::Tuple(TupleConstraints::OpenElements(other_elements)),
+            ) => other_elements.iter().all(|(index, type_id)| {
+                self_elements.get(index).into_iter().all(|self_type_id| {
+                    schema.types_are_compatible(*self_type_id, *type_id, checked_types)
+                })
+            }),
             (
                 Self::Function(FunctionConstraints {
                     argument_types: self_argument_types,
@@ -272,6 +349,9 @@ impl CategoryConstraints {
 
     pub fn update(&mut self, other: Self, ids: &CanonicalIds) {
         match (self, other) {
+            (self_category @ Self::Numeric, Self::Primitive(primitive)) => {
+                *self_category = Self::Primitive(primitive);
+            }
             (
                 Self::TagGroup(TagGroupConstraints::ClosedTags(self_tags)),
                 Self::TagGroup(TagGroupConstraints::ClosedTags(other_tags)),
@@ -279,7 +359,7 @@ impl CategoryConstraints {
                 let mut new_tags = self_tags.clone();
                 for (k, _) in self_tags.iter() {
                     if other_tags.get(k).is_none() {
-                        new_tags.remove(k);
+                        new_tags.shift_remove(k);
                     }
                 }
                 *self_tags = new_tags;
@@ -299,7 +379,7 @@ impl CategoryConstraints {
                 let mut new_variants = self_variants.clone();
                 for (k, _) in self_variants.iter() {
                     if other_variants.get(k).is_none() {
-                        new_variants.remove(k);
+                        new_variants.shift_remove(k);
                     }
                 }
                 *self_variants = new_variants;
@@ -319,7 +399,7 @@ impl CategoryConstraints {
                 let mut new_tags = self_fields.clone();
                 for (k, _) in self_fields.iter() {
                     if other_fields.get(k).is_none() {
-                        new_tags.remove(k);
+                        new_tags.shift_remove(k);
                     }
                 }
                 *self_fields = new_tags;
@@ -332,6 +412,27 @@ impl CategoryConstraints {
                     self_fields.insert(k, ids.get_canonical_id(v));
                 }
             }
+            (
+                Self::Tuple(TupleConstraints::OpenElements(self_elements)),
+                Self::Tuple(TupleConstraints::OpenElements(other_elements)),
+            ) => {
+                for (k, v) in other_elements {
+                    self_elements
+                        .entry(k)
+                        .or_insert_with(|| ids.get_canonical_id(v));
+                }
+            }
+            (
+                Self::Tuple(self_tuple @ TupleConstraints::OpenElements(_)),
+                Self::Tuple(TupleConstraints::ExactElements(other_elements)),
+            ) => {
+                *self_tuple = TupleConstraints::ExactElements(
+                    other_elements
+                        .iter()
+                        .map(|id| ids.get_canonical_id(*id))
+                        .collect(),
+                );
+            }
             _ => (),
         }
     }
@@ -357,6 +458,15 @@ impl CategoryConstraints {
         }
     }
 
+    #[must_use]
+    pub fn get_tuple_element_type(&self, index: usize) -> Option<TypeId> {
+        match self {
+            Self::Tuple(TupleConstraints::OpenElements(elements)) => elements.get(&index).copied(),
+            Self::Tuple(TupleConstraints::ExactElements(elements)) => elements.get(index).copied(),
+            _ => None,
+        }
+    }
+
     pub fn get_tag_content_types(&self, tag_name: &String) -> Result<Vec<TypeId>, String> {
         match self {
             Self::TagGroup(tag_group) => match tag_group {
@@ -482,6 +592,25 @@ pub struct ParsedConstraint {
     methods: ParsedMethodsConstraint,
 }
 
+/// The concrete types of the contents of each tag or enum variant.
+fn get_concrete_types_by_name(
+    types_by_name: &IndexMap<String, Vec<TypeId>>,
+    schema: &TypeSchema,
+) -> IndexMap<String, Vec<ConcreteType>> {
+    types_by_name
+        .iter()
+        .map(|(name, type_ids)| {
+            (
+                name.clone(),
+                type_ids
+                    .iter()
+                    .map(|type_id| schema.get_concrete_type_from_id(*type_id))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
 impl ParsedConstraint {
     pub fn new(
         type_id: TypeId,
@@ -493,6 +622,8 @@ impl ParsedConstraint {
         let category = match constraint {
             Constraint::EqualToPrimitive(p) => {
                 let (_, primitive_methods) = match p {
+                    PrimitiveType::Int => create_int_default_methods(schema)?,
+                    PrimitiveType::Float => create_float_default_methods(schema)?,
                     PrimitiveType::Str => create_string_default_methods(schema)?,
                     _ => (0, vec![]),
                 };
@@ -501,6 +632,7 @@ impl ParsedConstraint {
                 }
                 CategoryConstraints::Primitive(p)
             }
+            Constraint::Numeric => CategoryConstraints::Numeric,
             Constraint::ListOfType(t) => {
                 let (_, list_methods) = create_list_default_methods(schema, type_id, t)?;
                 for method in list_methods {
@@ -508,6 +640,24 @@ impl ParsedConstraint {
                 }
                 CategoryConstraints::List(t)
             }
+            Constraint::MapOfTypes(m) => {
+                let (_, map_methods) =
+                    create_map_default_methods(schema, type_id, m.key_type, m.value_type)?;
+                for method in map_methods {
+                    methods.add(method.method_name, method.method_type, &schema.types);
+                }
+                CategoryConstraints::Map(MapConstraints {
+                    key_type: m.key_type,
+                    value_type: m.value_type,
+                })
+            }
+            Constraint::SetOfType(t) => {
+                let (_, set_methods) = create_set_default_methods(schema, type_id, t)?;
+                for method in set_methods {
+                    methods.add(method.method_name, method.method_type, &schema.types);
+                }
+                CategoryConstraints::Set(t)
+            }
             Constraint::HasTag(t) => CategoryConstraints::TagGroup(TagGroupConstraints::OpenTags(
                 vec![(t.tag_name, t.tag_content_types)]
                     .into_iter()
@@ -517,7 +667,7 @@ impl ParsedConstraint {
                 CategoryConstraints::TagGroup(TagGroupConstraints::ClosedTags(t.tags))
             }
             Constraint::HasVariant(e) => {
-                let mut variants = HashMap::new();
+                let mut variants = IndexMap::new();
                 variants.insert(e.name, e.payload);
                 CategoryConstraints::Enum(EnumConstraints::OpenVariants(variants))
             }
@@ -530,6 +680,12 @@ impl ParsedConstraint {
             Constraint::HasExactFields(f) => {
                 CategoryConstraints::Record(RecordConstraints::ExactFields(f.fields))
             }
+            Constraint::HasTupleElement(t) => CategoryConstraints::Tuple(
+                TupleConstraints::OpenElements(HashMap::from([(t.index, t.element_type)])),
+            ),
+            Constraint::TupleOfTypes(t) => {
+                CategoryConstraints::Tuple(TupleConstraints::ExactElements(t))
+            }
             Constraint::HasFunctionShape(f) => CategoryConstraints::Function(FunctionConstraints {
                 argument_types: f.argument_types,
                 return_type: f.return_type,
@@ -579,11 +735,19 @@ impl ParsedConstraint {
 
     pub fn to_concrete_type(&self, schema: &TypeSchema) -> ConcreteType {
         match &self.category {
-            CategoryConstraints::Unknown => ConcreteType::Primitive(PrimitiveType::CompilerBoolean),
+            CategoryConstraints::Unknown => ConcreteType::Primitive(PrimitiveType::Unknown),
+            CategoryConstraints::Numeric => ConcreteType::Primitive(PrimitiveType::Num),
             CategoryConstraints::Primitive(p) => ConcreteType::Primitive(*p),
             CategoryConstraints::List(t) => ConcreteType::List(Box::new(ConcreteListType {
                 element_type: schema.get_concrete_type_from_id(*t),
             })),
+            CategoryConstraints::Map(m) => ConcreteType::Map(Box::new(ConcreteMapType {
+                key_type: schema.get_concrete_type_from_id(m.key_type),
+                value_type: schema.get_concrete_type_from_id(m.value_type),
+            })),
+            CategoryConstraints::Set(t) => ConcreteType::Set(Box::new(ConcreteSetType {
+                element_type: schema.get_concrete_type_from_id(*t),
+            })),
             CategoryConstraints::Function(f) => {
                 ConcreteType::Function(Box::new(ConcreteFunctionType {
                     argument_types: f
@@ -604,6 +768,28 @@ impl ParsedConstraint {
                     })
                     .collect(),
             })),
+            CategoryConstraints::Tuple(TupleConstraints::ExactElements(t)) => {
+                ConcreteType::Tuple(Box::new(ConcreteTupleType {
+                    element_types: t
+                        .iter()
+                        .map(|type_id| schema.get_concrete_type_from_id(*type_id))
+                        .collect(),
+                }))
+            }
+            CategoryConstraints::Tuple(TupleConstraints::OpenElements(t)) => {
+                // Elements that were never looked up are unknown.
+                let length = t.keys().max().map_or(0, |index| index + 1);
+                ConcreteType::Tuple(Box::new(ConcreteTupleType {
+                    element_types: (0..length)
+                        .map(|index| {
+                            t.get(&index).map_or(
+                                ConcreteType::Primitive(PrimitiveType::Unknown),
+                                |type_id| schema.get_concrete_type_from_id(*type_id),
+                            )
+                        })
+                        .collect(),
+                }))
+            }
             CategoryConstraints::TagGroup(
                 TagGroupConstraints::ClosedTags(t) | TagGroupConstraints::OpenTags(t),
             ) => {
@@ -618,18 +804,7 @@ impl ParsedConstraint {
                     }
                 }
                 ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
-                    tag_types: t
-                        .iter()
-                        .map(|(name, type_ids)| {
-                            (
-                                name.clone(),
-                                type_ids
-                                    .iter()
-                                    .map(|type_id| schema.get_concrete_type_from_id(*type_id))
-                                    .collect(),
-                            )
-                        })
-                        .collect(),
+                    tag_types: get_concrete_types_by_name(t, schema),
                 }))
             }
             CategoryConstraints::Enum(
@@ -639,18 +814,7 @@ impl ParsedConstraint {
                     ConcreteType::Primitive(PrimitiveType::CompilerBoolean)
                 }
                 _ => ConcreteType::Enum(Box::new(ConcreteEnumType {
-                    variants: e
-                        .iter()
-                        .map(|(name, type_ids)| {
-                            (
-                                name.clone(),
-                                type_ids
-                                    .iter()
-                                    .map(|type_id| schema.get_concrete_type_from_id(*type_id))
-                                    .collect(),
-                            )
-                        })
-                        .collect(),
+                    variants: get_concrete_types_by_name(e, schema),
                 })),
             },
         }
@@ -677,6 +841,11 @@ impl ParsedConstraint {
             .get_same_method_type(schema, method_name, method_type, checked_types)
     }
 
+    #[must_use]
+    pub fn get_tuple_element_type(&self, index: usize) -> Option<TypeId> {
+        self.category.get_tuple_element_type(index)
+    }
+
     pub fn get_tag_content_types(&self, tag_name: &String) -> Result<Vec<TypeId>, String> {
         self.category.get_tag_content_types(tag_name)
     }
@@ -687,8 +856,10 @@ mod test {
     use super::*;
     use crate::constraints::{
         EnumExactConstraint, HasExactFieldsConstraint, HasFieldConstraint, HasFunctionShape,
-        HasMethodConstraint, HasTagConstraint, HasVariantConstraint, TagAtMostConstraint,
+        HasMethodConstraint, HasTagConstraint, HasTupleElementConstraint, HasVariantConstraint,
+        MapOfTypesConstraint, TagAtMostConstraint,
     };
+    use crate::type_schema::{INT_TYPE_ID, STR_TYPE_ID};
 
     //
     // ParsedConstraint::new
@@ -796,6 +967,64 @@ mod test {
         );
     }
 
+    #[test]
+    fn new_parsed_constraint_with_map_constraint_sets_map() {
+        let mut schema = TypeSchema::new();
+        let key_type = schema.make_id();
+        let value_type = schema.make_id();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::MapOfTypes(MapOfTypesConstraint {
+                key_type,
+                value_type,
+            }),
+            &mut schema,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed_constraint.category,
+            CategoryConstraints::Map(MapConstraints {
+                key_type,
+                value_type
+            })
+        );
+    }
+
+    #[test]
+    fn new_parsed_constraint_with_map_constraint_adds_default_methods() {
+        let mut schema = TypeSchema::new();
+        let key_type = schema.make_id();
+        let value_type = schema.make_id();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::MapOfTypes(MapOfTypesConstraint {
+                key_type,
+                value_type,
+            }),
+            &mut schema,
+        )
+        .unwrap();
+        for method in ["get", "insert", "remove", "has", "size", "keys", "values"] {
+            assert!(parsed_constraint.methods.0.contains_key(method));
+        }
+    }
+
+    #[test]
+    fn new_parsed_constraint_with_set_constraint_sets_set() {
+        let mut schema = TypeSchema::new();
+        let type_id = schema.make_id();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::SetOfType(type_id),
+            &mut schema,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed_constraint.category,
+            CategoryConstraints::Set(type_id)
+        );
+    }
+
     #[test]
     fn new_parsed_constraint_with_open_tag_constraint_sets_tag() {
         let mut schema = TypeSchema::new();
@@ -1178,7 +1407,7 @@ mod test {
         let new_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("bar".to_string(), Vec::new())]),
+                tags: IndexMap::from([("bar".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -1186,7 +1415,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::TagGroup(TagGroupConstraints::ClosedTags(HashMap::from([(
+            CategoryConstraints::TagGroup(TagGroupConstraints::ClosedTags(IndexMap::from([(
                 "bar".to_string(),
                 Vec::new()
             )])))
@@ -1200,7 +1429,7 @@ mod test {
         let mut parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([
+                tags: IndexMap::from([
                     ("foo".to_string(), Vec::new()),
                     ("bar".to_string(), Vec::new()),
                 ]),
@@ -1211,7 +1440,7 @@ mod test {
         let new_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([
+                tags: IndexMap::from([
                     ("bar".to_string(), Vec::new()),
                     ("baz".to_string(), Vec::new()),
                 ]),
@@ -1222,7 +1451,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::TagGroup(TagGroupConstraints::ClosedTags(HashMap::from([(
+            CategoryConstraints::TagGroup(TagGroupConstraints::ClosedTags(IndexMap::from([(
                 "bar".to_string(),
                 Vec::new()
             )])))
@@ -1250,7 +1479,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::TagGroup(TagGroupConstraints::OpenTags(HashMap::from([(
+            CategoryConstraints::TagGroup(TagGroupConstraints::OpenTags(IndexMap::from([(
                 "bar".to_string(),
                 Vec::new()
             )])))
@@ -1281,7 +1510,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::TagGroup(TagGroupConstraints::OpenTags(HashMap::from([
+            CategoryConstraints::TagGroup(TagGroupConstraints::OpenTags(IndexMap::from([
                 ("foo".to_string(), vec![]),
                 ("bar".to_string(), vec![])
             ])))
@@ -1300,7 +1529,7 @@ mod test {
         let new_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("bar".to_string(), Vec::new())]),
+                variants: IndexMap::from([("bar".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -1308,7 +1537,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::Enum(EnumConstraints::ExactVariants(HashMap::from([(
+            CategoryConstraints::Enum(EnumConstraints::ExactVariants(IndexMap::from([(
                 "bar".to_string(),
                 Vec::new()
             )])))
@@ -1336,7 +1565,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::Enum(EnumConstraints::OpenVariants(HashMap::from([(
+            CategoryConstraints::Enum(EnumConstraints::OpenVariants(IndexMap::from([(
                 "bar".to_string(),
                 Vec::new()
             )])))
@@ -1349,7 +1578,7 @@ mod test {
         let mut parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), Vec::new())]),
+                variants: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -1366,7 +1595,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::Enum(EnumConstraints::ExactVariants(HashMap::from([(
+            CategoryConstraints::Enum(EnumConstraints::ExactVariants(IndexMap::from([(
                 "foo".to_string(),
                 vec![]
             ),])))
@@ -1386,7 +1615,7 @@ mod test {
         let new_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([("bar".to_string(), type_id)]),
+                fields: IndexMap::from([("bar".to_string(), type_id)]),
             }),
             &mut schema,
         )
@@ -1394,7 +1623,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::Record(RecordConstraints::ExactFields(HashMap::from([(
+            CategoryConstraints::Record(RecordConstraints::ExactFields(IndexMap::from([(
                 "bar".to_string(),
                 type_id
             )])))
@@ -1409,7 +1638,10 @@ mod test {
         let mut parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([("foo".to_string(), type_id), ("bar".to_string(), type_id)]),
+                fields: IndexMap::from([
+                    ("foo".to_string(), type_id),
+                    ("bar".to_string(), type_id),
+                ]),
             }),
             &mut schema,
         )
@@ -1417,7 +1649,10 @@ mod test {
         let new_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([("bar".to_string(), type_id), ("baz".to_string(), type_id)]),
+                fields: IndexMap::from([
+                    ("bar".to_string(), type_id),
+                    ("baz".to_string(), type_id),
+                ]),
             }),
             &mut schema,
         )
@@ -1425,7 +1660,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::Record(RecordConstraints::ExactFields(HashMap::from([(
+            CategoryConstraints::Record(RecordConstraints::ExactFields(IndexMap::from([(
                 "bar".to_string(),
                 type_id
             )])))
@@ -1454,7 +1689,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::Record(RecordConstraints::OpenFields(HashMap::from([(
+            CategoryConstraints::Record(RecordConstraints::OpenFields(IndexMap::from([(
                 "bar".to_string(),
                 type_id
             )])))
@@ -1486,7 +1721,7 @@ mod test {
         parsed_constraint.add_constraints(new_constraint, &schema.types);
         assert_eq!(
             parsed_constraint.category,
-            CategoryConstraints::Record(RecordConstraints::OpenFields(HashMap::from([
+            CategoryConstraints::Record(RecordConstraints::OpenFields(IndexMap::from([
                 ("foo".to_string(), type_id),
                 ("bar".to_string(), type_id)
             ])))
@@ -1816,6 +2051,89 @@ mod test {
         ));
     }
 
+    #[test]
+    fn int_is_not_compatible_with_float() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::EqualToPrimitive(PrimitiveType::Int),
+            &mut schema,
+        )
+        .unwrap();
+        let other_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::EqualToPrimitive(PrimitiveType::Float),
+            &mut schema,
+        )
+        .unwrap();
+        assert!(!parsed_constraint.is_compatible_with(
+            &other_constraint,
+            &schema,
+            &mut CheckedTypes::new(),
+        ));
+    }
+
+    #[test]
+    fn numeric_is_compatible_with_int_and_float() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint =
+            ParsedConstraint::new(schema.make_id(), Constraint::Numeric, &mut schema).unwrap();
+        for primitive in [PrimitiveType::Int, PrimitiveType::Float] {
+            let other_constraint = ParsedConstraint::new(
+                schema.make_id(),
+                Constraint::EqualToPrimitive(primitive),
+                &mut schema,
+            )
+            .unwrap();
+            assert!(parsed_constraint.is_compatible_with(
+                &other_constraint,
+                &schema,
+                &mut CheckedTypes::new(),
+            ));
+            assert!(other_constraint.is_compatible_with(
+                &parsed_constraint,
+                &schema,
+                &mut CheckedTypes::new(),
+            ));
+        }
+    }
+
+    #[test]
+    fn numeric_is_not_compatible_with_str() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint =
+            ParsedConstraint::new(schema.make_id(), Constraint::Numeric, &mut schema).unwrap();
+        let other_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::EqualToPrimitive(PrimitiveType::Str),
+            &mut schema,
+        )
+        .unwrap();
+        assert!(!parsed_constraint.is_compatible_with(
+            &other_constraint,
+            &schema,
+            &mut CheckedTypes::new(),
+        ));
+    }
+
+    #[test]
+    fn adding_float_to_numeric_narrows_it_to_float() {
+        let mut schema = TypeSchema::new();
+        let mut parsed_constraint =
+            ParsedConstraint::new(schema.make_id(), Constraint::Numeric, &mut schema).unwrap();
+        let new_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::EqualToPrimitive(PrimitiveType::Float),
+            &mut schema,
+        )
+        .unwrap();
+        parsed_constraint.add_constraints(new_constraint, &schema.types);
+        assert_eq!(
+            parsed_constraint.category,
+            CategoryConstraints::Primitive(PrimitiveType::Float)
+        );
+    }
+
     #[test]
     fn is_compatible_with_primitive_constraint_when_current_category_is_unknown() {
         let mut schema = TypeSchema::new();
@@ -1987,13 +2305,159 @@ mod test {
         ));
     }
 
+    #[test]
+    fn is_not_compatible_with_map_constraint_of_different_value_type() {
+        let mut schema = TypeSchema::new();
+        let key_type = schema.make_id();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::MapOfTypes(MapOfTypesConstraint {
+                key_type,
+                value_type: INT_TYPE_ID,
+            }),
+            &mut schema,
+        )
+        .unwrap();
+        let other_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::MapOfTypes(MapOfTypesConstraint {
+                key_type,
+                value_type: STR_TYPE_ID,
+            }),
+            &mut schema,
+        )
+        .unwrap();
+        assert!(!parsed_constraint.is_compatible_with(
+            &other_constraint,
+            &schema,
+            &mut CheckedTypes::new(),
+        ));
+    }
+
+    #[test]
+    fn is_not_compatible_with_list_constraint_when_current_category_is_set() {
+        let mut schema = TypeSchema::new();
+        let element_type = schema.make_id();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::SetOfType(element_type),
+            &mut schema,
+        )
+        .unwrap();
+        let other_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::ListOfType(element_type),
+            &mut schema,
+        )
+        .unwrap();
+        assert!(!parsed_constraint.is_compatible_with(
+            &other_constraint,
+            &schema,
+            &mut CheckedTypes::new(),
+        ));
+    }
+
+    #[test]
+    fn is_not_compatible_with_tuple_constraint_of_different_length() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::TupleOfTypes(vec![INT_TYPE_ID, INT_TYPE_ID]),
+            &mut schema,
+        )
+        .unwrap();
+        let other_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::TupleOfTypes(vec![INT_TYPE_ID, INT_TYPE_ID, INT_TYPE_ID]),
+            &mut schema,
+        )
+        .unwrap();
+        assert!(!parsed_constraint.is_compatible_with(
+            &other_constraint,
+            &schema,
+            &mut CheckedTypes::new(),
+        ));
+    }
+
+    #[test]
+    fn is_not_compatible_with_tuple_constraint_with_different_element_types() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::TupleOfTypes(vec![INT_TYPE_ID, STR_TYPE_ID]),
+            &mut schema,
+        )
+        .unwrap();
+        let other_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::TupleOfTypes(vec![INT_TYPE_ID, INT_TYPE_ID]),
+            &mut schema,
+        )
+        .unwrap();
+        assert!(!parsed_constraint.is_compatible_with(
+            &other_constraint,
+            &schema,
+            &mut CheckedTypes::new(),
+        ));
+    }
+
+    #[test]
+    fn is_compatible_with_tuple_element_constraint_within_the_tuple() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::TupleOfTypes(vec![INT_TYPE_ID, STR_TYPE_ID]),
+            &mut schema,
+        )
+        .unwrap();
+        let other_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::HasTupleElement(HasTupleElementConstraint {
+                index: 1,
+                element_type: STR_TYPE_ID,
+            }),
+            &mut schema,
+        )
+        .unwrap();
+        assert!(parsed_constraint.is_compatible_with(
+            &other_constraint,
+            &schema,
+            &mut CheckedTypes::new(),
+        ));
+    }
+
+    #[test]
+    fn is_not_compatible_with_tuple_element_constraint_past_the_end_of_the_tuple() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::TupleOfTypes(vec![INT_TYPE_ID, STR_TYPE_ID]),
+            &mut schema,
+        )
+        .unwrap();
+        let other_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::HasTupleElement(HasTupleElementConstraint {
+                index: 2,
+                element_type: STR_TYPE_ID,
+            }),
+            &mut schema,
+        )
+        .unwrap();
+        assert!(!parsed_constraint.is_compatible_with(
+            &other_constraint,
+            &schema,
+            &mut CheckedTypes::new(),
+        ));
+    }
+
     #[test]
     fn is_compatible_with_tag_at_most_constraint_with_same_tags() {
         let mut schema = TypeSchema::new();
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), Vec::new())]),
+                tags: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2001,7 +2465,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), Vec::new())]),
+                tags: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2019,7 +2483,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([
+                tags: IndexMap::from([
                     ("foo".to_string(), Vec::new()),
                     ("bar".to_string(), Vec::new()),
                 ]),
@@ -2030,7 +2494,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), Vec::new())]),
+                tags: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2048,7 +2512,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), Vec::new())]),
+                tags: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2056,7 +2520,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("bar".to_string(), Vec::new())]),
+                tags: IndexMap::from([("bar".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2074,7 +2538,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), Vec::new())]),
+                tags: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2082,7 +2546,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([
+                tags: IndexMap::from([
                     ("foo".to_string(), Vec::new()),
                     ("bar".to_string(), Vec::new()),
                 ]),
@@ -2104,7 +2568,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), Vec::new())]),
+                tags: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2112,7 +2576,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_id])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -2131,7 +2595,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_id])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -2139,7 +2603,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_id])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -2162,7 +2626,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_a])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -2170,7 +2634,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_b])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -2204,7 +2668,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_a])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -2212,7 +2676,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_b])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -2246,7 +2710,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_a])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -2254,7 +2718,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_b])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -2281,7 +2745,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), Vec::new())]),
+                tags: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2318,7 +2782,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), Vec::new())]),
+                tags: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2346,7 +2810,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("bar".to_string(), Vec::new())]),
+                tags: IndexMap::from([("bar".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2374,7 +2838,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_id])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -2402,7 +2866,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_id])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -2431,7 +2895,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_b])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -2474,7 +2938,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_b])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -2517,7 +2981,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([("foo".to_string(), vec![type_b])]),
+                tags: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -2807,7 +3271,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("foo"), Vec::new())]),
+                tags: IndexMap::from([(String::from("foo"), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2834,7 +3298,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([
+                tags: IndexMap::from([
                     (String::from("foo"), Vec::new()),
                     (String::from("bar"), Vec::new()),
                 ]),
@@ -2864,7 +3328,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("foo"), Vec::new())]),
+                tags: IndexMap::from([(String::from("foo"), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2891,7 +3355,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("foo"), Vec::new())]),
+                tags: IndexMap::from([(String::from("foo"), Vec::new())]),
             }),
             &mut schema,
         )
@@ -2919,7 +3383,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("foo"), vec![type_id])]),
+                tags: IndexMap::from([(String::from("foo"), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -2951,7 +3415,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("foo"), vec![type_a])]),
+                tags: IndexMap::from([(String::from("foo"), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -2994,7 +3458,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("foo"), vec![type_a])]),
+                tags: IndexMap::from([(String::from("foo"), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -3037,7 +3501,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("foo"), vec![type_a])]),
+                tags: IndexMap::from([(String::from("foo"), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -3064,7 +3528,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), Vec::new())]),
+                variants: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3072,7 +3536,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), Vec::new())]),
+                variants: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3090,7 +3554,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([
+                variants: IndexMap::from([
                     ("foo".to_string(), Vec::new()),
                     ("bar".to_string(), Vec::new()),
                 ]),
@@ -3101,7 +3565,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), Vec::new())]),
+                variants: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3119,7 +3583,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), Vec::new())]),
+                variants: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3127,7 +3591,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("bar".to_string(), Vec::new())]),
+                variants: IndexMap::from([("bar".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3145,7 +3609,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), Vec::new())]),
+                variants: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3153,7 +3617,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([
+                variants: IndexMap::from([
                     ("foo".to_string(), Vec::new()),
                     ("bar".to_string(), Vec::new()),
                 ]),
@@ -3175,7 +3639,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), Vec::new())]),
+                variants: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3183,7 +3647,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_id])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -3202,7 +3666,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_id])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -3210,7 +3674,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_id])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -3233,7 +3697,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_a])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -3241,7 +3705,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_b])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -3275,7 +3739,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_a])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -3283,7 +3747,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_b])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -3317,7 +3781,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_a])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -3325,7 +3789,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_b])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -3352,7 +3816,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), Vec::new())]),
+                variants: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3389,7 +3853,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), Vec::new())]),
+                variants: IndexMap::from([("foo".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3417,7 +3881,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("bar".to_string(), Vec::new())]),
+                variants: IndexMap::from([("bar".to_string(), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3445,7 +3909,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_id])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -3473,7 +3937,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_id])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -3502,7 +3966,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_b])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -3545,7 +4009,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_b])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -3588,7 +4052,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([("foo".to_string(), vec![type_b])]),
+                variants: IndexMap::from([("foo".to_string(), vec![type_b])]),
             }),
             &mut schema,
         )
@@ -3878,7 +4342,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([(String::from("foo"), Vec::new())]),
+                variants: IndexMap::from([(String::from("foo"), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3905,7 +4369,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([
+                variants: IndexMap::from([
                     (String::from("foo"), Vec::new()),
                     (String::from("bar"), Vec::new()),
                 ]),
@@ -3935,7 +4399,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([(String::from("foo"), Vec::new())]),
+                variants: IndexMap::from([(String::from("foo"), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3962,7 +4426,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([(String::from("foo"), Vec::new())]),
+                variants: IndexMap::from([(String::from("foo"), Vec::new())]),
             }),
             &mut schema,
         )
@@ -3990,7 +4454,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([(String::from("foo"), vec![type_id])]),
+                variants: IndexMap::from([(String::from("foo"), vec![type_id])]),
             }),
             &mut schema,
         )
@@ -4022,7 +4486,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([(String::from("foo"), vec![type_a])]),
+                variants: IndexMap::from([(String::from("foo"), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -4065,7 +4529,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([(String::from("foo"), vec![type_a])]),
+                variants: IndexMap::from([(String::from("foo"), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -4108,7 +4572,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([(String::from("foo"), vec![type_a])]),
+                variants: IndexMap::from([(String::from("foo"), vec![type_a])]),
             }),
             &mut schema,
         )
@@ -4136,7 +4600,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_id)]),
+                fields: IndexMap::from([(String::from("foo"), type_id)]),
             }),
             &mut schema,
         )
@@ -4144,7 +4608,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_id)]),
+                fields: IndexMap::from([(String::from("foo"), type_id)]),
             }),
             &mut schema,
         )
@@ -4164,7 +4628,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([
+                fields: IndexMap::from([
                     (String::from("foo"), type_a),
                     (String::from("bar"), type_b),
                 ]),
@@ -4175,7 +4639,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_a)]),
+                fields: IndexMap::from([(String::from("foo"), type_a)]),
             }),
             &mut schema,
         )
@@ -4195,7 +4659,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_a)]),
+                fields: IndexMap::from([(String::from("foo"), type_a)]),
             }),
             &mut schema,
         )
@@ -4203,7 +4667,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("bar"), type_b)]),
+                fields: IndexMap::from([(String::from("bar"), type_b)]),
             }),
             &mut schema,
         )
@@ -4222,7 +4686,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_id)]),
+                fields: IndexMap::from([(String::from("foo"), type_id)]),
             }),
             &mut schema,
         )
@@ -4230,7 +4694,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_id)]),
+                fields: IndexMap::from([(String::from("foo"), type_id)]),
             }),
             &mut schema,
         )
@@ -4253,7 +4717,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_a)]),
+                fields: IndexMap::from([(String::from("foo"), type_a)]),
             }),
             &mut schema,
         )
@@ -4261,7 +4725,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_b)]),
+                fields: IndexMap::from([(String::from("foo"), type_b)]),
             }),
             &mut schema,
         )
@@ -4295,7 +4759,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_a)]),
+                fields: IndexMap::from([(String::from("foo"), type_a)]),
             }),
             &mut schema,
         )
@@ -4303,7 +4767,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_b)]),
+                fields: IndexMap::from([(String::from("foo"), type_b)]),
             }),
             &mut schema,
         )
@@ -4337,7 +4801,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_a)]),
+                fields: IndexMap::from([(String::from("foo"), type_a)]),
             }),
             &mut schema,
         )
@@ -4345,7 +4809,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_b)]),
+                fields: IndexMap::from([(String::from("foo"), type_b)]),
             }),
             &mut schema,
         )
@@ -4373,7 +4837,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_id)]),
+                fields: IndexMap::from([(String::from("foo"), type_id)]),
             }),
             &mut schema,
         )
@@ -4412,7 +4876,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_a)]),
+                fields: IndexMap::from([(String::from("foo"), type_a)]),
             }),
             &mut schema,
         )
@@ -4441,7 +4905,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("bar"), type_b)]),
+                fields: IndexMap::from([(String::from("bar"), type_b)]),
             }),
             &mut schema,
         )
@@ -4469,7 +4933,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_id)]),
+                fields: IndexMap::from([(String::from("foo"), type_id)]),
             }),
             &mut schema,
         )
@@ -4501,7 +4965,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_b)]),
+                fields: IndexMap::from([(String::from("foo"), type_b)]),
             }),
             &mut schema,
         )
@@ -4544,7 +5008,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_b)]),
+                fields: IndexMap::from([(String::from("foo"), type_b)]),
             }),
             &mut schema,
         )
@@ -4587,7 +5051,7 @@ mod test {
         let other_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_b)]),
+                fields: IndexMap::from([(String::from("foo"), type_b)]),
             }),
             &mut schema,
         )
@@ -4853,7 +5317,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_id)]),
+                fields: IndexMap::from([(String::from("foo"), type_id)]),
             }),
             &mut schema,
         )
@@ -4881,7 +5345,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([
+                fields: IndexMap::from([
                     (String::from("foo"), type_id),
                     (String::from("bar"), type_id),
                 ]),
@@ -4912,7 +5376,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_id)]),
+                fields: IndexMap::from([(String::from("foo"), type_id)]),
             }),
             &mut schema,
         )
@@ -4940,7 +5404,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_id)]),
+                fields: IndexMap::from([(String::from("foo"), type_id)]),
             }),
             &mut schema,
         )
@@ -4972,7 +5436,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_a)]),
+                fields: IndexMap::from([(String::from("foo"), type_a)]),
             }),
             &mut schema,
         )
@@ -5015,7 +5479,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_a)]),
+                fields: IndexMap::from([(String::from("foo"), type_a)]),
             }),
             &mut schema,
         )
@@ -5058,7 +5522,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), type_a)]),
+                fields: IndexMap::from([(String::from("foo"), type_a)]),
             }),
             &mut schema,
         )
@@ -5418,7 +5882,7 @@ mod test {
     //
 
     #[test]
-    fn unknown_category_becomes_compiler_boolean() {
+    fn unknown_category_becomes_unknown() {
         let mut schema = TypeSchema::new();
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
@@ -5428,7 +5892,7 @@ mod test {
         .unwrap();
         assert_eq!(
             parsed_constraint.to_concrete_type(&schema),
-            ConcreteType::Primitive(PrimitiveType::CompilerBoolean)
+            ConcreteType::Primitive(PrimitiveType::Unknown)
         );
     }
 
@@ -5447,6 +5911,17 @@ mod test {
         );
     }
 
+    #[test]
+    fn numeric_to_concrete_type_is_num() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint =
+            ParsedConstraint::new(schema.make_id(), Constraint::Numeric, &mut schema).unwrap();
+        assert_eq!(
+            parsed_constraint.to_concrete_type(&schema),
+            ConcreteType::Primitive(PrimitiveType::Num)
+        );
+    }
+
     #[test]
     fn string_to_concrete_type() {
         let mut schema = TypeSchema::new();
@@ -5487,6 +5962,92 @@ mod test {
         );
     }
 
+    #[test]
+    fn map_to_concrete_type() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::MapOfTypes(MapOfTypesConstraint {
+                key_type: STR_TYPE_ID,
+                value_type: INT_TYPE_ID,
+            }),
+            &mut schema,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed_constraint.to_concrete_type(&schema),
+            ConcreteType::Map(Box::new(ConcreteMapType {
+                key_type: ConcreteType::Primitive(PrimitiveType::Str),
+                value_type: ConcreteType::Primitive(PrimitiveType::Int)
+            }))
+        );
+    }
+
+    #[test]
+    fn set_to_concrete_type() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::SetOfType(INT_TYPE_ID),
+            &mut schema,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed_constraint.to_concrete_type(&schema),
+            ConcreteType::Set(Box::new(ConcreteSetType {
+                element_type: ConcreteType::Primitive(PrimitiveType::Int)
+            }))
+        );
+    }
+
+    #[test]
+    fn tuple_to_concrete_type() {
+        let mut schema = TypeSchema::new();
+        let parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::TupleOfTypes(vec![INT_TYPE_ID, STR_TYPE_ID]),
+            &mut schema,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed_constraint.to_concrete_type(&schema),
+            ConcreteType::Tuple(Box::new(ConcreteTupleType {
+                element_types: vec![
+                    ConcreteType::Primitive(PrimitiveType::Int),
+                    ConcreteType::Primitive(PrimitiveType::Str)
+                ]
+            }))
+        );
+    }
+
+    #[test]
+    fn exact_tuple_constraint_replaces_open_tuple_constraint() {
+        let mut schema = TypeSchema::new();
+        let mut parsed_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::HasTupleElement(HasTupleElementConstraint {
+                index: 0,
+                element_type: INT_TYPE_ID,
+            }),
+            &mut schema,
+        )
+        .unwrap();
+        let other_constraint = ParsedConstraint::new(
+            schema.make_id(),
+            Constraint::TupleOfTypes(vec![INT_TYPE_ID, STR_TYPE_ID]),
+            &mut schema,
+        )
+        .unwrap();
+        parsed_constraint.add_constraints(other_constraint, &schema.types);
+        assert_eq!(
+            parsed_constraint.category,
+            CategoryConstraints::Tuple(TupleConstraints::ExactElements(vec![
+                INT_TYPE_ID,
+                STR_TYPE_ID
+            ]))
+        );
+    }
+
     #[test]
     fn function_to_concrete_type() {
         let mut schema = TypeSchema::new();
@@ -5538,7 +6099,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::HasExactFields(HasExactFieldsConstraint {
-                fields: HashMap::from([(String::from("foo"), field_type)]),
+                fields: IndexMap::from([(String::from("foo"), field_type)]),
             }),
             &mut schema,
         )
@@ -5546,7 +6107,7 @@ mod test {
         assert_eq!(
             parsed_constraint.to_concrete_type(&schema),
             ConcreteType::Record(Box::new(ConcreteRecordType {
-                field_types: HashMap::from([(
+                field_types: IndexMap::from([(
                     String::from("foo"),
                     ConcreteType::Primitive(PrimitiveType::Int)
                 )])
@@ -5577,7 +6138,7 @@ mod test {
         assert_eq!(
             parsed_constraint.to_concrete_type(&schema),
             ConcreteType::Record(Box::new(ConcreteRecordType {
-                field_types: HashMap::from([(
+                field_types: IndexMap::from([(
                     String::from("foo"),
                     ConcreteType::Primitive(PrimitiveType::Int)
                 )])
@@ -5599,7 +6160,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("foo"), vec![tag_type])]),
+                tags: IndexMap::from([(String::from("foo"), vec![tag_type])]),
             }),
             &mut schema,
         )
@@ -5607,7 +6168,7 @@ mod test {
         assert_eq!(
             parsed_constraint.to_concrete_type(&schema),
             ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
-                tag_types: HashMap::from([(
+                tag_types: IndexMap::from([(
                     String::from("foo"),
                     vec![ConcreteType::Primitive(PrimitiveType::Int)]
                 )])
@@ -5638,7 +6199,7 @@ mod test {
         assert_eq!(
             parsed_constraint.to_concrete_type(&schema),
             ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
-                tag_types: HashMap::from([(
+                tag_types: IndexMap::from([(
                     String::from("foo"),
                     vec![ConcreteType::Primitive(PrimitiveType::Int)]
                 )])
@@ -5652,7 +6213,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("true"), Vec::new())]),
+                tags: IndexMap::from([(String::from("true"), Vec::new())]),
             }),
             &mut schema,
         )
@@ -5669,7 +6230,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("false"), Vec::new())]),
+                tags: IndexMap::from([(String::from("false"), Vec::new())]),
             }),
             &mut schema,
         )
@@ -5686,7 +6247,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([
+                tags: IndexMap::from([
                     (String::from("true"), Vec::new()),
                     (String::from("false"), Vec::new()),
                 ]),
@@ -5714,7 +6275,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("true"), vec![tag_type])]),
+                tags: IndexMap::from([(String::from("true"), vec![tag_type])]),
             }),
             &mut schema,
         )
@@ -5722,7 +6283,7 @@ mod test {
         assert_eq!(
             parsed_constraint.to_concrete_type(&schema),
             ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
-                tag_types: HashMap::from([(
+                tag_types: IndexMap::from([(
                     String::from("true"),
                     vec![ConcreteType::Primitive(PrimitiveType::Int)]
                 )])
@@ -5744,7 +6305,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::TagAtMost(TagAtMostConstraint {
-                tags: HashMap::from([(String::from("false"), vec![tag_type])]),
+                tags: IndexMap::from([(String::from("false"), vec![tag_type])]),
             }),
             &mut schema,
         )
@@ -5752,7 +6313,7 @@ mod test {
         assert_eq!(
             parsed_constraint.to_concrete_type(&schema),
             ConcreteType::TagUnion(Box::new(ConcreteTagUnionType {
-                tag_types: HashMap::from([(
+                tag_types: IndexMap::from([(
                     String::from("false"),
                     vec![ConcreteType::Primitive(PrimitiveType::Int)]
                 )])
@@ -5774,7 +6335,7 @@ mod test {
         let parsed_constraint = ParsedConstraint::new(
             schema.make_id(),
             Constraint::EnumExact(EnumExactConstraint {
-                variants: HashMap::from([(String::from("foo"), vec![tag_type])]),
+                variants: IndexMap::from([(String::from("foo"), vec![tag_type])]),
             }),
             &mut schema,
         )
@@ -5782,7 +6343,7 @@ mod test {
         assert_eq!(
             parsed_constraint.to_concrete_type(&schema),
             ConcreteType::Enum(Box::new(ConcreteEnumType {
-                variants: HashMap::from([(
+                variants: IndexMap::from([(
                     String::from("foo"),
                     vec![ConcreteType::Primitive(PrimitiveType::Int)]
                 )])
@@ -5813,7 +6374,7 @@ mod test {
         assert_eq!(
             parsed_constraint.to_concrete_type(&schema),
             ConcreteType::Enum(Box::new(ConcreteEnumType {
-                variants: HashMap::from([(
+                variants: IndexMap::from([(
                     String::from("foo"),
                     vec![ConcreteType::Primitive(PrimitiveType::Int)]
                 )])
@@ -5834,7 +6395,7 @@ mod test {
             ParsedConstraint::new(
                 schema.make_id(),
                 Constraint::EnumExact(EnumExactConstraint {
-                    variants: HashMap::from([
+                    variants: IndexMap::from([
                         (String::from("true"), Vec::new()),
                         (String::from("false"), Vec::new()),
                     ]),
@@ -1,13 +1,15 @@
 use type_checker_errors::generate_backtrace_error;
 
 use crate::TypeId;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// An item in the scope stack.
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum ScopeItem {
     /// A delimiter that separates different scopes.
     Delimiter,
+    /// A delimiter that separates a function's scope from the scope it was declared in.
+    FunctionDelimiter,
     /// An type or variable identifier that is declared in the current scope.
     Identifier(String),
 }
@@ -16,6 +18,12 @@ enum ScopeItem {
 pub struct Scope {
     stack: Vec<ScopeItem>,
     pub identifiers: HashMap<String, TypeId>,
+    /// The number of functions that enclose the current scope.
+    function_depth: usize,
+    /// The function depth at which each identifier was declared.
+    identifier_function_depths: HashMap<String, usize>,
+    /// Identifiers that are referenced by a function nested inside the function that declares them.
+    captured_identifiers: HashSet<String>,
 }
 
 impl Scope {
@@ -24,14 +32,33 @@ impl Scope {
         Self {
             stack: Vec::new(),
             identifiers: HashMap::new(),
+            function_depth: 0,
+            identifier_function_depths: HashMap::new(),
+            captured_identifiers: HashSet::new(),
         }
     }
     pub fn start_sub_scope(&mut self) {
         self.stack.push(ScopeItem::Delimiter);
     }
+    pub fn start_function_scope(&mut self) {
+        self.stack.push(ScopeItem::FunctionDelimiter);
+        self.function_depth += 1;
+    }
+    /// Ends the most recent scope, whether it was started as a sub scope or a function scope.
     pub fn end_sub_scope(&mut self) {
-        while let Some(ScopeItem::Identifier(identifier)) = self.stack.pop() {
-            self.identifiers.remove(&identifier);
+        while let Some(item) = self.stack.pop() {
+            match item {
+                ScopeItem::Identifier(identifier) => {
+                    self.identifiers.remove(&identifier);
+                    self.identifier_function_depths.remove(&identifier);
+                    self.captured_identifiers.remove(&identifier);
+                }
+                ScopeItem::Delimiter => break,
+                ScopeItem::FunctionDelimiter => {
+                    self.function_depth -= 1;
+                    break;
+                }
+            }
         }
     }
     #[must_use]
@@ -39,6 +66,18 @@ impl Scope {
         let answer = self.identifiers.get(identifier_name).copied();
         answer
     }
+    /// Looks up an identifier that is being used as a value, remembering when it is
+    /// referenced from inside a function nested in the one that declares it.
+    pub fn reference_identifier(&mut self, identifier_name: &str) -> Option<TypeId> {
+        let type_id = self.get_variable_declaration_type(identifier_name)?;
+        if let Some(declaration_depth) = self.identifier_function_depths.get(identifier_name) {
+            if *declaration_depth > 0 && *declaration_depth < self.function_depth {
+                self.captured_identifiers
+                    .insert(identifier_name.to_owned());
+            }
+        }
+        Some(type_id)
+    }
     pub fn declare_identifier(
         &mut self,
         identifier_name: String,
@@ -51,9 +90,40 @@ impl Scope {
         }
         self.identifiers
             .insert(identifier_name.clone(), identifier_type);
+        self.identifier_function_depths
+            .insert(identifier_name.clone(), self.function_depth);
         self.stack.push(ScopeItem::Identifier(identifier_name));
         Ok(())
     }
+    /// Returns the type of an identifier that is about to be reassigned. Only identifiers
+    /// declared inside the current function, and not referenced by an inner function, can be
+    /// reassigned.
+    pub fn reassign_identifier(&self, identifier_name: &str) -> Result<TypeId, String> {
+        let Some(type_id) = self.get_variable_declaration_type(identifier_name) else {
+            return Err(generate_backtrace_error(format!("IdentifierNotFound: {identifier_name}")))
+        };
+        let declaration_depth = self
+            .identifier_function_depths
+            .get(identifier_name)
+            .copied()
+            .unwrap_or_default();
+        if self.function_depth == 0 {
+            return Err(generate_backtrace_error(format!(
+                "Identifier {identifier_name} cannot be reassigned outside of a function"
+            )));
+        }
+        if declaration_depth != self.function_depth {
+            return Err(generate_backtrace_error(format!(
+                "Identifier {identifier_name} cannot be reassigned because it is not local to this function"
+            )));
+        }
+        if self.captured_identifiers.contains(identifier_name) {
+            return Err(generate_backtrace_error(format!(
+                "Identifier {identifier_name} cannot be reassigned because an inner function references it"
+            )));
+        }
+        Ok(type_id)
+    }
 }
 
 #[cfg(test)]
@@ -178,4 +248,68 @@ mod test {
             .collect::<HashMap<_, _>>()
         );
     }
+
+    #[test]
+    fn starting_a_function_scope_adds_function_delimiter() {
+        let mut scope = Scope::new();
+        scope.start_function_scope();
+        assert_eq!(scope.stack, vec![ScopeItem::FunctionDelimiter]);
+        assert_eq!(scope.function_depth, 1);
+    }
+
+    #[test]
+    fn ending_a_function_scope_restores_the_function_depth() {
+        let mut scope = Scope::new();
+        scope.start_function_scope();
+        scope.declare_identifier("foo".to_string(), 0).unwrap();
+        scope.end_sub_scope();
+        assert_eq!(scope.stack, Vec::new());
+        assert_eq!(scope.function_depth, 0);
+        assert_eq!(scope.identifiers, HashMap::new());
+    }
+
+    #[test]
+    fn can_reassign_identifier_declared_in_the_current_function() {
+        let mut scope = Scope::new();
+        scope.start_function_scope();
+        scope.declare_identifier("foo".to_string(), 0).unwrap();
+        scope.start_sub_scope();
+        assert_eq!(scope.reassign_identifier("foo"), Ok(0));
+    }
+
+    #[test]
+    fn cannot_reassign_identifier_outside_of_a_function() {
+        let mut scope = Scope::new();
+        scope.declare_identifier("foo".to_string(), 0).unwrap();
+        assert!(scope.reassign_identifier("foo").is_err());
+    }
+
+    #[test]
+    fn cannot_reassign_identifier_declared_in_an_outer_function() {
+        let mut scope = Scope::new();
+        scope.start_function_scope();
+        scope.declare_identifier("foo".to_string(), 0).unwrap();
+        scope.start_function_scope();
+        assert!(scope.reassign_identifier("foo").is_err());
+    }
+
+    #[test]
+    fn cannot_reassign_identifier_referenced_by_an_inner_function() {
+        let mut scope = Scope::new();
+        scope.start_function_scope();
+        scope.declare_identifier("foo".to_string(), 0).unwrap();
+        scope.start_function_scope();
+        scope.reference_identifier("foo");
+        scope.end_sub_scope();
+        assert!(scope.reassign_identifier("foo").is_err());
+    }
+
+    #[test]
+    fn referencing_identifier_in_the_same_function_does_not_prevent_reassignment() {
+        let mut scope = Scope::new();
+        scope.start_function_scope();
+        scope.declare_identifier("foo".to_string(), 0).unwrap();
+        scope.reference_identifier("foo");
+        assert_eq!(scope.reassign_identifier("foo"), Ok(0));
+    }
 }
@@ -1,10 +1,13 @@
 use crate::{
-    constraints::{Constraint, EnumExactConstraint, HasMethodConstraint},
+    constraints::{
+        Constraint, EnumExactConstraint, HasMethodConstraint, HasTupleElementConstraint,
+    },
     parsed_constraint::ParsedConstraint,
     scope::Scope,
     type_checking_call_stack::CheckedTypes,
     TypeId,
 };
+use indexmap::IndexMap;
 use std::collections::HashMap;
 use type_checker_errors::generate_backtrace_error;
 use typed_ast::{ConcreteType, PrimitiveType};
@@ -14,6 +17,7 @@ pub struct CanonicalIds(Vec<TypeId>);
 
 pub const INT_TYPE_ID: usize = 0;
 pub const STR_TYPE_ID: usize = 1;
+pub const FLOAT_TYPE_ID: usize = 3;
 
 impl CanonicalIds {
     const fn new() -> Self {
@@ -75,9 +79,17 @@ impl TypeSchema {
             constraints: HashMap::new(),
             scope: Scope::new(),
         };
+        // The ids are made before any constraints are added because adding a
+        // constraint makes ids for the methods of its type.
         // if-change: update type id constants at the top of the file
+        let int_type_id = schema.make_id();
+        let str_type_id = schema.make_id();
+        let bool_type_id = schema.make_id();
+        let float_type_id = schema.make_id();
+        // end-if-change
         schema
             .declare_identifier_with_constraints(
+                int_type_id,
                 String::from("Int"),
                 vec![Constraint::EqualToPrimitive(PrimitiveType::Int)],
                 &mut CheckedTypes::new(),
@@ -85,6 +97,7 @@ impl TypeSchema {
             .unwrap();
         schema
             .declare_identifier_with_constraints(
+                str_type_id,
                 String::from("Str"),
                 vec![Constraint::EqualToPrimitive(PrimitiveType::Str)],
                 &mut CheckedTypes::new(),
@@ -92,10 +105,11 @@ impl TypeSchema {
             .unwrap();
         schema
             .declare_identifier_with_constraints(
+                bool_type_id,
                 String::from("Bool"),
                 vec![
                     Constraint::EnumExact(EnumExactConstraint {
-                        variants: HashMap::from([
+                        variants: IndexMap::from([
                             (String::from("true"), Vec::new()),
                             (String::from("false"), Vec::new()),
                         ]),
@@ -105,17 +119,24 @@ impl TypeSchema {
                 &mut CheckedTypes::new(),
             )
             .unwrap();
-        // end-if-change
+        schema
+            .declare_identifier_with_constraints(
+                float_type_id,
+                String::from("Float"),
+                vec![Constraint::EqualToPrimitive(PrimitiveType::Float)],
+                &mut CheckedTypes::new(),
+            )
+            .unwrap();
         schema
     }
 
     fn declare_identifier_with_constraints(
         &mut self,
+        type_id: TypeId,
         identifier_name: String,
         constraints: Vec<Constraint>,
         checked_types: &mut CheckedTypes,
     ) -> Result<(), String> {
-        let type_id = self.types.make_id();
         self.scope
             .declare_identifier(identifier_name, type_id)
             .map_err(generate_backtrace_error)?;
@@ -160,7 +181,7 @@ impl TypeSchema {
     pub fn get_concrete_type_from_id(&self, type_id: TypeId) -> ConcreteType {
         let canonical_id = self.get_canonical_id(type_id);
         self.constraints.get(&canonical_id).map_or_else(
-            || ConcreteType::Primitive(PrimitiveType::CompilerBoolean),
+            || ConcreteType::Primitive(PrimitiveType::Unknown),
             |parsed_constraint| parsed_constraint.to_concrete_type(self),
         )
     }
@@ -213,15 +234,17 @@ impl TypeSchema {
         if !self.types_are_compatible(canonical_type_id, other_type_id, checked_types) {
             return Err(generate_backtrace_error("TypesAreNotCompatible".to_owned()));
         }
-        match self
-            .constraints
-            .remove(&self.get_canonical_id(other_type_id))
-        {
+        // Constraints are stored under canonical ids, which the given ids might not be.
+        let canonical_id = self.get_canonical_id(canonical_type_id);
+        let other_canonical_id = self.get_canonical_id(other_type_id);
+        if canonical_id == other_canonical_id {
+            return Ok(());
+        }
+        match self.constraints.remove(&other_canonical_id) {
             None => {}
-            Some(merged_constraint) => match self.constraints.get_mut(&canonical_type_id) {
+            Some(merged_constraint) => match self.constraints.get_mut(&canonical_id) {
                 None => {
-                    self.constraints
-                        .insert(canonical_type_id, merged_constraint);
+                    self.constraints.insert(canonical_id, merged_constraint);
                 }
                 Some(existing_constraint) => {
                     existing_constraint.add_constraints(merged_constraint, &self.types);
@@ -288,6 +311,38 @@ impl TypeSchema {
         Ok(())
     }
 
+    /// Constrain `tuple_type_id` to be a tuple with an element of type
+    /// `element_type_id` at position `index`. If the element is already known,
+    /// the two types are unified.
+    pub fn set_equal_to_tuple_element(
+        &mut self,
+        tuple_type_id: TypeId,
+        index: usize,
+        element_type_id: TypeId,
+        checked_types: &mut CheckedTypes,
+    ) -> Result<(), String> {
+        let canonical_type_id = self.get_canonical_id(tuple_type_id);
+        if let Some(existing_element_type_id) = self
+            .constraints
+            .get(&canonical_type_id)
+            .and_then(|parsed_constraint| parsed_constraint.get_tuple_element_type(index))
+        {
+            return self.set_equal_to_canonical_type(
+                existing_element_type_id,
+                element_type_id,
+                checked_types,
+            );
+        }
+        self.add_constraint(
+            tuple_type_id,
+            Constraint::HasTupleElement(HasTupleElementConstraint {
+                index,
+                element_type: element_type_id,
+            }),
+            checked_types,
+        )
+    }
+
     pub fn set_equal_to_tag_contents(
         &mut self,
         tag_type_id: TypeId,
@@ -377,6 +432,55 @@ mod test {
         assert_eq!(type_schema.get_canonical_id(id_c), id_a);
     }
 
+    #[test]
+    fn setting_a_type_equal_to_itself_keeps_its_constraints() {
+        let mut type_schema = TypeSchema::new();
+        let id_a = type_schema.make_id();
+        let id_b = type_schema.make_id();
+        type_schema
+            .add_constraint(
+                id_a,
+                Constraint::EqualToPrimitive(PrimitiveType::Float),
+                &mut CheckedTypes::new(),
+            )
+            .unwrap();
+        type_schema
+            .set_equal_to_canonical_type(id_a, id_b, &mut CheckedTypes::new())
+            .unwrap();
+        type_schema
+            .set_equal_to_canonical_type(id_b, id_a, &mut CheckedTypes::new())
+            .unwrap();
+        assert_eq!(
+            type_schema.get_concrete_type_from_id(id_b),
+            ConcreteType::Primitive(PrimitiveType::Float)
+        );
+    }
+
+    #[test]
+    fn constraints_are_merged_into_the_canonical_id_of_the_first_type() {
+        let mut type_schema = TypeSchema::new();
+        let id_a = type_schema.make_id();
+        let id_b = type_schema.make_id();
+        let id_c = type_schema.make_id();
+        type_schema
+            .set_equal_to_canonical_type(id_a, id_b, &mut CheckedTypes::new())
+            .unwrap();
+        type_schema
+            .add_constraint(
+                id_c,
+                Constraint::EqualToPrimitive(PrimitiveType::Float),
+                &mut CheckedTypes::new(),
+            )
+            .unwrap();
+        type_schema
+            .set_equal_to_canonical_type(id_b, id_c, &mut CheckedTypes::new())
+            .unwrap();
+        assert_eq!(
+            type_schema.get_concrete_type_from_id(id_a),
+            ConcreteType::Primitive(PrimitiveType::Float)
+        );
+    }
+
     #[test]
     fn count_ids_counts_the_total_number_of_ids() {
         let mut type_schema = TypeSchema::new();
@@ -1,27 +1,35 @@
 use crate::{
     ConcreteType, TypedBinaryOperatorExpression, TypedBlockExpression,
     TypedBooleanLiteralExpression, TypedDeclarationExpression, TypedDocument, TypedEnumExpression,
-    TypedExpression, TypedFunctionExpression, TypedIdentifierExpression, TypedIfExpression,
-    TypedIntegerLiteralExpression, TypedListExpression, TypedRecordAssignmentExpression,
-    TypedRecordExpression, TypedStringLiteralExpression, TypedTagExpression,
-    TypedTypeDeclarationExpression, TypedTypeIdentifierExpression, TypedUnaryOperatorExpression,
-    TypedWhenCase, TypedWhenCaseName, TypedWhenExpression,
+    TypedExpression, TypedFloatLiteralExpression, TypedFunctionExpression,
+    TypedIdentifierExpression, TypedIfExpression, TypedIntegerLiteralExpression,
+    TypedListExpression, TypedMapEntry, TypedMapExpression, TypedRecordAssignmentExpression,
+    TypedRecordExpression, TypedSetExpression, TypedStringLiteralExpression, TypedTagExpression,
+    TypedTupleDeclarationExpression, TypedTupleExpression, TypedTypeDeclarationExpression,
+    TypedTypeIdentifierExpression, TypedUnaryOperatorExpression, TypedWhenCase, TypedWhenCaseName,
+    TypedWhenExpression,
 };
 
 pub type ConcreteBinaryOperatorExpression = TypedBinaryOperatorExpression<ConcreteType>;
 pub type ConcreteBlockExpression = TypedBlockExpression<ConcreteType>;
 pub type ConcreteBooleanExpression = TypedBooleanLiteralExpression<ConcreteType>;
 pub type ConcreteDeclarationExpression = TypedDeclarationExpression<ConcreteType>;
+pub type ConcreteFloatLiteralExpression = TypedFloatLiteralExpression<ConcreteType>;
 pub type ConcreteFunctionExpression = TypedFunctionExpression<ConcreteType>;
 pub type ConcreteIdentifierExpression = TypedIdentifierExpression<ConcreteType>;
 pub type ConcreteIfExpression = TypedIfExpression<ConcreteType>;
 pub type ConcreteIntegerLiteralExpression = TypedIntegerLiteralExpression<ConcreteType>;
 pub type ConcreteListExpression = TypedListExpression<ConcreteType>;
+pub type ConcreteMapEntry = TypedMapEntry<ConcreteType>;
+pub type ConcreteMapExpression = TypedMapExpression<ConcreteType>;
 pub type ConcreteRecordExpression = TypedRecordExpression<ConcreteType>;
 pub type ConcreteRecordAssignmentExpression = TypedRecordAssignmentExpression<ConcreteType>;
+pub type ConcreteSetExpression = TypedSetExpression<ConcreteType>;
 pub type ConcreteStringLiteralExpression = TypedStringLiteralExpression<ConcreteType>;
 pub type ConcreteTagExpression = TypedTagExpression<ConcreteType>;
 pub type ConcreteEnumExpression = TypedEnumExpression<ConcreteType>;
+pub type ConcreteTupleExpression = TypedTupleExpression<ConcreteType>;
+pub type ConcreteTupleDeclarationExpression = TypedTupleDeclarationExpression<ConcreteType>;
 pub type ConcreteTypeDeclarationExpression = TypedTypeDeclarationExpression<ConcreteType>;
 pub type ConcreteTypeIdentifierExpression = TypedTypeIdentifierExpression<ConcreteType>;
 pub type ConcreteUnaryOperatorExpression = TypedUnaryOperatorExpression<ConcreteType>;
@@ -33,6 +41,66 @@ pub type ConcreteExpression = TypedExpression<ConcreteType>;
 
 pub type ConcreteDocument<'a> = TypedDocument<'a, ConcreteType>;
 
+#[must_use]
+pub fn get_concrete_type(input: &ConcreteExpression) -> &ConcreteType {
+    match input {
+        ConcreteExpression::BinaryOperator(node) => &node.expression_type,
+        ConcreteExpression::Block(node) => &node.expression_type,
+        ConcreteExpression::Boolean(node) => &node.expression_type,
+        ConcreteExpression::Declaration(node) => &node.expression_type,
+        ConcreteExpression::Float(node) => &node.expression_type,
+        ConcreteExpression::Function(node) => &node.expression_type,
+        ConcreteExpression::FunctionArguments(_) => unreachable!(),
+        ConcreteExpression::Identifier(node) => &node.expression_type,
+        ConcreteExpression::If(node) => &node.expression_type,
+        ConcreteExpression::Integer(node) => &node.expression_type,
+        ConcreteExpression::List(node) => &node.expression_type,
+        ConcreteExpression::Map(node) => &node.expression_type,
+        ConcreteExpression::Record(node) => &node.expression_type,
+        ConcreteExpression::RecordAssignment(node) => &node.expression_type,
+        ConcreteExpression::Set(node) => &node.expression_type,
+        ConcreteExpression::StringLiteral(node) => &node.expression_type,
+        ConcreteExpression::Tag(node) => &node.expression_type,
+        ConcreteExpression::Enum(node) => &node.expression_type,
+        ConcreteExpression::Tuple(node) => &node.expression_type,
+        ConcreteExpression::TupleDeclaration(node) => &node.expression_type,
+        ConcreteExpression::TypeDeclaration(node) => &node.expression_type,
+        ConcreteExpression::TypeIdentifier(node) => &node.expression_type,
+        ConcreteExpression::UnaryOperator(node) => &node.expression_type,
+        ConcreteExpression::When(node) => &node.expression_type,
+    }
+}
+
+#[must_use]
+pub fn get_concrete_type_mut(input: &mut ConcreteExpression) -> &mut ConcreteType {
+    match input {
+        ConcreteExpression::BinaryOperator(node) => &mut node.expression_type,
+        ConcreteExpression::Block(node) => &mut node.expression_type,
+        ConcreteExpression::Boolean(node) => &mut node.expression_type,
+        ConcreteExpression::Declaration(node) => &mut node.expression_type,
+        ConcreteExpression::Float(node) => &mut node.expression_type,
+        ConcreteExpression::Function(node) => &mut node.expression_type,
+        ConcreteExpression::FunctionArguments(_) => unreachable!(),
+        ConcreteExpression::Identifier(node) => &mut node.expression_type,
+        ConcreteExpression::If(node) => &mut node.expression_type,
+        ConcreteExpression::Integer(node) => &mut node.expression_type,
+        ConcreteExpression::List(node) => &mut node.expression_type,
+        ConcreteExpression::Map(node) => &mut node.expression_type,
+        ConcreteExpression::Record(node) => &mut node.expression_type,
+        ConcreteExpression::RecordAssignment(node) => &mut node.expression_type,
+        ConcreteExpression::Set(node) => &mut node.expression_type,
+        ConcreteExpression::StringLiteral(node) => &mut node.expression_type,
+        ConcreteExpression::Tag(node) => &mut node.expression_type,
+        ConcreteExpression::Enum(node) => &mut node.expression_type,
+        ConcreteExpression::Tuple(node) => &mut node.expression_type,
+        ConcreteExpression::TupleDeclaration(node) => &mut node.expression_type,
+        ConcreteExpression::TypeDeclaration(node) => &mut node.expression_type,
+        ConcreteExpression::TypeIdentifier(node) => &mut node.expression_type,
+        ConcreteExpression::UnaryOperator(node) => &mut node.expression_type,
+        ConcreteExpression::When(node) => &mut node.expression_type,
+    }
+}
+
 impl ConcreteExpression {
     #[must_use]
     pub fn raw_identifier_for_test(name: &str) -> ConcreteIdentifierExpression {
@@ -64,6 +132,14 @@ impl ConcreteExpression {
         }))
     }
 
+    #[must_use]
+    pub fn float_for_test(float: &str) -> Self {
+        Self::Float(Box::new(ConcreteFloatLiteralExpression {
+            expression_type: ConcreteType::default_float_for_test(),
+            value: float.to_string(),
+        }))
+    }
+
     #[must_use]
     pub fn block_for_test(expressions: Vec<Self>) -> Self {
         Self::Block(Box::new(ConcreteBlockExpression {
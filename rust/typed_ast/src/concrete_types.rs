@@ -1,10 +1,18 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
+use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum PrimitiveType {
     CompilerBoolean,
+    Float,
     Int,
     Str,
+    /// A number which could be either an `Int` or a `Float`, such as the arguments
+    /// of a generic function which only does arithmetic with them.
+    Num,
+    /// A type which nothing constrains, such as the arguments of a generic function
+    /// which only passes them around or compares them.
+    Unknown,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,13 +28,13 @@ pub struct ConcreteFunctionType {
 pub struct ConcreteTagUnionType {
     /// Map the name of a tag to an array of the types of its contained values.
     /// Tag with no contents maps to empty vec.
-    pub tag_types: HashMap<String, Vec<ConcreteType>>,
+    pub tag_types: IndexMap<String, Vec<ConcreteType>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ConcreteEnumType {
     /// Map the name of a variant to an array of the types of its payload types.
-    pub variants: HashMap<String, Vec<ConcreteType>>,
+    pub variants: IndexMap<String, Vec<ConcreteType>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -34,10 +42,28 @@ pub struct ConcreteListType {
     pub element_type: ConcreteType,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcreteMapType {
+    pub key_type: ConcreteType,
+    pub value_type: ConcreteType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcreteSetType {
+    pub element_type: ConcreteType,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ConcreteRecordType {
-    /// Map field name to type of that field.
-    pub field_types: HashMap<String, ConcreteType>,
+    /// Map field name to type of that field, in the order the fields were first
+    /// written.
+    pub field_types: IndexMap<String, ConcreteType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcreteTupleType {
+    /// Types of the elements of a tuple, in order.
+    pub element_types: Vec<ConcreteType>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -47,7 +73,10 @@ pub enum ConcreteType {
     TagUnion(Box<ConcreteTagUnionType>),
     Enum(Box<ConcreteEnumType>),
     List(Box<ConcreteListType>),
+    Map(Box<ConcreteMapType>),
     Record(Box<ConcreteRecordType>),
+    Set(Box<ConcreteSetType>),
+    Tuple(Box<ConcreteTupleType>),
 }
 
 impl ConcreteType {
@@ -59,10 +88,15 @@ impl ConcreteType {
     #[must_use]
     pub fn default_record_for_test() -> Self {
         Self::Record(Box::new(ConcreteRecordType {
-            field_types: HashMap::new(),
+            field_types: IndexMap::new(),
         }))
     }
 
+    #[must_use]
+    pub const fn default_float_for_test() -> Self {
+        Self::Primitive(PrimitiveType::Float)
+    }
+
     #[must_use]
     pub const fn default_integer_for_test() -> Self {
         Self::Primitive(PrimitiveType::Int)
@@ -80,6 +114,31 @@ impl ConcreteType {
         }))
     }
 
+    #[must_use]
+    pub fn default_map_for_test() -> Self {
+        Self::Map(Box::new(ConcreteMapType {
+            key_type: Self::default_string_for_test(),
+            value_type: Self::default_integer_for_test(),
+        }))
+    }
+
+    #[must_use]
+    pub fn default_set_for_test() -> Self {
+        Self::Set(Box::new(ConcreteSetType {
+            element_type: Self::default_string_for_test(),
+        }))
+    }
+
+    #[must_use]
+    pub fn default_tuple_for_test() -> Self {
+        Self::Tuple(Box::new(ConcreteTupleType {
+            element_types: vec![
+                Self::default_integer_for_test(),
+                Self::default_string_for_test(),
+            ],
+        }))
+    }
+
     #[must_use]
     pub fn default_binary_operator_for_test() -> Self {
         Self::Function(Box::new(ConcreteFunctionType {
@@ -91,14 +150,14 @@ impl ConcreteType {
     #[must_use]
     pub fn default_tag_union_for_test() -> Self {
         Self::TagUnion(Box::new(ConcreteTagUnionType {
-            tag_types: HashMap::new(),
+            tag_types: IndexMap::new(),
         }))
     }
 
     #[must_use]
     pub fn default_enum_for_test() -> Self {
         Self::Enum(Box::new(ConcreteEnumType {
-            variants: HashMap::new(),
+            variants: IndexMap::new(),
         }))
     }
 
@@ -110,3 +169,66 @@ impl ConcreteType {
         }))
     }
 }
+
+fn join_types(types: &[ConcreteType]) -> String {
+    types
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Writes the variants of a tag union or enum, such as `#some(Int) | #none`.
+fn write_variants(
+    formatter: &mut fmt::Formatter,
+    prefix: char,
+    variants: &IndexMap<String, Vec<ConcreteType>>,
+) -> fmt::Result {
+    for (index, (name, contents)) in variants.iter().enumerate() {
+        if index > 0 {
+            write!(formatter, " | ")?;
+        }
+        write!(formatter, "{prefix}{name}")?;
+        if !contents.is_empty() {
+            write!(formatter, "({})", join_types(contents))?;
+        }
+    }
+    Ok(())
+}
+
+/// Types are displayed the way they would be written in Buri.
+impl fmt::Display for ConcreteType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Primitive(PrimitiveType::CompilerBoolean) => write!(formatter, "Bool"),
+            Self::Primitive(PrimitiveType::Float) => write!(formatter, "Float"),
+            Self::Primitive(PrimitiveType::Int) => write!(formatter, "Int"),
+            Self::Primitive(PrimitiveType::Str) => write!(formatter, "Str"),
+            Self::Primitive(PrimitiveType::Num) => write!(formatter, "Num"),
+            Self::Primitive(PrimitiveType::Unknown) => write!(formatter, "*"),
+            Self::Function(function) => write!(
+                formatter,
+                "({}) => {}",
+                join_types(&function.argument_types),
+                function.return_type
+            ),
+            Self::TagUnion(tag_union) => write_variants(formatter, '#', &tag_union.tag_types),
+            Self::Enum(enum_type) => write_variants(formatter, '.', &enum_type.variants),
+            Self::List(list) => write!(formatter, "[{}]", list.element_type),
+            Self::Map(map) => write!(formatter, "Map({}, {})", map.key_type, map.value_type),
+            Self::Record(record) if record.field_types.is_empty() => write!(formatter, "{{}}"),
+            Self::Record(record) => write!(
+                formatter,
+                "{{ {} }}",
+                record
+                    .field_types
+                    .iter()
+                    .map(|(name, field_type)| format!("{name}: {field_type}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Set(set) => write!(formatter, "Set({})", set.element_type),
+            Self::Tuple(tuple) => write!(formatter, "({})", join_types(&tuple.element_types)),
+        }
+    }
+}
@@ -1,9 +1,36 @@
-use ast::{BinaryOperatorSymbol, ImportNode, TopLevelDeclaration, UnaryOperatorSymbol};
-use std::collections::HashMap;
+use ast::{
+    BinaryOperatorSymbol, ImportNode, ParserInput, TopLevelDeclaration, UnaryOperatorSymbol,
+};
+use indexmap::IndexMap;
+
+/// Where a node starts in the Buri source, used to generate source maps.
+/// Lines and columns start at 1; the default location is unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SourceLocation {
+    pub line: u32,
+    pub column: u32,
+}
+
+impl SourceLocation {
+    #[must_use]
+    pub const fn is_known(self) -> bool {
+        self.line > 0
+    }
+}
+
+impl From<&ParserInput<'_>> for SourceLocation {
+    fn from(input: &ParserInput) -> Self {
+        Self {
+            line: input.location_line(),
+            column: u32::try_from(input.get_utf8_column()).unwrap_or(u32::MAX),
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypedBinaryOperatorExpression<T> {
     pub expression_type: T,
+    pub source: SourceLocation,
     pub symbol: BinaryOperatorSymbol,
     pub left_child: TypedExpression<T>,
     pub right_child: TypedExpression<T>,
@@ -27,11 +54,14 @@ pub struct TypedDeclarationExpression<T> {
     pub expression_type: T,
     pub identifier: TypedIdentifierExpression<T>,
     pub value: TypedExpression<T>,
+    pub is_reassignment: bool,
+    pub source: SourceLocation,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypedFunctionExpression<T> {
     pub expression_type: T,
+    pub source: SourceLocation,
     pub argument_names: Vec<String>,
     pub body: TypedExpression<T>,
 }
@@ -51,6 +81,12 @@ pub struct TypedIfExpression<T> {
     pub path_if_false: Option<TypedExpression<T>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedFloatLiteralExpression<T> {
+    pub expression_type: T,
+    pub value: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypedIntegerLiteralExpression<T> {
     pub expression_type: T,
@@ -63,6 +99,18 @@ pub struct TypedListExpression<T> {
     pub contents: Vec<TypedExpression<T>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedMapEntry<T> {
+    pub key: TypedExpression<T>,
+    pub value: TypedExpression<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedMapExpression<T> {
+    pub expression_type: T,
+    pub contents: Vec<TypedMapEntry<T>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypedRecordAssignmentExpression<T> {
     pub expression_type: T,
@@ -73,7 +121,14 @@ pub struct TypedRecordAssignmentExpression<T> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypedRecordExpression<T> {
     pub expression_type: T,
-    pub contents: HashMap<String, TypedExpression<T>>,
+    /// Fields are kept in source order, so they are printed the same way every time.
+    pub contents: IndexMap<String, TypedExpression<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedSetExpression<T> {
+    pub expression_type: T,
+    pub contents: Vec<TypedExpression<T>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -96,6 +151,20 @@ pub struct TypedEnumExpression<T> {
     pub payload: Vec<TypedExpression<T>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedTupleExpression<T> {
+    pub expression_type: T,
+    pub contents: Vec<TypedExpression<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedTupleDeclarationExpression<T> {
+    pub declaration_type: T,
+    pub expression_type: T,
+    pub identifiers: Vec<TypedIdentifierExpression<T>>,
+    pub value: TypedExpression<T>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypedTypeDeclarationExpression<T> {
     pub declaration_type: T,
@@ -143,17 +212,22 @@ pub enum TypedExpression<T> {
     Block(Box<TypedBlockExpression<T>>),
     Boolean(Box<TypedBooleanLiteralExpression<T>>),
     Declaration(Box<TypedDeclarationExpression<T>>),
+    Float(Box<TypedFloatLiteralExpression<T>>),
     Function(Box<TypedFunctionExpression<T>>),
     FunctionArguments(Vec<TypedExpression<T>>),
     Identifier(Box<TypedIdentifierExpression<T>>),
     If(Box<TypedIfExpression<T>>),
     Integer(Box<TypedIntegerLiteralExpression<T>>),
     List(Box<TypedListExpression<T>>),
+    Map(Box<TypedMapExpression<T>>),
     Record(Box<TypedRecordExpression<T>>),
     RecordAssignment(Box<TypedRecordAssignmentExpression<T>>),
+    Set(Box<TypedSetExpression<T>>),
     StringLiteral(Box<TypedStringLiteralExpression<T>>),
     Tag(Box<TypedTagExpression<T>>),
     Enum(Box<TypedEnumExpression<T>>),
+    Tuple(Box<TypedTupleExpression<T>>),
+    TupleDeclaration(Box<TypedTupleDeclarationExpression<T>>),
     TypeDeclaration(Box<TypedTypeDeclarationExpression<T>>),
     TypeIdentifier(Box<TypedTypeIdentifierExpression<T>>),
     UnaryOperator(Box<TypedUnaryOperatorExpression<T>>),
@@ -0,0 +1,34 @@
+import {
+    BdifferentNestedLists,
+    BdifferentRecords,
+    BsameElement,
+    BsameNestedLists,
+    BsameRecords,
+    BsameTagContents,
+    BsameValue,
+} from "@tests/js/valid/equality/generic.mjs"
+import { describe, expect, it } from "bun:test"
+import { tag } from "../helpers"
+
+describe("sameElement = (a, b) => [a] == [b]", () => {
+    it("compares lists by their elements", () => {
+        expect(BsameNestedLists).toBe(true)
+        expect(BdifferentNestedLists).toBe(false)
+    })
+
+    it("compares tags by their contents", () => {
+        expect(BsameTagContents).toBe(true)
+        expect(BsameElement(tag("some", [1]), tag("some", [2]))).toBe(false)
+    })
+})
+
+describe("sameValue = (a, b) => { value: a } == { value: b }", () => {
+    it("compares records by their fields", () => {
+        expect(BsameRecords).toBe(true)
+        expect(BdifferentRecords).toBe(false)
+    })
+
+    it("compares fields regardless of their order", () => {
+        expect(BsameValue({ name: "Sam", age: 30 }, { age: 30, name: "Sam" })).toBe(true)
+    })
+})
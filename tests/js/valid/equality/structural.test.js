@@ -0,0 +1,50 @@
+import {
+    BdifferentColors,
+    BdifferentEnumPayloads,
+    BdifferentListLengths,
+    BdifferentLists,
+    BdifferentRecords,
+    BdifferentTagContents,
+    BdifferentTuples,
+    BsameColors,
+    BsameEnumPayloads,
+    BsameLists,
+    BsameRecords,
+    BsameTagContents,
+    BsameTuples,
+} from "@tests/js/valid/equality/structural.mjs"
+import { expect, it } from "bun:test"
+
+it("lists are compared element by element", () => {
+    expect(BsameLists).toBe(true)
+    expect(BdifferentLists).toBe(false)
+})
+
+it("lists of different lengths are not equal", () => {
+    expect(BdifferentListLengths).toBe(true)
+})
+
+it("records are compared field by field", () => {
+    expect(BsameRecords).toBe(true)
+    expect(BdifferentRecords).toBe(false)
+})
+
+it("tags are compared by their contents", () => {
+    expect(BsameTagContents).toBe(true)
+    expect(BdifferentTagContents).toBe(false)
+})
+
+it("tuples are compared element by element", () => {
+    expect(BsameTuples).toBe(true)
+    expect(BdifferentTuples).toBe(true)
+})
+
+it("enums are compared by their payloads", () => {
+    expect(BsameEnumPayloads).toBe(true)
+    expect(BdifferentEnumPayloads).toBe(false)
+})
+
+it("enums without payloads are compared by variant", () => {
+    expect(BsameColors).toBe(true)
+    expect(BdifferentColors).toBe(true)
+})
@@ -0,0 +1,38 @@
+import {
+    BeightDivideThree,
+    Bhalve,
+    BintEightDivideThree,
+    BnegativeSevenDivideTwo,
+    Brounded,
+    BroundedDown,
+    BroundedUp,
+    BsevenDivideTwo,
+    BtoFloat,
+    Btruncated,
+} from "@tests/js/valid/floats/division.mjs"
+import { expect, it } from "bun:test"
+
+it("dividing floats does not truncate", () => {
+    expect(BeightDivideThree.valueOf()).toBe(8 / 3)
+    expect(BnegativeSevenDivideTwo.valueOf()).toBe(-3.5)
+})
+
+it("dividing ints still truncates", () => {
+    expect(BintEightDivideThree.valueOf()).toBe(2)
+})
+
+it("floats can be converted to ints", () => {
+    expect(BroundedDown.valueOf()).toBe(3)
+    expect(BroundedUp.valueOf()).toBe(4)
+    expect(Brounded.valueOf()).toBe(4)
+    expect(Btruncated.valueOf()).toBe(-3)
+})
+
+it("arguments divided by a float are floats", () => {
+    expect(Bhalve(5).valueOf()).toBe(2.5)
+})
+
+it("ints can be converted to floats", () => {
+    expect(BsevenDivideTwo.valueOf()).toBe(3.5)
+    expect(BtoFloat(3).valueOf()).toBe(3)
+})
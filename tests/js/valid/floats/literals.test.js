@@ -0,0 +1,37 @@
+import {
+    Bdifference,
+    Bhalf,
+    BisBigger,
+    BisThousand,
+    Bnegative,
+    Bsmall,
+    Bsum,
+    Bthousand,
+} from "@tests/js/valid/floats/literals.mjs"
+import { expect, it } from "bun:test"
+
+it("floats can have a decimal point", () => {
+    expect(Bhalf.valueOf()).toBe(0.5)
+})
+
+it("floats can use scientific notation", () => {
+    expect(Bthousand.valueOf()).toBe(1000)
+    expect(Bsmall.valueOf()).toBe(0.0025)
+})
+
+it("1.25 + 2.5 == 3.75", () => {
+    expect(Bsum.valueOf()).toBe(3.75)
+})
+
+it("1.5 - 2.75 == -1.25", () => {
+    expect(Bdifference.valueOf()).toBe(-1.25)
+})
+
+it("floats can be negated", () => {
+    expect(Bnegative.valueOf()).toBe(-0.5)
+})
+
+it("floats can be compared", () => {
+    expect(BisThousand).toBe(true)
+    expect(BisBigger).toBe(true)
+})
@@ -0,0 +1,43 @@
+import {
+    BdifferentLists,
+    Bdivide,
+    BeighthOfTwenty,
+    BeighthOfTwentyInts,
+    BhalfOfSeven,
+    BhalfOfSevenInts,
+    BquarterOfSeven,
+    Bsame,
+    BsameFloats,
+    BsameLists,
+} from "@tests/js/valid/functions/generics.mjs"
+import { describe, expect, it } from "bun:test"
+
+describe("divide = (dividend, divisor) => dividend / divisor", () => {
+    it("does not truncate floats", () => {
+        expect(BhalfOfSeven.valueOf()).toBe(3.5)
+        expect(BquarterOfSeven.valueOf()).toBe(1.75)
+    })
+
+    it("truncates ints", () => {
+        expect(BhalfOfSevenInts.valueOf()).toBe(3)
+        expect(Bdivide(-7, 2)).toBe(-3)
+    })
+
+    it("does not truncate floats in functions which aren't inlined", () => {
+        expect(BeighthOfTwenty.valueOf()).toBe(2.5)
+        expect(BeighthOfTwentyInts.valueOf()).toBe(2)
+    })
+})
+
+describe("same = (a, b) => a == b", () => {
+    it("compares lists by their elements", () => {
+        expect(BsameLists).toBe(true)
+        expect(BdifferentLists).toBe(false)
+        expect(Bsame([[1], [2]], [[1], [2]])).toBe(true)
+    })
+
+    it("compares floats by value", () => {
+        expect(BsameFloats).toBe(true)
+        expect(Bsame(1.5, 2.5)).toBe(false)
+    })
+})
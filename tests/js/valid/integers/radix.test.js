@@ -0,0 +1,30 @@
+import {
+    Bbinary,
+    Bhexadecimal,
+    Blargest,
+    Bmask,
+    Bmillion,
+    Boctal,
+} from "@tests/js/valid/integers/radix.mjs"
+import { expect, it } from "bun:test"
+
+it("0xFF == 255", () => {
+    expect(Bhexadecimal.valueOf()).toBe(255)
+})
+
+it("0o755 == 493", () => {
+    expect(Boctal.valueOf()).toBe(493)
+})
+
+it("0b1010 == 10", () => {
+    expect(Bbinary.valueOf()).toBe(10)
+})
+
+it("1_000_000 == 1000000", () => {
+    expect(Bmillion.valueOf()).toBe(1000000)
+})
+
+it("prefixed integers can be separated by underscores", () => {
+    expect(Bmask.valueOf()).toBe(255)
+    expect(Blargest.valueOf()).toBe(Number.MAX_SAFE_INTEGER)
+})
@@ -0,0 +1,45 @@
+import {
+    Bages,
+    BemptyMap,
+    BhasTheodore,
+    BmultilineMap,
+    Bnames,
+    BolderAges,
+    BsamsAge,
+    BwithoutSam,
+} from "@tests/js/valid/map/definitions.mjs"
+import { expect, it } from "bun:test"
+import { tag } from "../helpers"
+
+it("a map can be defined with entries", () => {
+    expect(Bages.size()).toBe(2)
+})
+
+it("a map can be empty", () => {
+    expect(BemptyMap.size()).toBe(0)
+})
+
+it("a map can span multiple lines", () => {
+    expect(BmultilineMap.get(2)).toEqual(tag("some", "two"))
+})
+
+it('ages:get("Sam") == #some(30)', () => {
+    expect(BsamsAge).toEqual(tag("some", 30))
+})
+
+it('ages:insert("Alice", 42) adds an entry', () => {
+    expect(BolderAges.get("Alice")).toEqual(tag("some", 42))
+    expect(Bages.has("Alice")).toBe(false)
+})
+
+it('ages:remove("Sam") removes an entry', () => {
+    expect(BwithoutSam.get("Sam")).toEqual(tag("none"))
+})
+
+it('ages:has("Theodore") == #true', () => {
+    expect(BhasTheodore).toBe(true)
+})
+
+it("ages:keys() lists the keys", () => {
+    expect(Bnames).toEqual(["Sam", "Theodore"])
+})
@@ -0,0 +1,11 @@
+import { Borigin, BpointNames } from "@tests/js/valid/map/structural-keys.mjs"
+import { expect, it } from "bun:test"
+import { tag } from "../helpers"
+
+it("records can be map keys", () => {
+    expect(BpointNames.size()).toBe(2)
+})
+
+it("keys are compared by structure", () => {
+    expect(Borigin).toEqual(tag("some", "origin"))
+})
@@ -0,0 +1,24 @@
+import {
+    BaddTwice,
+    BaddToArgument,
+    Bgreet,
+} from "@tests/js/valid/reassignment/locals.mjs"
+import { describe, expect, it } from "bun:test"
+
+describe("addTwice", () => {
+    it("addTwice(1, 2) == 5", () => {
+        expect(BaddTwice(1, 2).valueOf()).toEqual(5)
+    })
+})
+
+describe("addToArgument", () => {
+    it("addToArgument(41) == 42", () => {
+        expect(BaddToArgument(41).valueOf()).toEqual(42)
+    })
+})
+
+describe("greet", () => {
+    it('greet("world") == "hello world"', () => {
+        expect(Bgreet("world")).toEqual("hello world")
+    })
+})
@@ -0,0 +1,28 @@
+import {
+    BemptySet,
+    BhasThree,
+    BmorePrimes,
+    Bprimes,
+    Btags,
+} from "@tests/js/valid/set/definitions.mjs"
+import { expect, it } from "bun:test"
+
+it("duplicate elements are only stored once", () => {
+    expect(Bprimes.values()).toEqual([2, 3, 5])
+})
+
+it("a set can be empty", () => {
+    expect(BemptySet.size()).toBe(0)
+})
+
+it("primes:insert(7) adds an element", () => {
+    expect(BmorePrimes.values()).toEqual([2, 3, 5, 7])
+})
+
+it("primes:has(3) == #true", () => {
+    expect(BhasThree).toBe(true)
+})
+
+it("elements are compared by structure", () => {
+    expect(Btags.size()).toBe(2)
+})
@@ -0,0 +1,18 @@
+import { BcountDown, BlastTag, BmakeCounters, BsumTo } from "@tests/js/valid/tail-calls/loops.mjs"
+import { expect, it } from "bun:test"
+
+it("self tail calls don't grow the stack", () => {
+    expect(BsumTo(100000, 0)).toBe(5000050000)
+})
+
+it("tail calls in any branch of an if loop", () => {
+    expect(BcountDown(100000)).toEqual(["done"])
+})
+
+it("tail calls in a when case loop", () => {
+    expect(BlastTag(["more", 100000])).toBe(0)
+})
+
+it("closures capture the arguments of their own iteration", () => {
+    expect(BmakeCounters(3, []).map((counter) => counter())).toEqual([3, 2, 1])
+})
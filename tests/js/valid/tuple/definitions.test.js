@@ -0,0 +1,48 @@
+import {
+    Bdivide,
+    Bfirst,
+    BinnerSecond,
+    BisSamePair,
+    BmultilinePair,
+    Bnested,
+    Bpair,
+    Bsecond,
+    Bundivide,
+} from "@tests/js/valid/tuple/definitions.mjs"
+import { expect, it } from "bun:test"
+
+it("a tuple can be defined with elements of different types", () => {
+    expect(Bpair).toEqual([1, "one"])
+})
+
+it("a tuple can span multiple lines", () => {
+    expect(BmultilinePair).toEqual([2, "two"])
+})
+
+it("a tuple can contain tuples", () => {
+    expect(Bnested).toEqual([[1, 2], "three"])
+})
+
+it("pair.0 == 1", () => {
+    expect(Bfirst).toBe(1)
+})
+
+it('pair.1 == "one"', () => {
+    expect(Bsecond).toBe("one")
+})
+
+it("nested.0.1 == 2", () => {
+    expect(BinnerSecond).toBe(2)
+})
+
+it("a function can return a tuple", () => {
+    expect(Bdivide(7, 2)).toEqual([3, 1])
+})
+
+it("a tuple can be destructured", () => {
+    expect(Bundivide(7, 2)).toBe(7)
+})
+
+it("tuples are compared by their elements", () => {
+    expect(BisSamePair).toBe(true)
+})